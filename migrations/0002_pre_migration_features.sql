@@ -0,0 +1,180 @@
+-- Everything added to the schema before migrations were embedded in the bot
+-- Written to also apply on databases set up with a newer setup script, which already have some of it
+
+ALTER TABLE questions
+    ADD COLUMN IF NOT EXISTS pack_name varchar,
+    ADD COLUMN IF NOT EXISTS pack_author varchar;
+
+ALTER TABLE custom_questions
+    ADD COLUMN IF NOT EXISTS cooldown_days integer,
+    ADD COLUMN IF NOT EXISTS pack_name varchar,
+    ADD COLUMN IF NOT EXISTS pack_author varchar,
+    ADD COLUMN IF NOT EXISTS submitted_by varchar,
+    ADD COLUMN IF NOT EXISTS status varchar NOT NULL DEFAULT 'active',
+    ADD COLUMN IF NOT EXISTS flag_reason varchar,
+    ADD COLUMN IF NOT EXISTS in_use bool NOT NULL DEFAULT true;
+
+ALTER TABLE custom_polls
+    ADD COLUMN IF NOT EXISTS emoji varchar[],
+    ADD COLUMN IF NOT EXISTS duration_hours integer,
+    ADD COLUMN IF NOT EXISTS anonymous bool NOT NULL DEFAULT false;
+
+CREATE TABLE IF NOT EXISTS queued_questions (
+    guild_id varchar NOT NULL,
+    post_date date NOT NULL,
+    question_id integer NOT NULL REFERENCES custom_questions ON DELETE CASCADE,
+    consumed bool NOT NULL DEFAULT false,
+    PRIMARY KEY (guild_id, post_date)
+);
+
+CREATE TABLE IF NOT EXISTS question_notes (
+    question_id integer PRIMARY KEY REFERENCES custom_questions ON DELETE CASCADE,
+    note varchar NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS custom_question_posts (
+    guild_id varchar NOT NULL,
+    question_id integer NOT NULL REFERENCES custom_questions ON DELETE CASCADE,
+    last_posted timestamptz NOT NULL,
+    PRIMARY KEY (guild_id, question_id)
+);
+
+CREATE TABLE IF NOT EXISTS shared_pools (
+    pool_id serial PRIMARY KEY,
+    created_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS pool_members (
+    guild_id varchar PRIMARY KEY,
+    pool_id integer NOT NULL REFERENCES shared_pools ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS pool_invites (
+    code varchar PRIMARY KEY,
+    pool_id integer NOT NULL REFERENCES shared_pools ON DELETE CASCADE,
+    expires_at timestamptz NOT NULL
+);
+
+-- Settings were added one by one, so databases part of the way there get the missing ones
+CREATE TABLE IF NOT EXISTS guild_settings (
+    guild_id varchar PRIMARY KEY
+);
+
+ALTER TABLE guild_settings
+    ADD COLUMN IF NOT EXISTS question_cooldown_days integer NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS archive_channel_id varchar,
+    ADD COLUMN IF NOT EXISTS save_emoji varchar NOT NULL DEFAULT '📌',
+    ADD COLUMN IF NOT EXISTS everyone_approval bool NOT NULL DEFAULT false,
+    ADD COLUMN IF NOT EXISTS show_attribution bool NOT NULL DEFAULT true,
+    ADD COLUMN IF NOT EXISTS answer_window_hours integer,
+    ADD COLUMN IF NOT EXISTS fair_rotation bool NOT NULL DEFAULT false,
+    ADD COLUMN IF NOT EXISTS fallback_question varchar,
+    ADD COLUMN IF NOT EXISTS post_schedule varchar,
+    ADD COLUMN IF NOT EXISTS next_post_at timestamptz,
+    ADD COLUMN IF NOT EXISTS teaser_minutes integer,
+    ADD COLUMN IF NOT EXISTS teased_post timestamptz,
+    ADD COLUMN IF NOT EXISTS moderation_strictness varchar NOT NULL DEFAULT 'off',
+    ADD COLUMN IF NOT EXISTS mention_template varchar,
+    ADD COLUMN IF NOT EXISTS cleanup_seconds integer,
+    ADD COLUMN IF NOT EXISTS cleanup_commands bool NOT NULL DEFAULT false,
+    ADD COLUMN IF NOT EXISTS timezone varchar,
+    ADD COLUMN IF NOT EXISTS accessible_posts bool NOT NULL DEFAULT false,
+    ADD COLUMN IF NOT EXISTS paused bool NOT NULL DEFAULT false,
+    ADD COLUMN IF NOT EXISTS poll_nudge varchar NOT NULL DEFAULT 'off',
+    ADD COLUMN IF NOT EXISTS poll_schedule varchar,
+    ADD COLUMN IF NOT EXISTS poll_source varchar NOT NULL DEFAULT 'default',
+    ADD COLUMN IF NOT EXISTS next_poll_at timestamptz,
+    ADD COLUMN IF NOT EXISTS skip_days varchar[] NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS next_question varchar,
+    ADD COLUMN IF NOT EXISTS next_attribution varchar,
+    ADD COLUMN IF NOT EXISTS poll_buttons bool NOT NULL DEFAULT false,
+    ADD COLUMN IF NOT EXISTS ephemeral_replies bool NOT NULL DEFAULT true,
+    ADD COLUMN IF NOT EXISTS poll_intro varchar,
+    ADD COLUMN IF NOT EXISTS poll_title varchar;
+
+CREATE TABLE IF NOT EXISTS streams (
+    guild_id varchar NOT NULL,
+    name varchar NOT NULL,
+    channel_id varchar NOT NULL,
+    ping_role varchar NOT NULL DEFAULT '0',
+    mention_template varchar,
+    PRIMARY KEY (guild_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS post_history (
+    post_id serial PRIMARY KEY,
+    guild_id varchar NOT NULL,
+    channel_id varchar NOT NULL,
+    message_id varchar NOT NULL UNIQUE,
+    question_string varchar NOT NULL,
+    posted_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS saved_questions (
+    user_id varchar NOT NULL,
+    post_id integer NOT NULL REFERENCES post_history ON DELETE CASCADE,
+    saved_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_id, post_id)
+);
+
+CREATE TABLE IF NOT EXISTS notify_preferences (
+    guild_id varchar NOT NULL,
+    user_id varchar NOT NULL,
+    mode varchar NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS poll_posts (
+    post_id serial PRIMARY KEY,
+    guild_id varchar NOT NULL,
+    poll_id integer NOT NULL,
+    custom bool NOT NULL,
+    channel_id varchar NOT NULL,
+    message_id varchar NOT NULL UNIQUE,
+    posted_at timestamptz NOT NULL DEFAULT now(),
+    votes_a integer NOT NULL DEFAULT 0,
+    votes_b integer NOT NULL DEFAULT 0,
+    emoji varchar[] NOT NULL DEFAULT '{🟠,🔵}',
+    anonymous bool NOT NULL DEFAULT false,
+    closed bool NOT NULL DEFAULT false
+);
+
+CREATE TABLE IF NOT EXISTS poll_votes (
+    message_id varchar NOT NULL REFERENCES poll_posts (message_id) ON DELETE CASCADE,
+    user_id varchar NOT NULL,
+    option integer NOT NULL,
+    PRIMARY KEY (message_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS deadlines (
+    deadline_id serial PRIMARY KEY,
+    kind varchar NOT NULL,
+    guild_id varchar NOT NULL,
+    channel_id varchar NOT NULL,
+    message_id varchar,
+    due_at timestamptz NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS api_tokens (
+    guild_id varchar NOT NULL,
+    scope varchar NOT NULL,
+    token_hash varchar NOT NULL UNIQUE,
+    created_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (guild_id, scope)
+);
+
+CREATE TABLE IF NOT EXISTS guild_events (
+    guild_id varchar NOT NULL,
+    event varchar NOT NULL,
+    happened_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS guild_events_guild_id ON guild_events (guild_id, happened_at);
+
+CREATE TABLE IF NOT EXISTS command_stats (
+    guild_id varchar NOT NULL,
+    command varchar NOT NULL,
+    day date NOT NULL DEFAULT current_date,
+    uses integer NOT NULL DEFAULT 1,
+    PRIMARY KEY (guild_id, command, day)
+);
@@ -0,0 +1,25 @@
+-- Hash of every question, used to find duplicates
+-- Adding the columns computes the hash of every existing question
+
+-- Hash of a question ignoring case, spacing and punctuation at the end, the same as normalize_question in the bot
+CREATE OR REPLACE FUNCTION hash_question(question varchar) RETURNS varchar
+LANGUAGE sql IMMUTABLE
+AS $$
+    SELECT md5(lower(rtrim(
+        regexp_replace(regexp_replace(question, '^\s+|\s+$', '', 'g'), '\s+', ' ', 'g'),
+        '!"#$%&''()*+,-./:;<=>?@[\]^_`{|}~'
+    )))
+$$;
+
+-- Duplicates in the global pool are removed before the unique index is made, the oldest copy is kept
+DELETE FROM questions q USING questions older
+WHERE hash_question(q.question_string) = hash_question(older.question_string)
+AND older.question_id < q.question_id;
+
+ALTER TABLE questions
+    ADD COLUMN IF NOT EXISTS question_hash varchar GENERATED ALWAYS AS (hash_question(question_string)) STORED;
+CREATE UNIQUE INDEX IF NOT EXISTS questions_question_hash ON questions (question_hash);
+
+ALTER TABLE custom_questions
+    ADD COLUMN IF NOT EXISTS question_hash varchar GENERATED ALWAYS AS (hash_question(question_string)) STORED;
+CREATE INDEX IF NOT EXISTS custom_questions_question_hash ON custom_questions (guild_id, question_hash);
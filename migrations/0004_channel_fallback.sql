@@ -0,0 +1,3 @@
+-- Without a channel set, qotd and poll commands post where they're used unless this is turned off
+ALTER TABLE guild_settings
+    ADD COLUMN channel_fallback bool NOT NULL DEFAULT true;
@@ -0,0 +1,5 @@
+-- Adds seconds to a timestamp, the SQLite backend registers the same function
+-- Queries use it instead of intervals so they run on both backends
+CREATE FUNCTION add_seconds(at timestamptz, seconds double precision) RETURNS timestamptz
+    LANGUAGE sql STABLE
+    AS $$ SELECT at + seconds * interval '1 second' $$;
@@ -0,0 +1,8 @@
+-- Members blocked from the submission commands of a guild
+CREATE TABLE blocked_users (
+    guild_id varchar NOT NULL,
+    user_id varchar NOT NULL,
+    blocked_by varchar NOT NULL,
+    blocked_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (guild_id, user_id)
+);
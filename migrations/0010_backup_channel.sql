@@ -0,0 +1,17 @@
+-- Questions and polls go to the backup channel when posting to their channel fails
+ALTER TABLE guild_settings
+    ADD COLUMN backup_channel_id varchar;
+-- The admin who set the channel is told when posting fails over
+ALTER TABLE channels
+    ADD COLUMN set_by varchar;
+
+-- Events of a guild admins can look back on, like posting failing over to the backup channel
+CREATE TABLE audit_log (
+    entry_id serial PRIMARY KEY,
+    guild_id varchar NOT NULL,
+    action varchar NOT NULL,
+    detail varchar NOT NULL,
+    logged_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX audit_log_guild_id ON audit_log (guild_id, logged_at);
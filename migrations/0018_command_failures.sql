@@ -0,0 +1,3 @@
+-- Counts the uses of a command that ended in an error, for the error rates of the owner API
+ALTER TABLE command_stats
+    ADD COLUMN failures integer NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+-- Custom questions remember when they were submitted, questions from before this are left without a time
+-- Guilds can show who submitted a custom question in the posted question
+ALTER TABLE custom_questions
+    ADD COLUMN submitted_at timestamptz;
+
+ALTER TABLE guild_settings
+    ADD COLUMN show_submitter boolean NOT NULL DEFAULT false;
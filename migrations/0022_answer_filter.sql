@@ -0,0 +1,5 @@
+-- Answers in answer threads only count when they're long enough and aren't repeats from the same member
+-- With a member limit only that many answers of each member count, without it every answer that passes counts
+ALTER TABLE guild_settings
+    ADD COLUMN answer_min_length integer NOT NULL DEFAULT 3,
+    ADD COLUMN answer_member_limit integer;
@@ -0,0 +1,3 @@
+-- Guilds can ping the member whose custom question was posted, in a reply to the post
+ALTER TABLE guild_settings
+    ADD COLUMN mention_submitter boolean NOT NULL DEFAULT false;
@@ -0,0 +1,3 @@
+-- Percentage of random and scheduled questions picked from the custom questions of the guild, null leaves the mix off
+ALTER TABLE guild_settings
+    ADD COLUMN custom_question_share integer;
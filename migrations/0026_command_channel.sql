@@ -0,0 +1,3 @@
+-- Guilds can restrict bot commands to one channel, null allows them everywhere
+ALTER TABLE guild_settings
+    ADD COLUMN command_channel_id varchar;
@@ -0,0 +1,19 @@
+-- Roles whose button votes count more on weighted polls, a member counts with the weight of their heaviest role
+CREATE TABLE poll_role_weights (
+    guild_id varchar NOT NULL,
+    role_id varchar NOT NULL,
+    weight integer NOT NULL,
+    PRIMARY KEY (guild_id, role_id)
+);
+
+-- Custom polls weighting votes by role, and whether a posted poll did
+ALTER TABLE custom_polls
+    ADD COLUMN weighted boolean NOT NULL DEFAULT false;
+
+ALTER TABLE poll_posts
+    ADD COLUMN weighted boolean NOT NULL DEFAULT false;
+
+-- The weight of a vote and the role it came from, for the breakdown of the results
+ALTER TABLE poll_votes
+    ADD COLUMN weight integer NOT NULL DEFAULT 1,
+    ADD COLUMN weight_role varchar;
@@ -0,0 +1,7 @@
+-- Answers counted when the answer thread of a post closed, null for posts without one
+ALTER TABLE post_history
+    ADD COLUMN answers integer;
+
+-- Guilds can prefer questions like their most answered and saved ones
+ALTER TABLE guild_settings
+    ADD COLUMN recommend boolean NOT NULL DEFAULT false;
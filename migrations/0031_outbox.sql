@@ -0,0 +1,18 @@
+-- Scheduled posts are queued here in the same step their schedule moves on and sent by a background loop,
+-- so a post cut off by a restart is sent once afterwards instead of twice or not at all
+CREATE TABLE outbox (
+    outbox_id serial PRIMARY KEY,
+    guild_id varchar NOT NULL,
+    kind varchar NOT NULL,
+    due_at timestamptz NOT NULL,
+    status varchar NOT NULL DEFAULT 'pending',
+    attempts integer NOT NULL DEFAULT 0,
+    attempted_at timestamptz,
+    send_after timestamptz NOT NULL DEFAULT now(),
+    channel_id varchar,
+    message_id varchar,
+    settled_at timestamptz,
+    UNIQUE (guild_id, kind, due_at)
+);
+
+CREATE INDEX outbox_pending ON outbox (send_after) WHERE status = 'pending';
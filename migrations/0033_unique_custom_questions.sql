@@ -0,0 +1,12 @@
+-- Custom questions are unique per guild by their hash, copies saved before are marked deleted and the oldest is kept
+UPDATE custom_questions SET deleted_at = now()
+WHERE deleted_at IS NULL AND EXISTS (
+    SELECT 1 FROM custom_questions older
+    WHERE older.guild_id = custom_questions.guild_id
+    AND older.question_hash = custom_questions.question_hash
+    AND older.deleted_at IS NULL
+    AND older.question_id < custom_questions.question_id
+);
+
+CREATE UNIQUE INDEX custom_questions_unique_hash ON custom_questions (guild_id, question_hash)
+WHERE deleted_at IS NULL;
@@ -0,0 +1,10 @@
+-- Quotas the owner of the bot set for the guild, in place of the ones configured for every guild.
+-- Commands a day, DM fan-outs a month and imports a month
+ALTER TABLE guild_settings
+    ADD COLUMN command_quota integer;
+
+ALTER TABLE guild_settings
+    ADD COLUMN dm_fanout_quota integer;
+
+ALTER TABLE guild_settings
+    ADD COLUMN import_quota integer;
@@ -0,0 +1,213 @@
+-- Schema of the SQLite backend, matching the Postgres migrations up to 0005
+-- Arrays are stored as JSON text, timestamps as text in the format rusqlite writes them
+-- hash_question and add_seconds are registered by the bot when it opens the database
+
+CREATE TABLE channels (
+    guild_id varchar PRIMARY KEY,
+    channel_id varchar NOT NULL
+);
+
+CREATE TABLE questions (
+    question_id integer PRIMARY KEY AUTOINCREMENT,
+    question_string varchar NOT NULL,
+    in_use bool NOT NULL,
+    pack_name varchar,
+    pack_author varchar,
+    question_hash varchar GENERATED ALWAYS AS (hash_question(question_string)) STORED
+);
+
+CREATE UNIQUE INDEX questions_question_hash ON questions (question_hash);
+
+CREATE TABLE custom_questions (
+    question_id integer PRIMARY KEY AUTOINCREMENT,
+    guild_id varchar NOT NULL,
+    question_string varchar NOT NULL,
+    cooldown_days integer,
+    pack_name varchar,
+    pack_author varchar,
+    submitted_by varchar,
+    status varchar NOT NULL DEFAULT 'active',
+    flag_reason varchar,
+    in_use bool NOT NULL DEFAULT true,
+    question_hash varchar GENERATED ALWAYS AS (hash_question(question_string)) STORED
+);
+
+CREATE INDEX custom_questions_question_hash ON custom_questions (guild_id, question_hash);
+
+CREATE TABLE ping_roles (
+    guild_id varchar PRIMARY KEY,
+    ping_role varchar NOT NULL
+);
+
+CREATE TABLE polls (
+    poll_id integer PRIMARY KEY AUTOINCREMENT,
+    poll_string varchar NOT NULL,
+    in_use bool NOT NULL
+);
+
+CREATE TABLE custom_polls (
+    poll_id integer PRIMARY KEY AUTOINCREMENT,
+    guild_id varchar NOT NULL,
+    poll_string varchar NOT NULL,
+    emoji varchar,
+    duration_hours integer,
+    anonymous bool NOT NULL DEFAULT false
+);
+
+CREATE TABLE guild_settings (
+    guild_id varchar PRIMARY KEY,
+    question_cooldown_days integer NOT NULL DEFAULT 0,
+    archive_channel_id varchar,
+    save_emoji varchar NOT NULL DEFAULT '📌',
+    everyone_approval bool NOT NULL DEFAULT false,
+    show_attribution bool NOT NULL DEFAULT true,
+    answer_window_hours integer,
+    fair_rotation bool NOT NULL DEFAULT false,
+    fallback_question varchar,
+    post_schedule varchar,
+    next_post_at timestamptz,
+    teaser_minutes integer,
+    teased_post timestamptz,
+    moderation_strictness varchar NOT NULL DEFAULT 'off',
+    mention_template varchar,
+    cleanup_seconds integer,
+    cleanup_commands bool NOT NULL DEFAULT false,
+    timezone varchar,
+    accessible_posts bool NOT NULL DEFAULT false,
+    paused bool NOT NULL DEFAULT false,
+    poll_nudge varchar NOT NULL DEFAULT 'off',
+    poll_schedule varchar,
+    poll_source varchar NOT NULL DEFAULT 'default',
+    next_poll_at timestamptz,
+    skip_days varchar NOT NULL DEFAULT '[]',
+    next_question varchar,
+    next_attribution varchar,
+    poll_buttons bool NOT NULL DEFAULT false,
+    ephemeral_replies bool NOT NULL DEFAULT true,
+    poll_intro varchar,
+    poll_title varchar,
+    channel_fallback bool NOT NULL DEFAULT true
+);
+
+CREATE TABLE custom_question_posts (
+    guild_id varchar NOT NULL,
+    question_id integer NOT NULL REFERENCES custom_questions ON DELETE CASCADE,
+    last_posted timestamptz NOT NULL,
+    PRIMARY KEY (guild_id, question_id)
+);
+
+CREATE TABLE question_notes (
+    question_id integer PRIMARY KEY REFERENCES custom_questions ON DELETE CASCADE,
+    note varchar NOT NULL
+);
+
+CREATE TABLE queued_questions (
+    guild_id varchar NOT NULL,
+    post_date date NOT NULL,
+    question_id integer NOT NULL REFERENCES custom_questions ON DELETE CASCADE,
+    consumed bool NOT NULL DEFAULT false,
+    PRIMARY KEY (guild_id, post_date)
+);
+
+CREATE TABLE post_history (
+    post_id integer PRIMARY KEY AUTOINCREMENT,
+    guild_id varchar NOT NULL,
+    channel_id varchar NOT NULL,
+    message_id varchar NOT NULL UNIQUE,
+    question_string varchar NOT NULL,
+    posted_at timestamptz NOT NULL DEFAULT (now())
+);
+
+CREATE TABLE saved_questions (
+    user_id varchar NOT NULL,
+    post_id integer NOT NULL REFERENCES post_history ON DELETE CASCADE,
+    saved_at timestamptz NOT NULL DEFAULT (now()),
+    PRIMARY KEY (user_id, post_id)
+);
+
+CREATE TABLE poll_posts (
+    post_id integer PRIMARY KEY AUTOINCREMENT,
+    guild_id varchar NOT NULL,
+    poll_id integer NOT NULL,
+    custom bool NOT NULL,
+    channel_id varchar NOT NULL,
+    message_id varchar NOT NULL UNIQUE,
+    posted_at timestamptz NOT NULL DEFAULT (now()),
+    votes_a integer NOT NULL DEFAULT 0,
+    votes_b integer NOT NULL DEFAULT 0,
+    emoji varchar NOT NULL DEFAULT '["🟠","🔵"]',
+    anonymous bool NOT NULL DEFAULT false,
+    closed bool NOT NULL DEFAULT false
+);
+
+CREATE TABLE poll_votes (
+    message_id varchar NOT NULL REFERENCES poll_posts (message_id) ON DELETE CASCADE,
+    user_id varchar NOT NULL,
+    option integer NOT NULL,
+    PRIMARY KEY (message_id, user_id)
+);
+
+CREATE TABLE deadlines (
+    deadline_id integer PRIMARY KEY AUTOINCREMENT,
+    kind varchar NOT NULL,
+    guild_id varchar NOT NULL,
+    channel_id varchar NOT NULL,
+    message_id varchar,
+    due_at timestamptz NOT NULL
+);
+
+CREATE TABLE notify_preferences (
+    guild_id varchar NOT NULL,
+    user_id varchar NOT NULL,
+    mode varchar NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE TABLE streams (
+    guild_id varchar NOT NULL,
+    name varchar NOT NULL,
+    channel_id varchar NOT NULL,
+    ping_role varchar NOT NULL DEFAULT '0',
+    mention_template varchar,
+    PRIMARY KEY (guild_id, name)
+);
+
+CREATE TABLE shared_pools (
+    pool_id integer PRIMARY KEY AUTOINCREMENT,
+    created_at timestamptz NOT NULL DEFAULT (now())
+);
+
+CREATE TABLE pool_members (
+    guild_id varchar PRIMARY KEY,
+    pool_id integer NOT NULL REFERENCES shared_pools ON DELETE CASCADE
+);
+
+CREATE TABLE pool_invites (
+    code varchar PRIMARY KEY,
+    pool_id integer NOT NULL REFERENCES shared_pools ON DELETE CASCADE,
+    expires_at timestamptz NOT NULL
+);
+
+CREATE TABLE api_tokens (
+    guild_id varchar NOT NULL,
+    scope varchar NOT NULL,
+    token_hash varchar NOT NULL UNIQUE,
+    created_at timestamptz NOT NULL DEFAULT (now()),
+    PRIMARY KEY (guild_id, scope)
+);
+
+CREATE TABLE guild_events (
+    guild_id varchar NOT NULL,
+    event varchar NOT NULL,
+    happened_at timestamptz NOT NULL DEFAULT (now())
+);
+
+CREATE INDEX guild_events_guild_id ON guild_events (guild_id, happened_at);
+
+CREATE TABLE command_stats (
+    guild_id varchar NOT NULL,
+    command varchar NOT NULL,
+    day date NOT NULL DEFAULT (date('now')),
+    uses integer NOT NULL DEFAULT 1,
+    PRIMARY KEY (guild_id, command, day)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE drafts (
+    draft_id integer PRIMARY KEY AUTOINCREMENT,
+    guild_id varchar NOT NULL,
+    user_id varchar NOT NULL,
+    kind varchar NOT NULL,
+    content varchar NOT NULL,
+    created_at timestamptz NOT NULL DEFAULT (now())
+);
+
+CREATE INDEX drafts_guild_id ON drafts (guild_id, user_id);
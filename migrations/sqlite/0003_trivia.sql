@@ -0,0 +1,21 @@
+-- Trivia mode, custom questions with an answer are graded in their answer thread
+ALTER TABLE custom_questions
+    ADD COLUMN answer varchar;
+ALTER TABLE guild_settings
+    ADD COLUMN trivia_mode bool NOT NULL DEFAULT false;
+
+-- Answer threads of posted trivia questions, winner_id is set by the first correct answer
+CREATE TABLE trivia_rounds (
+    thread_id varchar PRIMARY KEY,
+    guild_id varchar NOT NULL,
+    answer varchar NOT NULL,
+    winner_id varchar,
+    won_at timestamptz
+);
+
+CREATE TABLE trivia_scores (
+    guild_id varchar NOT NULL,
+    user_id varchar NOT NULL,
+    points integer NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, user_id)
+);
@@ -0,0 +1,3 @@
+-- Deleted custom questions are kept for 30 days so they can be restored, then purged
+ALTER TABLE custom_questions
+    ADD COLUMN deleted_at timestamptz;
@@ -0,0 +1,11 @@
+-- Options added to posted polls, the tallies at the time are kept to report the votes before the change
+CREATE TABLE poll_amendments (
+    message_id varchar PRIMARY KEY REFERENCES poll_posts (message_id) ON DELETE CASCADE,
+    option_string varchar NOT NULL,
+    emoji varchar NOT NULL,
+    votes integer NOT NULL DEFAULT 0,
+    votes_a_before integer NOT NULL,
+    votes_b_before integer NOT NULL,
+    amended_by varchar NOT NULL,
+    amended_at timestamptz NOT NULL DEFAULT (now())
+);
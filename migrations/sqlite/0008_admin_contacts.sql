@@ -0,0 +1,13 @@
+-- Admins told about operational problems, like posting failing over or submissions waiting for moderation
+CREATE TABLE admin_contacts (
+    guild_id varchar NOT NULL,
+    user_id varchar NOT NULL,
+    added_at timestamptz NOT NULL DEFAULT (now()),
+    PRIMARY KEY (guild_id, user_id)
+);
+
+-- Notifications go to every contact or rotate between them, the cursor picks the next one
+ALTER TABLE guild_settings
+    ADD COLUMN notify_policy varchar NOT NULL DEFAULT 'all';
+ALTER TABLE guild_settings
+    ADD COLUMN notify_cursor integer NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+-- Questions of the day can be posted rendered onto an image card
+ALTER TABLE guild_settings
+    ADD COLUMN image_cards boolean NOT NULL DEFAULT false;
+ALTER TABLE guild_settings
+    ADD COLUMN card_color varchar;
+ALTER TABLE guild_settings
+    ADD COLUMN card_template varchar NOT NULL DEFAULT 'plain';
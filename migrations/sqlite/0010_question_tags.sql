@@ -0,0 +1,7 @@
+-- Questions can be tagged, guilds can limit the questions picked for them to some tags
+ALTER TABLE questions
+    ADD COLUMN tags varchar NOT NULL DEFAULT '[]';
+ALTER TABLE custom_questions
+    ADD COLUMN tags varchar NOT NULL DEFAULT '[]';
+ALTER TABLE guild_settings
+    ADD COLUMN question_tags varchar;
@@ -0,0 +1,18 @@
+-- DMs sent to many members at once, like questions for members notified by DM,
+-- are queued and sent by a background loop at a limited rate
+CREATE TABLE dm_queue (
+    dm_id integer PRIMARY KEY AUTOINCREMENT,
+    user_id varchar NOT NULL,
+    content varchar NOT NULL,
+    embed_title varchar,
+    embed_description varchar,
+    attempts integer NOT NULL DEFAULT 0,
+    send_after timestamptz NOT NULL DEFAULT (now())
+);
+
+-- DMs in a row that failed because the member doesn't take DMs from the bot
+CREATE TABLE dm_failures (
+    user_id varchar PRIMARY KEY,
+    failures integer NOT NULL DEFAULT 0,
+    failed_at timestamptz NOT NULL DEFAULT (now())
+);
@@ -0,0 +1,13 @@
+-- Default questions each guild has posted, random questions skip them until every question has been used
+CREATE TABLE question_history (
+    guild_id varchar NOT NULL,
+    question_id integer NOT NULL REFERENCES questions ON DELETE CASCADE,
+    posted_at timestamptz NOT NULL DEFAULT (now()),
+    PRIMARY KEY (guild_id, question_id)
+);
+
+-- Questions posted before the history was kept are found from the posts
+INSERT INTO question_history (guild_id, question_id, posted_at)
+SELECT ph.guild_id, q.question_id, max(ph.posted_at) FROM post_history ph
+JOIN questions q ON q.question_hash = hash_question(ph.question_string)
+GROUP BY ph.guild_id, q.question_id;
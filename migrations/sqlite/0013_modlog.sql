@@ -0,0 +1,6 @@
+-- Question events like submissions and deletions can be sent to a modlog channel or webhook of the guild
+-- The target is a channel id or a webhook URL, without events set every event is sent
+ALTER TABLE guild_settings
+    ADD COLUMN modlog_target varchar;
+ALTER TABLE guild_settings
+    ADD COLUMN modlog_events varchar;
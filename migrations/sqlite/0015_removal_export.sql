@@ -0,0 +1,3 @@
+-- The owner of a guild is sent an export of it when the bot is removed, unless the guild turned it off
+ALTER TABLE guild_settings
+    ADD COLUMN removal_export boolean NOT NULL DEFAULT true;
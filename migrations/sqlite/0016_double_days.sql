@@ -0,0 +1,18 @@
+-- Guilds can have two questions posted side by side on some weekdays, members react to pick the one they answer
+-- The votes are kept with the tags of both questions, so the styles members pick more can be picked more often
+ALTER TABLE guild_settings
+    ADD COLUMN double_days varchar NOT NULL DEFAULT '[]';
+
+CREATE TABLE double_posts (
+    message_id varchar PRIMARY KEY,
+    guild_id varchar NOT NULL,
+    question_a varchar NOT NULL,
+    question_b varchar NOT NULL,
+    tags_a varchar NOT NULL DEFAULT '[]',
+    tags_b varchar NOT NULL DEFAULT '[]',
+    votes_a integer NOT NULL DEFAULT 0,
+    votes_b integer NOT NULL DEFAULT 0,
+    posted_at timestamptz NOT NULL DEFAULT (now())
+);
+
+CREATE INDEX double_posts_guild_id ON double_posts (guild_id, posted_at);
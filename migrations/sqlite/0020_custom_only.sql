@@ -0,0 +1,3 @@
+-- Guilds can pick random and scheduled questions from their custom questions only, leaving out the default questions
+ALTER TABLE guild_settings
+    ADD COLUMN custom_only boolean NOT NULL DEFAULT false;
@@ -0,0 +1,3 @@
+-- Default questions with a seasonal tag are preferred in their season and held back outside it, unless the guild turns it off
+ALTER TABLE guild_settings
+    ADD COLUMN seasonal boolean NOT NULL DEFAULT true;
@@ -0,0 +1,9 @@
+-- Questions can be flagged as mature, family friendly guilds never get them picked
+ALTER TABLE questions
+    ADD COLUMN mature boolean NOT NULL DEFAULT false;
+
+ALTER TABLE custom_questions
+    ADD COLUMN mature boolean NOT NULL DEFAULT false;
+
+ALTER TABLE guild_settings
+    ADD COLUMN family_friendly boolean NOT NULL DEFAULT false;
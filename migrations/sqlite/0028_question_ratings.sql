@@ -0,0 +1,6 @@
+-- Thumbs up and down members rated a posted question with
+ALTER TABLE post_history
+    ADD COLUMN rated_up integer NOT NULL DEFAULT 0;
+
+ALTER TABLE post_history
+    ADD COLUMN rated_down integer NOT NULL DEFAULT 0;
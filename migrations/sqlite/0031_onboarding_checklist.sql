@@ -0,0 +1,6 @@
+-- Pinned setup checklist of the guild, edited as its settings change
+ALTER TABLE guild_settings
+    ADD COLUMN checklist_channel_id varchar;
+
+ALTER TABLE guild_settings
+    ADD COLUMN checklist_message_id varchar;
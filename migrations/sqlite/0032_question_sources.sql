@@ -0,0 +1,12 @@
+-- Where imported, API and pack questions were taken from, so the owner can check the global pool is credited
+ALTER TABLE questions
+    ADD COLUMN source_url varchar;
+
+ALTER TABLE questions
+    ADD COLUMN license varchar;
+
+ALTER TABLE custom_questions
+    ADD COLUMN source_url varchar;
+
+ALTER TABLE custom_questions
+    ADD COLUMN license varchar;
@@ -1,1183 +1,15921 @@
+// The guild export builds its settings in a single json! call, which outgrows the default limit
+#![recursion_limit = "256"]
+
+mod card;
+mod placeholders;
+mod storage;
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::future::Future;
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use serenity::framework::standard::{
-    macros::{command, group},
-    CommandResult, StandardFramework,
+use serenity::builder::{
+    CreateAllowedMentions, CreateComponents, CreateEmbed, CreateMessage, ParseValue,
 };
+use serenity::framework::standard::CommandResult;
+use serenity::framework::Framework;
 
 use serenity::model::channel::ReactionType::Unicode;
+use serenity::model::channel::{Attachment, AttachmentType, Embed, Reaction, ReactionType};
 
-use serenity::model::id::ChannelId;
-use serenity::utils::{parse_channel, parse_role, Color};
+use serenity::http::{error::Error as HttpError, Http};
+use serenity::model::application::command::{CommandOptionType, CommandType};
+use serenity::model::application::component::{ActionRowComponent, ButtonStyle, InputTextStyle};
+use serenity::model::application::interaction::application_command::{
+    ApplicationCommandInteraction, CommandDataOption, CommandDataOptionValue,
+};
+use serenity::model::application::interaction::autocomplete::AutocompleteInteraction;
+use serenity::model::application::interaction::modal::ModalSubmitInteraction;
+use serenity::model::application::interaction::{Interaction, InteractionResponseType};
+use serenity::model::guild::{Guild, Member, UnavailableGuild};
+use serenity::model::id::{ChannelId, GuildId, MessageId, RoleId, UserId};
+use serenity::model::user::User;
+use serenity::model::webhook::Webhook;
+use serenity::model::Permissions;
+use serenity::utils::{parse_channel, parse_role, parse_username, Color};
 use serenity::{
     async_trait,
-    model::{channel::Message, gateway::Ready},
+    model::{
+        channel::Message,
+        gateway::{Activity, Ready},
+    },
     prelude::*,
 };
 
-use tokio_postgres::{NoTls, Row};
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use croner::Cron;
 
-// Container for psql client
-struct DataClient {
-    _tokio_postgres: tokio_postgres::Client,
-}
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-impl TypeMapKey for DataClient {
-    type Value = Arc<tokio_postgres::Client>;
-}
-
-// General framework for commands
-#[group]
-#[allowed_roles(qotd_admin)]
-#[commands(
-    help,
-    set_channel,
-    channel,
-    qotd,
-    custom_qotd,
-    submit_qotd,
-    delete_question,
-    list_qotd,
-    ping_role,
-    poll,
-    submit_poll,
-    custom_poll,
-    list_polls,
-    delete_poll
-)]
-struct General;
-
-struct MessageHandler;
+use card::{parse_card_color, render_card, CardStyle, CARD_TEMPLATES};
+use storage::{
+    run_migrations, ClientPool, DbError, LeaderLock, QuestionEdit, QuestionSource, Row, Store,
+    QUOTA_COLUMNS,
+};
 
-#[async_trait]
-impl EventHandler for MessageHandler {
-    async fn ready(&self, _: Context, ready: Ready) {
-        println!("{} online", ready.user.name);
-    }
-}
+use axum::extract::{Path, Query, State};
+use axum::http::{header, Request, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get};
+use axum::{Extension, Json, Router};
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::Rng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
 
-#[tokio::main]
-async fn main() {
-    let token = env::var("DISCORD_TOKEN").expect("Discord token not found");
+// Container for the database connections, the supervisor swaps them when the database goes away
+struct DataClient;
 
-    // Database settings from environment variable.
-    // Format: host= <> dbname= <> user= <> password= <>
-    let db_connection_settings = env::var("DB_CONNECTION")
-        .expect("Database connection string not found. Set environment variable!");
+impl TypeMapKey for DataClient {
+    type Value = Arc<ClientPool>;
+}
 
-    let (db_client, db_connection) = tokio_postgres::connect(&db_connection_settings, NoTls)
-        .await
-        .expect("Connection to the database failed!");
+// Container for the store the commands go through, the same pool as DataClient
+struct DataStore;
 
-    // moving database connection to its own thread
-    tokio::spawn(async move {
-        if let Err(e) = db_connection.await {
-            eprintln!("Connection Error: {}", e);
-        }
-    });
+impl TypeMapKey for DataStore {
+    type Value = Arc<dyn Store>;
+}
 
-    // Serenity framework
-    let framework = StandardFramework::new()
-        .configure(|c| c.prefix("q!").case_insensitivity(true))
-        .group(&GENERAL_GROUP);
+// Container for the command framework, interactions are run through it by hand
+struct DataFramework;
 
-    // Serenity discord client builder
-    let mut discord_client = Client::builder(&token)
-        .event_handler(MessageHandler)
-        .framework(framework)
-        .await
-        .expect("Building discord client failed");
+impl TypeMapKey for DataFramework {
+    type Value = Arc<CommandFramework>;
+}
 
-    // psql container Arc
-    {
-        let mut data = discord_client.data.write().await;
-        data.insert::<DataClient>(Arc::new(db_client));
-    }
+// Image cards rendered before, by title, question and style
+struct CardCache;
 
-    // Starting discord client
-    if let Err(e) = discord_client.start().await {
-        println!("Starting client error {}", e)
-    }
+impl TypeMapKey for CardCache {
+    type Value = Arc<Mutex<HashMap<(String, String, CardStyle), Arc<Vec<u8>>>>>;
 }
 
-/// Setting the channel id from the database for the server id in question
-/// guild_id is from parsed within the command.
-/// channel_id: String - Channel id to be set in the database
-async fn set_ping_channel_id(
-    channel_id: String,
-    guild_id: String,
-    ctx: &Context,
-) -> Result<u64, tokio_postgres::Error> {
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
-
-    // Assuming the channel ID is a valid one, parsed at command level
-    // Upserting into the database
-    let upsert = client
-        .execute(
-            "INSERT INTO channels (guild_id, channel_id)
-            VALUES ($1, $2)
-            ON CONFLICT (guild_id)
-            DO
-            UPDATE SET channel_id = EXCLUDED.channel_id",
-            &[&guild_id, &channel_id],
-        )
-        .await;
+// Stream of settings changes, a change is sent after a command that changes settings goes through
+struct SettingsEvents;
 
-    upsert
+impl TypeMapKey for SettingsEvents {
+    type Value = tokio::sync::broadcast::Sender<SettingsChange>;
 }
 
-/// Pulls channel id formatted for parse_channel() from the database using the guild id.
-/// Returns "0" if no result
-async fn get_ping_channel_id(guild_id: String, ctx: &Context) -> String {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
-
-    let channel_id: String;
-    let rows = client
-        .query(
-            "SELECT channel_id FROM channels WHERE guild_id = $1",
-            &[&guild_id],
-        )
-        .await
-        .expect("Error querying database");
-    let channel_string;
-    if !rows.is_empty() {
-        channel_id = rows[0].get(0);
-        channel_string = format!("<#{}>", channel_id);
-    } else {
-        channel_string = String::from("0");
-    }
-    channel_string
+// A command changed the settings of a guild, with the channel it was used in
+#[derive(Clone)]
+struct SettingsChange {
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    command: String,
 }
 
-/// Gets a random question from the database and returns it as a string
-async fn get_random_question(ctx: &Context) -> String {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
+// Commands that change what the setup checklist shows, they're sent on the settings change stream
+const SETTINGS_COMMANDS: [&str; 10] = [
+    "set_channel",
+    "ping_role",
+    "schedule",
+    "admin_contact",
+    "submit_qotd",
+    "import",
+    "merge_pool",
+    "delete_question",
+    "delete_questions",
+    "clear_questions",
+];
+
+// Cards kept before the cache is emptied, a card is only reused for a while after posting anyway
+const CARD_CACHE_SIZE: usize = 64;
+// Name of the card in the message it is attached to
+const CARD_FILENAME: &str = "question.png";
+
+// Timing of the command being dispatched, for checking it against the latency budget
+struct CommandTiming {
+    started: Instant,
+    // Time spent waiting for the user to answer a prompt
+    waited: Duration,
+    slowest_query: Option<(String, Duration)>,
+}
 
-    // Getting a random entry from the database by querying the database with random order and displaying one.
-    // NOTE: This is rather inefficient because the function in psql is slow, and not exactly efficient
-    // Future implementations might make this a bit faster but while there isn't thousands of question this will work fine
-    // Using a random number generator with the multi-threading was kinda annoying and since there's less than 1000 entries, this should be fine, for now.
-    let rows = client
-        .query(
-            "SELECT question_string FROM questions WHERE in_use = $1 ORDER BY random() LIMIT 1",
-            &[&true],
-        )
-        .await
-        .expect("Error querying database");
+tokio::task_local! {
+    static COMMAND_TIMING: RefCell<CommandTiming>;
+}
 
-    rows[0].get(0)
+// Command framework running the prefix commands in messages, slash commands are run through it from interactions.
+// Both are timed and checked the same way, and the handlers get the options given to the command.
+struct CommandFramework {
+    // Owners of the application, they can use the owner commands
+    owners: HashSet<UserId>,
+    // Messages from the gateway aren't commands in slash only mode, only interactions are dispatched
+    slash_only: bool,
 }
 
-/// Adds a custom question to the database with the associated guild_id
-async fn add_custom_question(
-    guild_id: String,
-    question: String,
-    ctx: &Context,
-) -> Result<u64, tokio_postgres::Error> {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
-
-    let insert = client
-        .execute(
-            "INSERT INTO custom_questions (guild_id, question_string) VALUES ($1, $2)",
-            &[&guild_id, &question],
-        )
-        .await;
+impl CommandFramework {
+    /// Runs the command, timing it
+    async fn run(&self, ctx: Context, invocation: Invocation) {
+        let timing = RefCell::new(CommandTiming {
+            started: Instant::now(),
+            waited: Duration::ZERO,
+            slowest_query: None,
+        });
+        COMMAND_TIMING
+            .scope(timing, self.run_checked(&ctx, &invocation))
+            .await;
+    }
 
-    insert
-}
-
-/// Deletes a specified question from the database.
-/// Using the guild_id provided, the function checks ownership of the question matches the ID.
-/// If match, the question is deleted.
-/// Returns 1 on successful deletion
-/// Returns 0 if deletion failed.
-async fn delete_custom_question(guild_id: String, question_id: i32, ctx: &Context) -> i32 {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
-
-    // Checking if a question with the guild_id of the requesting server exists, if it exists, delete the question.
-    // This prevents from other servers deleting each others questions.
-    let rows = client
-        .query(
-            "SELECT * FROM custom_questions WHERE guild_id = $1 AND question_id = $2",
-            &[&guild_id, &question_id],
-        )
-        .await
-        .expect("Select Failed");
-    if !rows.is_empty() {
-        let _delete = client
-            .execute(
-                "DELETE FROM custom_questions WHERE question_id = $1",
-                &[&question_id],
-            )
-            .await
-            .expect("Delete failed");
+    /// Runs the command if the author can use it there, then records it and cleans up after it
+    async fn run_checked(&self, ctx: &Context, invocation: &Invocation) {
+        if !self.can_use(ctx, invocation).await {
+            // Prefix commands are ignored, interactions have to be answered
+            if invocation.message().is_none() {
+                invocation
+                    .refuse(ctx, "You can't use this command here!")
+                    .await;
+            }
+            return;
+        }
+        if !command_allowed(ctx, invocation).await {
+            return;
+        }
 
-        1
-    } else {
-        0
+        if let Err(e) = invocation.acknowledge(ctx).await {
+            println!("Responding to {} failed: {}", invocation.name, e);
+            return;
+        }
+        let result = run_command(ctx, invocation).await;
+        invocation.finish(ctx, result.is_err()).await;
+        after_command(ctx, invocation, result).await;
+    }
+
+    /// Checks the author can use the command.
+    /// Owner commands are only for the owners, admin commands need administrator or the qotd_admin role in the server.
+    /// The owners can use admin commands too.
+    async fn can_use(&self, ctx: &Context, invocation: &Invocation) -> bool {
+        let owner = self.owners.contains(&invocation.author.id);
+        if is_owner_command(invocation.name) {
+            return owner;
+        }
+        if MEMBER_COMMANDS.contains(&invocation.name) {
+            return true;
+        }
+
+        match invocation.guild_id {
+            Some(guild_id) => owner || invocation.author_is_admin(ctx, guild_id).await,
+            None => false,
+        }
     }
 }
 
-/// Gets all the questions submitted by the guild_id and returns vector of rows
-async fn get_list_custom_questions(guild_id: String, ctx: &Context) -> Vec<Row> {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
+#[async_trait]
+impl Framework for CommandFramework {
+    async fn dispatch(&self, ctx: Context, msg: Message) {
+        if self.slash_only || msg.author.bot || msg.webhook_id.is_some() {
+            return;
+        }
 
-    let rows = client
-        .query(
-            "SELECT * FROM custom_questions WHERE guild_id = $1",
-            &[&guild_id],
-        )
-        .await
-        .expect("Error querying database");
+        let (definition, written) = match find_prefix_command(&msg.content) {
+            Some(found) => found,
+            None => return,
+        };
+        let args = parse_prefix_arguments(definition, written, &msg.attachments);
+        self.run(ctx, Invocation::from_message(definition.name, args, msg))
+            .await;
+    }
+}
 
-    rows
+// Value given for an option of a command
+#[derive(Clone, Debug)]
+enum ArgValue {
+    Text(String),
+    Integer(i64),
+    Channel(ChannelId),
+    Role(RoleId),
+    User(UserId),
+    Attachment(Attachment),
 }
 
-/// Queries the database for a custom question
-async fn get_random_custom_question(guild_id: String, ctx: &Context) -> String {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
+// Options given to a command, by the name of the option
+#[derive(Default, Debug)]
+struct CommandArgs {
+    values: Vec<(String, ArgValue)>,
+}
 
-    let rows = client
-        .query(
-            "SELECT question_string FROM custom_questions WHERE guild_id = $1 ORDER BY random() LIMIT 1",
-            &[&guild_id]
-        )
-        .await
-        .expect("Error querying database");
+impl CommandArgs {
+    /// Reads the options given to a slash command
+    fn from_options(options: &[CommandDataOption]) -> CommandArgs {
+        let mut args = CommandArgs::default();
+        for given in options {
+            let value = match &given.resolved {
+                Some(CommandDataOptionValue::String(value)) => ArgValue::Text(value.clone()),
+                Some(CommandDataOptionValue::Integer(value)) => ArgValue::Integer(*value),
+                Some(CommandDataOptionValue::Channel(channel)) => ArgValue::Channel(channel.id),
+                Some(CommandDataOptionValue::Role(role)) => ArgValue::Role(role.id),
+                Some(CommandDataOptionValue::User(user, _)) => ArgValue::User(user.id),
+                Some(CommandDataOptionValue::Attachment(attachment)) => {
+                    ArgValue::Attachment(attachment.clone())
+                }
+                _ => continue,
+            };
+            args.set(&given.name, value);
+        }
+        args
+    }
 
-    if !rows.is_empty() {
-        rows[0].get(0)
-    } else {
-        String::from("No custom questions found!")
+    fn set(&mut self, name: &str, value: ArgValue) {
+        self.values.push((name.to_string(), value));
     }
-}
 
-/// Gets a specific custom question from the database based on id
-async fn get_specific_custom_question(guild_id: String, question_id: i32, ctx: &Context) -> String {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
+    /// Gets the value given for the option, None if it wasn't given
+    fn get(&self, name: &str) -> Option<&ArgValue> {
+        self.values
+            .iter()
+            .find(|(given, _)| given == name)
+            .map(|(_, value)| value)
+    }
 
-    let rows = client
-        .query(
-            "SELECT question_string FROM custom_questions WHERE guild_id = $1 AND question_id = $2",
-            &[&guild_id, &question_id],
-        )
-        .await
-        .expect("Error querying database");
+    /// Gets the text given for the option, empty if no text was given
+    fn text(&self, name: &str) -> &str {
+        match self.get(name) {
+            Some(ArgValue::Text(text)) => text.trim(),
+            _ => "",
+        }
+    }
 
-    if !rows.is_empty() {
-        rows[0].get(0)
-    } else {
-        String::from("Question does not exist!")
+    fn integer(&self, name: &str) -> Option<i64> {
+        match self.get(name) {
+            Some(ArgValue::Integer(value)) => Some(*value),
+            _ => None,
+        }
     }
-}
 
-/// Saves a role id to be used to ping into the database.
-/// guild_id is the id of the server the command is called from.
-/// 0 is used for no ping
-/// 1 is used for EVERYONE
-/// submitted id is used for specific role
-async fn set_ping_role(
-    guild_id: String,
-    ping_role: String,
-    ctx: &Context,
-) -> Result<u64, tokio_postgres::Error> {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
-
-    let upsert = client
-        .execute(
-            "INSERT INTO ping_roles (guild_id, ping_role)
-            VALUES ($1, $2)
-            ON CONFLICT (guild_id)
-            DO
-            UPDATE SET ping_role = EXCLUDED.ping_role",
-            &[&guild_id, &ping_role],
-        )
-        .await;
+    /// Gets the id of a question, poll or draft given for the option
+    fn id(&self, name: &str) -> Option<i32> {
+        self.integer(name).and_then(|id| i32::try_from(id).ok())
+    }
 
-    upsert
-}
+    fn channel(&self, name: &str) -> Option<ChannelId> {
+        match self.get(name) {
+            Some(ArgValue::Channel(channel)) => Some(*channel),
+            _ => None,
+        }
+    }
 
-/// Gets the role id to be used for pinging based on the guild_id
-///  0 is used for no ping
-/// 1 is used for EVERYONE
-/// submitted id is used for specific role
-async fn get_ping_role(guild_id: String, ctx: &Context) -> String {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
+    fn role(&self, name: &str) -> Option<RoleId> {
+        match self.get(name) {
+            Some(ArgValue::Role(role)) => Some(*role),
+            _ => None,
+        }
+    }
 
-    let rows = client
-        .query(
-            "SELECT ping_role FROM ping_roles WHERE guild_id = $1",
-            &[&guild_id],
-        )
-        .await
-        .expect("Error querying database");
+    fn user(&self, name: &str) -> Option<UserId> {
+        match self.get(name) {
+            Some(ArgValue::User(user)) => Some(*user),
+            _ => None,
+        }
+    }
 
-    // Return the ping role as string
-    if !rows.is_empty() {
-        rows[0].get(0)
-    } else {
-        //Return 0 if there's no ping role assigned
-        String::from("0")
+    fn attachment(&self, name: &str) -> Option<&Attachment> {
+        match self.get(name) {
+            Some(ArgValue::Attachment(attachment)) => Some(attachment),
+            _ => None,
+        }
     }
-}
 
-/// Appends the correct ping to the message based on the ping_role parameter
-/// Returns completed string
-async fn format_string_for_pings(ping_role: String, message: String) -> String {
-    let question_string;
-    if ping_role == *"0" {
-        question_string = message;
-    } else if ping_role == *"1" {
-        question_string = format!("@everyone {}", message);
-    } else {
-        // Role validity checked when it is saved to the database
-        question_string = format!("<@&{}> {}", ping_role, message);
+    fn is_empty(&self) -> bool {
+        self.values.is_empty()
     }
-    question_string
 }
 
-/// Checks whether the amount of custom question entries in the database is under the limit imposed by the function.
-/// Returns true if the current count is under the limit
-/// Returns false if the current count is over the limit
-async fn question_is_under_limit(guild_id: String, ctx: &Context) -> bool {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
-    let limit: i64 = 100; // CUSTOM QUESTION LIMIT
+// Where a command came from, interactions are answered through the interaction
+enum InvocationSource {
+    Message(Box<Message>),
+    Command(Box<ApplicationCommandInteraction>),
+    Modal(Box<ModalSubmitInteraction>),
+}
 
-    let rows = client
-        .query(
-            "SELECT COUNT(*) FROM custom_questions WHERE guild_id = $1",
-            &[&guild_id],
-        )
-        .await
-        .expect("psql count failed");
-    let count: i64 = rows[0].get(0);
-    count < limit
+// A command being run with the options given to it.
+// Prefix commands come from a message, slash commands, the submission modal and Save as QOTD from an interaction.
+struct Invocation {
+    // Name of the command in its definition
+    name: &'static str,
+    author: User,
+    // Member running the command, interactions come with it
+    member: Option<Member>,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    args: CommandArgs,
+    source: InvocationSource,
+    // Interactions answered so only the author sees the replies
+    ephemeral: bool,
+    // Replies sent so far, the first reply to an interaction is the response to it
+    replies: std::sync::Mutex<Vec<Message>>,
 }
 
-/// Checking whether the server has reached its limit on polls submitted to the database
-/// Returns true if server is under the limit
-/// Returns false if server is over limit
-async fn poll_is_under_limit(guild_id: String, ctx: &Context) -> bool {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
-    let limit: i64 = 100; // CUSTOM QUESTION LIMIT
+impl Invocation {
+    fn from_message(name: &'static str, args: CommandArgs, msg: Message) -> Invocation {
+        Invocation {
+            name,
+            author: msg.author.clone(),
+            member: None,
+            guild_id: msg.guild_id,
+            channel_id: msg.channel_id,
+            args,
+            source: InvocationSource::Message(Box::new(msg)),
+            ephemeral: false,
+            replies: std::sync::Mutex::new(Vec::new()),
+        }
+    }
 
-    let rows = client
-        .query(
-            "SELECT COUNT(*) FROM custom_polls WHERE guild_id = $1",
-            &[&guild_id],
-        )
-        .await
-        .expect("psql count failed");
+    fn from_command(
+        name: &'static str,
+        args: CommandArgs,
+        command: ApplicationCommandInteraction,
+        ephemeral: bool,
+    ) -> Invocation {
+        Invocation {
+            name,
+            author: command.user.clone(),
+            member: command.member.clone(),
+            guild_id: command.guild_id,
+            channel_id: command.channel_id,
+            args,
+            source: InvocationSource::Command(Box::new(command)),
+            ephemeral,
+            replies: std::sync::Mutex::new(Vec::new()),
+        }
+    }
 
-    let count: i64 = rows[0].get(0);
-    count < limit
-}
+    fn from_modal(
+        name: &'static str,
+        args: CommandArgs,
+        submit: ModalSubmitInteraction,
+        ephemeral: bool,
+    ) -> Invocation {
+        Invocation {
+            name,
+            author: submit.user.clone(),
+            member: submit.member.clone(),
+            guild_id: submit.guild_id,
+            channel_id: submit.channel_id,
+            args,
+            source: InvocationSource::Modal(Box::new(submit)),
+            ephemeral,
+            replies: std::sync::Mutex::new(Vec::new()),
+        }
+    }
 
-/// Gets a random poll from the database and returns it
-async fn get_random_poll(ctx: &Context) -> Vec<String> {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
+    /// Gets the message of a prefix command, interactions have none
+    fn message(&self) -> Option<&Message> {
+        match &self.source {
+            InvocationSource::Message(msg) => Some(msg),
+            _ => None,
+        }
+    }
 
-    let rows = client
-        .query(
-            "SELECT poll_string FROM polls WHERE in_use = $1 ORDER BY random() LIMIT 1",
-            &[&true],
-        )
-        .await
-        .expect("Selecting question failed");
+    /// Checks the author is an admin in the guild with is_qotd_admin.
+    /// The member of a prefix command is looked up, with its permissions worked out from the cached guild.
+    async fn author_is_admin(&self, ctx: &Context, guild_id: GuildId) -> bool {
+        if let Some(member) = &self.member {
+            return is_qotd_admin(ctx, guild_id, member);
+        }
 
-    rows[0].get(0)
-}
+        match guild_id.member(ctx, self.author.id).await {
+            Ok(mut member) => {
+                member.permissions = member.permissions(&ctx.cache).ok();
+                is_qotd_admin(ctx, guild_id, &member)
+            }
+            Err(_) => false,
+        }
+    }
 
-/// Inserts a custom poll into the database and associates it with a guild_id
-async fn add_custom_poll(
-    guild_id: String,
-    new_poll: Vec<String>,
-    ctx: &Context,
-) -> Result<u64, tokio_postgres::Error> {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
-
-    let insert = client
-        .execute(
-            "INSERT INTO custom_polls (guild_id, poll_string) VALUES ($1, $2)",
-            &[&guild_id, &new_poll],
-        )
-        .await;
+    /// Defers the response to an interaction, the first reply is edited into it.
+    /// Interactions have to be answered within three seconds and commands can take longer.
+    async fn acknowledge(&self, ctx: &Context) -> Result<(), SerenityError> {
+        match &self.source {
+            InvocationSource::Message(_) => Ok(()),
+            InvocationSource::Command(command) => {
+                command
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                            .interaction_response_data(|d| d.ephemeral(self.ephemeral))
+                    })
+                    .await
+            }
+            InvocationSource::Modal(submit) => {
+                submit
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                            .interaction_response_data(|d| d.ephemeral(self.ephemeral))
+                    })
+                    .await
+            }
+        }
+    }
 
-    insert
-}
+    /// Answers with a notice instead of running the command, only the author sees it for interactions
+    async fn refuse(&self, ctx: &Context, notice: impl std::fmt::Display) {
+        let refused = match &self.source {
+            InvocationSource::Message(msg) => msg.reply(ctx, notice).await.map(|_| ()),
+            InvocationSource::Command(command) => {
+                command
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| d.content(notice).ephemeral(true))
+                    })
+                    .await
+            }
+            InvocationSource::Modal(submit) => {
+                submit
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| d.content(notice).ephemeral(true))
+                    })
+                    .await
+            }
+        };
+        if let Err(e) = refused {
+            println!("Answering {} failed: {}", self.name, e);
+        }
+    }
 
-/// Returns a random custom poll from the list of polls saved in the database for the guild.
-/// Returns an empty array if no custom polls are saved
-async fn get_random_custom_poll(guild_id: String, ctx: &Context) -> Vec<String> {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
-    let poll_vec;
-    let rows = client
-        .query(
-            "SELECT poll_string FROM custom_polls WHERE guild_id = $1 ORDER BY random() LIMIT 1",
-            &[&guild_id],
-        )
-        .await
-        .expect("Error querying database");
+    /// Replies to the command. Replies to an ephemeral interaction are only shown to the author.
+    async fn reply(
+        &self,
+        ctx: &Context,
+        content: impl std::fmt::Display,
+    ) -> Result<Message, SerenityError> {
+        self.send_reply(ctx, content.to_string(), true).await
+    }
 
-    if !rows.is_empty() {
-        poll_vec = rows[0].get(0);
-    } else {
-        poll_vec = vec![];
+    /// Replies to the command without pinging the members and roles mentioned in the reply
+    async fn reply_without_pings(
+        &self,
+        ctx: &Context,
+        content: impl std::fmt::Display,
+    ) -> Result<Message, SerenityError> {
+        self.send_reply(ctx, content.to_string(), false).await
     }
 
-    poll_vec
-}
+    async fn send_reply(
+        &self,
+        ctx: &Context,
+        content: String,
+        pings: bool,
+    ) -> Result<Message, SerenityError> {
+        let first = self.replies.lock().unwrap().is_empty();
+        let reply = match &self.source {
+            InvocationSource::Message(msg) if pings => msg.reply(ctx, content).await?,
+            InvocationSource::Message(msg) => {
+                msg.channel_id
+                    .send_message(ctx, |m| {
+                        m.content(content)
+                            .reference_message(&**msg)
+                            .allowed_mentions(|mentions| mentions.empty_parse())
+                    })
+                    .await?
+            }
+            InvocationSource::Command(command) if first => {
+                command
+                    .edit_original_interaction_response(&ctx.http, |r| {
+                        if !pings {
+                            r.allowed_mentions(|mentions| mentions.empty_parse());
+                        }
+                        r.content(content)
+                    })
+                    .await?
+            }
+            InvocationSource::Command(command) => {
+                command
+                    .create_followup_message(&ctx.http, |f| {
+                        if !pings {
+                            f.allowed_mentions(|mentions| mentions.empty_parse());
+                        }
+                        f.content(content).ephemeral(self.ephemeral)
+                    })
+                    .await?
+            }
+            InvocationSource::Modal(submit) if first => {
+                submit
+                    .edit_original_interaction_response(&ctx.http, |r| {
+                        if !pings {
+                            r.allowed_mentions(|mentions| mentions.empty_parse());
+                        }
+                        r.content(content)
+                    })
+                    .await?
+            }
+            InvocationSource::Modal(submit) => {
+                submit
+                    .create_followup_message(&ctx.http, |f| {
+                        if !pings {
+                            f.allowed_mentions(|mentions| mentions.empty_parse());
+                        }
+                        f.content(content).ephemeral(self.ephemeral)
+                    })
+                    .await?
+            }
+        };
 
-/// Returns a custom poll from the database using a specified id
-async fn get_specific_custom_poll(guild_id: String, poll_id: i32, ctx: &Context) -> Vec<String> {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
+        self.replies.lock().unwrap().push(reply.clone());
+        Ok(reply)
+    }
 
-    let rows = client
-        .query(
-            "SELECT poll_string FROM custom_polls WHERE guild_id = $1 AND poll_id = $2",
-            &[&guild_id, &poll_id],
-        )
-        .await
-        .expect("Error querying database");
+    /// Edits the first reply to the command
+    async fn edit_first_reply(&self, ctx: &Context, content: String) -> Result<(), SerenityError> {
+        let first = match self.replies.lock().unwrap().first() {
+            Some(first) => first.clone(),
+            None => return Ok(()),
+        };
+        match &self.source {
+            InvocationSource::Message(_) => {
+                first
+                    .channel_id
+                    .edit_message(ctx, first.id, |m| m.content(content))
+                    .await?;
+            }
+            InvocationSource::Command(command) => {
+                command
+                    .edit_original_interaction_response(&ctx.http, |r| r.content(content))
+                    .await?;
+            }
+            InvocationSource::Modal(submit) => {
+                submit
+                    .edit_original_interaction_response(&ctx.http, |r| r.content(content))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
 
-    if !rows.is_empty() {
-        rows[0].get(0)
-    } else {
-        vec![]
+    /// Wraps up the response to an interaction once the command is done.
+    /// A command that didn't reply, like one posting a question, has the deferred response removed.
+    async fn finish(&self, ctx: &Context, failed: bool) {
+        if self.message().is_some() || !self.replies.lock().unwrap().is_empty() {
+            return;
+        }
+
+        let finished = if failed {
+            self.reply(ctx, "Something went wrong!").await.map(|_| ())
+        } else {
+            match &self.source {
+                InvocationSource::Message(_) => Ok(()),
+                InvocationSource::Command(command) => {
+                    command
+                        .delete_original_interaction_response(&ctx.http)
+                        .await
+                }
+                InvocationSource::Modal(submit) => {
+                    submit.delete_original_interaction_response(&ctx.http).await
+                }
+            }
+        };
+        if let Err(e) = finished {
+            println!("Finishing the response to {} failed: {}", self.name, e);
+        }
     }
 }
 
-/// Returns a vector of rows containing all the custom polls saved for the server
-/// Returns and empty vector if no polls exist.
-async fn get_list_of_custom_polls(guild_id: String, ctx: &Context) -> Vec<Row> {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
+/// Whether the bot runs in slash only mode, set with SLASH_ONLY=true.
+/// Prefix commands are off and the message content intent isn't requested, everything goes through interactions.
+fn slash_only() -> bool {
+    matches!(env::var("SLASH_ONLY").as_deref(), Ok("true") | Ok("1"))
+}
 
-    let rows = client
-        .query(
-            "SELECT * FROM custom_polls WHERE guild_id = $1",
-            &[&guild_id],
-        )
-        .await
-        .expect("Error querying database");
+// Custom question and poll pool exported from a guild
+#[derive(Deserialize)]
+struct PoolExport {
+    #[serde(default)]
+    questions: Vec<String>,
+    #[serde(default)]
+    polls: Vec<Vec<String>>,
+}
 
-    rows
+// Posting destination with its own ping configuration
+// The default stream, daily, is the channel and ping role set with set_channel and ping_role
+struct Stream {
+    name: String,
+    channel: Option<ChannelId>,
+    ping_role: String,
+    mention_template: Option<String>,
 }
 
-/// Deletes a custom poll based on a ID
-/// Checks guild_id of the requesting command against the guild_id associated with the poll
-async fn delete_custom_poll(guild_id: String, id_to_delete: i32, ctx: &Context) -> i32 {
-    // Pulling in psql client
-    let read = ctx.data.read().await;
-    let client = read.get::<DataClient>().expect("PSQL Client error").clone();
+// Option added to a posted poll with amend_poll
+struct PollAmendment {
+    option: String,
+    emoji: String,
+    votes: i32,
+    // Tallies of the first two options when the option was added
+    votes_before: (i32, i32),
+    amended_at: DateTime<Utc>,
+}
 
-    // Checking if a poll with the guild_id of the requesting server exists, if it exists, delete the question.
-    // This prevents from other servers deleting each others questions.
-    let rows = client
-        .query(
-            "SELECT * FROM custom_polls WHERE guild_id = $1 AND poll_id = $2",
-            &[&guild_id, &id_to_delete],
-        )
-        .await
-        .expect("Select Failed");
-    if !rows.is_empty() {
-        let _delete = client
-            .execute(
-                "DELETE FROM custom_polls WHERE poll_id = $1",
-                &[&id_to_delete],
-            )
-            .await
-            .expect("Delete failed");
+// Limits Discord puts on the length of a message and its embed, in characters
+const CONTENT_LIMIT: usize = 2000;
+const EMBED_TITLE_LIMIT: usize = 256;
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+const EMBED_FOOTER_LIMIT: usize = 2048;
+const EMBED_TOTAL_LIMIT: usize = 6000;
+
+// A question or poll laid out to fit the limits of Discord
+// Whatever doesn't fit in the message is sent in follow up messages replying to it
+struct Post {
+    content: String,
+    embed: Option<PostEmbed>,
+    // Image card attached to the message and shown in the embed
+    card: Option<Arc<Vec<u8>>>,
+    follow_ups: Vec<String>,
+    // Who the message may ping, mentions in the question itself stay inert
+    pings: Pings,
+}
 
-        1
-    } else {
-        0
-    }
+// Who a message is allowed to ping, any other mention in it is shown without pinging
+#[derive(Default)]
+struct Pings {
+    everyone: bool,
+    roles: Vec<RoleId>,
+    users: Vec<UserId>,
 }
 
-#[command]
-async fn help(ctx: &Context, msg: &Message) -> CommandResult {
-    msg.channel_id.send_message(ctx, |m| {
-        m
-            .content(format!("<@{}>", msg.author.id))
-            .embed(|embed| {
-                embed
-                    .title("Help")
-                    .description("
-                    **Current command prefix:** q! \n
-                    \n **Questions**
-                    **qotd** - Sends a random question of the day! \n
-                    **custom_qotd <Optional: id>** - Sends a question of the day from the list of custom questions! \n\
-                    **submit_qotd <question>** - Submit a custom question.\n
-                    **delete_question <id>** - Deletes the specified question from the list of questions.\n
-                    **list_qotd** - Lists all custom questions saved for the server.\n
-                    \n **Polls**
-                    **poll** - Sends a random poll of the day!\n
-                    **custom_poll <Optional: id>** - Sends a poll of the day from a list of custom polls!\n
-                    **submit_poll** - Submits a new custom poll!\n
-                    **delete_poll <id>** - Deletes the specified poll from the list of custom polls\n
-                    **list_polls** - Lists all polls currently saved for the server!\n
-                    \n **Config**
-                    **set_channel** - Sets which channel is used for questions of the day. \n
-                    **channel** - Lists which channel is currently used for questions of the day.\n
-                    **ping_role <0 (default)/1/<role>>** - Sets the ping setting for question of the day. \n
-                    **help** - Brings up this message!")
-                    .color(Color::DARK_GREEN)
-            })
-    }).await?;
+impl Pings {
+    /// The ping set with ping_role, nobody for 0 and @everyone for 1
+    fn for_ping_role(ping_role: &str) -> Pings {
+        match ping_role {
+            "0" => Pings::default(),
+            "1" => Pings {
+                everyone: true,
+                ..Pings::default()
+            },
+            role => Pings {
+                roles: role.parse::<u64>().map(RoleId).into_iter().collect(),
+                ..Pings::default()
+            },
+        }
+    }
 
-    Ok(())
+    fn allow<'a>(&self, mentions: &'a mut CreateAllowedMentions) -> &'a mut CreateAllowedMentions {
+        mentions
+            .empty_parse()
+            .roles(self.roles.clone())
+            .users(self.users.clone());
+        if self.everyone {
+            mentions.parse(ParseValue::Everyone);
+        }
+        mentions
+    }
 }
 
-#[command]
-async fn set_channel(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap(); // lazy solution, expecting the message to exist
+struct PostEmbed {
+    title: Option<String>,
+    description: String,
+    footer: Option<String>,
+    color: Color,
+}
 
-    // If message is a valid message
-    if msg.content.len() >= 14 {
-        // Parsing channel id from the user message
-        match parse_channel(&msg.content[14..]) {
-            Some(cid) => {
-                let channel_id_slice = cid;
+impl Post {
+    /// Lays out a post sent as plain text only
+    fn plain(text: &str) -> Post {
+        let mut parts = split_text(text, CONTENT_LIMIT).into_iter();
+        Post {
+            content: parts.next().unwrap_or_default(),
+            embed: None,
+            card: None,
+            follow_ups: parts.collect(),
+            pings: Pings::default(),
+        }
+    }
 
-                // Checking that the channel is in the server.
-                // We safely assume that this command is being called from a server so not handling null
-                let guild_channels = ctx
-                    .cache
-                    .guild_channels(guild_id)
-                    .await
-                    .ok_or("Command not being called from a guild?")?;
-                let channel_id = ChannelId(channel_id_slice);
+    /// Lays out a post showing an image card in its embed, the text is on the card
+    fn card(content: &str, card: Arc<Vec<u8>>, footer: Option<&str>, color: Color) -> Post {
+        let mut post = Post::embed(content, "", "", footer, color);
+        if let Some(embed) = &mut post.embed {
+            embed.title = None;
+        }
+        post.card = Some(card);
+        post
+    }
 
-                if guild_channels.contains_key(&channel_id) {
-                    // Calling function to set the the stuff to database
-                    set_ping_channel_id(channel_id_slice.to_string(), guild_id.to_string(), ctx)
-                        .await?;
-                    msg.reply(ctx, "Channel set!").await?;
-                } else {
-                    msg.reply(ctx, "Channel not found on this server!").await?;
-                }
-            }
-            None => {
-                msg.reply(ctx, "Not a valid channel!").await?;
+    /// Lays out a post with a content line and an embed.
+    /// Content past the limit and the end of a too long description become follow ups,
+    /// a too long title moves into the description and a too long footer is cut short.
+    fn embed(
+        content: &str,
+        title: &str,
+        description: &str,
+        footer: Option<&str>,
+        color: Color,
+    ) -> Post {
+        let mut parts = split_text(content, CONTENT_LIMIT).into_iter();
+        let content = parts.next().unwrap_or_default();
+        let mut follow_ups: Vec<String> = parts.collect();
+
+        let (title, description) = if title.chars().count() > EMBED_TITLE_LIMIT {
+            (None, format!("**{}**\n{}", title, description))
+        } else {
+            (Some(title.to_string()), description.to_string())
+        };
+        let mut descriptions = split_text(&description, EMBED_DESCRIPTION_LIMIT).into_iter();
+        let description = descriptions.next().unwrap_or_default();
+        for rest in descriptions {
+            follow_ups.extend(split_text(&rest, CONTENT_LIMIT));
+        }
+
+        let used =
+            title.as_ref().map_or(0, |title| title.chars().count()) + description.chars().count();
+        let footer_limit = EMBED_FOOTER_LIMIT.min(EMBED_TOTAL_LIMIT - used);
+        let footer = footer.map(|footer| {
+            if footer.chars().count() > footer_limit {
+                let cut: String = footer.chars().take(footer_limit - 1).collect();
+                format!("{}…", cut)
+            } else {
+                footer.to_string()
             }
+        });
+
+        Post {
+            content,
+            embed: Some(PostEmbed {
+                title,
+                description,
+                footer,
+                color,
+            }),
+            card: None,
+            follow_ups,
+            pings: Pings::default(),
         }
     }
-    // If message isn't long enough or something else broken in it
-    else {
-        msg.reply(ctx, "Not a valid channel!").await?;
-    }
-
-    Ok(())
-}
-
-#[command]
-async fn channel(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap(); // lazy solution, expecting the message to exist
 
-    let channel_id = get_ping_channel_id(guild_id.to_string(), ctx).await;
-
-    // Slightly convoluted. If the string returned is a 0, that means there was no result
-    // This assumes channel id 0 does not exist on any server (safe assumption)
-    // If the string returned isn't a 0, it's the id of the channel assigned
-    // which is then used for parse_channel.
+    /// Lets the post ping who it is meant to, by default it pings nobody
+    fn with_pings(mut self, pings: Pings) -> Post {
+        self.pings = pings;
+        self
+    }
 
-    // Fails if string was 0 and there was no result. Please don't judge me for this solution.
-    match parse_channel(&channel_id) {
-        Some(_cid) => {
-            msg.reply(ctx, format!("Channel is set to {}", channel_id))
-                .await?;
+    /// Fills the message of the post in, components can be added after
+    fn build<'a, 'b>(&self, message: &'b mut CreateMessage<'a>) -> &'b mut CreateMessage<'a> {
+        if !self.content.is_empty() {
+            message.content(&self.content);
         }
-        None => {
-            msg.reply(ctx, "Channel not set!").await?;
+        message.allowed_mentions(|mentions| self.pings.allow(mentions));
+        if let Some(card) = &self.card {
+            message.add_file(AttachmentType::Bytes {
+                data: Cow::Owned(card.to_vec()),
+                filename: String::from(CARD_FILENAME),
+            });
+        }
+        if let Some(post_embed) = &self.embed {
+            message.embed(|embed| {
+                if let Some(title) = &post_embed.title {
+                    embed.title(title);
+                }
+                if !post_embed.description.is_empty() {
+                    embed.description(&post_embed.description);
+                }
+                if self.card.is_some() {
+                    embed.image(format!("attachment://{}", CARD_FILENAME));
+                }
+                embed.color(post_embed.color);
+                if let Some(footer) = &post_embed.footer {
+                    embed.footer(|f| f.text(footer));
+                }
+                embed
+            });
         }
+        message
     }
 
-    Ok(())
-}
-
-#[command]
-async fn qotd(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
-    let question = get_random_question(ctx).await;
-    let channel_id = get_ping_channel_id(guild_id.to_string(), ctx).await;
-    let ping_role = get_ping_role(guild_id.to_string(), ctx).await;
-    let question_string =
-        format_string_for_pings(ping_role, String::from("Question of the day!")).await;
+    /// Sends the post, and the follow ups replying to it
+    async fn send(&self, ctx: &Context, channel: ChannelId) -> Result<Message, SerenityError> {
+        let message = channel.send_message(ctx, |m| self.build(m)).await?;
+        self.send_follow_ups(ctx, channel, &message).await?;
+        Ok(message)
+    }
 
-    match parse_channel(&channel_id) {
-        Some(cid) => {
-            // Sending message to the channel assigned to the server
-            let channel = ChannelId(cid);
+    /// Sends what didn't fit in the message as replies to it
+    async fn send_follow_ups(
+        &self,
+        ctx: &Context,
+        channel: ChannelId,
+        message: &Message,
+    ) -> Result<(), SerenityError> {
+        for follow_up in &self.follow_ups {
             channel
-                .send_message(ctx, |message| {
-                    message.content(question_string).embed(|embed| {
-                        embed
-                            .title("Question")
-                            .description(question)
-                            .color(Color::FABLED_PINK)
-                    })
+                .send_message(ctx, |m| {
+                    m.content(follow_up)
+                        .reference_message(message)
+                        .allowed_mentions(|mentions| mentions.empty_parse())
                 })
                 .await?;
         }
-        None => {
-            msg.reply(ctx, "Channel not set!").await?;
-        }
+        Ok(())
     }
+}
 
-    Ok(())
+// How to resolve an imported entry that already exists in the guilds pool
+enum MergeChoice {
+    Skip,
+    KeepBoth,
+    Replace,
+    SkipRest,
 }
 
-#[command]
-async fn custom_qotd(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
-    let custom_question;
-    let channel_id = get_ping_channel_id(guild_id.to_string(), ctx).await;
-    let ping_role = get_ping_role(guild_id.to_string(), ctx).await;
+// Commands available to every member, the rest are admin commands or owner commands
+const MEMBER_COMMANDS: &[&str] = &[
+    "saved",
+    "notify",
+    "answer",
+    "trivia_scores",
+    "history",
+    "top_questions",
+    "suggest",
+];
+
+/// Gets the store of the bot, for the questions, polls and settings of guilds
+async fn store(ctx: &Context) -> Arc<dyn Store> {
+    ctx.data
+        .read()
+        .await
+        .get::<DataStore>()
+        .expect("Store error")
+        .clone()
+}
 
-    if msg.content.len() >= 14 {
-        match &msg.content[14..].parse::<i32>() {
-            Ok(id_to_use) => {
-                let id_to_use = *id_to_use;
-                custom_question =
-                    get_specific_custom_question(guild_id.to_string(), id_to_use, ctx).await;
-            }
-            _ => {
-                msg.reply(ctx, "Not a valid question ID").await?;
-                return Ok(());
-            }
+/// Logs a database error where there's no reply to show it in, the caller goes on without the value
+fn logged<T>(result: Result<T, DbError>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            println!("Database error: {}", e);
+            None
         }
-    } else {
-        custom_question = get_random_custom_question(guild_id.to_string(), ctx).await;
     }
+}
 
-    let question_string =
-        format_string_for_pings(ping_role, String::from("Question of the day!")).await;
-
-    match parse_channel(&channel_id) {
-        Some(channel) => {
-            // Sending message to the channel assigned to the server
-            let channel = ChannelId(channel);
-            channel
-                .send_message(ctx, |message| {
-                    message.content(question_string).embed(|embed| {
-                        embed
-                            .title("Custom Question")
-                            .description(custom_question)
-                            .color(Color::FABLED_PINK)
-                    })
-                })
-                .await?;
+/// Keeps the slowest query of the command being dispatched, queries outside of commands aren't timed
+fn record_query_time(statement: &str, elapsed: Duration) {
+    let _ = COMMAND_TIMING.try_with(|timing| {
+        let mut timing = timing.borrow_mut();
+        let slower = timing
+            .slowest_query
+            .as_ref()
+            .is_none_or(|(_, slowest)| elapsed > *slowest);
+        if slower {
+            timing.slowest_query = Some((statement.to_string(), elapsed));
         }
-        None => {
-            msg.reply(ctx, "Channel not set!").await?;
-        }
-    }
-
-    Ok(())
+    });
 }
 
-#[command]
-async fn submit_qotd(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
-    let user_submission;
+/// Waits for the user to answer a prompt, the time waited doesn't count towards the latency budget
+async fn waiting_on_user<F: Future>(wait: F) -> F::Output {
+    let started = Instant::now();
+    let output = wait.await;
+    let _ = COMMAND_TIMING.try_with(|timing| timing.borrow_mut().waited += started.elapsed());
+    output
+}
 
-    // Could add regex for bad words etc here.
-    // If message is valid
-    if msg.content.len() >= 14 {
-        user_submission = &msg.content[14..];
+/// Warns about commands going over the latency budget, COMMAND_BUDGET_MS or 2000 milliseconds.
+/// The warning is logged as JSON with the slowest query of the command, and a note is added to the reply.
+async fn check_latency_budget(ctx: &Context, invocation: &Invocation) {
+    let (elapsed, slowest_query) = match COMMAND_TIMING.try_with(|timing| {
+        let timing = timing.borrow();
+        (
+            timing.started.elapsed().saturating_sub(timing.waited),
+            timing.slowest_query.clone(),
+        )
+    }) {
+        Ok(timing) => timing,
+        Err(_) => return,
+    };
+    let budget = env::var("COMMAND_BUDGET_MS")
+        .ok()
+        .and_then(|budget| budget.parse::<u64>().ok())
+        .unwrap_or(2000);
+    if elapsed < Duration::from_millis(budget) {
+        return;
+    }
 
-        if question_is_under_limit(guild_id.to_string(), ctx).await {
-            match add_custom_question(guild_id.to_string(), user_submission.to_string(), ctx).await
-            {
-                Ok(_s) => {
-                    msg.reply(ctx, "Question Submitted").await?;
-                }
-                Err(e) => {
-                    println!("{}", e);
-                    msg.reply(ctx, "Something went wrong!").await?;
-                }
+    let (query, query_ms) = match slowest_query {
+        Some((query, took)) => (
+            Some(query.split_whitespace().collect::<Vec<&str>>().join(" ")),
+            Some(took.as_millis() as u64),
+        ),
+        None => (None, None),
+    };
+    println!(
+        "{}",
+        json!({
+            "level": "warn",
+            "event": "slow_command",
+            "command": invocation.name,
+            "guild_id": invocation.guild_id.map(|guild_id| guild_id.to_string()),
+            "elapsed_ms": elapsed.as_millis() as u64,
+            "budget_ms": budget,
+            "slowest_query": query,
+            "slowest_query_ms": query_ms,
+        })
+    );
+
+    // Noting the load on the reply to the command
+    let first = invocation.replies.lock().unwrap().first().cloned();
+    if let Some(first) = first {
+        if first.content.len() < 1950 {
+            let content = format!("{}\n-# (bot is under heavy load)", first.content);
+            if let Err(e) = invocation.edit_first_reply(ctx, content).await {
+                println!("Adding the load note failed: {}", e);
             }
-        } else {
-            msg.reply(
-                ctx,
-                "Too many custom questions saved! Please delete some before adding more!",
-            )
-            .await?;
         }
-    } else {
-        msg.reply(ctx, "Question not accepted").await?;
     }
+}
 
-    Ok(())
+// Commands adding questions or polls, blocked members can't use them
+const SUBMISSION_COMMANDS: &[&str] = &[
+    "submit_qotd",
+    "suggest",
+    "submit_poll",
+    "build_poll",
+    "draft",
+    "publish_draft",
+];
+
+// Commands that work anywhere when commands are restricted to a channel.
+// restrict so admins can't lock themselves out, the rest are used in answer threads.
+const UNRESTRICTED_COMMANDS: &[&str] = &["restrict", "answer", "trivia_winner"];
+
+/// Gets the channel commands are restricted to in the guild, if the command can't be used in the channel.
+/// Returns None if the command can be used there.
+async fn command_channel_violation(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    command_name: &str,
+) -> Option<ChannelId> {
+    if UNRESTRICTED_COMMANDS.contains(&command_name) {
+        return None;
+    }
+
+    let command_channel = logged(
+        store(ctx)
+            .await
+            .get_command_channel_id(guild_id.to_string())
+            .await,
+    )?;
+    match parse_channel(&command_channel) {
+        Some(cid) if cid != channel_id.0 => Some(ChannelId(cid)),
+        _ => None,
+    }
 }
 
-#[command]
-async fn delete_question(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
+/// Stops members blocked in the guild from submitting, and commands used outside the command channel.
+/// A prefix command outside the command channel is deleted so it doesn't clutter the channel, the notice goes to DMs.
+/// Interactions get a notice only the member sees instead.
+async fn command_allowed(ctx: &Context, invocation: &Invocation) -> bool {
+    let guild_id = match invocation.guild_id {
+        Some(guild_id) => guild_id,
+        None => return true,
+    };
+
+    if SUBMISSION_COMMANDS.contains(&invocation.name)
+        && logged(
+            store(ctx)
+                .await
+                .is_user_blocked(guild_id.to_string(), invocation.author.id.to_string())
+                .await,
+        )
+        .unwrap_or(false)
+    {
+        invocation
+            .refuse(
+                ctx,
+                "You're blocked from submitting questions and polls in this server.",
+            )
+            .await;
+        return false;
+    }
 
-    if msg.content.len() >= 18 {
-        // Parsing id from the message
-        match &msg.content[18..].parse::<i32>() {
-            Ok(id_to_delete) => {
-                let id_to_delete = id_to_delete;
-                let test = delete_custom_question(guild_id.to_string(), *id_to_delete, ctx).await;
-                if test == 1 {
-                    msg.reply(ctx, "Question deleted!").await?;
-                } else {
-                    msg.reply(ctx, "Question not found!").await?;
-                }
+    // Owner commands work anywhere
+    if is_owner_command(invocation.name) {
+        return true;
+    }
+    let channel = match command_channel_violation(
+        ctx,
+        guild_id,
+        invocation.channel_id,
+        invocation.name,
+    )
+    .await
+    {
+        Some(channel) => channel,
+        None => return true,
+    };
+
+    match invocation.message() {
+        Some(msg) => {
+            let notice = format!(
+                "Commands in {} can only be used in {}!",
+                guild_display_name(ctx, &guild_id.to_string()),
+                channel.mention()
+            );
+            if let Err(e) = msg.delete(ctx).await {
+                println!("Deleting command outside the command channel failed: {}", e);
             }
-            _ => {
-                msg.reply(ctx, "Please enter a valid ID!").await?;
+            if let Err(e) = msg.author.direct_message(ctx, |m| m.content(notice)).await {
+                println!("Sending command channel notice failed: {}", e);
             }
         }
-    } else {
-        // Getting all questions
-        let question_list = get_list_custom_questions(guild_id.to_string(), ctx).await;
-
-        // If there are custom questions saved
-        if !question_list.is_empty() {
-            // Formatting vector for printing
-            let length = question_list.len();
-
-            let mut pretty_list = "ID - Question\n".to_string();
-            // Putting the questions onto the list
-            for i in 0..length {
-                let qid: i32 = question_list[i].get(0);
-                let string: String = question_list[i].get(2);
-                pretty_list = format!("{}{} - {} \n", pretty_list, qid, string)
-            }
-            // Listing questions in message
-            msg.channel_id
-                .send_message(ctx, |m| {
-                    m.content(format!(
-                        "<@{}> Please specify the ID of question",
-                        msg.author.id
-                    ))
-                    .embed(|embed| {
-                        embed
-                            .title("Questions")
-                            .description(pretty_list)
-                            .color(Color::DARK_BLUE)
-                    })
-                })
-                .await?;
-        } else {
-            msg.reply(ctx, "No custom questions found!").await?;
+        None => {
+            invocation
+                .refuse(
+                    ctx,
+                    format!(
+                        "Commands can only be used in {} in this server!",
+                        channel.mention()
+                    ),
+                )
+                .await;
         }
     }
-
-    Ok(())
+    false
 }
 
-#[command]
-async fn list_qotd(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
-    // Getting all questions
-    let question_list = get_list_custom_questions(guild_id.to_string(), ctx).await;
-
-    // If there are custom questions saved
-    if !question_list.is_empty() {
-        // Formatting vector for printing
-        let length = question_list.len();
+/// Records the command use, sends settings changes on the settings change stream,
+/// and cleans up the replies of the command, and the command itself, if the guild has cleanup on
+async fn after_command(ctx: &Context, invocation: &Invocation, command_result: CommandResult) {
+    let store = store(ctx).await;
+    check_latency_budget(ctx, invocation).await;
+
+    let guild_id = match invocation.guild_id {
+        Some(guild_id) => guild_id,
+        None => return,
+    };
+
+    if let Err(e) = store
+        .record_command_use(
+            guild_id.to_string(),
+            invocation.name,
+            command_result.is_err(),
+        )
+        .await
+    {
+        println!("Recording command use failed: {}", e);
+    }
 
-        let mut pretty_list = "ID - Question\n".to_string();
-        // Putting the questions onto the list
-        for i in 0..length {
-            let qid: i32 = question_list[i].get(0);
-            let string: String = question_list[i].get(2);
-            pretty_list = format!("{}{} - {} \n", pretty_list, qid, string)
+    if command_result.is_ok() && SETTINGS_COMMANDS.contains(&invocation.name) {
+        let change = SettingsChange {
+            guild_id,
+            channel_id: invocation.channel_id,
+            command: invocation.name.to_string(),
+        };
+        // Nothing listening only means the checklists aren't running yet
+        if let Some(events) = ctx.data.read().await.get::<SettingsEvents>() {
+            let _ = events.send(change);
         }
-        // Listing questions in message
-        msg.channel_id
-            .send_message(ctx, |m| {
-                m.content(format!(
-                    "<@{}> Here's a list of all saved custom questions",
-                    msg.author.id
-                ))
-                .embed(|embed| {
-                    embed
-                        .title("Questions")
-                        .description(pretty_list)
-                        .color(Color::RED)
-                })
-            })
-            .await?;
+    }
+
+    let (seconds, delete_command) = match logged(store.get_cleanup(guild_id.to_string()).await) {
+        Some((Some(seconds), delete_command)) => (seconds, delete_command),
+        _ => return,
+    };
+
+    // Private replies to interactions aren't in the channel
+    let mut to_delete: Vec<MessageId> = if invocation.ephemeral {
+        Vec::new()
     } else {
-        msg.reply(ctx, "No custom questions found!").await?;
+        invocation
+            .replies
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|reply| reply.id)
+            .collect()
+    };
+
+    // Prompts mentioning the admin are sent to the channel, posted questions and polls are left alone.
+    // Interaction ids are snowflakes like message ids, so messages after the interaction are found the same way.
+    let after = match &invocation.source {
+        InvocationSource::Message(msg) => msg.id,
+        InvocationSource::Command(command) => MessageId(command.id.0),
+        InvocationSource::Modal(submit) => MessageId(submit.id.0),
+    };
+    let sent = match invocation
+        .channel_id
+        .messages(ctx, |retriever| retriever.after(after).limit(50))
+        .await
+    {
+        Ok(sent) => sent,
+        Err(e) => {
+            println!("Fetching replies for cleanup failed: {}", e);
+            return;
+        }
+    };
+    let bot_id = ctx.cache.current_user_id();
+    let admin_mention = format!("<@{}>", invocation.author.id);
+    let prompts: Vec<MessageId> = sent
+        .iter()
+        .filter(|message| message.author.id == bot_id)
+        .filter(|message| message.content.starts_with(&admin_mention))
+        .filter(|message| !to_delete.contains(&message.id))
+        .map(|message| message.id)
+        .collect();
+    to_delete.extend(prompts);
+    if let (true, Some(msg)) = (delete_command, invocation.message()) {
+        to_delete.push(msg.id);
     }
 
-    Ok(())
+    for message_id in to_delete {
+        schedule_message_deletion(ctx, guild_id, invocation.channel_id, message_id, seconds).await;
+    }
 }
 
-/// Command to set ping role
-#[command]
-async fn ping_role(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
-    let mut current_role = get_ping_role(guild_id.to_string(), ctx).await;
+struct MessageHandler {
+    // Background loops are started on the first ready, ready fires again on reconnects
+    loops_started: AtomicBool,
+}
 
-    // Checking if there's parameters in the command
-    if msg.content.len() >= 12 {
-        let parameter = &msg.content[12..];
+#[async_trait]
+impl EventHandler for MessageHandler {
+    async fn ready(&self, ctx: Context, ready: Ready) {
+        println!("{} online", ready.user.name);
 
-        // If role parameter is one of the preset options
-        if parameter == "1" || parameter == "0" {
-            match set_ping_role(guild_id.to_string(), String::from(parameter), ctx).await {
-                Ok(_) => {
-                    msg.reply(ctx, "Ping role updated!").await?;
+        // Registering every command as a slash command, this replaces the ones registered before
+        if let Err(e) = ctx
+            .http
+            .create_global_application_commands(&slash_command_registrations())
+            .await
+        {
+            println!("Registering application commands failed: {}", e);
+        }
+
+        if !self.loops_started.swap(true, Ordering::SeqCst) {
+            // The HTTP API only runs if an address is set for it
+            if let Ok(address) = env::var("API_ADDRESS") {
+                tokio::spawn(run_api(address, ctx.clone()));
+            }
+            tokio::spawn(run_presence(ctx.clone()));
+            tokio::spawn(run_db_supervisor(ctx.clone()));
+            tokio::spawn(run_leader_election(ctx.clone()));
+            tokio::spawn(run_deadlines(ctx.clone()));
+            tokio::spawn(run_dm_queue(ctx.clone()));
+            tokio::spawn(run_outbox(ctx.clone()));
+            tokio::spawn(run_checklists(ctx.clone()));
+            tokio::spawn(run_purge(ctx.clone()));
+            tokio::spawn(run_scheduler(ctx));
+        }
+    }
+
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        if let Interaction::ApplicationCommand(command) = interaction {
+            if command.data.kind == CommandType::Message {
+                if command.data.name == SAVE_AS_QOTD {
+                    save_message_as_question(ctx, command).await;
                 }
-                Err(e) => {
-                    println!("{}", e);
-                    msg.reply(ctx, "Something went wrong!").await?;
+                return;
+            }
+
+            // /qotd outside of servers the bot is in only draws from the global pool, there are no settings there
+            let in_bot_guild = command
+                .guild_id
+                .is_some_and(|guild_id| ctx.cache.guild(guild_id).is_some());
+            if command.data.name == "qotd" && !in_bot_guild {
+                let (question, attribution) = logged(get_random_question(&ctx).await)
+                    .flatten()
+                    .unwrap_or((String::from("No questions available right now!"), None));
+                let response = command
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| {
+                                d.embed(|embed| {
+                                    embed
+                                        .title("Question")
+                                        .description(&question)
+                                        .color(Color::FABLED_PINK);
+                                    if let Some(attribution) = &attribution {
+                                        embed.footer(|footer| footer.text(attribution));
+                                    }
+                                    embed
+                                })
+                            })
+                    })
+                    .await;
+
+                if let Err(e) = response {
+                    println!("Responding to /qotd failed: {}", e);
                 }
+            } else if command.data.name == "submit_qotd" && command.data.options.is_empty() {
+                // Without the question given inline it is typed into a modal, which fits long questions better
+                show_submission_modal(ctx, command).await;
+            } else {
+                dispatch_slash_command(ctx, command).await;
             }
-        }
-        // Else check whether the role is valid, and submit it if it is
-        else {
-            // If role is a valid role, submit it to the database
-            match parse_role(parameter) {
-                Some(role) => {
-                    match set_ping_role(guild_id.to_string(), role.to_string(), ctx).await {
-                        Ok(_) => {
-                            msg.reply(ctx, "Ping role updated!").await?;
-                        }
-                        Err(e) => {
-                            println!("{}", e);
-                            msg.reply(ctx, "Something went wrong!").await?;
-                        }
-                    }
+        } else if let Interaction::Autocomplete(autocomplete) = interaction {
+            suggest_ids(ctx, autocomplete).await;
+        } else if let Interaction::ModalSubmit(submit) = interaction {
+            if submit.data.custom_id == "submit_qotd" {
+                submit_from_modal(ctx, submit).await;
+            }
+        } else if let Interaction::MessageComponent(component) = interaction {
+            // Votes on anonymous and button polls, other buttons are handled by the collectors waiting on them
+            let option = match component.data.custom_id.as_str() {
+                "poll_vote_1" => 1,
+                "poll_vote_2" => 2,
+                "poll_vote_3" => 3,
+                _ => return,
+            };
+
+            // Weighted polls count the vote by the roles of the member
+            let role_ids = component.member.as_ref().map_or(Vec::new(), |member| {
+                member.roles.iter().map(|role| role.to_string()).collect()
+            });
+            let vote = store(&ctx)
+                .await
+                .cast_anonymous_vote(
+                    component.message.id.to_string(),
+                    component.user.id.to_string(),
+                    option,
+                    role_ids,
+                )
+                .await;
+            let reply = match vote {
+                Ok(Some(_)) => "Vote counted!",
+                Ok(None) => "This poll is closed!",
+                Err(ref e) => {
+                    println!("Counting anonymous vote failed: {}", e);
+                    "Something went wrong!"
                 }
-                None => {
-                    msg.reply(ctx, "Not a valid role!").await?;
+            };
+
+            let response = component
+                .create_interaction_response(&ctx.http, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|d| d.content(reply).ephemeral(true))
+                })
+                .await;
+
+            if let Err(e) = response {
+                println!("Responding to poll vote failed: {}", e);
+            }
+
+            // Editing the tallies in after responding, the response has to be sent within a few seconds
+            if let Ok(Some(tallies)) = vote {
+                let shown =
+                    show_live_tallies(&ctx, component.channel_id, component.message.id, tallies)
+                        .await;
+                if let Err(e) = shown {
+                    println!("Showing live tallies failed: {}", e);
                 }
             }
         }
     }
-    // If no parameters, send default help message
-    else {
-        // Formatting current role to taggable form if it's not 0 or 1
-        if (current_role != *"1") && (current_role != *"0") {
-            // No need to check if the role is a valid role, validity is checked on submission to the database.
-            current_role = format!("<@&{}>", current_role);
+
+    // Grading answers in the answer threads of trivia questions.
+    // Messages have no content in slash only mode, answers are given with the answer command there
+    async fn message(&self, ctx: Context, msg: Message) {
+        // Commands used in the thread aren't answers
+        if msg.author.bot || slash_only() || msg.content.to_lowercase().starts_with("q!") {
+            return;
         }
-        // Crafting message
-        msg.channel_id
-            .send_message(ctx, |m| {
-                m.content(format!(
-                    "<@{}> Use this command to set the role to be pinged when posting a qotd \n \
-                    Current setting is {}",
-                    msg.author.id, current_role
-                ))
-                .embed(|embed| {
-                    embed
-                        .title("Parameters")
-                        .description("<role> - Specific role \n 1 - Everyone \n 0 - Off (default)")
-                })
-            })
-            .await?;
-    }
 
-    Ok(())
-}
+        let guild_id = match msg.guild_id {
+            Some(guild_id) => guild_id,
+            None => return,
+        };
+        let announcement =
+            match grade_trivia_answer(&ctx, guild_id, msg.channel_id, msg.author.id, &msg.content)
+                .await
+            {
+                Ok(Some(announcement)) => announcement,
+                Ok(None) => return,
+                Err(e) => {
+                    println!("Grading trivia answer failed: {}", e);
+                    return;
+                }
+            };
+        if let Err(e) = msg.react(&ctx, Unicode(String::from("✅"))).await {
+            println!("Reacting to trivia answer failed: {}", e);
+        }
+        if let Err(e) = msg.reply(&ctx, announcement).await {
+            println!("Announcing trivia winner failed: {}", e);
+        }
+    }
 
-#[command]
-async fn poll(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
-    let poll = get_random_poll(ctx).await;
-    let channel_id = get_ping_channel_id(guild_id.to_string(), ctx).await;
-    let ping_role = get_ping_role(guild_id.to_string(), ctx).await;
-    let poll_string = format_string_for_pings(ping_role, String::from("Poll of the day!")).await;
+    // Guilds are sent on each startup too, a join is only recorded if the guild isn't counted as joined already
+    async fn guild_create(&self, ctx: Context, guild: Guild, _is_new: bool) {
+        if let Err(e) = store(&ctx)
+            .await
+            .record_guild_event(guild.id.to_string(), "join")
+            .await
+        {
+            println!("Recording guild join failed: {}", e);
+        }
+    }
 
-    match parse_channel(&channel_id) {
-        Some(cid) => {
-            // Sending message to the channel assigned to the server
-            let channel = ChannelId(cid);
-            let message = channel
-                .send_message(ctx, |message| {
-                    message.content(poll_string).embed(|embed| {
-                        embed
-                            .title(&poll[0])
-                            .description(format!("🟠 - {}\n🔵 - {}", &poll[1], &poll[2]))
-                            .color(Color::DARK_MAGENTA)
-                    })
-                })
-                .await?;
-            // Orange circle unicode
-            message.react(ctx, Unicode(String::from("🟠"))).await?;
-            // Blue circle unicode
-            message.react(ctx, Unicode(String::from("🔵"))).await?;
+    // Guilds going unavailable in an outage are still joined
+    // The owner is only known if the guild was cached
+    async fn guild_delete(&self, ctx: Context, incomplete: UnavailableGuild, full: Option<Guild>) {
+        if incomplete.unavailable {
+            return;
         }
-        None => {
-            msg.reply(ctx, "Channel not set!").await?;
+        if let Err(e) = store(&ctx)
+            .await
+            .record_guild_event(incomplete.id.to_string(), "leave")
+            .await
+        {
+            println!("Recording guild leave failed: {}", e);
+        }
+        if let Some(guild) = full {
+            send_removal_export(&guild, &ctx).await;
         }
     }
 
-    Ok(())
-}
+    // Saving posted questions for users reacting with the guilds save emoji
+    async fn reaction_add(&self, ctx: Context, reaction: Reaction) {
+        let (guild_id, user_id) = match (reaction.guild_id, reaction.user_id) {
+            (Some(guild_id), Some(user_id)) => (guild_id, user_id),
+            _ => return,
+        };
 
-#[command]
-async fn submit_poll(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
-    let user_submission;
+        if user_id == ctx.cache.current_user_id() {
+            return;
+        }
 
-    // Could add regex for bad words etc here.
-    // If message has content
-    if msg.content.len() >= 14 {
-        user_submission = &msg.content[14..];
-        let split = user_submission.split('\n'); // Splitting message to its parts
+        // Tallying votes on posted polls
+        if let Err(e) = store(&ctx)
+            .await
+            .update_poll_tally(
+                reaction.message_id.to_string(),
+                reaction.emoji.to_string(),
+                1,
+            )
+            .await
+        {
+            println!("Tallying poll vote failed: {}", e);
+        }
+        tally_double_post_vote(&ctx, &reaction, 1).await;
+        tally_question_rating(&ctx, &reaction, 1).await;
 
-        // Converting slices to strings
-        let mut full_poll: Vec<String> = vec![];
-        for i in split {
-            full_poll.push(i.to_string());
+        let save_emoji = logged(store(&ctx).await.get_save_emoji(guild_id.to_string()).await);
+        if save_emoji != Some(reaction.emoji.to_string()) {
+            return;
         }
 
-        // If message is in correct format
-        if full_poll.len() == 3 {
-            if poll_is_under_limit(guild_id.to_string(), ctx).await {
-                match add_custom_poll(guild_id.to_string(), full_poll, ctx).await {
-                    Ok(_s) => {
-                        msg.reply(ctx, "Poll Submitted").await?;
-                    }
-                    Err(e) => {
-                        println!("{}", e);
-                        msg.reply(ctx, "Something went wrong!").await?;
-                    }
-                }
-            } else {
-                msg.reply(
-                    ctx,
-                    "Too many custom polls saved! Please delete some before adding more!",
+        if let Err(e) = store(&ctx)
+            .await
+            .save_question_for_user(user_id.to_string(), reaction.message_id.to_string())
+            .await
+        {
+            println!("Saving question failed: {}", e);
+        }
+    }
+
+    // Taking removed votes off the poll tallies
+    async fn reaction_remove(&self, ctx: Context, reaction: Reaction) {
+        match reaction.user_id {
+            Some(user_id) if user_id != ctx.cache.current_user_id() => {}
+            _ => return,
+        }
+
+        if let Err(e) = store(&ctx)
+            .await
+            .update_poll_tally(
+                reaction.message_id.to_string(),
+                reaction.emoji.to_string(),
+                -1,
+            )
+            .await
+        {
+            println!("Tallying poll vote failed: {}", e);
+        }
+        tally_double_post_vote(&ctx, &reaction, -1).await;
+        tally_question_rating(&ctx, &reaction, -1).await;
+    }
+}
+
+/// Counts a reaction with one of RATING_EMOJI as a rating of the posted question
+async fn tally_question_rating(ctx: &Context, reaction: &Reaction, change: i32) {
+    let emoji = reaction.emoji.to_string();
+    if !RATING_EMOJI.contains(&emoji.as_str()) {
+        return;
+    }
+
+    if let Err(e) = store(ctx)
+        .await
+        .update_question_rating(
+            reaction.message_id.to_string(),
+            emoji == RATING_EMOJI[0],
+            change,
+        )
+        .await
+    {
+        println!("Tallying question rating failed: {}", e);
+    }
+}
+
+/// Counts a reaction with one of DOUBLE_CHOICES as a vote on the question of a double post
+async fn tally_double_post_vote(ctx: &Context, reaction: &Reaction, change: i32) {
+    let emoji = reaction.emoji.to_string();
+    let choice = match DOUBLE_CHOICES.iter().position(|choice| *choice == emoji) {
+        Some(choice) => choice as i32,
+        None => return,
+    };
+
+    if let Err(e) = store(ctx)
+        .await
+        .update_double_post_tally(reaction.message_id.to_string(), choice, change)
+        .await
+    {
+        println!("Tallying double post vote failed: {}", e);
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let token = env::var("DISCORD_TOKEN").expect("Discord token not found");
+
+    // Database settings from environment variable.
+    // Format: host= <> dbname= <> user= <> password= <>
+    // With DB_BACKEND=sqlite it's the path of the database file instead
+    let db_connection_settings = env::var("DB_CONNECTION")
+        .expect("Database connection string not found. Set environment variable!");
+
+    let db_pool = match env::var("DB_BACKEND").as_deref() {
+        Ok("sqlite") => {
+            ClientPool::sqlite(db_connection_settings).expect("Opening the SQLite database failed!")
+        }
+        Ok("postgres") | Err(_) => {
+            // Connections are opened as needed, up to DB_POOL_SIZE at once
+            let pool_size = env::var("DB_POOL_SIZE")
+                .ok()
+                .and_then(|size| size.parse::<usize>().ok())
+                .unwrap_or(16);
+            let db_config = db_connection_settings
+                .parse::<tokio_postgres::Config>()
+                .expect("Database connection string is not valid!");
+            ClientPool::postgres(db_config, pool_size)
+        }
+        Ok(backend) => panic!("Unknown DB_BACKEND {}, use postgres or sqlite", backend),
+    };
+    // Checking a connection so a wrong connection string fails on startup
+    db_pool
+        .check()
+        .await
+        .expect("Connection to the database failed!");
+    run_migrations(&db_pool)
+        .await
+        .expect("Migrating the database failed!");
+
+    // The owner of the application, or the owner of the team it belongs to, can use the owner commands
+    let mut owners = HashSet::new();
+    match Http::new(&token).get_current_application_info().await {
+        Ok(info) => {
+            match info.team {
+                Some(team) => owners.insert(team.owner_user_id),
+                None => owners.insert(info.owner.id),
+            };
+        }
+        Err(e) => println!("Getting the owner of the bot failed: {}", e),
+    }
+
+    // Command framework, kept in an Arc so interactions can be run through it too
+    let slash_only = slash_only();
+    let framework = Arc::new(CommandFramework { owners, slash_only });
+
+    // Serenity discord client builder
+    // Message content is a privileged intent, it has to be enabled for the bot in the developer portal.
+    // It isn't requested in slash only mode
+    // Server members is needed for finding who hasn't voted on polls
+    let mut intents = GatewayIntents::non_privileged() | GatewayIntents::GUILD_MEMBERS;
+    if !slash_only {
+        intents |= GatewayIntents::MESSAGE_CONTENT;
+    }
+    let mut discord_client = Client::builder(&token, intents)
+        .event_handler(MessageHandler {
+            loops_started: AtomicBool::new(false),
+        })
+        .framework_arc(framework.clone())
+        .await
+        .expect("Building discord client failed");
+
+    // psql container Arc
+    {
+        let mut data = discord_client.data.write().await;
+        let db_pool = Arc::new(db_pool);
+        data.insert::<DataClient>(db_pool.clone());
+        data.insert::<DataStore>(db_pool);
+        data.insert::<DataFramework>(framework);
+        data.insert::<CardCache>(Arc::new(Mutex::new(HashMap::new())));
+        data.insert::<SettingsEvents>(tokio::sync::broadcast::channel(256).0);
+    }
+
+    // Starting discord client
+    if let Err(e) = discord_client.start().await {
+        println!("Starting client error {}", e)
+    }
+}
+
+/// Picks the channel a qotd or poll command posts in.
+/// Without a configured channel it's the invoking channel if the guild has channel fallback on,
+/// otherwise the command is answered with "Channel not set!" and None is returned.
+async fn posting_channel(
+    ctx: &Context,
+    invocation: &Invocation,
+    channel: Option<ChannelId>,
+) -> CommandResult<Option<ChannelId>> {
+    if channel.is_some() {
+        return Ok(channel);
+    }
+
+    let guild_id = invocation.guild_id.unwrap();
+    if store(ctx)
+        .await
+        .get_channel_fallback(guild_id.to_string())
+        .await?
+    {
+        Ok(Some(invocation.channel_id))
+    } else {
+        invocation.reply(ctx, "Channel not set!").await?;
+        Ok(None)
+    }
+}
+
+/// Gets a random question from the database.
+/// Returns the question string and the pack attribution, if the question came from a pack.
+/// Returns None if there are no questions.
+async fn get_random_question(ctx: &Context) -> Result<Option<(String, Option<String>)>, DbError> {
+    Ok(store(ctx)
+        .await
+        .get_random_question_candidates(1, None, None)
+        .await?
+        .into_iter()
+        .next()
+        .map(|(question, attribution, _)| (question, attribution)))
+}
+
+/// Gets a random question for posting in the guild.
+/// Questions the guild has posted before aren't picked until every question has been used, then the history starts over.
+/// Questions that are also in the guilds custom pool or were posted recently in the guild are rerolled,
+/// so guilds using both sources don't see the same question twice in a row.
+/// Guilds with a question filter only get questions with one of its tags.
+/// Guilds with double days pick questions with the styles their members vote for more often, see style_scores.
+/// The excluded question isn't picked, for the second question of a double post.
+/// Guilds with custom_only on only get their own custom questions, picked like custom_qotd does.
+/// Guilds with a question mix get a custom question the set share of the time,
+/// or a default question when no custom question can be posted.
+/// Unless the guild turned seasonal off, questions with a tag of SEASONS are picked in their season,
+/// and held back outside it while there are other questions to pick.
+/// Guilds with recommend on pick questions like the ones their members answered and saved most more often.
+/// Questions members rated down across every guild are picked less often, ones rated up more often.
+async fn get_random_question_for_guild(
+    guild_id: String,
+    exclude: Option<&str>,
+    ctx: &Context,
+) -> Result<Option<(String, Option<String>)>, DbError> {
+    let store = store(ctx).await;
+    let tags = store.get_question_filter(guild_id.clone()).await?;
+    if store.get_custom_only(guild_id.clone()).await? {
+        return get_random_custom_question_for_guild(guild_id, tags, exclude, ctx).await;
+    }
+    if let Some(custom_share) = store.get_question_mix(guild_id.clone()).await? {
+        if rand::thread_rng().gen_range(0..100) < custom_share {
+            let picked =
+                get_random_custom_question_for_guild(guild_id.clone(), tags.clone(), exclude, ctx)
+                    .await?;
+            if picked.is_some() {
+                return Ok(picked);
+            }
+        }
+    }
+    let mut candidates = store
+        .get_random_question_candidates(10, tags.clone(), Some(guild_id.clone()))
+        .await?;
+    if candidates.is_empty() {
+        if let Err(e) = store
+            .clear_question_history(guild_id.clone(), tags.clone())
+            .await
+        {
+            println!("Clearing question history failed: {}", e);
+        }
+        candidates = store
+            .get_random_question_candidates(10, tags.clone(), Some(guild_id.clone()))
+            .await?;
+    }
+    if store.get_seasonal(guild_id.clone()).await? {
+        let today = Utc::now()
+            .with_timezone(&store.get_guild_timezone(guild_id.clone()).await?)
+            .date_naive();
+        let in_season = seasons_of(today);
+        let mut seasonal = if in_season.is_empty() {
+            Vec::new()
+        } else {
+            store
+                .get_random_question_candidates(10, Some(in_season.clone()), Some(guild_id.clone()))
+                .await?
+        };
+        if let Some(tags) = &tags {
+            seasonal
+                .retain(|(_, _, question_tags)| question_tags.iter().any(|tag| tags.contains(tag)));
+        }
+
+        if !seasonal.is_empty() {
+            candidates = seasonal;
+        } else {
+            let out_of_season = |question_tags: &[String]| {
+                question_tags.iter().any(|tag| {
+                    SEASONS.iter().any(|(season, _, _)| season == tag) && !in_season.contains(tag)
+                })
+            };
+            if candidates
+                .iter()
+                .any(|(_, _, question_tags)| !out_of_season(question_tags))
+            {
+                candidates.retain(|(_, _, question_tags)| !out_of_season(question_tags));
+            }
+        }
+    }
+    let scores = style_scores(&store.get_double_post_votes(guild_id.clone(), 100).await?);
+    let profile = if store.get_recommend(guild_id.clone()).await? {
+        engagement_profile(&store.get_engaged_questions(guild_id.clone(), 50).await?)
+    } else {
+        None
+    };
+    let ratings = store
+        .get_question_ratings(
+            candidates
+                .iter()
+                .map(|(question, _, _)| question.clone())
+                .collect(),
+        )
+        .await?;
+    let taken = store.get_taken_questions(guild_id).await?;
+    let taken: HashSet<String> = taken.iter().map(|q| normalize_question(q)).collect();
+    if let Some(exclude) = exclude {
+        candidates
+            .retain(|(question, _, _)| normalize_question(question) != normalize_question(exclude));
+    }
+
+    // If every candidate is taken the first one is used, posting something beats posting nothing
+    let fresh: Vec<&(String, Option<String>, Vec<String>)> = candidates
+        .iter()
+        .filter(|(question, _, _)| !taken.contains(&normalize_question(question)))
+        .collect();
+    let picked = if scores.is_empty() && profile.is_none() && ratings.is_empty() {
+        fresh.first().copied()
+    } else {
+        fresh
+            .choose_weighted(&mut rand::thread_rng(), |(question, _, tags)| {
+                let style = if scores.is_empty() {
+                    1.0
+                } else {
+                    style_weight(&scores, tags)
+                };
+                let engagement = profile
+                    .as_ref()
+                    .map_or(1.0, |profile| engagement_weight(profile, question, tags));
+                let rating = ratings
+                    .get(question)
+                    .map_or(1.0, |(up, down)| rating_weight(*up, *down));
+                style * engagement * rating
+            })
+            .ok()
+            .copied()
+    };
+    Ok(picked
+        .or(candidates.first())
+        .map(|(question, attribution, _)| (question.clone(), attribution.clone())))
+}
+
+// Reactions members rate posted questions with, thumbs up first
+const RATING_EMOJI: [&str; 2] = ["👍", "👎"];
+
+/// Weight of a question in picking by its ratings, 1 for a question rated as often up as down.
+/// Two thumbs of each are counted on top, so a few ratings don't swing it much.
+fn rating_weight(up: i64, down: i64) -> f64 {
+    (up + 2) as f64 / (down + 2) as f64
+}
+
+/// Adds the RATING_EMOJI reactions to a posted question, so members can rate it
+async fn add_rating_reactions(ctx: &Context, message: &Message) {
+    for emoji in RATING_EMOJI {
+        if let Err(e) = message
+            .react(ctx, ReactionType::Unicode(emoji.to_string()))
+            .await
+        {
+            println!("Adding rating reactions failed: {}", e);
+            return;
+        }
+    }
+}
+
+// What the questions a guild engaged with most have in common.
+// Each tag and word has its share of the engagement of the questions it's in, from 0 to 1.
+struct EngagementProfile {
+    tags: HashMap<String, f64>,
+    words: HashMap<String, f64>,
+}
+
+/// Builds the engagement profile of a guild from the rows of get_engaged_questions.
+/// Returns None if nothing has been engaged with yet.
+fn engagement_profile(rows: &[Row]) -> Option<EngagementProfile> {
+    let mut profile = EngagementProfile {
+        tags: HashMap::new(),
+        words: HashMap::new(),
+    };
+    let mut total = 0.0;
+    for row in rows {
+        let (question, tags, engagement): (String, Option<Vec<String>>, i64) =
+            (row.get(0), row.get(1), row.get(2));
+        let engagement = engagement as f64;
+        total += engagement;
+        for tag in tags.unwrap_or_default() {
+            *profile.tags.entry(tag).or_insert(0.0) += engagement;
+        }
+        for word in question_words(&question) {
+            *profile.words.entry(word).or_insert(0.0) += engagement;
+        }
+    }
+
+    if total == 0.0 {
+        return None;
+    }
+    for share in profile.tags.values_mut().chain(profile.words.values_mut()) {
+        *share /= total;
+    }
+    Some(profile)
+}
+
+/// Gets the words of a question that tell it apart, lowercase and without the short filler words
+fn question_words(question: &str) -> HashSet<String> {
+    question
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 4)
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Weight of a question in picking by how much it looks like the questions the guild engaged with most.
+/// The best tag and the average word count, a question like none of them weighs 0.5 and one like all of them 2.5.
+fn engagement_weight(profile: &EngagementProfile, question: &str, tags: &[String]) -> f64 {
+    let tag_share = tags
+        .iter()
+        .filter_map(|tag| profile.tags.get(tag))
+        .copied()
+        .reduce(f64::max)
+        .unwrap_or(0.0);
+    let words = question_words(question);
+    let word_share = if words.is_empty() {
+        0.0
+    } else {
+        words
+            .iter()
+            .filter_map(|word| profile.words.get(word))
+            .sum::<f64>()
+            / words.len() as f64
+    };
+
+    0.5 + tag_share + word_share
+}
+
+// Seasonal tags of default questions and when they're in season, from month and day to month and day.
+// A season can wrap over the new year.
+type MonthDay = (u32, u32);
+const SEASONS: &[(&str, MonthDay, MonthDay)] = &[
+    ("new_year", (12, 27), (1, 7)),
+    ("valentines", (2, 7), (2, 14)),
+    ("halloween", (10, 15), (10, 31)),
+    ("thanksgiving", (11, 15), (11, 30)),
+    ("christmas", (12, 1), (12, 26)),
+];
+
+/// Gets the tags of the seasons the date is in, see SEASONS
+fn seasons_of(date: NaiveDate) -> Vec<String> {
+    let day = (date.month(), date.day());
+    SEASONS
+        .iter()
+        .filter(|(_, start, end)| {
+            if start <= end {
+                *start <= day && day <= *end
+            } else {
+                *start <= day || day <= *end
+            }
+        })
+        .map(|(season, _, _)| season.to_string())
+        .collect()
+}
+
+/// Gets a random custom question for posting in the guild, picked like custom_qotd does.
+/// The excluded question is rerolled once, a guild with a single question left has no second question to give.
+async fn get_random_custom_question_for_guild(
+    guild_id: String,
+    tags: Option<Vec<String>>,
+    exclude: Option<&str>,
+    ctx: &Context,
+) -> Result<Option<(String, Option<String>)>, DbError> {
+    let store = store(ctx).await;
+    let picked = store
+        .get_random_custom_question(guild_id.clone(), tags.clone())
+        .await?;
+    Ok(match (picked, exclude) {
+        (Some((question, _)), Some(exclude))
+            if normalize_question(&question) == normalize_question(exclude) =>
+        {
+            store
+                .get_random_custom_question(guild_id, tags)
+                .await?
+                .filter(|(question, _)| normalize_question(question) != normalize_question(exclude))
+        }
+        (picked, _) => picked,
+    })
+}
+
+/// Scores each question tag by the share of double post votes the questions with it got, from 0 to 1.
+/// Rows come from get_double_post_votes. Scores are smoothed, so a tag with few votes stays close to 0.5.
+fn style_scores(rows: &[Row]) -> HashMap<String, f64> {
+    let mut votes: HashMap<String, (i64, i64)> = HashMap::new();
+    for row in rows {
+        let (votes_a, votes_b): (i32, i32) = (row.get(1), row.get(3));
+        let total = (votes_a + votes_b) as i64;
+        if total == 0 {
+            continue;
+        }
+        for (tags, won) in [
+            (row.get::<_, Vec<String>>(0), votes_a),
+            (row.get(2), votes_b),
+        ] {
+            for tag in tags {
+                let entry = votes.entry(tag).or_insert((0, 0));
+                entry.0 += won as i64;
+                entry.1 += total;
+            }
+        }
+    }
+
+    votes
+        .into_iter()
+        .map(|(tag, (won, total))| (tag, (won + 1) as f64 / (total + 2) as f64))
+        .collect()
+}
+
+/// Weight of a question in picking by the style scores of its tags, its best tag counts.
+/// Untagged questions and tags without votes weigh as much as a tag with an even share.
+fn style_weight(scores: &HashMap<String, f64>, tags: &[String]) -> f64 {
+    tags.iter()
+        .filter_map(|tag| scores.get(tag))
+        .copied()
+        .reduce(f64::max)
+        .unwrap_or(0.5)
+}
+
+/// Rates a submitted question with the heuristics of the strictness level.
+/// Returns the reason the question looks suspicious, None if it looks fine.
+fn rate_submission(question: &str, strictness: &str) -> Option<String> {
+    // (shortest, longest, flag any link, flag mentions and shouting)
+    let (min_length, max_length, strict_links, strict_content) = match strictness {
+        "low" => (5, 500, false, false),
+        "high" => (10, 250, true, true),
+        _ => return None,
+    };
+
+    let length = question.chars().count();
+    let lowercase = question.to_lowercase();
+
+    if length < min_length {
+        return Some("too short".to_string());
+    }
+    if length > max_length {
+        return Some("too long".to_string());
+    }
+    // Invites are flagged on every level, other links only on high
+    if lowercase.contains("discord.gg/") || lowercase.contains("discord.com/invite") {
+        return Some("contains an invite".to_string());
+    }
+    if strict_links
+        && (lowercase.contains("http://")
+            || lowercase.contains("https://")
+            || lowercase.contains("www."))
+    {
+        return Some("contains a link".to_string());
+    }
+    if strict_content {
+        if question.contains("<@") || lowercase.contains("@everyone") || lowercase.contains("@here")
+        {
+            return Some("contains a mention".to_string());
+        }
+
+        let letters: Vec<char> = question.chars().filter(|c| c.is_alphabetic()).collect();
+        let uppercase = letters.iter().filter(|c| c.is_uppercase()).count();
+        if letters.len() >= 10 && uppercase * 10 > letters.len() * 7 {
+            return Some("mostly capital letters".to_string());
+        }
+    }
+
+    None
+}
+
+/// Checks the question with an external moderation API if MODERATION_API_URL is set.
+/// The API gets {"input": question} and answers in the {"results": [{"flagged": bool}]} format,
+/// MODERATION_API_KEY is sent as a bearer token if set.
+/// Returns the reason if the API flags the question. Errors are logged and let the question through.
+async fn external_moderation_check(question: &str) -> Option<String> {
+    let api_url = env::var("MODERATION_API_URL").ok()?;
+
+    let mut request = reqwest::Client::new()
+        .post(api_url)
+        .json(&json!({ "input": question }));
+    if let Ok(api_key) = env::var("MODERATION_API_KEY") {
+        request = request.bearer_auth(api_key);
+    }
+
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            println!("Moderation API error: {}", e);
+            return None;
+        }
+    };
+
+    match response.json::<serde_json::Value>().await {
+        Ok(body) => {
+            if body["results"][0]["flagged"].as_bool().unwrap_or(false) {
+                Some("flagged by the moderation service".to_string())
+            } else {
+                None
+            }
+        }
+        Err(e) => {
+            println!("Moderation API error: {}", e);
+            None
+        }
+    }
+}
+
+/// Runs a submission through the rating pipeline of the guild.
+/// Returns the reason the question should be held for moderation, None if it can go straight into the pool.
+async fn rate_question(
+    guild_id: String,
+    question: &str,
+    ctx: &Context,
+) -> Result<Option<String>, DbError> {
+    let strictness = store(ctx).await.get_moderation_strictness(guild_id).await?;
+    if strictness == "off" {
+        return Ok(None);
+    }
+
+    Ok(match rate_submission(question, &strictness) {
+        Some(reason) => Some(reason),
+        None => external_moderation_check(question).await,
+    })
+}
+
+/// Formats the pack name and author of a question for the embed footer.
+/// Returns None for questions that didn't come from a pack.
+fn format_attribution(pack_name: Option<String>, pack_author: Option<String>) -> Option<String> {
+    match (pack_name, pack_author) {
+        (Some(name), Some(author)) => Some(format!("From {} by {}", name, author)),
+        (Some(name), None) => Some(format!("From {}", name)),
+        (None, Some(author)) => Some(format!("By {}", author)),
+        (None, None) => None,
+    }
+}
+
+// Longest question that can be submitted in characters, posts fit it with room for the ping and attribution
+const QUESTION_LENGTH_LIMIT: usize = 1000;
+
+/// Checks a question fits QUESTION_LENGTH_LIMIT, returns the error to reply with if it doesn't
+fn question_length_error(question: &str) -> Option<String> {
+    let length = question.chars().count();
+    (length > QUESTION_LENGTH_LIMIT).then(|| {
+        format!(
+            "Questions can be up to {} characters long, this one is {}!",
+            QUESTION_LENGTH_LIMIT, length
+        )
+    })
+}
+
+/// Normalizes question text for comparing, so case, extra whitespace and trailing punctuation don't matter.
+fn normalize_question(question: &str) -> String {
+    question
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .trim_end_matches(|c: char| c.is_ascii_punctuation())
+        .to_lowercase()
+}
+
+/// Finds the saved custom question of the guild most like the question, close enough to be a repeat of it.
+/// Questions a typo for every five characters apart count as alike, the same as trivia answers.
+/// Returns the id and text of the question, None if none are alike.
+async fn find_similar_question(
+    guild_id: String,
+    question: &str,
+    ctx: &Context,
+) -> Result<Option<(i32, String)>, DbError> {
+    let question = normalize_answer(question);
+    Ok(store(ctx)
+        .await
+        .get_list_custom_questions(guild_id)
+        .await?
+        .iter()
+        .map(|row| {
+            let saved: String = row.get("question_string");
+            let normalized = normalize_answer(&saved);
+            let distance = edit_distance(&question, &normalized);
+            let allowed = question.chars().count().max(normalized.chars().count()) / 5;
+            (row.get("question_id"), saved, distance, allowed)
+        })
+        .filter(|(_, _, distance, allowed)| distance <= allowed)
+        .min_by_key(|(_, _, distance, _)| *distance)
+        .map(|(question_id, saved, _, _)| (question_id, saved)))
+}
+
+/// Warning to add to the reply of a submission that looks like a saved question, empty if it doesn't
+fn similar_question_warning(similar: Option<(i32, String)>) -> String {
+    match similar {
+        Some((question_id, saved)) => format!(
+            "\nIt looks a lot like question {}: \"{}\". Did you mean that one? Use delete_question if it's a repeat.",
+            question_id, saved
+        ),
+        None => String::new(),
+    }
+}
+
+/// Mirrors a posted question or poll into the guilds archive channel, without any pings.
+/// Does nothing if the guild has no archive channel set.
+/// Failing to archive is only logged, the post itself already went through.
+async fn mirror_to_archive(
+    guild_id: String,
+    title: &str,
+    description: &str,
+    color: Color,
+    ctx: &Context,
+) {
+    let archive_channel = logged(store(ctx).await.get_archive_channel_id(guild_id).await);
+
+    if let Some(cid) = archive_channel.and_then(|channel| parse_channel(&channel)) {
+        let archived = Post::embed("", title, description, None, color)
+            .send(ctx, ChannelId(cid))
+            .await;
+
+        if let Err(e) = archived {
+            println!("Archiving failed: {}", e);
+        }
+    }
+}
+
+/// Deletes a message after the delay.
+/// The deletion is also saved as a deadline, so the deadline loop picks it up if the bot restarts before it's done.
+async fn schedule_message_deletion(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    seconds: i32,
+) {
+    let store = store(ctx).await;
+    if let Err(e) = store
+        .add_deadline(
+            "delete_message",
+            guild_id.to_string(),
+            channel_id.to_string(),
+            Some(message_id.to_string()),
+            seconds,
+        )
+        .await
+    {
+        println!("Saving message cleanup failed: {}", e);
+    }
+
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(seconds as u64)).await;
+
+        // Whoever takes the deadline deletes the message, this task or the deadline loop
+        match store
+            .take_message_deadline("delete_message", message_id.to_string())
+            .await
+        {
+            Ok(true) => {
+                if let Err(e) = channel_id.delete_message(&ctx, message_id).await {
+                    println!("Cleaning up message failed: {}", e);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => {
+                println!("Cleaning up message failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Background loop checking for due deadlines every minute
+async fn run_deadlines(ctx: Context) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+
+    loop {
+        interval.tick().await;
+
+        let due = match store(&ctx).await.take_due_deadlines().await {
+            Ok(due) => due,
+            Err(e) => {
+                println!("Checking deadlines failed: {}", e);
+                continue;
+            }
+        };
+
+        for deadline in due {
+            let kind: String = deadline.get(0);
+            let guild_id: String = deadline.get(1);
+            let guild_id = GuildId(guild_id.parse::<u64>().unwrap_or(0));
+            let channel_id: String = deadline.get(2);
+            let channel_id = match channel_id.parse::<u64>() {
+                Ok(channel_id) => ChannelId(channel_id),
+                Err(_) => continue,
+            };
+
+            let message_id: Option<String> = deadline.get(3);
+            let message_id = message_id.and_then(|id| id.parse::<u64>().ok());
+
+            let handled: CommandResult = match (kind.as_str(), message_id) {
+                ("close_thread", _) => close_answer_thread(&ctx, guild_id, channel_id).await,
+                ("close_poll", Some(message_id)) => {
+                    close_poll(&ctx, channel_id, MessageId(message_id))
+                        .await
+                        .map_err(Into::into)
+                }
+                ("poll_nudge", Some(message_id)) => {
+                    nudge_non_voters(&ctx, guild_id, channel_id, MessageId(message_id)).await
+                }
+                // Cleanups left over from before a restart
+                ("delete_message", Some(message_id)) => channel_id
+                    .delete_message(&ctx, MessageId(message_id))
+                    .await
+                    .map_err(Into::into),
+                _ => Ok(()),
+            };
+
+            if let Err(e) = handled {
+                println!("Handling {} deadline failed: {}", kind, e);
+            }
+        }
+    }
+}
+
+// DMs the DM queue sends a second, opening DMs with many members quickly gets bots flagged for spam
+const DM_RATE: i64 = 2;
+// DMs waiting in the queue before new ones are turned away
+const DM_QUEUE_LIMIT: i64 = 20000;
+// Times a DM is tried before it's dropped
+const DM_ATTEMPTS: i32 = 5;
+// Closed DM failures in a row before DM notifications of the member are turned off
+const DM_FAILURE_LIMIT: i32 = 3;
+// Seconds a DM taken from the queue is held for the sender, it's tried again after if the bot stops in between
+const DM_LEASE_SECONDS: i32 = 300;
+
+/// Whether a DM failed because the user doesn't take DMs from the bot
+fn is_closed_dm(error: &SerenityError) -> bool {
+    match error {
+        SerenityError::Http(error) => matches!(
+            error.as_ref(),
+            HttpError::UnsuccessfulRequest(response) if response.error.code == 50007
+        ),
+        _ => false,
+    }
+}
+
+/// Sends a DM taken from the queue. Sent DMs are taken off the queue and other failures are retried
+/// with a growing delay, up to DM_ATTEMPTS tries. DMs to closed DMs aren't retried but count towards unsubscribing.
+async fn send_queued_dm(ctx: &Context, dm: &Row) -> Result<(), DbError> {
+    let store = store(ctx).await;
+    let dm_id: i32 = dm.get(0);
+    let user_id: String = dm.get(1);
+    let content: String = dm.get(2);
+    let embed_title: Option<String> = dm.get(3);
+    let embed_description: Option<String> = dm.get(4);
+    let attempts: i32 = dm.get(5);
+
+    let user = match user_id.parse::<u64>() {
+        Ok(user) => UserId(user),
+        Err(_) => {
+            store.remove_queued_dm(dm_id).await?;
+            return Ok(());
+        }
+    };
+
+    let sent = match user.create_dm_channel(ctx).await {
+        Ok(channel) => match (&embed_title, &embed_description) {
+            (Some(title), Some(description)) => {
+                Post::embed(&content, title, description, None, Color::FABLED_PINK)
+                    .send(ctx, channel.id)
+                    .await
+                    .map(|_| ())
+            }
+            _ => channel.say(ctx, &content).await.map(|_| ()),
+        },
+        Err(e) => Err(e),
+    };
+
+    match sent {
+        Ok(()) => {
+            store.remove_queued_dm(dm_id).await?;
+            store.clear_dm_failures(user_id).await?;
+        }
+        Err(e) if is_closed_dm(&e) => {
+            store.remove_queued_dm(dm_id).await?;
+            if store.record_dm_failure(user_id.clone()).await? {
+                println!(
+                    "Turned off DM notifications of {}, their DMs are closed",
+                    user_id
+                );
+            }
+        }
+        Err(e) if attempts >= DM_ATTEMPTS => {
+            println!("Giving up on DM to {}: {}", user_id, e);
+            store.remove_queued_dm(dm_id).await?;
+        }
+        Err(e) => {
+            println!("Sending DM to {} failed, trying again: {}", user_id, e);
+            store.retry_queued_dm(dm_id, 60 << attempts).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Background loop sending queued DMs, DM_RATE a second.
+/// DMs are sent one after another, so when Discord slows the bot down the queue waits instead of piling up requests
+async fn run_dm_queue(ctx: Context) {
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+
+        let due = match store(&ctx).await.take_due_dms().await {
+            Ok(due) => due,
+            Err(e) => {
+                println!("Checking DM queue failed: {}", e);
+                continue;
+            }
+        };
+
+        for dm in due {
+            if let Err(e) = send_queued_dm(&ctx, &dm).await {
+                println!("Updating DM queue failed: {}", e);
+            }
+        }
+    }
+}
+
+/// DMs the owner of a guild the bot was removed from an export of the guild, so its questions aren't lost.
+/// Guilds without custom questions or polls and guilds that turned it off with removal_export are skipped.
+async fn send_removal_export(guild: &Guild, ctx: &Context) {
+    if !logged(
+        store(ctx)
+            .await
+            .get_removal_export(guild.id.to_string())
+            .await,
+    )
+    .unwrap_or(false)
+    {
+        return;
+    }
+
+    let (data, questions, polls) = match build_guild_export(guild.id, ctx).await {
+        Ok(export) => export,
+        Err(e) => {
+            println!("Building removal export failed: {}", e);
+            return;
+        }
+    };
+    if questions == 0 && polls == 0 {
+        return;
+    }
+
+    let filename = format!("qotd_export_{}.json", guild.id);
+    let sent = match guild.owner_id.create_dm_channel(ctx).await {
+        Ok(channel) => channel
+            .send_message(ctx, |m| {
+                m.content(format!(
+                    "I was removed from **{}**, here are its {} custom questions and {} polls with the server settings. \
+                    Add them back to a server with merge_pool.",
+                    guild.name, questions, polls
+                ))
+                .add_file((data.as_slice(), filename.as_str()))
+            })
+            .await
+            .map(|_| ()),
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = sent {
+        println!("Sending removal export failed: {}", e);
+    }
+}
+
+/// Background loop purging custom questions deleted more than RESTORE_DAYS ago, checks every hour
+async fn run_purge(ctx: Context) {
+    let mut interval = tokio::time::interval(Duration::from_secs(3600));
+
+    loop {
+        interval.tick().await;
+
+        match store(&ctx)
+            .await
+            .purge_deleted_questions(RESTORE_DAYS)
+            .await
+        {
+            Ok(0) => {}
+            Ok(purged) => println!("Purged {} deleted custom questions", purged),
+            Err(e) => println!("Purging deleted questions failed: {}", e),
+        }
+    }
+}
+
+/// Opens a discussion thread on a posted question if the guild has an answer window set,
+/// and sets the deadline for closing it.
+/// Trivia questions get a thread without an answer window too, answers in it are graded against the answer.
+/// Failing to open the thread is only logged, the question itself already went through.
+async fn open_answer_thread(
+    guild_id: String,
+    message: &Message,
+    answer: Option<String>,
+    ctx: &Context,
+) {
+    let store = store(ctx).await;
+    let hours = match store.get_answer_window(guild_id.clone()).await {
+        Ok(hours) => hours,
+        Err(e) => {
+            println!("Getting answer window failed: {}", e);
+            return;
+        }
+    };
+    if hours.is_none() && answer.is_none() {
+        return;
+    }
+
+    let thread = message
+        .channel_id
+        .create_public_thread(ctx, message.id, |thread| {
+            thread.name("Answers").auto_archive_duration(10080)
+        })
+        .await;
+
+    match thread {
+        Ok(thread) => {
+            if let Some(answer) = answer {
+                if let Err(e) = store
+                    .add_trivia_round(guild_id.clone(), thread.id.to_string(), answer)
+                    .await
+                {
+                    println!("Saving trivia round failed: {}", e);
+                }
+            }
+            if let Some(hours) = hours {
+                if let Err(e) = store
+                    .add_deadline(
+                        "close_thread",
+                        guild_id,
+                        thread.id.to_string(),
+                        None,
+                        hours * 3600,
+                    )
+                    .await
+                {
+                    println!("Saving thread deadline failed: {}", e);
+                }
+            }
+        }
+        Err(e) => {
+            println!("Opening answer thread failed: {}", e);
+        }
+    }
+}
+
+/// Counts the answers in an answer thread that aren't spam, and the members they came from.
+/// Messages from bots don't count. With content filters, answers shorter than min_length characters
+/// without an attachment and repeats of a members earlier answer don't count either.
+/// With a member limit only that many answers of each member count.
+fn count_answers(
+    messages: &[Message],
+    content_filters: bool,
+    min_length: i32,
+    member_limit: Option<i32>,
+) -> (usize, usize) {
+    let mut counted: HashMap<UserId, (i32, HashSet<String>)> = HashMap::new();
+    let mut answers = 0;
+
+    // Messages come newest first, the earliest answer of a repeat is the one counted
+    for message in messages.iter().rev() {
+        if message.author.bot {
+            continue;
+        }
+        let text = message
+            .content
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_lowercase();
+        if content_filters
+            && message.attachments.is_empty()
+            && text.chars().count() < min_length.max(0) as usize
+        {
+            continue;
+        }
+
+        let (count, seen) = counted.entry(message.author.id).or_default();
+        if member_limit.is_some_and(|limit| *count >= limit) {
+            continue;
+        }
+        if content_filters && !text.is_empty() && !seen.insert(text) {
+            continue;
+        }
+        *count += 1;
+        answers += 1;
+    }
+
+    let members = counted.values().filter(|(count, _)| *count > 0).count();
+    (answers, members)
+}
+
+/// Posts a closing summary in the answer thread, then locks and archives it.
+/// Only answers passing the answer filter of the guild are counted.
+async fn close_answer_thread(
+    ctx: &Context,
+    guild_id: GuildId,
+    thread_id: ChannelId,
+) -> CommandResult {
+    let store = store(ctx).await;
+    let messages = thread_id
+        .messages(ctx, |retriever| retriever.limit(100))
+        .await?;
+    let (min_length, member_limit) = store.get_answer_filter(guild_id.to_string()).await?;
+    // Message contents can't be read in slash only mode, only the member limit applies there
+    let (answers, members) = count_answers(&messages, !slash_only(), min_length, member_limit);
+    // The thread was started from the post, so it has the id of the posted message
+    if let Err(e) = store
+        .record_post_answers(thread_id.to_string(), answers as i32)
+        .await
+    {
+        println!("Recording answers failed: {}", e);
+    }
+
+    let mut summary = format!(
+        "{} answers from {} members. Thanks for answering!",
+        answers, members
+    );
+    // Nobody getting a trivia question right, the answer is revealed when the thread closes
+    if let Some((answer, None)) = store.get_trivia_round(thread_id.to_string()).await? {
+        summary = format!(
+            "{}\nNobody got it, the answer was **{}**.",
+            summary,
+            shown_answer(&answer)
+        );
+    }
+
+    thread_id
+        .send_message(ctx, |message| {
+            message.embed(|embed| {
+                embed
+                    .title("Answers closed!")
+                    .description(summary)
+                    .color(Color::DARK_GREEN)
+            })
+        })
+        .await?;
+
+    thread_id
+        .edit_thread(ctx, |thread| thread.archived(true).locked(true))
+        .await?;
+
+    Ok(())
+}
+
+/// Lowercases an answer and drops punctuation and extra spacing, so answers are compared by their words
+fn normalize_answer(answer: &str) -> String {
+    answer
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Amount of characters to insert, remove or change to turn one string into the other
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Checks a guess against the answer of a trivia question, alternative answers are separated by |.
+/// Short answers have to match exactly, longer ones allow a typo for every five characters.
+fn answer_matches(guess: &str, answer: &str) -> bool {
+    let guess = normalize_answer(guess);
+    if guess.is_empty() {
+        return false;
+    }
+    answer.split('|').map(normalize_answer).any(|accepted| {
+        !accepted.is_empty() && edit_distance(&guess, &accepted) <= accepted.chars().count() / 5
+    })
+}
+
+/// The answer as it's shown to members, the first of the alternatives
+fn shown_answer(answer: &str) -> &str {
+    answer.split('|').next().unwrap_or(answer).trim()
+}
+
+/// Formats an amount of trivia points
+fn format_points(points: i32) -> String {
+    if points == 1 {
+        String::from("1 point")
+    } else {
+        format!("{} points", points)
+    }
+}
+
+/// Grades a guess sent in the answer thread of a trivia question. The first correct answer wins
+/// and the winner gets a point.
+/// Returns the announcement of the winner for the thread, None if the guess didn't win the round.
+async fn grade_trivia_answer(
+    ctx: &Context,
+    guild_id: GuildId,
+    thread_id: ChannelId,
+    author_id: UserId,
+    guess: &str,
+) -> CommandResult<Option<String>> {
+    let store = store(ctx).await;
+    let answer = match store.get_trivia_round(thread_id.to_string()).await? {
+        Some((answer, None)) => answer,
+        _ => return Ok(None),
+    };
+    if !answer_matches(guess, &answer) {
+        return Ok(None);
+    }
+
+    let user_id = author_id.to_string();
+    if !store
+        .set_trivia_winner(thread_id.to_string(), Some(user_id.clone()), false)
+        .await?
+    {
+        return Ok(None);
+    }
+    let points = store
+        .add_trivia_points(guild_id.to_string(), user_id, 1)
+        .await?;
+
+    Ok(Some(format!(
+        "<@{}> got it first! The answer was **{}**. They have {} now.",
+        author_id,
+        shown_answer(&answer),
+        format_points(points)
+    )))
+}
+
+/// Fills {date}, in the guilds timezone, and {question} into a poll text
+async fn fill_poll_text(
+    guild_id: String,
+    text: &str,
+    question: &str,
+    ctx: &Context,
+) -> Result<String, DbError> {
+    let timezone = store(ctx).await.get_guild_timezone(guild_id).await?;
+    let date = Utc::now().with_timezone(&timezone).format("%Y-%m-%d");
+
+    Ok(text
+        .replace("{date}", &date.to_string())
+        .replace("{question}", question))
+}
+
+/// Gets the intro line of the guilds polls with the placeholders filled in
+async fn get_poll_intro(
+    guild_id: String,
+    question: &str,
+    ctx: &Context,
+) -> Result<String, DbError> {
+    let (intro, _) = store(ctx).await.get_poll_texts(guild_id.clone()).await?;
+    fill_poll_text(guild_id, &intro, question, ctx).await
+}
+
+/// Builds the daily stream of the guild from the settings joined into a due schedule row
+fn scheduled_stream(row: &Row) -> Stream {
+    let channel_id: Option<String> = row.get("channel_id");
+
+    Stream {
+        name: String::from("daily"),
+        channel: channel_id
+            .and_then(|channel_id| parse_channel(&channel_id))
+            .map(ChannelId),
+        ping_role: row.get("ping_role"),
+        mention_template: row.get("mention_template"),
+    }
+}
+
+/// Calculates the next scheduled question and poll of the guild again from now.
+/// Used after changing settings the times depend on, like the timezone.
+async fn reschedule_guild(guild_id: String, ctx: &Context) -> Result<(), DbError> {
+    let store = store(ctx).await;
+    let timezone = store.get_guild_timezone(guild_id.clone()).await?;
+    let skip_days = store.get_skip_days(guild_id.clone()).await?;
+
+    if let Some((schedule, _)) = store.get_post_schedule(guild_id.clone()).await? {
+        let next_post_at = next_post_time(&schedule, timezone, &skip_days, Utc::now());
+        store
+            .set_post_schedule(guild_id.clone(), Some(schedule), next_post_at)
+            .await?;
+    }
+    if let Some((schedule, source, _)) = store.get_poll_schedule(guild_id.clone()).await? {
+        let next_poll_at = next_post_time(&schedule, timezone, &skip_days, Utc::now());
+        store
+            .set_poll_schedule(guild_id, Some(schedule), source, next_poll_at)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Parses a stored timezone name, names are validated when saved so anything else falls back to UTC
+fn parse_timezone(timezone: Option<String>) -> Tz {
+    timezone
+        .and_then(|timezone| timezone.parse::<Tz>().ok())
+        .unwrap_or(Tz::UTC)
+}
+
+/// Parses a cron schedule with five fields, minute hour day month weekday
+fn parse_schedule(schedule: &str) -> Option<Cron> {
+    Cron::new(schedule).parse().ok()
+}
+
+/// Reads a schedule given to a command, HH:MM is a daily schedule and anything else is read as a cron expression.
+/// Returns the schedule in the stored cron format, or None if it isn't valid.
+fn parse_schedule_parameter(parameter: &str) -> Option<String> {
+    let time: Vec<Result<u32, _>> = parameter
+        .split(':')
+        .map(|part| part.parse::<u32>())
+        .collect();
+    match time.as_slice() {
+        [Ok(hours), Ok(minutes)] if *hours < 24 && *minutes < 60 => {
+            Some(format!("{} {} * * *", minutes, hours))
+        }
+        _ if parameter.split_whitespace().count() == 5 && parse_schedule(parameter).is_some() => {
+            Some(
+                parameter
+                    .split_whitespace()
+                    .collect::<Vec<&str>>()
+                    .join(" "),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// Gets the first time after the given time the schedule fires in the timezone, on a day that isn't skipped.
+/// Returns None if the schedule is invalid or never fires.
+fn next_post_time(
+    schedule: &str,
+    timezone: Tz,
+    skip_days: &[Weekday],
+    after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let cron = parse_schedule(schedule)?;
+    let mut next = cron
+        .find_next_occurrence(&after.with_timezone(&timezone), false)
+        .ok()?;
+
+    // A skipped day moves the search to the start of the next day, a week covers every weekday
+    for _ in 0..7 {
+        if !skip_days.contains(&next.weekday()) {
+            return Some(next.with_timezone(&Utc));
+        }
+        let next_day = next.date_naive().succ_opt()?.and_hms_opt(0, 0, 0)?;
+        let start = timezone.from_local_datetime(&next_day).earliest()?;
+        next = cron.find_next_occurrence(&start, true).ok()?;
+    }
+
+    None
+}
+
+/// Gets the times the schedule fires between the two times, in the timezone and leaving out skipped days.
+/// At most limit times are returned, so schedules firing every minute stay cheap.
+fn post_times_between(
+    schedule: &str,
+    timezone: Tz,
+    skip_days: &[Weekday],
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+    limit: usize,
+) -> Vec<DateTime<Tz>> {
+    let mut times = Vec::new();
+    let mut after = from;
+    while times.len() < limit {
+        match next_post_time(schedule, timezone, skip_days, after) {
+            Some(next) if next < until => {
+                times.push(next.with_timezone(&timezone));
+                after = next;
+            }
+            _ => break,
+        }
+    }
+    times
+}
+
+/// Parses the stored skip days, anything that isn't a weekday is left out
+fn parse_skip_days(skip_days: Vec<String>) -> Vec<Weekday> {
+    skip_days
+        .iter()
+        .filter_map(|day| day.parse::<Weekday>().ok())
+        .collect()
+}
+
+/// Describes a schedule for messages, daily schedules made with HH:MM are shown as a time
+fn describe_schedule(schedule: &str, timezone: Tz) -> String {
+    let fields: Vec<&str> = schedule.split_whitespace().collect();
+    match fields.as_slice() {
+        [minute, hour, "*", "*", "*"] => match (minute.parse::<u32>(), hour.parse::<u32>()) {
+            (Ok(minute), Ok(hour)) => {
+                format!("every day at {:02}:{:02} {}", hour, minute, timezone)
+            }
+            _ => format!("on the schedule `{}` ({})", schedule, timezone),
+        },
+        _ => format!("on the schedule `{}` ({})", schedule, timezone),
+    }
+}
+
+/// Formats a lead time in minutes for the teaser, whole hours are shown as hours
+fn format_lead_time(minutes: i32) -> String {
+    match minutes {
+        60 => String::from("1 hour"),
+        m if m % 60 == 0 => format!("{} hours", m / 60),
+        1 => String::from("1 minute"),
+        m => format!("{} minutes", m),
+    }
+}
+
+/// Posts a teaser ping ahead of the scheduled question
+async fn post_teaser(ctx: &Context, stream: &Stream, minutes_left: i32) -> CommandResult {
+    let channel = stream.channel.ok_or("Channel not set")?;
+
+    let teaser = format_string_for_pings(
+        stream.ping_role.clone(),
+        format!("QOTD drops in {} 👀", format_lead_time(minutes_left)),
+        None,
+        "daily",
+    )
+    .await;
+
+    let pings = Pings::for_ping_role(&stream.ping_role);
+    channel
+        .send_message(ctx, |m| {
+            m.content(teaser)
+                .allowed_mentions(|mentions| pings.allow(mentions))
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Background loop rotating the presence of the bot through the PRESENCE_MESSAGES set by the owner.
+/// {servers} is replaced with the amount of servers and {questions} with the amount of questions posted.
+async fn run_presence(ctx: Context) {
+    let messages: Vec<String> = env::var("PRESENCE_MESSAGES")
+        .unwrap_or_else(|_| {
+            let help = if slash_only() { "/help" } else { "q!help" };
+            format!(
+                "{} | serving {{servers}} servers;Question #{{questions}} today",
+                help
+            )
+        })
+        .split(';')
+        .map(|message| message.trim().to_string())
+        .filter(|message| !message.is_empty())
+        .collect();
+    if messages.is_empty() {
+        return;
+    }
+    let seconds = env::var("PRESENCE_INTERVAL")
+        .ok()
+        .and_then(|seconds| seconds.parse::<u64>().ok())
+        .unwrap_or(300)
+        .max(60);
+    let mut interval = tokio::time::interval(Duration::from_secs(seconds));
+
+    for message in messages.iter().cycle() {
+        interval.tick().await;
+
+        let mut text = message.replace("{servers}", &ctx.cache.guild_count().to_string());
+        if text.contains("{questions}") {
+            match store(&ctx).await.get_posted_question_count().await {
+                Ok(count) => text = text.replace("{questions}", &count.to_string()),
+                Err(e) => {
+                    println!("Counting posted questions failed: {}", e);
+                    continue;
+                }
+            }
+        }
+        ctx.set_activity(Activity::playing(text)).await;
+    }
+}
+
+/// Checks the database connection every 30 seconds, and when it's broken reconnects with backoff.
+/// The reconnected pool replaces the broken one in the client data, so commands pick it up.
+async fn run_db_supervisor(ctx: Context) {
+    let mut interval = tokio::time::interval(Duration::from_secs(30));
+
+    loop {
+        interval.tick().await;
+
+        let broken = ctx
+            .data
+            .read()
+            .await
+            .get::<DataClient>()
+            .expect("PSQL Client error")
+            .clone();
+        let e = match broken.check().await {
+            Ok(()) => continue,
+            Err(e) => e,
+        };
+        println!("Database connection lost: {}", e);
+        // Failing the connections still checked out instead of leaving them hanging
+        broken.close();
+
+        // Waiting 1, 2, 4... seconds between attempts, up to a minute
+        let mut backoff = 1;
+        loop {
+            let reconnected = match broken.reopen() {
+                Ok(pool) => pool.check().await.map(|()| pool),
+                Err(e) => Err(e.to_string()),
+            };
+            match reconnected {
+                Ok(pool) => {
+                    let pool = Arc::new(pool);
+                    let mut data = ctx.data.write().await;
+                    data.insert::<DataClient>(pool.clone());
+                    data.insert::<DataStore>(pool);
+                    println!("Reconnected to the database");
+                    break;
+                }
+                Err(e) => {
+                    println!(
+                        "Reconnecting to the database failed, retrying in {} seconds: {}",
+                        backoff, e
+                    );
+                    tokio::time::sleep(Duration::from_secs(backoff)).await;
+                    backoff = (backoff * 2).min(60);
+                }
+            }
+        }
+    }
+}
+
+// Whether this process leads the scheduled posts, only the leader of a deployment runs the scheduler and outbox
+static LEADING: AtomicBool = AtomicBool::new(false);
+
+/// Background loop electing the process that runs the scheduler and outbox, when more than one process
+/// serves the bot. Every process serves commands, the others take over within 15 seconds if the leader goes away.
+async fn run_leader_election(ctx: Context) {
+    let mut interval = tokio::time::interval(Duration::from_secs(15));
+    let mut lock: Option<LeaderLock> = None;
+
+    loop {
+        interval.tick().await;
+
+        if let Some(held) = &lock {
+            if held.is_held().await {
+                continue;
+            }
+            println!("Lost the lead of scheduled posts, its database connection closed");
+            LEADING.store(false, Ordering::SeqCst);
+            lock = None;
+        }
+
+        let pool = ctx
+            .data
+            .read()
+            .await
+            .get::<DataClient>()
+            .expect("PSQL Client error")
+            .clone();
+        match pool.try_lead().await {
+            Ok(Some(held)) => {
+                println!("Leading scheduled posts");
+                lock = Some(held);
+                LEADING.store(true, Ordering::SeqCst);
+            }
+            Ok(None) => {}
+            Err(e) => println!("Electing the scheduled posts leader failed: {}", e),
+        }
+    }
+}
+
+/// Background loop posting the scheduled questions, checks every minute in the leading process only
+async fn run_scheduler(ctx: Context) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+
+    loop {
+        interval.tick().await;
+        if !LEADING.load(Ordering::SeqCst) {
+            continue;
+        }
+
+        // Everything due is loaded at once so the tick stays one query however many guilds there are
+        let due = match store(&ctx).await.get_due_schedules(Utc::now()).await {
+            Ok(due) => due,
+            Err(e) => {
+                println!("Checking scheduled posts failed: {}", e);
+                continue;
+            }
+        };
+
+        for row in &due {
+            let post_schedule: Option<String> = row.get("post_schedule");
+            if let Some(schedule) = post_schedule {
+                check_schedule(&ctx, row, schedule).await;
+            }
+            let poll_schedule: Option<String> = row.get("poll_schedule");
+            if let Some(schedule) = poll_schedule {
+                check_poll_schedule(&ctx, row, schedule).await;
+            }
+        }
+    }
+}
+
+/// Posts the guilds scheduled poll if it's due, in the guilds own timezone
+async fn check_poll_schedule(ctx: &Context, row: &Row, schedule: String) {
+    let guild_id_string: String = row.get("guild_id");
+    let guild_id = match guild_id_string.parse::<u64>() {
+        Ok(guild_id) => GuildId(guild_id),
+        Err(_) => return,
+    };
+    let next_poll_at: Option<DateTime<Utc>> = row.get("next_poll_at");
+    let timezone = parse_timezone(row.get("timezone"));
+    let skip_days = parse_skip_days(row.get("skip_days"));
+
+    let now = Utc::now();
+    if next_poll_at.is_some_and(|next| next > now) {
+        return;
+    }
+
+    // Same as questions, missed runs are posted once and a guild without a due time only gets it calculated.
+    // The due poll is queued in the outbox with the claim, run_outbox posts it
+    let next = next_post_time(&schedule, timezone, &skip_days, now);
+    if let Err(e) = store(ctx)
+        .await
+        .claim_scheduled_poll(guild_id_string, next_poll_at, next)
+        .await
+    {
+        println!("Claiming scheduled poll for {} failed: {}", guild_id, e);
+    }
+}
+
+/// Posts the scheduled poll for the guild.
+/// A guild without custom polls gets a poll from the global pool instead, so the run isn't skipped.
+/// Returns None if there is no poll to post, not even in the global pool.
+async fn post_scheduled_poll(
+    ctx: &Context,
+    guild_id: GuildId,
+    stream: &Stream,
+    custom: bool,
+) -> CommandResult<Option<Message>> {
+    let store = store(ctx).await;
+    let channel = stream.channel.ok_or("Channel not set")?;
+
+    let (mut poll_id, mut poll) = (0, Vec::new());
+    if custom {
+        (poll_id, poll) = store.get_random_custom_poll(guild_id.to_string()).await?;
+    }
+    let custom = poll.len() >= 3;
+    if !custom {
+        (poll_id, poll) = match store.get_random_poll().await? {
+            Some(picked) => picked,
+            None => return Ok(None),
+        };
+    }
+
+    let poll_string = format_string_for_pings(
+        stream.ping_role.clone(),
+        get_poll_intro(guild_id.to_string(), &poll[0], ctx).await?,
+        None,
+        "daily",
+    )
+    .await;
+
+    let pings = Pings::for_ping_role(&stream.ping_role);
+    send_poll(
+        ctx,
+        guild_id,
+        channel,
+        poll_id,
+        custom,
+        &poll,
+        poll_string,
+        pings,
+    )
+    .await
+    .map(Some)
+}
+
+/// Posts the guilds scheduled question and teaser if either is due, in the guilds own timezone
+async fn check_schedule(ctx: &Context, row: &Row, schedule: String) {
+    let store = store(ctx).await;
+    let guild_id_string: String = row.get("guild_id");
+    let guild_id = match guild_id_string.parse::<u64>() {
+        Ok(guild_id) => GuildId(guild_id),
+        Err(_) => return,
+    };
+    let mut next_post_at: Option<DateTime<Utc>> = row.get("next_post_at");
+    let timezone = parse_timezone(row.get("timezone"));
+    let teaser_minutes: Option<i32> = row.get("teaser_minutes");
+    let teased_post: Option<DateTime<Utc>> = row.get("teased_post");
+    let skip_days = parse_skip_days(row.get("skip_days"));
+    let stream = scheduled_stream(row);
+
+    let now = Utc::now();
+
+    // Runs missed while the bot was down are posted once, then the schedule continues from now
+    if next_post_at.is_none_or(|next| next <= now) {
+        let next = next_post_time(&schedule, timezone, &skip_days, now);
+        // The due question is queued in the outbox with the claim, run_outbox posts it.
+        // A guild without a due time yet only gets its next time calculated
+        match store
+            .claim_scheduled_post(guild_id_string.clone(), next_post_at, next)
+            .await
+        {
+            Ok(true) => next_post_at = next,
+            Ok(false) => return,
+            Err(e) => {
+                println!("Claiming scheduled post for {} failed: {}", guild_id, e);
+                return;
+            }
+        }
+    }
+
+    // Teasers are checked after posting, a guild that just got its question is teased for the next one
+    let (teaser_minutes, next_post) = match (teaser_minutes, next_post_at) {
+        (Some(minutes), Some(next_post)) => (minutes, next_post),
+        _ => return,
+    };
+    let seconds_left = (next_post - now).num_seconds();
+    let minutes_left = ((seconds_left + 59) / 60).max(1) as i32;
+
+    if minutes_left > teaser_minutes || teased_post.is_some_and(|teased| teased >= next_post) {
+        return;
+    }
+
+    match store.claim_teaser(guild_id_string, next_post).await {
+        Ok(true) => {
+            if let Err(e) = post_teaser(ctx, &stream, minutes_left).await {
+                println!("Teaser for {} failed: {}", guild_id, e);
+            }
+        }
+        Ok(false) => {}
+        Err(e) => println!("Claiming teaser for {} failed: {}", guild_id, e),
+    }
+}
+
+/// Checks the question drawn for the next scheduled post can still be picked with the settings of the guild
+async fn is_drawn_question_eligible(
+    ctx: &Context,
+    guild_id: GuildId,
+    question: &str,
+) -> Result<bool, DbError> {
+    let store = store(ctx).await;
+    let tags = store.get_question_filter(guild_id.to_string()).await?;
+    let custom_only = store.get_custom_only(guild_id.to_string()).await?;
+    store
+        .is_question_eligible(
+            guild_id.to_string(),
+            question.to_string(),
+            tags,
+            custom_only,
+        )
+        .await
+}
+
+/// Posts the scheduled question of the day due at the given time for the guild.
+/// A question queued for the day in the guilds timezone is posted first, otherwise one is picked randomly.
+/// If nothing can be picked, the fallback text is posted instead so the day isn't skipped.
+async fn post_scheduled_question(
+    ctx: &Context,
+    guild_id: GuildId,
+    stream: &Stream,
+    timezone: Tz,
+    due: DateTime<Utc>,
+) -> CommandResult<Message> {
+    let store = store(ctx).await;
+    if stream.channel.is_none() {
+        return Err("Channel not set".into());
+    }
+
+    // The day it was due, a post sent late after a restart is still the one for its day
+    let today = due.with_timezone(&timezone).date_naive();
+    // A queued question deleted after queueing falls through to random selection
+    let queued = match store
+        .take_queued_question(guild_id.to_string(), today)
+        .await?
+    {
+        Some(question_id) => {
+            store
+                .get_specific_custom_question(guild_id.to_string(), question_id)
+                .await?
+        }
+        None => None,
+    };
+
+    // The question shown by next was drawn beforehand, it's used unless a queued one takes its place.
+    // One that can't be picked anymore since, like a deleted or filtered out question, is drawn again
+    let drawn = match queued {
+        Some(selected) => Some(selected),
+        None => match store.take_drawn_question(guild_id.to_string()).await? {
+            Some((question, attribution))
+                if is_drawn_question_eligible(ctx, guild_id, &question).await? =>
+            {
+                Some((question, attribution))
+            }
+            _ => None,
+        },
+    };
+
+    let (question, attribution) = match drawn {
+        Some(selected) => selected,
+        None => match get_random_question_for_guild(guild_id.to_string(), None, ctx).await? {
+            Some(selected) => selected,
+            None => {
+                let notice = format!(
+                    "{}: There were no questions left to post, the fallback text was posted instead. \
+                    Add questions with submit_qotd or import.",
+                    guild_display_name(ctx, &guild_id.to_string())
+                );
+                notify_admins(ctx, guild_id, &notice).await;
+                (
+                    store.get_fallback_question(guild_id.to_string()).await?,
+                    None,
+                )
+            }
+        },
+    };
+
+    // On double days a second question is posted next to it, if there is another one to pick
+    let second = if store
+        .get_double_days(guild_id.to_string())
+        .await?
+        .contains(&today.weekday())
+    {
+        get_random_question_for_guild(guild_id.to_string(), Some(&question), ctx).await?
+    } else {
+        None
+    };
+    let message = match second {
+        Some(second) => {
+            send_double_question(ctx, guild_id, stream, (question, attribution), second).await?
+        }
+        None => {
+            let message =
+                send_question(ctx, guild_id, stream, "Question", question, attribution).await?;
+            add_rating_reactions(ctx, &message).await;
+            message
+        }
+    };
+
+    // Drawing the next one after posting, so it isn't a repeat of this one
+    let next = get_random_question_for_guild(guild_id.to_string(), None, ctx).await?;
+    store.set_drawn_question(guild_id.to_string(), next).await?;
+
+    Ok(message)
+}
+
+// Times a scheduled post in the outbox is tried before it's given up on
+const OUTBOX_ATTEMPTS: i32 = 3;
+// Seconds an outbox entry is held for an attempt, it's looked at again after if the bot stops in between
+const OUTBOX_LEASE_SECONDS: i32 = 300;
+
+/// Background loop sending the scheduled posts queued in the outbox, in the leading process only
+async fn run_outbox(ctx: Context) {
+    let mut interval = tokio::time::interval(Duration::from_secs(5));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        if !LEADING.load(Ordering::SeqCst) {
+            continue;
+        }
+
+        let due = match store(&ctx).await.get_due_outbox(Utc::now()).await {
+            Ok(due) => due,
+            Err(e) => {
+                println!("Checking outbox failed: {}", e);
+                continue;
+            }
+        };
+
+        for entry in &due {
+            if let Err(e) = send_outbox_entry(&ctx, entry).await {
+                println!("Updating outbox failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Posts a scheduled post from the outbox and settles it with the message it was posted as.
+/// An entry tried before is first checked against the posts made since that attempt started,
+/// an attempt cut off after posting is settled with its post instead of posting again.
+/// Failures are retried with a growing delay, up to OUTBOX_ATTEMPTS tries.
+async fn send_outbox_entry(ctx: &Context, entry: &Row) -> Result<(), DbError> {
+    let store = store(ctx).await;
+    let outbox_id: i32 = entry.get("outbox_id");
+    let kind: String = entry.get("kind");
+    let due: DateTime<Utc> = entry.get("due_at");
+    let attempts: i32 = entry.get("attempts");
+    let attempted_at: Option<DateTime<Utc>> = entry.get("attempted_at");
+    let guild_id_string: String = entry.get("guild_id");
+
+    if !store
+        .claim_outbox_entry(outbox_id, attempts, OUTBOX_LEASE_SECONDS)
+        .await?
+    {
+        return Ok(());
+    }
+    let guild_id = match guild_id_string.parse::<u64>() {
+        Ok(guild_id) => GuildId(guild_id),
+        Err(_) => {
+            store.settle_outbox_entry(outbox_id, "failed", None).await?;
+            return Ok(());
+        }
+    };
+
+    if let Some(attempted_at) = attempted_at {
+        if let Some(posted) = store
+            .find_scheduled_post(guild_id_string, kind.clone(), attempted_at)
+            .await?
+        {
+            println!("Scheduled {} for {} was already posted", kind, guild_id);
+            store
+                .settle_outbox_entry(outbox_id, "sent", Some(posted))
+                .await?;
+            return Ok(());
+        }
+    }
+
+    let stream = scheduled_stream(entry);
+    let posted = if kind == "poll" {
+        let poll_source: String = entry.get("poll_source");
+        post_scheduled_poll(ctx, guild_id, &stream, poll_source == "custom").await
+    } else {
+        let timezone = parse_timezone(entry.get("timezone"));
+        post_scheduled_question(ctx, guild_id, &stream, timezone, due)
+            .await
+            .map(Some)
+    };
+
+    match posted {
+        Ok(Some(message)) => {
+            let posted = (message.channel_id.to_string(), message.id.to_string());
+            store
+                .settle_outbox_entry(outbox_id, "sent", Some(posted))
+                .await?;
+        }
+        // Trying again won't help while there's nothing to post
+        Ok(None) => {
+            println!("No {} to post for {}", kind, guild_id);
+            store.settle_outbox_entry(outbox_id, "failed", None).await?;
+        }
+        Err(e) if attempts + 1 >= OUTBOX_ATTEMPTS => {
+            println!("Giving up on scheduled {} for {}: {}", kind, guild_id, e);
+            store.settle_outbox_entry(outbox_id, "failed", None).await?;
+        }
+        Err(e) => {
+            println!(
+                "Scheduled {} for {} failed, trying again: {}",
+                kind, guild_id, e
+            );
+            store.retry_outbox_entry(outbox_id, 60 << attempts).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends a question to the channel of the stream with the streams ping.
+/// The post is then recorded, gets its answer thread and is mirrored to the archive.
+/// Picks the backup channel of the guild after posting to the failed channel went wrong.
+/// The failover is recorded in the audit log, and the admin contacts are told in DMs,
+/// or the backup channel if none of them can be reached.
+/// Returns the error back if the guild has no backup channel, or the backup channel is the one that failed.
+async fn fail_over(
+    ctx: &Context,
+    guild_id: GuildId,
+    failed: ChannelId,
+    error: SerenityError,
+) -> CommandResult<ChannelId> {
+    let store = store(ctx).await;
+    let backup = match parse_channel(store.get_backup_channel_id(guild_id.to_string()).await?) {
+        Some(backup) if backup != failed.0 => ChannelId(backup),
+        _ => return Err(error.into()),
+    };
+
+    let detail = format!(
+        "Posting to <#{}> failed ({}), posted to <#{}> instead.",
+        failed, error, backup
+    );
+    println!("{} {}", guild_id, detail);
+    if let Err(e) = store
+        .record_audit_event(guild_id.to_string(), "failover", &detail)
+        .await
+    {
+        println!("Recording failover failed: {}", e);
+    }
+
+    let notice = format!(
+        "{}: {} Use diagnose to see what the bot is missing there.",
+        guild_display_name(ctx, &guild_id.to_string()),
+        detail
+    );
+    if !notify_admins(ctx, guild_id, &notice).await {
+        if let Err(e) = backup.say(ctx, &notice).await {
+            println!("Telling admins about the failover failed: {}", e);
+        }
+    }
+
+    Ok(backup)
+}
+
+/// Builds the setup checklist of the guild, a line for each part of the setup with how to do what's missing
+async fn render_checklist(guild_id: GuildId, ctx: &Context) -> Result<String, DbError> {
+    let store = store(ctx).await;
+    let guild = guild_id.to_string();
+    let items = [
+        (
+            "Question channel",
+            parse_channel(&store.get_ping_channel_id(guild.clone()).await?).is_some(),
+            "set_channel",
+        ),
+        (
+            "Schedule",
+            store.get_post_schedule(guild.clone()).await?.is_some(),
+            "schedule <HH:MM>",
+        ),
+        (
+            "Ping role",
+            store.get_ping_role(guild.clone()).await? != "0",
+            "ping_role <role>",
+        ),
+        (
+            "Admin contact",
+            !store.get_admin_contacts(guild.clone()).await?.is_empty(),
+            "admin_contact add <user>",
+        ),
+        (
+            "Custom questions",
+            !store.get_list_custom_questions(guild).await?.is_empty(),
+            "submit_qotd <question> or import",
+        ),
+    ];
+
+    let done = items.iter().filter(|(_, done, _)| *done).count();
+    let mut checklist = format!("{} of {} done\n\n", done, items.len());
+    for (item, done, hint) in items {
+        if done {
+            checklist.push_str(&format!("✅ {}\n", item));
+        } else {
+            checklist.push_str(&format!("❌ {} - use `{}`\n", item, hint));
+        }
+    }
+    Ok(checklist)
+}
+
+/// Posts the setup checklist of the guild to the channel and pins it, remembering it to keep it updated
+async fn post_checklist(guild_id: GuildId, channel_id: ChannelId, ctx: &Context) -> CommandResult {
+    let checklist = render_checklist(guild_id, ctx).await?;
+    let message = channel_id
+        .send_message(ctx, |m| {
+            m.embed(|embed| {
+                embed
+                    .title("Setup checklist")
+                    .description(checklist)
+                    .color(Color::DARK_BLUE)
+            })
+        })
+        .await?;
+    // Pinning needs Manage Messages, the checklist is still kept updated without it
+    if let Err(e) = message.pin(ctx).await {
+        println!("Pinning checklist failed: {}", e);
+    }
+
+    store(ctx)
+        .await
+        .set_checklist_message(
+            guild_id.to_string(),
+            Some((channel_id.to_string(), message.id.to_string())),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Edits the setup checklist of the guild after its settings changed.
+/// A guild without one gets it posted where it set its channel for the first time, usually an admin channel.
+async fn update_checklist(ctx: &Context, change: &SettingsChange) -> CommandResult {
+    let (channel_id, message_id) = match store(ctx)
+        .await
+        .get_checklist_message(change.guild_id.to_string())
+        .await?
+    {
+        Some(checklist) => checklist,
+        None if change.command == "set_channel" => {
+            return post_checklist(change.guild_id, change.channel_id, ctx).await;
+        }
+        None => return Ok(()),
+    };
+    let (channel_id, message_id) = match (parse_channel(&channel_id), message_id.parse::<u64>()) {
+        (Some(channel_id), Ok(message_id)) => (ChannelId(channel_id), MessageId(message_id)),
+        _ => return Ok(()),
+    };
+
+    let checklist = render_checklist(change.guild_id, ctx).await?;
+    channel_id
+        .edit_message(ctx, message_id, |m| {
+            m.embed(|embed| {
+                embed
+                    .title("Setup checklist")
+                    .description(checklist)
+                    .color(Color::DARK_BLUE)
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Background loop keeping the setup checklists up to date from the settings change stream
+async fn run_checklists(ctx: Context) {
+    let mut events = match ctx.data.read().await.get::<SettingsEvents>() {
+        Some(events) => events.subscribe(),
+        None => return,
+    };
+
+    loop {
+        let change = match events.recv().await {
+            Ok(change) => change,
+            // Changes missed while the loop was behind only mean a checklist is edited on the next one
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+        };
+
+        if let Err(e) = update_checklist(&ctx, &change).await {
+            println!("Updating checklist of {} failed: {}", change.guild_id, e);
+        }
+    }
+}
+
+/// Tells the admin contacts of the guild about an operational problem in DMs.
+/// The notice goes to every contact or to the next one in rotation depending on the notification policy,
+/// guilds without contacts fall back to the admin who set the channel.
+/// Returns false if nobody could be reached.
+async fn notify_admins(ctx: &Context, guild_id: GuildId, notice: &str) -> bool {
+    let store = store(ctx).await;
+    let mut contacts =
+        logged(store.get_admin_contacts(guild_id.to_string()).await).unwrap_or_default();
+    if contacts.is_empty() {
+        contacts.extend(logged(store.get_channel_set_by(guild_id.to_string()).await).flatten());
+    }
+    let contacts: Vec<UserId> = contacts
+        .iter()
+        .filter_map(|user_id| user_id.parse::<u64>().ok())
+        .map(UserId)
+        .collect();
+    if contacts.is_empty() {
+        return false;
+    }
+
+    let policy = logged(store.get_notify_policy(guild_id.to_string()).await);
+    if policy.as_deref() == Some("rotate") {
+        let turn = match store.take_notify_turn(guild_id.to_string()).await {
+            Ok(turn) => turn.max(0) as usize,
+            Err(e) => {
+                println!("Taking notification turn failed: {}", e);
+                0
+            }
+        };
+        // Moving on to the next contact if the one whose turn it is can't be reached
+        for offset in 0..contacts.len() {
+            let contact = contacts[(turn + offset) % contacts.len()];
+            if send_notice(ctx, contact, notice).await {
+                return true;
+            }
+        }
+        false
+    } else {
+        let mut told = false;
+        for contact in contacts {
+            told |= send_notice(ctx, contact, notice).await;
+        }
+        told
+    }
+}
+
+// Question events a guild can send to its modlog, for moderation bots to pick up
+const MODLOG_EVENTS: &[&str] = &["submitted", "approved", "rejected", "deleted", "edited"];
+
+// Question event sent to the modlog of a guild
+struct QuestionEvent<'a> {
+    // One of MODLOG_EVENTS
+    kind: &'static str,
+    // Questions the event is about, empty when they aren't known, like for new submissions
+    question_ids: Vec<i32>,
+    // Member who caused the event, None for the HTTP API
+    by: Option<UserId>,
+    detail: &'a str,
+}
+
+/// Sends a question event to the modlog channel or webhook of the guild, if it has one and wants the event.
+/// The footer of the embed is qotd:<event>, so moderation bots can match on it.
+/// Failing to send is only logged, the action itself already went through.
+async fn emit_question_event(ctx: &Context, guild_id: String, event: QuestionEvent<'_>) {
+    let store = store(ctx).await;
+    let target = match logged(store.get_modlog_target(guild_id.clone()).await) {
+        Some(Some(target)) => target,
+        _ => return,
+    };
+    if let Some(Some(events)) = logged(store.get_modlog_events(guild_id).await) {
+        if !events.iter().any(|kind| kind == event.kind) {
+            return;
+        }
+    }
+
+    let by = match event.by {
+        Some(user_id) => format!("<@{}>", user_id),
+        None => String::from("HTTP API"),
+    };
+    let mut embed = CreateEmbed::default();
+    embed
+        .title(format!("Question {}", event.kind))
+        .description(event.detail)
+        .color(match event.kind {
+            "submitted" => Color::BLUE,
+            "approved" => Color::DARK_GREEN,
+            "edited" => Color::GOLD,
+            _ => Color::RED,
+        })
+        .field("By", by, true)
+        .footer(|footer| footer.text(format!("qotd:{}", event.kind)));
+    if !event.question_ids.is_empty() {
+        let ids = event
+            .question_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        embed.field("Questions", ids, true);
+    }
+
+    let sent = if target.starts_with("https://") {
+        match Webhook::from_url(ctx, &target).await {
+            Ok(webhook) => webhook
+                .execute(ctx, false, |w| {
+                    w.username("Question of the Day")
+                        .embeds(vec![Embed::fake(|e| {
+                            *e = embed;
+                            e
+                        })])
+                })
+                .await
+                .map(|_| ()),
+            Err(e) => Err(e),
+        }
+    } else {
+        match target.parse::<u64>() {
+            Ok(channel_id) => ChannelId(channel_id)
+                .send_message(ctx, |m| m.set_embed(embed))
+                .await
+                .map(|_| ()),
+            Err(_) => return,
+        }
+    };
+    if let Err(e) = sent {
+        println!("Sending {} event to the modlog failed: {}", event.kind, e);
+    }
+}
+
+/// DMs a notice to an admin, returns false if it couldn't be sent
+async fn send_notice(ctx: &Context, user_id: UserId, notice: &str) -> bool {
+    match user_id.create_dm_channel(ctx).await {
+        Ok(dm) => dm.say(ctx, notice).await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Fills the placeholders of a question for the guild it's posted in, questions without any are returned as they are.
+/// The random member is picked from the cached members who aren't bots, by name so nobody gets pinged.
+async fn fill_question_placeholders(
+    ctx: &Context,
+    guild_id: GuildId,
+    question: &str,
+) -> Result<String, DbError> {
+    if !placeholders::has_placeholders(question) {
+        return Ok(question.to_string());
+    }
+    let store = store(ctx).await;
+    let now = Utc::now().with_timezone(&store.get_guild_timezone(guild_id.to_string()).await?);
+    let random_member = ctx
+        .cache
+        .guild_field(guild_id, |guild| {
+            guild
+                .members
+                .values()
+                .filter(|member| !member.user.bot)
+                .choose(&mut rand::thread_rng())
+                .map(|member| member.display_name().to_string())
+        })
+        .flatten()
+        .unwrap_or_else(|| String::from("someone"));
+    let values = placeholders::PlaceholderValues {
+        server: guild_display_name(ctx, &guild_id.to_string()),
+        random_member,
+        month: now.format("%B").to_string(),
+        weekday: now.format("%A").to_string(),
+    };
+    Ok(placeholders::fill_placeholders(question, &values))
+}
+
+async fn send_question(
+    ctx: &Context,
+    guild_id: GuildId,
+    stream: &Stream,
+    title: &str,
+    question: String,
+    mut attribution: Option<String>,
+) -> CommandResult<Message> {
+    let store = store(ctx).await;
+    let channel = stream.channel.ok_or("Channel not set")?;
+    let mut question_string = format_string_for_pings(
+        stream.ping_role.clone(),
+        String::from("Question of the day!"),
+        stream.mention_template.as_deref(),
+        &stream.name,
+    )
+    .await;
+
+    // Members who chose to be mentioned are added to the role ping, members who chose DMs get the question in DMs
+    let (pinged, dm_users) = resolve_notifications(ctx, guild_id, &stream.ping_role).await?;
+    let mut pings = Pings::for_ping_role(&stream.ping_role);
+    let mut overflow_mentions = Vec::new();
+    let mentions = pinged
+        .iter()
+        .map(|user| format!("<@{}>", user))
+        .collect::<Vec<String>>()
+        .join(" ");
+    // Too many mentions for the post itself are sent right after it
+    if mentions.len() <= 1500 {
+        question_string = format!("{} {}", question_string, mentions)
+            .trim()
+            .to_string();
+        pings.users = pinged;
+    } else {
+        overflow_mentions = pinged;
+    }
+    if !store.get_show_attribution(guild_id.to_string()).await? {
+        attribution = None;
+    }
+    let show_submitter = store.get_show_submitter(guild_id.to_string()).await?;
+    let mention_submitter = store.get_mention_submitter(guild_id.to_string()).await?;
+    let submitter = if show_submitter || mention_submitter {
+        store
+            .get_question_submitter(guild_id.to_string(), &question)
+            .await?
+            .and_then(|submitter| submitter.parse::<u64>().ok())
+            .map(UserId)
+    } else {
+        None
+    };
+    // Footers can't show mentions, the submitter is credited by name
+    if let Some(user_id) = submitter.filter(|_| show_submitter) {
+        if let Ok(user) = user_id.to_user(ctx).await {
+            let credit = format!("Submitted by {}", user.name);
+            attribution = Some(match attribution {
+                Some(attribution) => format!("{} · {}", attribution, credit),
+                None => credit,
+            });
+        }
+    }
+    // The question is stored as written, only what's posted has its placeholders filled
+    let shown = fill_question_placeholders(ctx, guild_id, &question).await?;
+    let accessible = store.get_accessible_posts(guild_id.to_string()).await?;
+    // Questions too long for a card, or with characters it can't draw, are posted in the embed as usual
+    let card = if !accessible && store.get_image_cards(guild_id.to_string()).await? {
+        let style = store.get_card_style(guild_id.to_string()).await?;
+        get_image_card(ctx, title, &shown, style).await
+    } else {
+        None
+    };
+
+    // Plain text reads out better on screen readers than an embed
+    let post = if accessible {
+        let mut text = format!("{}\n{}: {}", question_string, title, shown);
+        if let Some(attribution) = &attribution {
+            text = format!("{}\n{}", text, attribution);
+        }
+        Post::plain(&text).with_pings(pings)
+    } else if let Some(card) = card {
+        Post::card(
+            &question_string,
+            card,
+            attribution.as_deref(),
+            Color::FABLED_PINK,
+        )
+        .with_pings(pings)
+    } else {
+        Post::embed(
+            &question_string,
+            title,
+            &shown,
+            attribution.as_deref(),
+            Color::FABLED_PINK,
+        )
+        .with_pings(pings)
+    };
+
+    // Sending message to the channel assigned to the server, or the backup channel if that fails
+    let (channel, message) = match post.send(ctx, channel).await {
+        Ok(message) => (channel, message),
+        Err(e) => {
+            let backup = fail_over(ctx, guild_id, channel, e).await?;
+            (backup, post.send(ctx, backup).await?)
+        }
+    };
+
+    if !overflow_mentions.is_empty() {
+        for (chunk, users) in chunk_mentions(&overflow_mentions) {
+            channel
+                .send_message(ctx, |m| {
+                    m.content(chunk)
+                        .reference_message(&message)
+                        .allowed_mentions(|mentions| mentions.empty_parse().users(users))
+                })
+                .await?;
+        }
+    }
+    // The credit can only ping the submitter, whatever the rest of the message says
+    if let Some(user_id) = submitter.filter(|_| mention_submitter) {
+        if let Err(e) = channel
+            .send_message(ctx, |m| {
+                m.content(format!("Today's question comes from <@{}>!", user_id))
+                    .reference_message(&message)
+                    .allowed_mentions(|mentions| {
+                        mentions
+                            .empty_parse()
+                            .users(vec![user_id])
+                            .replied_user(false)
+                    })
+            })
+            .await
+        {
+            println!("Mentioning question submitter failed: {}", e);
+        }
+    }
+    // Members notified by DM get the question through the DM queue, so big servers don't flood Discord
+    if !dm_users.is_empty() {
+        match store
+            .queue_dms(&dm_users, &message.link(), Some((title, &shown)))
+            .await
+        {
+            Ok(queued) => {
+                if queued < dm_users.len() as u64 {
+                    println!(
+                        "Queued {} of {} question DMs, the rest have closed DMs or the queue is full",
+                        queued,
+                        dm_users.len()
+                    );
+                }
+                record_dm_fanout(guild_id.to_string(), queued, ctx).await;
+            }
+            Err(e) => println!("Queueing question DMs failed: {}", e),
+        }
+    }
+
+    store
+        .record_posted_question(
+            guild_id.to_string(),
+            channel.to_string(),
+            message.id.to_string(),
+            question.clone(),
+        )
+        .await?;
+    // Trivia questions are graded in their answer thread
+    let answer = if store.get_trivia_mode(guild_id.to_string()).await? {
+        store
+            .get_question_answer(guild_id.to_string(), &question)
+            .await?
+    } else {
+        None
+    };
+    open_answer_thread(guild_id.to_string(), &message, answer, ctx).await;
+
+    mirror_to_archive(guild_id.to_string(), title, &shown, Color::FABLED_PINK, ctx).await;
+
+    Ok(message)
+}
+
+// Reactions members pick the first or second question of a double post with
+const DOUBLE_CHOICES: [&str; 2] = ["🇦", "🇧"];
+
+/// Posts two questions side by side, members react with DOUBLE_CHOICES to pick the one they answer.
+/// The post is recorded with the tags of both questions, the votes on it weigh future picks.
+async fn send_double_question(
+    ctx: &Context,
+    guild_id: GuildId,
+    stream: &Stream,
+    first: (String, Option<String>),
+    second: (String, Option<String>),
+) -> CommandResult<Message> {
+    let store = store(ctx).await;
+    let text = format!(
+        "{} {}\n\n{} {}\n\nReact to pick the one you answer!",
+        DOUBLE_CHOICES[0],
+        fill_question_placeholders(ctx, guild_id, &first.0).await?,
+        DOUBLE_CHOICES[1],
+        fill_question_placeholders(ctx, guild_id, &second.0).await?
+    );
+    let attribution = [&first.1, &second.1]
+        .iter()
+        .zip(DOUBLE_CHOICES)
+        .filter_map(|(attribution, choice)| {
+            attribution
+                .as_ref()
+                .map(|attribution| format!("{} {}", choice, attribution))
+        })
+        .collect::<Vec<String>>();
+    let attribution = (!attribution.is_empty()).then(|| attribution.join("\n"));
+
+    let message =
+        send_question(ctx, guild_id, stream, "Double Question", text, attribution).await?;
+    for choice in DOUBLE_CHOICES {
+        message
+            .react(ctx, ReactionType::Unicode(choice.to_string()))
+            .await?;
+    }
+
+    let tags = [
+        store
+            .get_question_tags(guild_id.to_string(), first.0.clone())
+            .await?,
+        store
+            .get_question_tags(guild_id.to_string(), second.0.clone())
+            .await?,
+    ];
+    store
+        .record_double_post(
+            guild_id.to_string(),
+            message.id.to_string(),
+            [first.0, second.0],
+            tags,
+        )
+        .await?;
+
+    Ok(message)
+}
+
+/// Renders the question onto an image card in the style of the guild, reusing the card if it was rendered recently.
+/// Returns None if the question doesn't fit on a card.
+async fn get_image_card(
+    ctx: &Context,
+    title: &str,
+    question: &str,
+    style: CardStyle,
+) -> Option<Arc<Vec<u8>>> {
+    let cache = ctx
+        .data
+        .read()
+        .await
+        .get::<CardCache>()
+        .expect("Card cache error")
+        .clone();
+    let key = (title.to_string(), question.to_string(), style);
+    if let Some(card) = cache.lock().await.get(&key) {
+        return Some(card.clone());
+    }
+
+    // Drawing the card off the async runtime, it takes a moment for long questions
+    let (card_title, card_question, card_style) = key.clone();
+    let card =
+        tokio::task::spawn_blocking(move || render_card(&card_title, &card_question, &card_style))
+            .await
+            .ok()??;
+    let card = Arc::new(card);
+
+    let mut cache = cache.lock().await;
+    if cache.len() >= CARD_CACHE_SIZE {
+        cache.clear();
+    }
+    cache.insert(key, card.clone());
+    Some(card)
+}
+
+/// Works out who gets mentioned and who gets a DM for a post, following the preferences set with notify.
+/// The role ping of the post stays as it is, members who chose ping are mentioned unless the role already pings them.
+/// Members are only looked up in the cache, one who isn't cached is mentioned in case they don't have the role.
+/// Discord can't leave members out of a role ping, so none only stops the mentions and DMs of the bot.
+/// Returns the members to mention and the members to DM.
+async fn resolve_notifications(
+    ctx: &Context,
+    guild_id: GuildId,
+    ping_role: &str,
+) -> Result<(Vec<UserId>, Vec<UserId>), DbError> {
+    let preferences = store(ctx)
+        .await
+        .get_notify_preferences(guild_id.to_string())
+        .await?;
+
+    let mut pinged = Vec::new();
+    let mut dms = Vec::new();
+    for (user, mode) in &preferences {
+        let user = match user.parse::<u64>() {
+            Ok(user) => UserId(user),
+            Err(_) => continue,
+        };
+        match mode.as_str() {
+            "ping" => {
+                let pinged_by_role = match ping_role {
+                    "0" => false,
+                    "1" => true,
+                    role => role.parse::<u64>().is_ok_and(|role| {
+                        ctx.cache
+                            .member_field(guild_id, user, |member| {
+                                member.roles.contains(&RoleId(role))
+                            })
+                            .unwrap_or(false)
+                    }),
+                };
+                if !pinged_by_role {
+                    pinged.push(user);
+                }
+            }
+            "dm" => dms.push(user),
+            _ => {}
+        }
+    }
+
+    Ok((pinged, dms))
+}
+
+// Command stats entry counting the DM fan-outs of a guild, one use for every time DMs were queued for members
+const DM_FANOUT_STAT: &str = "dm_fanout";
+// Commands counted towards the import quota
+const IMPORT_COMMANDS: [&str; 2] = ["import", "merge_pool"];
+
+/// Counts a DM fan-out of the guild in the command stats, if any DMs were queued
+async fn record_dm_fanout(guild_id: String, queued: u64, ctx: &Context) {
+    if queued == 0 {
+        return;
+    }
+    if let Err(e) = store(ctx)
+        .await
+        .record_command_use(guild_id, DM_FANOUT_STAT, false)
+        .await
+    {
+        println!("Recording DM fan-out failed: {}", e);
+    }
+}
+
+/// Formats a stored ping role for showing it in a message
+fn format_ping_role(ping_role: &str) -> String {
+    match ping_role {
+        "0" => String::from("no ping"),
+        "1" => String::from("@everyone"),
+        role => format!("<@&{}>", role),
+    }
+}
+
+/// Checks whether the member is allowed to use the admin commands.
+/// Same rule as the command framework, administrators or members with the qotd_admin role.
+fn is_qotd_admin(ctx: &Context, guild_id: GuildId, member: &Member) -> bool {
+    if member
+        .permissions
+        .is_some_and(|permissions| permissions.administrator())
+    {
+        return true;
+    }
+
+    member.roles.iter().any(|role_id| {
+        ctx.cache
+            .role(guild_id, *role_id)
+            .is_some_and(|role| role.name == "qotd_admin")
+    })
+}
+
+/// Asks for a second admin to approve an action that pings @everyone.
+/// Posts buttons under the request, a different admin than the one running the command has to approve within 10 minutes.
+/// Returns true right away if the guild doesn't require approval.
+/// Returns false if the request was denied or timed out.
+async fn everyone_ping_approved(
+    ctx: &Context,
+    invocation: &Invocation,
+    action: &str,
+) -> CommandResult<bool> {
+    let guild_id = invocation.guild_id.unwrap();
+    if !store(ctx)
+        .await
+        .get_everyone_approval(guild_id.to_string())
+        .await?
+    {
+        return Ok(true);
+    }
+
+    let prompt = invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!(
+                "<@{}> wants to {}. Another admin has to approve this within 10 minutes.",
+                invocation.author.id, action
+            ))
+            .components(|c| {
+                c.create_action_row(|row| {
+                    row.create_button(|b| {
+                        b.custom_id("everyone_approve")
+                            .label("Approve")
+                            .style(ButtonStyle::Success)
+                    })
+                    .create_button(|b| {
+                        b.custom_id("everyone_deny")
+                            .label("Deny")
+                            .style(ButtonStyle::Danger)
+                    })
+                })
+            })
+        })
+        .await?;
+
+    let deadline = Instant::now() + Duration::from_secs(600);
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let answer =
+            waiting_on_user(prompt.await_component_interaction(ctx).timeout(remaining)).await;
+        let interaction = match answer {
+            Some(interaction) => interaction,
+            None => {
+                let mut prompt = prompt;
+                prompt
+                    .edit(ctx, |m| {
+                        m.content(format!("Approval to {} timed out.", action))
+                            .components(|c| c)
+                    })
+                    .await?;
+                return Ok(false);
+            }
+        };
+
+        let approving = interaction.data.custom_id == "everyone_approve";
+        let clicker_is_admin = interaction
+            .member
+            .as_ref()
+            .is_some_and(|member| is_qotd_admin(ctx, guild_id, member));
+        let clicker_is_requester = interaction.user.id == invocation.author.id;
+
+        // The requester can cancel their own request, but approving needs someone else
+        if !clicker_is_admin || (approving && clicker_is_requester) {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|d| {
+                            d.content("Another admin has to approve this!")
+                                .ephemeral(true)
+                        })
+                })
+                .await?;
+            continue;
+        }
+
+        let outcome = if approving { "Approved" } else { "Denied" };
+        interaction
+            .create_interaction_response(ctx, |r| {
+                r.kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|d| {
+                        d.content(format!(
+                            "{} by <@{}>: {}",
+                            outcome, interaction.user.id, action
+                        ))
+                        .components(|c| c)
+                    })
+            })
+            .await?;
+
+        return Ok(approving);
+    }
+}
+
+/// Whether the text pings @everyone or @here
+fn mentions_everyone(text: &str) -> bool {
+    text.contains("@everyone") || text.contains("@here")
+}
+
+/// Appends the correct ping to the message based on the ping_role parameter
+/// If a mention template is given it's used instead of the message, with {role} and {stream} filled in.
+/// Posts are sent with only the ping of ping_role allowed, other pings written in a template don't ping.
+/// Returns completed string
+async fn format_string_for_pings(
+    ping_role: String,
+    message: String,
+    template: Option<&str>,
+    stream: &str,
+) -> String {
+    if let Some(template) = template {
+        let ping = match ping_role.as_str() {
+            "0" => String::new(),
+            "1" => String::from("@everyone"),
+            role => format!("<@&{}>", role),
+        };
+        return template
+            .replace("{role}", &ping)
+            .replace("{stream}", stream)
+            .trim()
+            .to_string();
+    }
+
+    let question_string;
+    if ping_role == *"0" {
+        question_string = message;
+    } else if ping_role == *"1" {
+        question_string = format!("@everyone {}", message);
+    } else {
+        // Role validity checked when it is saved to the database
+        question_string = format!("<@&{}> {}", ping_role, message);
+    }
+    question_string
+}
+
+/// Adds the vote buttons of a poll, with the tallies in the labels once there are votes
+fn create_vote_buttons<'a>(
+    components: &'a mut CreateComponents,
+    poll: &[String],
+    emoji: &[String],
+    tallies: Option<(i32, i32)>,
+    amendment: Option<&PollAmendment>,
+) -> &'a mut CreateComponents {
+    let (label_a, label_b) = match tallies {
+        Some((votes_a, votes_b)) => (
+            format!("{} ({})", &poll[1], votes_a),
+            format!("{} ({})", &poll[2], votes_b),
+        ),
+        None => (poll[1].clone(), poll[2].clone()),
+    };
+
+    components.create_action_row(|row| {
+        row.create_button(|b| {
+            b.custom_id("poll_vote_1")
+                .label(label_a)
+                .emoji(Unicode(emoji[0].clone()))
+                .style(ButtonStyle::Secondary)
+        })
+        .create_button(|b| {
+            b.custom_id("poll_vote_2")
+                .label(label_b)
+                .emoji(Unicode(emoji[1].clone()))
+                .style(ButtonStyle::Secondary)
+        });
+        // The option added with amend_poll gets a third button
+        if let Some(amendment) = amendment {
+            let label_c = match tallies {
+                Some(_) => format!("{} ({})", amendment.option, amendment.votes),
+                None => amendment.option.clone(),
+            };
+            row.create_button(|b| {
+                b.custom_id("poll_vote_3")
+                    .label(label_c)
+                    .emoji(Unicode(amendment.emoji.clone()))
+                    .style(ButtonStyle::Secondary)
+            });
+        }
+        row
+    })
+}
+
+// Options shown on one page of the delete menu, the most a select menu can have
+const DELETE_MENU_PAGE: usize = 25;
+
+/// Builds the delete menu, a page of questions to pick from or the confirmation for the picked one
+fn create_delete_menu<'a>(
+    components: &'a mut CreateComponents,
+    questions: &[(i32, String)],
+    page: usize,
+    picked: Option<i32>,
+) -> &'a mut CreateComponents {
+    if picked.is_some() {
+        return components.create_action_row(|row| {
+            row.create_button(|b| {
+                b.custom_id("delete_confirm")
+                    .label("Delete")
+                    .style(ButtonStyle::Danger)
+            })
+            .create_button(|b| {
+                b.custom_id("delete_back")
+                    .label("Back")
+                    .style(ButtonStyle::Secondary)
+            })
+        });
+    }
+
+    let pages = questions.len().div_ceil(DELETE_MENU_PAGE);
+    components
+        .create_action_row(|row| {
+            row.create_select_menu(|menu| {
+                menu.custom_id("delete_pick")
+                    .placeholder(format!("Question to delete (page {}/{})", page + 1, pages))
+                    .options(|options| {
+                        for (id, question) in questions
+                            .iter()
+                            .skip(page * DELETE_MENU_PAGE)
+                            .take(DELETE_MENU_PAGE)
+                        {
+                            // Option labels can be up to 100 characters
+                            let mut label = format!("{} - {}", id, question);
+                            if label.chars().count() > 100 {
+                                label =
+                                    format!("{}...", label.chars().take(97).collect::<String>());
+                            }
+                            options.create_option(|option| option.label(label).value(id));
+                        }
+                        options
+                    })
+            })
+        })
+        .create_action_row(|row| {
+            row.create_button(|b| {
+                b.custom_id("delete_previous")
+                    .label("Previous")
+                    .style(ButtonStyle::Secondary)
+                    .disabled(page == 0)
+            })
+            .create_button(|b| {
+                b.custom_id("delete_next")
+                    .label("Next")
+                    .style(ButtonStyle::Secondary)
+                    .disabled(page + 1 >= pages)
+            })
+            .create_button(|b| {
+                b.custom_id("delete_cancel")
+                    .label("Cancel")
+                    .style(ButtonStyle::Secondary)
+            })
+        })
+}
+
+/// Lets the admin pick the question to delete from a paged select menu, deleting it once confirmed
+async fn pick_question_to_delete(
+    ctx: &Context,
+    invocation: &Invocation,
+    questions: &[(i32, String)],
+) -> CommandResult {
+    let guild_id = invocation.guild_id.ok_or("Not in a guild")?;
+    let pages = questions.len().div_ceil(DELETE_MENU_PAGE);
+    let (mut page, mut picked) = (0, None);
+
+    let mut prompt = invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!(
+                "<@{}> Pick the question to delete",
+                invocation.author.id
+            ))
+            .components(|c| create_delete_menu(c, questions, page, picked))
+        })
+        .await?;
+
+    loop {
+        let interaction = waiting_on_user(
+            prompt
+                .await_component_interaction(ctx)
+                .author_id(invocation.author.id)
+                .timeout(Duration::from_secs(120)),
+        )
+        .await;
+        let interaction = match interaction {
+            Some(interaction) => interaction,
+            None => {
+                prompt
+                    .edit(ctx, |m| {
+                        m.content("Deleting timed out, nothing was deleted.")
+                            .components(|c| c)
+                    })
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let mut done = None;
+        match interaction.data.custom_id.as_str() {
+            "delete_pick" => {
+                picked = interaction
+                    .data
+                    .values
+                    .first()
+                    .and_then(|id| id.parse::<i32>().ok());
+            }
+            "delete_previous" => page = page.saturating_sub(1),
+            "delete_next" => page = (page + 1).min(pages - 1),
+            "delete_back" => picked = None,
+            "delete_confirm" => {
+                let deleted = match picked {
+                    Some(id) => {
+                        store(ctx)
+                            .await
+                            .delete_custom_question(
+                                guild_id.to_string(),
+                                id,
+                                Some(invocation.author.id.to_string()),
+                            )
+                            .await?
+                    }
+                    None => 0,
+                };
+                if let (Some(id), 1) = (picked, deleted) {
+                    let question = questions
+                        .iter()
+                        .find(|(question_id, _)| *question_id == id)
+                        .map(|(_, question)| question.as_str())
+                        .unwrap_or_default();
+                    let detail = format!("Question {} deleted: {}", id, question);
+                    let event = QuestionEvent {
+                        kind: "deleted",
+                        question_ids: vec![id],
+                        by: Some(invocation.author.id),
+                        detail: &detail,
+                    };
+                    emit_question_event(ctx, guild_id.to_string(), event).await;
+                }
+                done = Some(match picked {
+                    Some(id) if deleted == 1 => format!(
+                        "Question deleted! Use restore_question {} within {} days to bring it back.",
+                        id, RESTORE_DAYS
+                    ),
+                    _ => String::from("Question not found!"),
+                });
+            }
+            _ => done = Some(String::from("Nothing was deleted.")),
+        }
+
+        let content = match (&done, picked) {
+            (Some(done), _) => done.clone(),
+            (None, Some(id)) => {
+                let question = questions
+                    .iter()
+                    .find(|(question_id, _)| *question_id == id)
+                    .map(|(_, question)| question.as_str())
+                    .unwrap_or_default();
+                format!("Delete question {}? {}", id, question)
+            }
+            (None, None) => format!("<@{}> Pick the question to delete", invocation.author.id),
+        };
+        interaction
+            .create_interaction_response(ctx, |r| {
+                r.kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|d| {
+                        d.content(content).components(|c| match done {
+                            Some(_) => c,
+                            None => create_delete_menu(c, questions, page, picked),
+                        })
+                    })
+            })
+            .await?;
+
+        if done.is_some() {
+            return Ok(());
+        }
+    }
+}
+
+/// Gets the option added to a posted poll, None if it wasn't amended or reading it failed
+async fn get_poll_amendment(ctx: &Context, message_id: MessageId) -> Option<PollAmendment> {
+    match store(ctx)
+        .await
+        .get_poll_amendment(message_id.to_string())
+        .await
+    {
+        Ok(row) => row.map(|row| PollAmendment {
+            option: row.get(0),
+            emoji: row.get(1),
+            votes: row.get(2),
+            votes_before: (row.get(3), row.get(4)),
+            amended_at: row.get(5),
+        }),
+        Err(e) => {
+            println!("Getting poll amendment failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Shows the new tallies on the buttons of a poll after a vote
+async fn show_live_tallies(
+    ctx: &Context,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    tallies: (i32, i32),
+) -> Result<(), SerenityError> {
+    let (poll, emoji) = match store(ctx)
+        .await
+        .get_posted_poll(message_id.to_string())
+        .await
+    {
+        Ok(Some(posted)) => posted,
+        Ok(None) => return Ok(()),
+        Err(e) => {
+            println!("Getting posted poll failed: {}", e);
+            return Ok(());
+        }
+    };
+    let amendment = get_poll_amendment(ctx, message_id).await;
+
+    channel_id
+        .edit_message(ctx, message_id, |m| {
+            m.components(|c| {
+                create_vote_buttons(c, &poll, &emoji, Some(tallies), amendment.as_ref())
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Nudges the members with the guilds ping role who haven't voted on the poll yet.
+/// Depending on the guilds setting they are mentioned in a reply to the poll or sent a DM.
+/// Nothing is sent if the poll is already closed or the guild pings no role or everyone.
+async fn nudge_non_voters(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> CommandResult {
+    let store = store(ctx).await;
+    let mode = store.get_poll_nudge(guild_id.to_string()).await?;
+    let role = match store
+        .get_ping_role(guild_id.to_string())
+        .await?
+        .parse::<u64>()
+    {
+        Ok(role) if role > 1 && mode != "off" => RoleId(role),
+        _ => return Ok(()),
+    };
+
+    let voters = match store.get_open_poll_voters(message_id.to_string()).await {
+        Ok(Some(voters)) => voters,
+        Ok(None) => return Ok(()),
+        Err(e) => {
+            println!("Getting poll voters failed: {}", e);
+            return Ok(());
+        }
+    };
+
+    // Members come in pages of up to 1000
+    let mut non_voters = Vec::new();
+    let mut after = None;
+    loop {
+        let members = guild_id.members(ctx, Some(1000), after).await?;
+        for member in &members {
+            if member.roles.contains(&role)
+                && !member.user.bot
+                && !voters.contains(&member.user.id.to_string())
+            {
+                non_voters.push(member.user.id);
+            }
+        }
+        match members.last() {
+            Some(last) if members.len() == 1000 => after = Some(last.user.id),
+            _ => break,
+        }
+    }
+
+    if non_voters.is_empty() {
+        return Ok(());
+    }
+
+    if mode == "dm" {
+        let link = message_id.link(channel_id, Some(guild_id));
+        let nudge = format!(
+            "You haven't voted on the poll yet, it's halfway through! {}",
+            link
+        );
+        match store.queue_dms(&non_voters, &nudge, None).await {
+            Ok(queued) => record_dm_fanout(guild_id.to_string(), queued, ctx).await,
+            Err(e) => println!("Queueing poll nudges failed: {}", e),
+        }
+    } else {
+        for (chunk, users) in chunk_mentions(&non_voters) {
+            channel_id
+                .send_message(ctx, |m| {
+                    m.content(format!(
+                        "Haven't voted yet? The poll is halfway through! {}",
+                        chunk
+                    ))
+                    .reference_message((channel_id, message_id))
+                    .allowed_mentions(|mentions| mentions.empty_parse().users(users))
+                })
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Splits the mentions of the users so every message stays under the message length limit,
+/// and under the 100 users a message can be allowed to ping.
+/// Returns the mentions of each message with the users they ping.
+fn chunk_mentions(users: &[UserId]) -> Vec<(String, Vec<UserId>)> {
+    let mut chunks = Vec::new();
+    let mut chunk = (String::new(), Vec::new());
+    for user in users {
+        let mention = format!("<@{}> ", user);
+        if chunk.0.len() + mention.len() > 1900 || chunk.1.len() == 100 {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+        chunk.0.push_str(&mention);
+        chunk.1.push(*user);
+    }
+    chunks.push(chunk);
+    chunks
+}
+
+/// Splits text into parts of at most limit characters, breaking at line breaks or spaces when it can
+fn split_text(text: &str, limit: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut rest = text.trim();
+    while rest.chars().count() > limit {
+        let cut = rest
+            .char_indices()
+            .nth(limit)
+            .map_or(rest.len(), |(index, _)| index);
+        let head = &rest[..cut];
+        let split_at = head
+            .rfind('\n')
+            .or_else(|| head.rfind(' '))
+            .filter(|&index| index > 0)
+            .unwrap_or(cut);
+        parts.push(rest[..split_at].trim_end().to_string());
+        rest = rest[split_at..].trim_start();
+    }
+    if !rest.is_empty() {
+        parts.push(rest.to_string());
+    }
+    parts
+}
+
+/// Closes a poll once its duration is over, editing the final results into the poll and removing the vote buttons.
+async fn close_poll(
+    ctx: &Context,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> Result<(), SerenityError> {
+    let store = store(ctx).await;
+    let (votes_a, votes_b) = match store.close_poll_post(message_id.to_string()).await {
+        Ok(Some(tallies)) => tallies,
+        Ok(None) => return Ok(()),
+        Err(e) => {
+            println!("Closing poll failed: {}", e);
+            return Ok(());
+        }
+    };
+    // Weighted polls show what their tallies are made of
+    let breakdown = match store.get_poll_vote_breakdown(message_id.to_string()).await {
+        Ok(rows) => format_vote_breakdown(&rows),
+        Err(e) => {
+            println!("Getting the vote breakdown failed: {}", e);
+            String::new()
+        }
+    };
+    // Polls with an added option report the tallies from before it was added too
+    let amendment = get_poll_amendment(ctx, message_id).await;
+    let amended = amendment.as_ref().map_or(String::new(), |amendment| {
+        format!(
+            "\nBefore {} was added <t:{}:f>: {} - {} votes.",
+            amendment.option,
+            amendment.amended_at.timestamp(),
+            amendment.votes_before.0,
+            amendment.votes_before.1
+        )
+    });
+
+    let mut message = channel_id.message(ctx, message_id).await?;
+    let embed = match message.embeds.first() {
+        Some(embed) => embed.clone(),
+        None => {
+            // Polls posted in accessibility mode are plain text, the results replace the closing time
+            let options: Vec<&str> = message
+                .content
+                .lines()
+                .filter(|line| !line.starts_with("Closes"))
+                .collect();
+            let option_c = amendment.as_ref().map_or(String::new(), |amendment| {
+                format!(", Option C got {} votes", amendment.votes)
+            });
+            let results = format!(
+                "{}\nPoll closed! Option A got {} votes, Option B got {} votes{}.{}{}",
+                options.join("\n"),
+                votes_a,
+                votes_b,
+                option_c,
+                amended,
+                breakdown
+            );
+            message
+                .edit(ctx, |m| m.content(results).components(|c| c))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    // The first lines of the poll are the options, the closing time after them is replaced with the results
+    let description = embed.description.unwrap_or_default();
+    let lines = if amendment.is_some() { 3 } else { 2 };
+    let options: Vec<&str> = description.lines().take(lines).collect();
+    let results = match (options.as_slice(), &amendment) {
+        ([option_a, option_b], None) => format!(
+            "{} - **{}** votes\n{} - **{}** votes\nPoll closed!",
+            option_a, votes_a, option_b, votes_b
+        ),
+        ([option_a, option_b, option_c], Some(amendment)) => format!(
+            "{} - **{}** votes\n{} - **{}** votes\n{} - **{}** votes\nPoll closed!{}",
+            option_a, votes_a, option_b, votes_b, option_c, amendment.votes, amended
+        ),
+        _ => format!("Poll closed! {} - {}", votes_a, votes_b),
+    };
+    let results = format!("{}{}", results, breakdown);
+
+    message
+        .edit(ctx, |m| {
+            m.embed(|e| {
+                e.title(embed.title.unwrap_or_default())
+                    .description(results)
+                    .color(Color::DARK_MAGENTA)
+            })
+            .components(|c| c)
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Formats the rows of get_poll_vote_breakdown for the results of a weighted poll, one line per option.
+/// Returns an empty string for polls that aren't weighted.
+fn format_vote_breakdown(rows: &[Row]) -> String {
+    let mut lines: Vec<(i32, Vec<String>)> = Vec::new();
+    for row in rows {
+        let (option, role, weight, voters): (i32, Option<String>, i32, i64) =
+            (row.get(0), row.get(1), row.get(2), row.get(3));
+        let voted_as = match role {
+            Some(role) => format!("{} × <@&{}> ({}x)", voters, role, weight),
+            None => format!("{} × no weighted role", voters),
+        };
+        match lines.last_mut() {
+            Some((last, parts)) if *last == option => parts.push(voted_as),
+            _ => lines.push((option, vec![voted_as])),
+        }
+    }
+
+    if lines.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<String> = lines
+        .into_iter()
+        .map(|(option, parts)| {
+            let label = ["A", "B", "C"].get((option - 1) as usize).unwrap_or(&"?");
+            format!("Option {}: {}", label, parts.join(", "))
+        })
+        .collect();
+    format!("\nVotes weighted by role:\n{}", lines.join("\n"))
+}
+
+/// Posts a poll into the channel and records it for vote tallying.
+/// Custom polls use their own emoji, duration and anonymity, the global polls use the defaults.
+/// Only the pings given are live, mentions written in the poll are shown without pinging.
+#[allow(clippy::too_many_arguments)]
+async fn send_poll(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel: ChannelId,
+    poll_id: i32,
+    custom: bool,
+    poll: &[String],
+    message_string: String,
+    pings: Pings,
+) -> CommandResult<Message> {
+    let store = store(ctx).await;
+    let (emoji, duration_hours, anonymous, weighted) = if custom {
+        store.get_custom_poll_settings(poll_id).await?
+    } else {
+        (
+            vec![String::from("🟠"), String::from("🔵")],
+            None,
+            false,
+            false,
+        )
+    };
+    // Guilds voting with buttons get every poll as an anonymous one, weights are only known for button votes
+    let anonymous = anonymous || weighted || store.get_poll_buttons(guild_id.to_string()).await?;
+
+    let accessible = store.get_accessible_posts(guild_id.to_string()).await?;
+    let (_, title) = store.get_poll_texts(guild_id.to_string()).await?;
+    let title = fill_poll_text(guild_id.to_string(), &title, &poll[0], ctx).await?;
+
+    let options = format!("{} - {}\n{} - {}", emoji[0], &poll[1], emoji[1], &poll[2]);
+    // Accessibility mode spells the options out instead of leaning on the emoji legend
+    let spelled_options = if anonymous {
+        format!(
+            "Option A: {}\nOption B: {}\nVote with the buttons below.",
+            &poll[1], &poll[2]
+        )
+    } else {
+        format!(
+            "Option A: {}, react with {}\nOption B: {}, react with {}",
+            &poll[1], emoji[0], &poll[2], emoji[1]
+        )
+    };
+    let mut description = if accessible {
+        spelled_options
+    } else {
+        options.clone()
+    };
+    if weighted {
+        description = format!("{}\nVotes are weighted by role.", description);
+    }
+    if let Some(hours) = duration_hours {
+        let closes_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs())
+            .unwrap_or(0)
+            + hours as u64 * 3600;
+        description = format!("{}\nCloses <t:{}:R>", description, closes_at);
+    }
+
+    let post = if accessible {
+        Post::plain(&format!(
+            "{}\nPoll: {}\n{}",
+            message_string, title, description
+        ))
+        .with_pings(pings)
+    } else {
+        Post::embed(
+            &message_string,
+            &title,
+            &description,
+            None,
+            Color::DARK_MAGENTA,
+        )
+        .with_pings(pings)
+    };
+    let send = |channel: ChannelId| {
+        let (post, emoji) = (&post, &emoji);
+        channel.send_message(ctx, move |message| {
+            post.build(message);
+            // Anonymous polls are voted on with buttons, so voters aren't shown on the message
+            if anonymous {
+                message.components(|c| create_vote_buttons(c, poll, emoji, None, None));
+            }
+            message
+        })
+    };
+    // Posting to the backup channel if the poll can't be posted to its own
+    let (channel, message) = match send(channel).await {
+        Ok(message) => (channel, message),
+        Err(e) => {
+            let backup = fail_over(ctx, guild_id, channel, e).await?;
+            (backup, send(backup).await?)
+        }
+    };
+    post.send_follow_ups(ctx, channel, &message).await?;
+
+    store
+        .record_posted_poll(
+            guild_id.to_string(),
+            poll_id,
+            custom,
+            channel.to_string(),
+            message.id.to_string(),
+            emoji.clone(),
+            anonymous,
+            weighted,
+        )
+        .await?;
+
+    if !anonymous {
+        message.react(ctx, Unicode(emoji[0].clone())).await?;
+        message.react(ctx, Unicode(emoji[1].clone())).await?;
+    }
+
+    if let Some(hours) = duration_hours {
+        store
+            .add_deadline(
+                "close_poll",
+                guild_id.to_string(),
+                channel.to_string(),
+                Some(message.id.to_string()),
+                hours * 3600,
+            )
+            .await?;
+
+        // Non-voters can only be told apart on anonymous polls, reactions aren't stored per user
+        if anonymous && store.get_poll_nudge(guild_id.to_string()).await? != "off" {
+            store
+                .add_deadline(
+                    "poll_nudge",
+                    guild_id.to_string(),
+                    channel.to_string(),
+                    Some(message.id.to_string()),
+                    hours * 1800,
+                )
+                .await?;
+        }
+    }
+
+    mirror_to_archive(
+        guild_id.to_string(),
+        &title,
+        &options,
+        Color::DARK_MAGENTA,
+        ctx,
+    )
+    .await;
+
+    Ok(message)
+}
+
+/// Renders the share of votes for the first option as a compact trend line.
+/// Each character is one time the poll was posted, · for a poll without votes.
+fn render_poll_trend(tallies: &[(i32, i32)]) -> String {
+    let levels = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    tallies
+        .iter()
+        .map(|(votes_a, votes_b)| {
+            let total = votes_a + votes_b;
+            if total == 0 {
+                '·'
+            } else {
+                let level = (*votes_a as usize * (levels.len() - 1)) / total as usize;
+                levels[level]
+            }
+        })
+        .collect()
+}
+
+/// Asks the admin building a poll to type a value, re-asking while it's longer than max_length.
+/// Returns None if the admin cancels or doesn't answer in two minutes.
+async fn ask_builder_text(
+    ctx: &Context,
+    invocation: &Invocation,
+    prompt: &str,
+    max_length: usize,
+) -> Result<Option<String>, SerenityError> {
+    // Replies can't be read in slash only mode, the value is typed into a modal instead
+    if slash_only() {
+        return ask_builder_modal(ctx, invocation, prompt, max_length).await;
+    }
+
+    invocation
+        .channel_id
+        .say(
+            ctx,
+            format!(
+                "<@{}> {} (type cancel to stop)",
+                invocation.author.id, prompt
+            ),
+        )
+        .await?;
+
+    loop {
+        let reply = waiting_on_user(
+            invocation
+                .channel_id
+                .await_reply(ctx)
+                .author_id(invocation.author.id)
+                .timeout(Duration::from_secs(120)),
+        )
+        .await;
+
+        let answer = match reply {
+            Some(reply) => reply.content.trim().to_string(),
+            None => {
+                invocation.reply(ctx, "Poll builder timed out!").await?;
+                return Ok(None);
+            }
+        };
+
+        if answer.eq_ignore_ascii_case("cancel") {
+            invocation.reply(ctx, "Poll builder cancelled!").await?;
+            return Ok(None);
+        }
+        if answer.is_empty() || answer.chars().count() > max_length {
+            invocation
+                .reply(
+                    ctx,
+                    format!("Please keep it between 1 and {} characters!", max_length),
+                )
+                .await?;
+            continue;
+        }
+
+        return Ok(Some(answer));
+    }
+}
+
+/// Asks the admin building a poll to type a value into a modal opened with a button.
+/// Returns None if the admin cancels or doesn't answer in two minutes.
+async fn ask_builder_modal(
+    ctx: &Context,
+    invocation: &Invocation,
+    prompt: &str,
+    max_length: usize,
+) -> Result<Option<String>, SerenityError> {
+    let mut question = invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}> {}", invocation.author.id, prompt))
+                .components(|c| {
+                    c.create_action_row(|row| {
+                        row.create_button(|b| {
+                            b.custom_id("builder_type")
+                                .label("Answer")
+                                .style(ButtonStyle::Primary)
+                        })
+                        .create_button(|b| {
+                            b.custom_id("builder_cancel")
+                                .label("Cancel")
+                                .style(ButtonStyle::Secondary)
+                        })
+                    })
+                })
+        })
+        .await?;
+
+    let interaction = waiting_on_user(
+        question
+            .await_component_interaction(ctx)
+            .author_id(invocation.author.id)
+            .timeout(Duration::from_secs(120)),
+    )
+    .await;
+
+    let interaction = match interaction {
+        Some(interaction) if interaction.data.custom_id == "builder_type" => interaction,
+        Some(interaction) => {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|d| {
+                            d.content("Poll builder cancelled!").components(|c| c)
+                        })
+                })
+                .await?;
+            return Ok(None);
+        }
+        None => {
+            question
+                .edit(ctx, |m| {
+                    m.content("Poll builder timed out!").components(|c| c)
+                })
+                .await?;
+            return Ok(None);
+        }
+    };
+
+    interaction
+        .create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::Modal)
+                .interaction_response_data(|d| {
+                    d.custom_id("builder_text")
+                        .title("Poll builder")
+                        .components(|c| {
+                            c.create_action_row(|row| {
+                                row.create_input_text(|input| {
+                                    input
+                                        .custom_id("value")
+                                        .label(prompt)
+                                        .style(InputTextStyle::Short)
+                                        .max_length(max_length as u64)
+                                        .required(true)
+                                })
+                            })
+                        })
+                })
+        })
+        .await?;
+
+    let submit = waiting_on_user(
+        question
+            .await_modal_interaction(ctx)
+            .author_id(invocation.author.id)
+            .timeout(Duration::from_secs(120)),
+    )
+    .await;
+
+    let submit = match submit {
+        Some(submit) => submit,
+        None => {
+            question
+                .edit(ctx, |m| {
+                    m.content("Poll builder timed out!").components(|c| c)
+                })
+                .await?;
+            return Ok(None);
+        }
+    };
+
+    let answer = submit
+        .data
+        .components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == "value" => {
+                Some(input.value.trim().to_string())
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    // Leaving the answer in the message so the conversation reads back
+    submit
+        .create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::UpdateMessage)
+                .interaction_response_data(|d| {
+                    d.content(format!("{} **{}**", prompt, answer))
+                        .components(|c| c)
+                })
+        })
+        .await?;
+
+    if answer.is_empty() {
+        invocation.reply(ctx, "Poll builder cancelled!").await?;
+        return Ok(None);
+    }
+    Ok(Some(answer))
+}
+
+/// Asks the admin building a poll to pick one of the choices with buttons.
+/// Returns the index of the picked choice, None if the admin doesn't pick in two minutes.
+async fn ask_builder_choice(
+    ctx: &Context,
+    invocation: &Invocation,
+    prompt: &str,
+    choices: &[&str],
+) -> Result<Option<usize>, SerenityError> {
+    let mut question = invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}> {}", invocation.author.id, prompt))
+                .components(|c| {
+                    c.create_action_row(|row| {
+                        for (i, choice) in choices.iter().enumerate() {
+                            row.create_button(|b| {
+                                b.custom_id(format!("builder_{}", i))
+                                    .label(choice)
+                                    .style(ButtonStyle::Primary)
+                            });
+                        }
+                        row
+                    })
+                })
+        })
+        .await?;
+
+    let interaction = waiting_on_user(
+        question
+            .await_component_interaction(ctx)
+            .author_id(invocation.author.id)
+            .timeout(Duration::from_secs(120)),
+    )
+    .await;
+
+    let interaction = match interaction {
+        Some(interaction) => interaction,
+        None => {
+            question
+                .edit(ctx, |m| {
+                    m.content("Poll builder timed out!").components(|c| c)
+                })
+                .await?;
+            return Ok(None);
+        }
+    };
+
+    let picked = interaction
+        .data
+        .custom_id
+        .trim_start_matches("builder_")
+        .parse::<usize>()
+        .ok()
+        .filter(|picked| *picked < choices.len());
+
+    // Leaving the picked choice in the message so the conversation reads back
+    let answer = picked.map(|picked| choices[picked]).unwrap_or("?");
+    interaction
+        .create_interaction_response(ctx, |r| {
+            r.kind(InteractionResponseType::UpdateMessage)
+                .interaction_response_data(|d| {
+                    d.content(format!("{} **{}**", prompt, answer))
+                        .components(|c| c)
+                })
+        })
+        .await?;
+
+    Ok(picked)
+}
+
+async fn help(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    // Commands are split into sections, all of them don't fit into one embed
+    let section = invocation.args.text("section").to_lowercase();
+
+    let (title, description) = match section.as_str() {
+        "questions" => ("Help - Questions", "
+                    **qotd [stream]** - Sends a random question of the day! \n
+                    **custom_qotd <Optional: id/tag>** - Sends a question of the day from the list of custom questions, or one with the tag! \n\
+                    **submit_qotd <Optional: --mature> <question>** - Submit a custom question, --mature flags it as mature. Questions can span several lines, up to 1000 characters. {server}, {random_member}, {month} and {weekday} are filled in when the question is posted. /submit_qotd without a question opens a form for longer ones, and Apps > Save as QOTD on a message adds the message.\n
+                    **delete_question <id>** - Deletes the specified question from the list of questions. Without an id, pick it from a menu.\n
+                    **delete_questions <ids>** - Deletes many questions at once, like delete_questions 3 5 7 or 10-20.\n
+                    **clear_questions** - Deletes every custom question, after confirming.\n
+                    **edit_question <id> <new question>** - Fixes the text of a custom question, it keeps its id.\n
+                    **list_qotd** - Lists all custom questions saved for the server.\n
+                    **restore_question <id>** - Brings back a question deleted in the last 30 days. Without an id lists the deleted questions.\n
+                    **retire <id>** / **unretire <id>** - Stops a custom question from being picked without deleting it, or brings it back.\n
+                    **note <id> <note/clear>** - Adds a private note to a custom question, shown in list_qotd.\n
+                    **tag <id> <tags>** - Tags a custom question, like tag 12 deep. A tag starting with - is removed, like tag 12 -deep.\n
+                    **mature <id> <on/off>** - Flags a custom question as mature, or unflags it.\n
+                    **family_friendly <on/off>** - Never picks questions flagged as mature, for scheduled and random questions.\n
+                    **question_filter <tags/off>** - Only picks questions with one of the tags, for scheduled and random questions.\n
+                    **draft <question/poll> <text>** - Saves a question, or a poll in the submit_poll format, privately for later. Without text lists your drafts, delete <id> removes one.\n
+                    **publish_draft <id>** - Adds one of your drafts to the custom questions or polls.\n
+                    **trivia <on/off>** - Grades the answer threads of questions with an answer, the first correct answer gets a point.\n
+                    **trivia_answer <id> <answer/clear>** - Sets the answer of a custom question for trivia, alternatives separated by |.\n
+                    **trivia_winner <member/none>** - Overrides who won, used in the answer thread of a trivia question.\n
+                    **trivia_scores** - Shows the members with the most trivia points.\n
+                    **suggest <question>** - Suggests a question to the server, it's posted once an admin approves it. Anyone can use this.\n
+                    **history [count]** - Shows the latest questions posted in the server with their dates, 10 by default and up to 25.\n
+                    **top_questions [worst]** - Shows the questions members rated 👍 most, or 👎 most with worst.\n
+                    **answer <answer>** - Answers the trivia question of the answer thread, for when answers can't be read from messages.\n
+                    **saved** - Sends you a DM with all the questions you have saved.\n
+                    **notify <ping/dm/none/default>** - Chooses how you're notified of questions of the day on top of the server's ping role. none stops the bot's mentions and DMs, the ping role still reaches you while you have the role.\n
+                    **cooldown <days>** - Sets how many days before a custom question can be picked again. 0 disables it.\n
+                    **question_cooldown <id> <days/default>** - Overrides the cooldown for a single custom question.\n
+                    **fair_rotation <on/off>** - Gives every submitter the same chance of having their question picked.\n
+                    **custom_only <on/off>** - Picks qotd and scheduled questions only from the custom questions, leaving out the default ones.\n
+                    **recommend <on/off>** - Picks questions like the ones members answered and saved most more often.\n
+                    **seasonal <on/off>** - Prefers default questions tagged for the season, like halloween or christmas, and holds them back the rest of the year. On by default.\n
+                    **mix <default %> <custom %>** - Picks qotd and scheduled questions from the default and custom questions by the percentages, like mix 70 30. off picks only default ones.\n
+                    **pending** - Lists the questions waiting for approval, like suggestions from members.\n
+                    **approve <ids>** / **reject <ids>** - Approves or rejects questions held for moderation, like 3, 3-9 or 3,5,7. approve_all and reject_all clear the whole queue.\n
+                    **strictness <off/low/high>** - Sets how strictly submissions are checked. Suspicious ones are held for moderation.\n
+                    **share_pool <invite/join <code>/leave>** - Shares one custom question pool with partner servers.\n
+                    **queue_question <id> <YYYY-MM-DD>** - Reserves a custom question for the scheduled post on the date. remove <date> undoes it.\n
+                    **merge_pool** - Merges an exported pool of questions and polls attached to the message into this server.\n
+                    **import** - Adds the questions of an attached CSV or JSON file, like from another QOTD bot. CSV needs a question column or one question per line. Optional source_url and license columns or fields credit where the questions are from.\n
+                    **export** - Sends the custom questions, polls and settings of the server as a JSON file, for backups. The file works with merge_pool.\n
+                    **removal_export <on/off>** - Sends the server owner the export in DMs if the bot is removed from the server. On by default.\n
+                    **restrict <channel/off>** - Only allows bot commands in the channel. Commands used elsewhere are deleted and the member is told where to use them.\n
+                    **block <member>** / **unblock <member>** - Stops a member from submitting questions and polls, or lets them again. block alone lists the blocked members.\n
+                    **harvest <channel> [count]** - Goes through up to count past messages of the channel, 100 by default, and imports the ones you pick as questions.\n"),
+        "polls" => ("Help - Polls", "
+                    **poll** - Sends a random poll of the day!\n
+                    **custom_poll <Optional: id>** - Sends a poll of the day from a list of custom polls!\n
+                    **submit_poll** - Submits a new custom poll!\n
+                    **build_poll** - Builds a custom poll step by step, with emoji, duration and anonymous voting.\n
+                    **delete_poll <id>** - Deletes the specified poll from the list of custom polls\n
+                    **list_polls** - Lists all polls currently saved for the server!\n
+                    **poll_buttons <on/off>** - Votes on every poll with buttons showing live tallies, instead of reactions.\n
+                    **poll_weight <role> <weight/off>** - Counts the votes of members with the role as weight votes on weighted polls, like poll_weight @Booster 2. Without a role lists the weights.\n
+                    **weighted_poll <id> <on/off>** - Weighs the votes on a custom poll by role. Weighted polls are voted on with buttons and show the breakdown when they close.\n
+                    **poll_nudge <off/ping/dm>** - Reminds members with the ping role who haven't voted halfway through anonymous polls with a duration.\n
+                    **poll_history <id>** - Shows how the results of a custom poll changed every time it was posted.\n
+                    **amend_poll <message link> add \"<option>\"** - Adds a third option to a live poll, the votes from before it was added are shown when the poll closes.\n
+                    **poll_text <intro/title> <text/default>** - Sets the line posted with polls and the title of the poll embed. {date} and {question} are filled in.\n"),
+        "config" => ("Help - Config", "
+                    **config** - Shows all the settings of the server.\n
+                    **diagnose** - Checks the bot has the permissions it needs in every configured channel, with fixes.\n
+                    **set_channel** - Sets which channel is used for questions of the day. \n
+                    **channel** - Lists which channel is currently used for questions of the day.\n
+                    **channel_fallback <on/off>** - Posts qotd and poll commands in the channel they're used in while no channel is set. On by default.\n
+                    **ping_role <0 (default)/1/<role>>** - Sets the ping setting for question of the day. \n
+                    **stream <name> <channel/role/template/remove> <value>** - Sets up extra posting streams, each with its own ping. Post to one with qotd <name>.\n
+                    **schedule <HH:MM/cron/off>** - Posts questions automatically every day at the set time, or on a cron schedule like `0 9 * * MON,WED,FRI`.\n
+                    **poll_schedule <HH:MM/cron/off> [custom]** - Posts polls automatically on a schedule, from the servers custom polls with custom.\n
+                    **calendar** - Shows the scheduled questions, polls, queued questions and skipped days of the next 14 days.\n
+                    **next** - Sends you the next scheduled question and when it's posted in DMs.\n
+                    **pause** / **resume** - Pauses and resumes scheduled questions and polls.\n
+                    **skip_days <days/off>** - Leaves weekdays out of scheduled posting, like skip_days sat sun.\n
+                    **double_days <days/off>** - Posts two scheduled questions side by side on the weekdays, members react to pick one. Question tags that win more votes get picked more often.\n
+                    **set_timezone <tz/UTC>** - Sets the timezone of the schedule, like Europe/Helsinki.\n
+                    **teaser <minutes/off>** - Posts a teaser ping the set amount of minutes before the scheduled question.\n
+                    **fallback <text/default>** - Sets the text posted when there are no questions to pick from.\n
+                    **answer_window <hours/off>** - Opens an answer thread on questions and locks it after the set hours.\n
+                    **answer_filter <min/limit> <value>** - Sets the shortest answer that counts in answer threads, 3 characters by default, and how many answers of a member count. Repeats never count.\n
+                    **set_archive <channel/off>** - Sets a channel where posted questions and polls are archived without pings.\n
+                    **backup_channel <channel/off>** - Posts questions and polls here when posting to their channel fails, and tells the admin contacts.\n
+                    **admin_contact <add/remove> <member>** - Adds or removes an admin told about failed posts, running out of questions and submissions held for moderation. Without contacts the admin who set the channel is told.\n
+                    **notify_policy <all/rotate>** - Sends notifications to every admin contact, or to one at a time taking turns.\n
+                    **audit_log** - Shows the latest events of the server, like posts failing over to the backup channel.\n
+                    **checklist <Optional: channel/off>** - Posts the setup checklist here or in the channel and pins it, it's kept updated as settings change. off removes it.\n
+                    **usage** - Shows what the server used of its quotas: commands today, DM fan-outs and imports this month.\n
+                    **outbox** - Shows whether the latest scheduled questions and polls went out, with links to the posts.\n
+                    **modlog <channel/webhook URL/off>** - Sends question submissions, approvals, rejections, edits and deletions to a channel or webhook, for moderation bots. Pick them with modlog events <events/all>.\n
+                    **save_emoji <emoji>** - Sets the emoji members react with to save a question for later. Default is 📌\n
+                    **attribution <on/off>** - Shows or hides the pack a question came from in posted questions. attribution submitter <on/off> credits who submitted a custom question, attribution mention <on/off> pings them in a reply.\n
+                    **accessible <on/off>** - Posts questions and polls as plain text with the options spelled out, for screen readers.\n
+                    **image_card <on/off/preview>** - Posts questions of the day rendered onto an image card. Style it with image_card color <#hex/default> and image_card template <plain/stripe/frame>.\n
+                    **cleanup <seconds/off> [commands]** - Deletes the bots replies after a while, and the commands too with commands.\n
+                    **ephemeral_replies <on/off>** - Shows the replies to set_channel, ping_role and delete_question as slash commands only to you. On by default.\n
+                    **everyone_approval <on/off>** - Requires a second admin to approve @everyone pings.\n
+                    **api_token <create/revoke> <read/manage>** - Creates or revokes a token for the HTTP API, sent to you in DMs.\n"),
+        _ => ("Help", "
+                    {prefix}\n
+                    \n Use **help <section>** to see the commands of a section.\n
+                    \n **questions** - Posting questions and managing custom questions.\n
+                    **polls** - Posting polls and managing custom polls.\n
+                    **config** - Channels, pings, the schedule and other settings.\n
+                    \n **help** - Brings up this message!"),
+    };
+    // Commands are only slash commands in slash only mode
+    let prefix = if slash_only() {
+        "Commands are slash commands, like /qotd. Settings are under /settings and poll admin commands under /poll_admin."
+    } else {
+        "**Current command prefix:** q! \n
+                    Every command works as a slash command too, like /qotd. Settings are under /settings and poll admin commands under /poll_admin."
+    };
+    let description = description.replace("{prefix}", prefix);
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
+                .embed(|embed| {
+                    embed
+                        .title(title)
+                        .description(description)
+                        .color(Color::DARK_GREEN)
+                })
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn set_channel(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap(); // lazy solution, expecting the message to exist
+
+    match invocation.args.channel("channel").map(|channel| channel.0) {
+        Some(cid) => {
+            let channel_id_slice = cid;
+
+            // Checking that the channel is in the server.
+            // We safely assume that this command is being called from a server so not handling null
+            let guild_channels = ctx
+                .cache
+                .guild_channels(guild_id)
+                .ok_or("Command not being called from a guild?")?;
+            let channel_id = ChannelId(channel_id_slice);
+
+            if guild_channels.contains_key(&channel_id) {
+                // Calling function to set the the stuff to database
+                store(ctx)
+                    .await
+                    .set_ping_channel_id(
+                        channel_id_slice.to_string(),
+                        guild_id.to_string(),
+                        invocation.author.id.to_string(),
+                    )
+                    .await?;
+                invocation.reply(ctx, "Channel set!").await?;
+            } else {
+                invocation
+                    .reply(ctx, "Channel not found on this server!")
+                    .await?;
+            }
+        }
+        None => {
+            invocation.reply(ctx, "Not a valid channel!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn channel(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap(); // lazy solution, expecting the message to exist
+
+    let channel_id = store(ctx)
+        .await
+        .get_ping_channel_id(guild_id.to_string())
+        .await?;
+
+    // Slightly convoluted. If the string returned is a 0, that means there was no result
+    // This assumes channel id 0 does not exist on any server (safe assumption)
+    // If the string returned isn't a 0, it's the id of the channel assigned
+    // which is then used for parse_channel.
+
+    // Fails if string was 0 and there was no result. Please don't judge me for this solution.
+    match parse_channel(&channel_id) {
+        Some(_cid) => {
+            invocation
+                .reply(ctx, format!("Channel is set to {}", channel_id))
+                .await?;
+        }
+        None => {
+            invocation.reply(ctx, "Channel not set!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn qotd(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    // Posting to the default stream unless one is named
+    let named = invocation.args.text("stream");
+    let mut stream = if !named.is_empty() {
+        match store
+            .get_stream(guild_id.to_string(), named.to_lowercase())
+            .await?
+        {
+            Some(stream) => stream,
+            None => {
+                invocation.reply(ctx, "Stream not found!").await?;
+                return Ok(());
+            }
+        }
+    } else {
+        store.get_default_stream(guild_id.to_string()).await?
+    };
+
+    stream.channel = match posting_channel(ctx, invocation, stream.channel).await? {
+        Some(channel) => Some(channel),
+        None => return Ok(()),
+    };
+
+    if stream.ping_role == "1"
+        && !everyone_ping_approved(ctx, invocation, "post a question with an @everyone ping")
+            .await?
+    {
+        return Ok(());
+    }
+
+    let (question, attribution) =
+        match get_random_question_for_guild(guild_id.to_string(), None, ctx).await? {
+            Some(selected) => selected,
+            None => (
+                store.get_fallback_question(guild_id.to_string()).await?,
+                None,
+            ),
+        };
+
+    let message = send_question(ctx, guild_id, &stream, "Question", question, attribution).await?;
+    add_rating_reactions(ctx, &message).await;
+
+    Ok(())
+}
+
+async fn custom_qotd(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let mut stream = store.get_default_stream(guild_id.to_string()).await?;
+
+    // Checking the channel before selecting, selecting a question starts its cooldown.
+    stream.channel = match posting_channel(ctx, invocation, stream.channel).await? {
+        Some(channel) => Some(channel),
+        None => return Ok(()),
+    };
+
+    if stream.ping_role == "1"
+        && !everyone_ping_approved(
+            ctx,
+            invocation,
+            "post a custom question with an @everyone ping",
+        )
+        .await?
+    {
+        return Ok(());
+    }
+
+    let (custom_question, attribution) = if let Some(id_to_use) = invocation.args.id("id") {
+        match store
+            .get_specific_custom_question(guild_id.to_string(), id_to_use)
+            .await?
+        {
+            Some(selected) => selected,
+            None => {
+                invocation.reply(ctx, "Question does not exist!").await?;
+                return Ok(());
+            }
+        }
+    } else if invocation.args.get("id").is_some() {
+        // Anything else is a tag to pick a question from, for themed days
+        let tags = parse_tags(invocation.args.text("id"));
+        if tags.is_empty() {
+            invocation
+                .reply(ctx, "Not a valid question ID or tag")
+                .await?;
+            return Ok(());
+        }
+        match store
+            .get_random_custom_question(guild_id.to_string(), Some(tags))
+            .await?
+        {
+            Some(selected) => selected,
+            None => {
+                invocation
+                    .reply(ctx, "No questions with that tag can be posted right now!")
+                    .await?;
+                return Ok(());
+            }
+        }
+    } else {
+        let tags = store.get_question_filter(guild_id.to_string()).await?;
+        match store
+            .get_random_custom_question(guild_id.to_string(), tags)
+            .await?
+        {
+            Some(selected) => selected,
+            None => (
+                store.get_fallback_question(guild_id.to_string()).await?,
+                None,
+            ),
+        }
+    };
+
+    let message = send_question(
+        ctx,
+        guild_id,
+        &stream,
+        "Custom Question",
+        custom_question,
+        attribution,
+    )
+    .await?;
+    add_rating_reactions(ctx, &message).await;
+
+    Ok(())
+}
+
+async fn submit_qotd(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let user_submission = invocation.args.text("question");
+    // --mature flags the question, family friendly servers never get it picked
+    let mature = invocation.args.text("mature") == "--mature";
+
+    // If message is valid
+    if !user_submission.is_empty() {
+        if let Some(error) = question_length_error(user_submission) {
+            invocation.reply(ctx, error).await?;
+            return Ok(());
+        }
+
+        if store
+            .question_is_duplicate(guild_id.to_string(), user_submission)
+            .await?
+        {
+            invocation
+                .reply(ctx, "This question is already saved!")
+                .await?;
+        } else if store.question_is_under_limit(guild_id.to_string()).await? {
+            let flag_reason = rate_question(guild_id.to_string(), user_submission, ctx).await?;
+            let warning = similar_question_warning(
+                find_similar_question(guild_id.to_string(), user_submission, ctx).await?,
+            );
+            match store
+                .add_custom_question(
+                    guild_id.to_string(),
+                    user_submission.to_string(),
+                    Some(invocation.author.id.to_string()),
+                    flag_reason.clone(),
+                    mature,
+                )
+                .await
+            {
+                // Saved by someone else since the check above
+                Ok(0) => {
+                    invocation
+                        .reply(ctx, "This question is already saved!")
+                        .await?;
+                }
+                Ok(_) => {
+                    let detail = match &flag_reason {
+                        Some(reason) => {
+                            format!("{}\n\nHeld for moderation ({})", user_submission, reason)
+                        }
+                        None => user_submission.to_string(),
+                    };
+                    let event = QuestionEvent {
+                        kind: "submitted",
+                        question_ids: Vec::new(),
+                        by: Some(invocation.author.id),
+                        detail: &detail,
+                    };
+                    emit_question_event(ctx, guild_id.to_string(), event).await;
+
+                    match flag_reason {
+                        Some(reason) => {
+                            invocation
+                                .reply(
+                                    ctx,
+                                    format!("Question held for moderation ({}){}", reason, warning),
+                                )
+                                .await?;
+                            let notice = format!(
+                            "{}: A question from <@{}> is waiting for moderation ({}). Use approve or reject.",
+                            guild_display_name(ctx, &guild_id.to_string()),
+                            invocation.author.id,
+                            reason
+                        );
+                            notify_admins(ctx, guild_id, &notice).await;
+                        }
+                        None => {
+                            invocation
+                                .reply(ctx, format!("Question Submitted{}", warning))
+                                .await?;
+                        }
+                    }
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    invocation.reply(ctx, "Something went wrong!").await?;
+                }
+            }
+        } else {
+            invocation
+                .reply(
+                    ctx,
+                    "Too many custom questions saved! Please delete some before adding more!",
+                )
+                .await?;
+        }
+    } else {
+        invocation.reply(ctx, "Question not accepted").await?;
+    }
+
+    Ok(())
+}
+
+// Questions a member can have waiting for approval at once, so the queue can't be flooded
+const MEMBER_PENDING_LIMIT: usize = 5;
+
+async fn suggest(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = match invocation.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            invocation
+                .reply(
+                    ctx,
+                    "Questions are suggested to a server, use this in a server!",
+                )
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let suggestion = invocation.args.text("question");
+    if suggestion.is_empty() {
+        invocation.reply(ctx, "Usage: suggest <question>").await?;
+        return Ok(());
+    }
+    if let Some(error) = question_length_error(suggestion) {
+        invocation.reply(ctx, error).await?;
+        return Ok(());
+    }
+
+    let questions = store
+        .get_list_custom_questions(guild_id.to_string())
+        .await?;
+    let pending = questions
+        .iter()
+        .filter(|row| {
+            row.get::<_, String>("status") == "flagged"
+                && row.get::<_, Option<String>>("submitted_by")
+                    == Some(invocation.author.id.to_string())
+        })
+        .count();
+    if pending >= MEMBER_PENDING_LIMIT {
+        invocation.reply(
+            ctx,
+            format!(
+                "You have {} questions waiting for approval already, wait for the admins to go through them!",
+                pending
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+    if store
+        .question_is_duplicate(guild_id.to_string(), suggestion)
+        .await?
+    {
+        invocation
+            .reply(ctx, "This question is already saved!")
+            .await?;
+        return Ok(());
+    }
+    if !store.question_is_under_limit(guild_id.to_string()).await? {
+        invocation
+            .reply(ctx, "This server can't take more questions right now!")
+            .await?;
+        return Ok(());
+    }
+
+    // Suggestions always go into the moderation queue, with the reason it looks suspicious if it does
+    let reason = rate_question(guild_id.to_string(), suggestion, ctx)
+        .await?
+        .unwrap_or_else(|| String::from("member suggestion"));
+    let warning = similar_question_warning(
+        find_similar_question(guild_id.to_string(), suggestion, ctx).await?,
+    );
+    match store
+        .add_custom_question(
+            guild_id.to_string(),
+            suggestion.to_string(),
+            Some(invocation.author.id.to_string()),
+            Some(reason.clone()),
+            false,
+        )
+        .await
+    {
+        Ok(0) => {
+            invocation
+                .reply(ctx, "This question is already saved!")
+                .await?;
+        }
+        Ok(_) => {
+            let detail = format!("{}\n\nHeld for moderation ({})", suggestion, reason);
+            let event = QuestionEvent {
+                kind: "submitted",
+                question_ids: Vec::new(),
+                by: Some(invocation.author.id),
+                detail: &detail,
+            };
+            emit_question_event(ctx, guild_id.to_string(), event).await;
+
+            invocation
+                .reply(
+                    ctx,
+                    format!("Question sent to the admins for approval!{}", warning),
+                )
+                .await?;
+            let notice = format!(
+                "{}: <@{}> suggested a question ({}). See pending, then approve or reject.",
+                guild_display_name(ctx, &guild_id.to_string()),
+                invocation.author.id,
+                reason
+            );
+            notify_admins(ctx, guild_id, &notice).await;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn pending(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+    let questions = store(ctx)
+        .await
+        .get_list_custom_questions(guild_id.to_string())
+        .await?;
+
+    let mut pretty_list = String::new();
+    for row in questions
+        .iter()
+        .filter(|row| row.get::<_, String>("status") == "flagged")
+    {
+        let qid: i32 = row.get(0);
+        let question: String = row.get(2);
+        let reason: Option<String> = row.get("flag_reason");
+        let submitted_by: Option<String> = row.get("submitted_by");
+        let mut line = format!(
+            "{} - {} *({})*",
+            qid,
+            question,
+            reason.unwrap_or_else(|| String::from("held"))
+        );
+        if let Some(user_id) = submitted_by {
+            line = format!("{} by <@{}>", line, user_id);
+        }
+        pretty_list = format!("{}{}\n", pretty_list, line);
+    }
+
+    if pretty_list.is_empty() {
+        invocation
+            .reply(ctx, "No questions are waiting for approval!")
+            .await?;
+        return Ok(());
+    }
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!(
+                "<@{}> Questions waiting for approval, approve or reject them by id",
+                invocation.author.id
+            ))
+            .embed(|embed| {
+                embed
+                    .title("Pending questions")
+                    .description(pretty_list)
+                    .color(Color::ORANGE)
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Deletes the custom questions of the guild, tells the admin and sends the deletion to the modlog.
+/// None deletes every question of the guild.
+async fn delete_questions_in_bulk(
+    ctx: &Context,
+    invocation: &Invocation,
+    question_ids: Option<Vec<i32>>,
+) -> CommandResult {
+    let guild_id = invocation.guild_id.ok_or("Not in a guild")?;
+    let requested = question_ids.as_ref().map(|ids| ids.len());
+
+    let deleted = match store(ctx)
+        .await
+        .delete_custom_questions(
+            guild_id.to_string(),
+            question_ids,
+            invocation.author.id.to_string(),
+        )
+        .await
+    {
+        Ok(deleted) => deleted,
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+            return Ok(());
+        }
+    };
+    if deleted.is_empty() {
+        invocation.reply(ctx, "No questions were deleted!").await?;
+        return Ok(());
+    }
+
+    let detail = format!("{} question(s) deleted", deleted.len());
+    let count = deleted.len();
+    let event = QuestionEvent {
+        kind: "deleted",
+        question_ids: deleted,
+        by: Some(invocation.author.id),
+        detail: &detail,
+    };
+    emit_question_event(ctx, guild_id.to_string(), event).await;
+
+    let missing = match requested {
+        Some(requested) if requested > count => {
+            format!(" {} of the ids weren't found.", requested - count)
+        }
+        _ => String::new(),
+    };
+    invocation
+        .reply(
+            ctx,
+            format!(
+            "Deleted {} question(s)!{} Use restore_question <id> within {} days to bring one back.",
+            count, missing, RESTORE_DAYS
+        ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn delete_questions(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    match parse_id_list(invocation.args.text("ids")) {
+        Some(ids) => delete_questions_in_bulk(ctx, invocation, Some(ids)).await?,
+        None => {
+            invocation
+                .reply(ctx, "Usage: delete_questions <ids>, like 3 5 7 or 10-20")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn clear_questions(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    let count = store(ctx)
+        .await
+        .get_list_custom_questions(guild_id.to_string())
+        .await?
+        .len();
+    if count == 0 {
+        invocation
+            .reply(ctx, "No custom questions to delete!")
+            .await?;
+        return Ok(());
+    }
+
+    let prompt_text = format!(
+        "<@{}> Delete all {} custom questions? They can be restored for {} days.",
+        invocation.author.id, count, RESTORE_DAYS
+    );
+    let mut prompt = invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(&prompt_text).components(|c| {
+                c.create_action_row(|row| {
+                    row.create_button(|b| {
+                        b.custom_id("clear_confirm")
+                            .label(format!("Delete {} questions", count))
+                            .style(ButtonStyle::Danger)
+                    })
+                    .create_button(|b| {
+                        b.custom_id("clear_cancel")
+                            .label("Cancel")
+                            .style(ButtonStyle::Secondary)
+                    })
+                })
+            })
+        })
+        .await?;
+
+    let interaction = waiting_on_user(
+        prompt
+            .await_component_interaction(ctx)
+            .author_id(invocation.author.id)
+            .timeout(Duration::from_secs(60)),
+    )
+    .await;
+
+    let confirmed = match interaction {
+        Some(interaction) => {
+            let confirmed = interaction.data.custom_id == "clear_confirm";
+            let content = if confirmed {
+                "Deleting every custom question..."
+            } else {
+                "Nothing was deleted."
+            };
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|d| d.content(content).components(|c| c))
+                })
+                .await?;
+            confirmed
+        }
+        None => {
+            prompt
+                .edit(ctx, |m| {
+                    m.content("Deleting timed out, nothing was deleted.")
+                        .components(|c| c)
+                })
+                .await?;
+            false
+        }
+    };
+
+    if confirmed {
+        delete_questions_in_bulk(ctx, invocation, None).await?;
+    }
+
+    Ok(())
+}
+
+// Days a deleted custom question can be restored before it's purged
+const RESTORE_DAYS: i32 = 30;
+
+async fn delete_question(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if invocation.args.get("id").is_some() {
+        match invocation.args.id("id") {
+            Some(id_to_delete) => {
+                let test = store
+                    .delete_custom_question(
+                        guild_id.to_string(),
+                        id_to_delete,
+                        Some(invocation.author.id.to_string()),
+                    )
+                    .await?;
+                if test == 1 {
+                    let detail = format!("Question {} deleted", id_to_delete);
+                    let event = QuestionEvent {
+                        kind: "deleted",
+                        question_ids: vec![id_to_delete],
+                        by: Some(invocation.author.id),
+                        detail: &detail,
+                    };
+                    emit_question_event(ctx, guild_id.to_string(), event).await;
+                    invocation.reply(ctx, format!(
+                            "Question deleted! Use restore_question {} within {} days to bring it back.",
+                            id_to_delete, RESTORE_DAYS
+                        ),
+                    )
+                    .await?;
+                } else {
+                    invocation.reply(ctx, "Question not found!").await?;
+                }
+            }
+            _ => {
+                invocation.reply(ctx, "Please enter a valid ID!").await?;
+            }
+        }
+    } else {
+        // Getting all questions
+        let question_list = store
+            .get_list_custom_questions(guild_id.to_string())
+            .await?;
+
+        // If there are custom questions saved
+        if !question_list.is_empty() {
+            let questions: Vec<(i32, String)> = question_list
+                .iter()
+                .map(|row| (row.get(0), row.get(2)))
+                .collect();
+            pick_question_to_delete(ctx, invocation, &questions).await?;
+        } else {
+            invocation.reply(ctx, "No custom questions found!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn list_qotd(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+    // Getting all questions
+    let question_list = store(ctx)
+        .await
+        .get_list_custom_questions(guild_id.to_string())
+        .await?;
+
+    // If there are custom questions saved
+    if !question_list.is_empty() {
+        let mut pretty_list = "ID - Question\n".to_string();
+        // Putting the questions onto the list
+        for row in &question_list {
+            let qid: i32 = row.get(0);
+            let string: String = row.get(2);
+            let flag_reason: Option<String> = row.get("flag_reason");
+            let note: Option<String> = row.get("note");
+            let in_use: bool = row.get("in_use");
+            let tags: Vec<String> = row.get("tags");
+            let submitted_by: Option<String> = row.get("submitted_by");
+            let submitted_at: Option<DateTime<Utc>> = row.get("submitted_at");
+            let string = if row.get("mature") {
+                format!("{} 🔞", string)
+            } else {
+                string
+            };
+            let string = if tags.is_empty() {
+                string
+            } else {
+                format!("{} `{}`", string, tags.join("` `"))
+            };
+            let string = match (submitted_by, submitted_at) {
+                (Some(user_id), Some(at)) => format!(
+                    "{} *(submitted by <@{}> <t:{}:d>)*",
+                    string,
+                    user_id,
+                    at.timestamp()
+                ),
+                (Some(user_id), None) => format!("{} *(submitted by <@{}>)*", string, user_id),
+                (None, _) => string,
+            };
+            match flag_reason {
+                Some(reason) => {
+                    pretty_list = format!(
+                        "{}{} - {} *(flagged: {})* \n",
+                        pretty_list, qid, string, reason
+                    )
+                }
+                None if !in_use => {
+                    pretty_list = format!("{}{} - {} *(retired)* \n", pretty_list, qid, string)
+                }
+                None => pretty_list = format!("{}{} - {} \n", pretty_list, qid, string),
+            }
+            if let Some(note) = note {
+                pretty_list = format!("{}   📝 {} \n", pretty_list, note)
+            }
+        }
+        // Listing questions in message
+        invocation
+            .channel_id
+            .send_message(ctx, |m| {
+                m.content(format!(
+                    "<@{}> Here's a list of all saved custom questions",
+                    invocation.author.id
+                ))
+                .embed(|embed| {
+                    embed
+                        .title("Questions")
+                        .description(pretty_list)
+                        .color(Color::RED)
+                })
+            })
+            .await?;
+    } else {
+        invocation.reply(ctx, "No custom questions found!").await?;
+    }
+
+    Ok(())
+}
+
+/// Command to set ping role
+async fn ping_role(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let mut current_role = store.get_ping_role(guild_id.to_string()).await?;
+
+    // Checking if there's parameters in the command
+    if !invocation.args.is_empty() {
+        let parameter = invocation.args.text("setting");
+
+        // Switching to @everyone can need a second admin to approve it
+        if parameter == "1"
+            && !everyone_ping_approved(ctx, invocation, "set the ping role to @everyone").await?
+        {
+            return Ok(());
+        }
+
+        // If role parameter is one of the preset options
+        if parameter == "1" || parameter == "0" {
+            match store
+                .set_ping_role(guild_id.to_string(), String::from(parameter))
+                .await
+            {
+                Ok(_) => {
+                    invocation.reply(ctx, "Ping role updated!").await?;
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    invocation.reply(ctx, "Something went wrong!").await?;
+                }
+            }
+        }
+        // Else check whether the role is valid, and submit it if it is
+        else {
+            // If role is a valid role, submit it to the database
+            match invocation.args.role("role") {
+                Some(role) => {
+                    match store
+                        .set_ping_role(guild_id.to_string(), role.to_string())
+                        .await
+                    {
+                        Ok(_) => {
+                            invocation.reply(ctx, "Ping role updated!").await?;
+                        }
+                        Err(e) => {
+                            println!("{}", e);
+                            invocation.reply(ctx, "Something went wrong!").await?;
+                        }
+                    }
+                }
+                None => {
+                    invocation.reply(ctx, "Not a valid role!").await?;
+                }
+            }
+        }
+    }
+    // If no parameters, send default help message
+    else {
+        // Formatting current role to taggable form if it's not 0 or 1
+        if (current_role != *"1") && (current_role != *"0") {
+            // No need to check if the role is a valid role, validity is checked on submission to the database.
+            current_role = format!("<@&{}>", current_role);
+        }
+        // Crafting message
+        invocation
+            .channel_id
+            .send_message(ctx, |m| {
+                m.content(format!(
+                    "<@{}> Use this command to set the role to be pinged when posting a qotd \n \
+                    Current setting is {}",
+                    invocation.author.id, current_role
+                ))
+                .embed(|embed| {
+                    embed
+                        .title("Parameters")
+                        .description("<role> - Specific role \n 1 - Everyone \n 0 - Off (default)")
+                })
+            })
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn poll(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let (poll_id, poll) = match store.get_random_poll().await? {
+        Some(picked) => picked,
+        None => {
+            invocation
+                .reply(ctx, "No polls available right now!")
+                .await?;
+            return Ok(());
+        }
+    };
+    let channel_id = store.get_ping_channel_id(guild_id.to_string()).await?;
+    let ping_role = store.get_ping_role(guild_id.to_string()).await?;
+    let poll_string = format_string_for_pings(
+        ping_role.clone(),
+        get_poll_intro(guild_id.to_string(), &poll[0], ctx).await?,
+        None,
+        "daily",
+    )
+    .await;
+
+    let configured = parse_channel(&channel_id).map(ChannelId);
+    match posting_channel(ctx, invocation, configured).await? {
+        Some(channel) => {
+            if ping_role == "1"
+                && !everyone_ping_approved(ctx, invocation, "post a poll with an @everyone ping")
+                    .await?
+            {
+                return Ok(());
+            }
+
+            // Sending message to the channel assigned to the server
+            let pings = Pings::for_ping_role(&ping_role);
+            send_poll(
+                ctx,
+                guild_id,
+                channel,
+                poll_id,
+                false,
+                &poll,
+                poll_string,
+                pings,
+            )
+            .await?;
+        }
+        None => return Ok(()),
+    }
+
+    Ok(())
+}
+
+async fn submit_poll(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let full_poll: Vec<String> = ["question", "option_a", "option_b"]
+        .iter()
+        .map(|part| invocation.args.text(part).to_string())
+        .collect();
+
+    // Could add regex for bad words etc here.
+    // If message has content
+    if !invocation.args.is_empty() {
+        // If message is in correct format, a prefix command with more lines runs them into the last option
+        if full_poll
+            .iter()
+            .all(|part| !part.is_empty() && !part.contains('\n'))
+        {
+            if store.poll_is_under_limit(guild_id.to_string()).await? {
+                match store
+                    .add_custom_poll(guild_id.to_string(), full_poll, None, None, false)
+                    .await
+                {
+                    Ok(_s) => {
+                        invocation.reply(ctx, "Poll Submitted").await?;
+                    }
+                    Err(e) => {
+                        println!("{}", e);
+                        invocation.reply(ctx, "Something went wrong!").await?;
+                    }
+                }
+            } else {
+                invocation
+                    .reply(
+                        ctx,
+                        "Too many custom polls saved! Please delete some before adding more!",
+                    )
+                    .await?;
+            }
+        } else {
+            invocation
+                .channel_id
+                .send_message(ctx, |message| {
+                    message
+                        .content(format!(
+                            "<@{}> Follow this format when submitting new questions!",
+                            invocation.author.id
+                        ))
+                        .embed(|embed| {
+                            embed
+                                .title("Custom poll format")
+                                .description("submit_poll Question\nOption1\nOption2")
+                                .color(Color::DARK_BLUE)
+                        })
+                })
+                .await?;
+        }
+    } else {
+        invocation
+            .channel_id
+            .send_message(ctx, |message| {
+                message
+                    .content(format!(
+                        "<@{}> Please use correct format!",
+                        invocation.author.id
+                    ))
+                    .embed(|embed| {
+                        embed
+                            .title("Custom poll format")
+                            .description("submit_poll Question\nOption1\nOption2")
+                            .color(Color::DARK_BLUE)
+                    })
+            })
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn custom_poll(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let (poll_id, custom_poll);
+    let channel_id = store.get_ping_channel_id(guild_id.to_string()).await?;
+    let ping_role = store.get_ping_role(guild_id.to_string()).await?;
+
+    if invocation.args.get("id").is_some() {
+        match invocation.args.id("id") {
+            Some(id_to_use) => {
+                (poll_id, custom_poll) = store
+                    .get_specific_custom_poll(guild_id.to_string(), id_to_use)
+                    .await?;
+            }
+            _ => {
+                invocation.reply(ctx, "Not a valid question ID").await?;
+                return Ok(());
+            }
+        }
+    } else {
+        (poll_id, custom_poll) = store.get_random_custom_poll(guild_id.to_string()).await?;
+    }
+
+    if custom_poll.len() < 3 {
+        invocation
+            .reply(ctx, "No custom polls saved!\nAdd some with submit_poll!")
+            .await?;
+        return Ok(());
+    }
+    let message_string = format_string_for_pings(
+        ping_role.clone(),
+        get_poll_intro(guild_id.to_string(), &custom_poll[0], ctx).await?,
+        None,
+        "daily",
+    )
+    .await;
+
+    let configured = parse_channel(&channel_id).map(ChannelId);
+    match posting_channel(ctx, invocation, configured).await? {
+        Some(channel) => {
+            if ping_role == "1"
+                && !everyone_ping_approved(
+                    ctx,
+                    invocation,
+                    "post a custom poll with an @everyone ping",
+                )
+                .await?
+            {
+                return Ok(());
+            }
+
+            // Sending message to the channel assigned to the server
+            send_poll(
+                ctx,
+                guild_id,
+                channel,
+                poll_id,
+                true,
+                &custom_poll,
+                message_string,
+                Pings::for_ping_role(&ping_role),
+            )
+            .await?;
+        }
+        None => return Ok(()),
+    }
+
+    Ok(())
+}
+
+async fn list_polls(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+    // Getting all questions
+    let polls_list = store(ctx)
+        .await
+        .get_list_of_custom_polls(guild_id.to_string())
+        .await?;
+
+    // If there are custom questions saved
+    if !polls_list.is_empty() {
+        let mut pretty_list = "ID - Poll Question\n".to_string();
+        // Putting the questions onto the list
+        for row in &polls_list {
+            let poll_id: i32 = row.get(0);
+            let poll_full: Vec<String> = row.get(2);
+            let poll_question_string = &poll_full[0];
+            pretty_list = format!("{}{} - {} \n", pretty_list, poll_id, poll_question_string)
+        }
+        // Listing questions in message
+        invocation
+            .channel_id
+            .send_message(ctx, |m| {
+                m.content(format!(
+                    "<@{}> Here's a list of all saved custom polls",
+                    invocation.author.id
+                ))
+                .embed(|embed| {
+                    embed
+                        .title("Polls")
+                        .description(pretty_list)
+                        .color(Color::RED)
+                })
+            })
+            .await?;
+    } else {
+        invocation.reply(ctx, "No custom polls found!").await?;
+    }
+
+    Ok(())
+}
+
+async fn delete_poll(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if invocation.args.get("id").is_some() {
+        match invocation.args.id("id") {
+            Some(id_to_delete) => {
+                let test = store
+                    .delete_custom_poll(
+                        guild_id.to_string(),
+                        id_to_delete,
+                        invocation.author.id.to_string(),
+                    )
+                    .await?;
+                if test == 1 {
+                    invocation.reply(ctx, "Poll deleted!").await?;
+                } else {
+                    invocation.reply(ctx, "Poll not found!").await?;
+                }
+            }
+            _ => {
+                invocation.reply(ctx, "Please enter a valid ID!").await?;
+            }
+        }
+    } else {
+        // Getting all polls
+        let polls_list = store.get_list_of_custom_polls(guild_id.to_string()).await?;
+
+        // If there are custom questions saved
+        if !polls_list.is_empty() {
+            let mut pretty_list = "ID - Poll\n".to_string();
+            // Putting the polls onto the list
+            for row in &polls_list {
+                let poll_id: i32 = row.get(0);
+                let poll_full: Vec<String> = row.get(2);
+                let poll_question_string = &poll_full[0];
+                pretty_list = format!("{}{} - {} \n", pretty_list, poll_id, poll_question_string)
+            }
+            // Listing questions in message
+            invocation
+                .channel_id
+                .send_message(ctx, |m| {
+                    m.content(format!(
+                        "<@{}> Please specify the ID of poll",
+                        invocation.author.id
+                    ))
+                    .embed(|embed| {
+                        embed
+                            .title("Questions")
+                            .description(pretty_list)
+                            .color(Color::DARK_BLUE)
+                    })
+                })
+                .await?;
+        } else {
+            invocation.reply(ctx, "No custom questions found!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn cooldown(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    // Checking if there's parameters in the command
+    if invocation.args.get("days").is_some() {
+        match invocation
+            .args
+            .integer("days")
+            .and_then(|days| i32::try_from(days).ok())
+        {
+            Some(days) if days >= 0 => {
+                match store
+                    .set_question_cooldown(guild_id.to_string(), days)
+                    .await
+                {
+                    Ok(_) => {
+                        invocation
+                            .reply(ctx, format!("Question cooldown set to {} days!", days))
+                            .await?;
+                    }
+                    Err(e) => {
+                        println!("{}", e);
+                        invocation.reply(ctx, "Something went wrong!").await?;
+                    }
+                }
+            }
+            _ => {
+                invocation
+                    .reply(ctx, "Please enter a valid amount of days!")
+                    .await?;
+            }
+        }
+    }
+    // If no parameters, show the current setting
+    else {
+        let current_cooldown = store.get_question_cooldown(guild_id.to_string()).await?;
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Custom questions can be picked again after {} days. \n\
+                Use cooldown <days> to change it, 0 disables the cooldown.",
+                    current_cooldown
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn question_cooldown(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    let days = invocation.args.text("days");
+    if invocation.args.get("id").is_some() && !days.is_empty() {
+        // "default" clears the override so the guild cooldown is used
+        let cooldown_days = if days.eq_ignore_ascii_case("default") {
+            Ok(None)
+        } else {
+            match days.parse::<i32>() {
+                Ok(days) if days >= 0 => Ok(Some(days)),
+                _ => Err(()),
+            }
+        };
+
+        match (invocation.args.id("id"), cooldown_days) {
+            (Some(question_id), Ok(cooldown_days)) => {
+                match store(ctx)
+                    .await
+                    .set_custom_question_cooldown(guild_id.to_string(), question_id, cooldown_days)
+                    .await
+                {
+                    Ok(0) => {
+                        invocation.reply(ctx, "Question not found!").await?;
+                    }
+                    Ok(_) => {
+                        invocation.reply(ctx, "Question cooldown updated!").await?;
+                    }
+                    Err(e) => {
+                        println!("{}", e);
+                        invocation.reply(ctx, "Something went wrong!").await?;
+                    }
+                }
+            }
+            _ => {
+                invocation
+                    .reply(ctx, "Please enter a valid ID and amount of days!")
+                    .await?;
+            }
+        }
+        return Ok(());
+    }
+
+    invocation
+        .reply(ctx, "Usage: question_cooldown <id> <days/default>")
+        .await?;
+
+    Ok(())
+}
+
+async fn set_archive(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    // If message is a valid message
+    if !invocation.args.is_empty() {
+        // Turning the archive off
+        if invocation.args.text("setting") == "off" {
+            store
+                .set_archive_channel_id(guild_id.to_string(), None)
+                .await?;
+            invocation.reply(ctx, "Archive turned off!").await?;
+            return Ok(());
+        }
+
+        match invocation.args.channel("channel").map(|channel| channel.0) {
+            Some(cid) => {
+                // Checking that the channel is in the server.
+                let guild_channels = ctx
+                    .cache
+                    .guild_channels(guild_id)
+                    .ok_or("Command not being called from a guild?")?;
+                let channel_id = ChannelId(cid);
+
+                if guild_channels.contains_key(&channel_id) {
+                    store
+                        .set_archive_channel_id(guild_id.to_string(), Some(cid.to_string()))
+                        .await?;
+                    invocation.reply(ctx, "Archive channel set!").await?;
+                } else {
+                    invocation
+                        .reply(ctx, "Channel not found on this server!")
+                        .await?;
+                }
+            }
+            None => {
+                invocation.reply(ctx, "Not a valid channel!").await?;
+            }
+        }
+    }
+    // No parameters, showing the current archive channel
+    else {
+        let archive_channel = store.get_archive_channel_id(guild_id.to_string()).await?;
+        match parse_channel(&archive_channel) {
+            Some(_cid) => {
+                invocation
+                    .reply(
+                        ctx,
+                        format!("Archive channel is set to {}", archive_channel),
+                    )
+                    .await?;
+            }
+            None => {
+                invocation.reply(ctx, "Archive channel not set!").await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn restrict(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    // If message is a valid message
+    if !invocation.args.is_empty() {
+        // Allowing commands everywhere again
+        if invocation.args.text("setting") == "off" {
+            store
+                .set_command_channel_id(guild_id.to_string(), None)
+                .await?;
+            invocation
+                .reply(ctx, "Commands can be used in every channel again!")
+                .await?;
+            return Ok(());
+        }
+
+        match invocation.args.channel("channel").map(|channel| channel.0) {
+            Some(cid) => {
+                // Checking that the channel is in the server.
+                let guild_channels = ctx
+                    .cache
+                    .guild_channels(guild_id)
+                    .ok_or("Command not being called from a guild?")?;
+                let channel_id = ChannelId(cid);
+
+                if guild_channels.contains_key(&channel_id) {
+                    store
+                        .set_command_channel_id(guild_id.to_string(), Some(cid.to_string()))
+                        .await?;
+                    invocation
+                        .reply(
+                            ctx,
+                            format!("Commands can now only be used in {}!", channel_id.mention()),
+                        )
+                        .await?;
+                } else {
+                    invocation
+                        .reply(ctx, "Channel not found on this server!")
+                        .await?;
+                }
+            }
+            None => {
+                invocation.reply(ctx, "Not a valid channel!").await?;
+            }
+        }
+    }
+    // No parameters, showing the current command channel
+    else {
+        let command_channel = store.get_command_channel_id(guild_id.to_string()).await?;
+        match parse_channel(&command_channel) {
+            Some(_cid) => {
+                invocation
+                    .reply(
+                        ctx,
+                        format!("Commands are restricted to {}", command_channel),
+                    )
+                    .await?;
+            }
+            None => {
+                invocation
+                    .reply(ctx, "Commands can be used in every channel!")
+                    .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn save_emoji(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let emoji = invocation.args.text("emoji");
+    if !emoji.is_empty() {
+        match emoji.parse::<ReactionType>() {
+            Ok(emoji) => match store
+                .set_save_emoji(guild_id.to_string(), emoji.to_string())
+                .await
+            {
+                Ok(_) => {
+                    invocation
+                        .reply(ctx, format!("Save emoji set to {}", emoji))
+                        .await?;
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    invocation.reply(ctx, "Something went wrong!").await?;
+                }
+            },
+            Err(_) => {
+                invocation.reply(ctx, "Not a valid emoji!").await?;
+            }
+        }
+    } else {
+        let current_emoji = store.get_save_emoji(guild_id.to_string()).await?;
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "React with {} to a question of the day to save it!",
+                    current_emoji
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn saved(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let saved_list = store(ctx)
+        .await
+        .get_saved_questions(invocation.author.id.to_string())
+        .await?;
+
+    let mut pretty_list = String::new();
+    for row in &saved_list {
+        let question: String = row.get(0);
+        let saved_on: String = row.get(1);
+        pretty_list = format!("{}{} - {} \n", pretty_list, saved_on, question)
+    }
+    if saved_list.is_empty() {
+        pretty_list =
+            String::from("No saved questions yet! React to a question of the day to save it.");
+    }
+
+    // Saved questions are personal, always sent in DMs
+    invocation
+        .author
+        .direct_message(ctx, |m| {
+            m.embed(|embed| {
+                embed
+                    .title("Saved Questions")
+                    .description(pretty_list)
+                    .color(Color::DARK_GREEN)
+            })
+        })
+        .await?;
+
+    if invocation.guild_id.is_some() {
+        invocation
+            .reply(ctx, "Sent you a DM with your saved questions!")
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn everyone_approval(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store
+                .set_everyone_approval(guild_id.to_string(), true)
+                .await?;
+            invocation
+                .reply(
+                    ctx,
+                    "@everyone pings now need approval from a second admin!",
+                )
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            // Turning the safety off needs the same approval, otherwise it could just be skipped
+            if everyone_ping_approved(ctx, invocation, "turn off approval for @everyone pings")
+                .await?
+            {
+                store
+                    .set_everyone_approval(guild_id.to_string(), false)
+                    .await?;
+                invocation
+                    .reply(ctx, "@everyone pings no longer need approval!")
+                    .await?;
+            }
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_everyone_approval(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        invocation.reply(
+            ctx,
+            format!(
+                "Second admin approval for @everyone pings is {}. Use everyone_approval <on/off> to change it.",
+                current_setting
+            ),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn attribution(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if !invocation.args.is_empty() {
+        let setting = invocation.args.text("setting");
+        let value = invocation.args.text("value");
+
+        // Crediting and pinging submitters are settings of their own, packs are shown by default but submitters aren't
+        if setting.eq_ignore_ascii_case("mention") {
+            let value = value.trim();
+            if value.eq_ignore_ascii_case("on") {
+                store
+                    .set_mention_submitter(guild_id.to_string(), true)
+                    .await?;
+                invocation
+                    .reply(
+                        ctx,
+                        "Members will be pinged when their custom question is posted!",
+                    )
+                    .await?;
+            } else if value.eq_ignore_ascii_case("off") {
+                store
+                    .set_mention_submitter(guild_id.to_string(), false)
+                    .await?;
+                invocation
+                    .reply(
+                        ctx,
+                        "Members won't be pinged when their custom question is posted!",
+                    )
+                    .await?;
+            } else {
+                invocation
+                    .reply(ctx, "Please use attribution mention <on/off>!")
+                    .await?;
+            }
+        } else if setting.eq_ignore_ascii_case("submitter") {
+            let value = value.trim();
+            if value.eq_ignore_ascii_case("on") {
+                store.set_show_submitter(guild_id.to_string(), true).await?;
+                invocation
+                    .reply(
+                        ctx,
+                        "Posted custom questions will credit who submitted them!",
+                    )
+                    .await?;
+            } else if value.eq_ignore_ascii_case("off") {
+                store
+                    .set_show_submitter(guild_id.to_string(), false)
+                    .await?;
+                invocation
+                    .reply(
+                        ctx,
+                        "Posted custom questions won't credit who submitted them!",
+                    )
+                    .await?;
+            } else {
+                invocation
+                    .reply(ctx, "Please use attribution submitter <on/off>!")
+                    .await?;
+            }
+        } else if setting.eq_ignore_ascii_case("on") {
+            store
+                .set_show_attribution(guild_id.to_string(), true)
+                .await?;
+            invocation
+                .reply(ctx, "Question packs will be shown in posted questions!")
+                .await?;
+        } else if setting.eq_ignore_ascii_case("off") {
+            store
+                .set_show_attribution(guild_id.to_string(), false)
+                .await?;
+            invocation
+                .reply(ctx, "Question packs will be hidden in posted questions!")
+                .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_show_attribution(guild_id.to_string()).await? {
+            "shown"
+        } else {
+            "hidden"
+        };
+        let submitter_setting = if store.get_show_submitter(guild_id.to_string()).await? {
+            "shown"
+        } else {
+            "hidden"
+        };
+        let mention_setting = if store.get_mention_submitter(guild_id.to_string()).await? {
+            "pinged"
+        } else {
+            "not pinged"
+        };
+        invocation.reply(
+            ctx,
+            format!(
+                "Question pack attribution is {}, submitters are {} and {}. \
+                Use attribution <on/off>, attribution submitter <on/off> or attribution mention <on/off> to change them.",
+                current_setting, submitter_setting, mention_setting
+            ),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn poll_history(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if invocation.args.get("id").is_some() {
+        match invocation.args.id("id") {
+            Some(poll_id) => {
+                let (_, poll) = store
+                    .get_specific_custom_poll(guild_id.to_string(), poll_id)
+                    .await?;
+                if poll.len() < 3 {
+                    invocation.reply(ctx, "Poll not found!").await?;
+                    return Ok(());
+                }
+
+                let history = store
+                    .get_poll_history(guild_id.to_string(), poll_id)
+                    .await?;
+                if history.is_empty() {
+                    invocation
+                        .reply(ctx, "This poll hasn't been posted yet!")
+                        .await?;
+                    return Ok(());
+                }
+
+                let mut tallies = vec![];
+                let mut pretty_list = String::new();
+                for row in &history {
+                    let posted_on: String = row.get(0);
+                    let votes_a: i32 = row.get(1);
+                    let votes_b: i32 = row.get(2);
+                    tallies.push((votes_a, votes_b));
+                    pretty_list = format!(
+                        "{}{} - 🟠 {} / 🔵 {} \n",
+                        pretty_list, posted_on, votes_a, votes_b
+                    )
+                }
+
+                invocation
+                    .channel_id
+                    .send_message(ctx, |m| {
+                        m.content(format!(
+                            "<@{}> Results of poll {}",
+                            invocation.author.id, poll_id
+                        ))
+                        .embed(|embed| {
+                            embed
+                                .title(&poll[0])
+                                .description(format!(
+                                    "🟠 - {}\n🔵 - {}\n\n**🟠 share over time**\n{}\n\n{}",
+                                    &poll[1],
+                                    &poll[2],
+                                    render_poll_trend(&tallies),
+                                    pretty_list
+                                ))
+                                .color(Color::DARK_MAGENTA)
+                        })
+                    })
+                    .await?;
+            }
+            None => {
+                invocation.reply(ctx, "Please enter a valid ID!").await?;
+            }
+        }
+    } else {
+        invocation.reply(ctx, "Usage: poll_history <id>").await?;
+    }
+
+    Ok(())
+}
+
+/// Parses the guild, channel and message ids out of a message link
+fn parse_message_link(link: &str) -> Option<(GuildId, ChannelId, MessageId)> {
+    let (_, ids) = link.split_once("/channels/")?;
+    let ids: Vec<u64> = ids
+        .split('/')
+        .map(|id| id.trim().parse::<u64>())
+        .collect::<Result<_, _>>()
+        .ok()?;
+
+    match ids.as_slice() {
+        [guild_id, channel_id, message_id] => Some((
+            GuildId(*guild_id),
+            ChannelId(*channel_id),
+            MessageId(*message_id),
+        )),
+        _ => None,
+    }
+}
+
+// Emoji the option added to a poll can get, the first one the poll doesn't use yet is picked
+const AMENDMENT_EMOJI: [&str; 3] = ["🟢", "🟣", "🟡"];
+
+async fn amend_poll(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let usage = "Usage: amend_poll <message link> add \"<option>\"";
+
+    let link = invocation.args.text("link");
+    let option = invocation.args.text("option");
+    let option = option
+        .strip_prefix("add ")
+        .unwrap_or(option)
+        .trim()
+        .trim_matches('"')
+        .trim();
+    if option.is_empty() {
+        invocation.reply(ctx, usage).await?;
+        return Ok(());
+    }
+    let (channel_id, message_id) = match parse_message_link(link) {
+        Some((link_guild, channel_id, message_id)) if link_guild == guild_id => {
+            (channel_id, message_id)
+        }
+        _ => {
+            invocation
+                .reply(ctx, "Please enter a link to a poll on this server!")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let (poll, emoji) = match store.get_posted_poll(message_id.to_string()).await {
+        Ok(Some(posted)) => posted,
+        Ok(None) => {
+            invocation.reply(ctx, "Poll not found!").await?;
+            return Ok(());
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+            return Ok(());
+        }
+    };
+    let added_emoji = AMENDMENT_EMOJI
+        .iter()
+        .find(|candidate| !emoji.iter().any(|used| used == *candidate))
+        .unwrap_or(&AMENDMENT_EMOJI[0])
+        .to_string();
+
+    match store
+        .amend_poll(
+            guild_id.to_string(),
+            message_id.to_string(),
+            option.to_string(),
+            added_emoji.clone(),
+            invocation.author.id.to_string(),
+        )
+        .await
+    {
+        Ok(1) => {}
+        Ok(_) => {
+            invocation
+                .reply(
+                    ctx,
+                    "Only open polls without an added option can be amended!",
+                )
+                .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+            return Ok(());
+        }
+    }
+    let amendment = match get_poll_amendment(ctx, message_id).await {
+        Some(amendment) => amendment,
+        None => {
+            invocation.reply(ctx, "Something went wrong!").await?;
+            return Ok(());
+        }
+    };
+
+    let mut message = channel_id.message(ctx, message_id).await?;
+    let buttons = !message.components.is_empty();
+    // Buttons only show tallies once someone has voted
+    let tallies = Some(amendment.votes_before).filter(|(votes_a, votes_b)| votes_a + votes_b > 0);
+    match message.embeds.first().cloned() {
+        Some(mut edited) => {
+            // The options are the first lines of the embed, the new one goes after them
+            let description = edited.description.unwrap_or_default();
+            let mut lines: Vec<String> = description.lines().map(String::from).collect();
+            let at = lines.len().min(2);
+            lines.insert(at, format!("{} - {}", added_emoji, option));
+            edited.description = Some(lines.join("\n"));
+            message
+                .edit(ctx, |m| {
+                    m.set_embed(CreateEmbed::from(edited));
+                    if buttons {
+                        m.components(|c| {
+                            create_vote_buttons(c, &poll, &emoji, tallies, Some(&amendment))
+                        });
+                    }
+                    m
+                })
+                .await?;
+        }
+        None => {
+            // Polls posted in accessibility mode spell the new option out after Option B
+            let option_c = if buttons {
+                format!("Option C: {}", option)
+            } else {
+                format!("Option C: {}, react with {}", option, added_emoji)
+            };
+            let mut lines: Vec<String> = message.content.lines().map(String::from).collect();
+            let at = lines
+                .iter()
+                .position(|line| line.starts_with("Option B:"))
+                .map_or(lines.len(), |position| position + 1);
+            lines.insert(at, option_c);
+            let content = lines.join("\n");
+            message
+                .edit(ctx, |m| {
+                    m.content(content);
+                    if buttons {
+                        m.components(|c| {
+                            create_vote_buttons(c, &poll, &emoji, tallies, Some(&amendment))
+                        });
+                    }
+                    m
+                })
+                .await?;
+        }
+    }
+    if !buttons {
+        message.react(ctx, Unicode(added_emoji)).await?;
+    }
+
+    invocation
+        .reply(
+            ctx,
+            format!(
+                "Option added! Votes before the change are reported when the poll closes: {} - {}",
+                amendment.votes_before.0, amendment.votes_before.1
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Asks the admin how to resolve an imported entry that already exists in the pool.
+/// Keeping both is only offered with keep_both, questions are unique per guild.
+/// Returns SkipRest if nothing was picked in 2 minutes.
+async fn ask_merge_choice(
+    ctx: &Context,
+    invocation: &Invocation,
+    existing: &str,
+    imported: &str,
+    keep_both: bool,
+) -> Result<MergeChoice, SerenityError> {
+    let mut prompt = invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!(
+                "<@{}> This is already in the pool!",
+                invocation.author.id
+            ))
+            .embed(|embed| {
+                embed
+                    .title("Duplicate")
+                    .field("Existing", existing, false)
+                    .field("Imported", imported, false)
+                    .color(Color::DARK_BLUE)
+            })
+            .components(|c| {
+                c.create_action_row(|row| {
+                    row.create_button(|b| {
+                        b.custom_id("merge_skip")
+                            .label("Skip")
+                            .style(ButtonStyle::Secondary)
+                    });
+                    if keep_both {
+                        row.create_button(|b| {
+                            b.custom_id("merge_keep_both")
+                                .label("Keep both")
+                                .style(ButtonStyle::Primary)
+                        });
+                    }
+                    row.create_button(|b| {
+                        b.custom_id("merge_replace")
+                            .label("Replace")
+                            .style(ButtonStyle::Success)
+                    })
+                    .create_button(|b| {
+                        b.custom_id("merge_skip_rest")
+                            .label("Skip all remaining")
+                            .style(ButtonStyle::Danger)
+                    })
+                })
+            })
+        })
+        .await?;
+
+    let interaction = waiting_on_user(
+        prompt
+            .await_component_interaction(ctx)
+            .author_id(invocation.author.id)
+            .timeout(Duration::from_secs(120)),
+    )
+    .await;
+
+    let choice = match &interaction {
+        Some(interaction) => match interaction.data.custom_id.as_str() {
+            "merge_skip" => MergeChoice::Skip,
+            "merge_keep_both" => MergeChoice::KeepBoth,
+            "merge_replace" => MergeChoice::Replace,
+            _ => MergeChoice::SkipRest,
+        },
+        None => MergeChoice::SkipRest,
+    };
+
+    match interaction {
+        Some(interaction) => {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|d| d.components(|c| c))
+                })
+                .await?;
+        }
+        None => {
+            prompt
+                .edit(ctx, |m| {
+                    m.content("No choice made, skipping the rest of the duplicates.")
+                        .components(|c| c)
+                })
+                .await?;
+        }
+    }
+
+    Ok(choice)
+}
+
+async fn merge_pool(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let attachment = match invocation.args.attachment("file") {
+        Some(attachment) => attachment,
+        None => {
+            invocation
+                .reply(ctx, "Attach an exported pool file to merge!")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let pool: PoolExport = match serde_json::from_slice(&attachment.download().await?) {
+        Ok(pool) => pool,
+        Err(e) => {
+            println!("{}", e);
+            invocation
+                .reply(ctx, "Not a valid exported pool file!")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let (mut added, mut replaced, mut skipped, mut over_limit) = (0, 0, 0, 0);
+    let mut skip_rest = false;
+
+    // Questions
+    let existing_questions = store
+        .get_list_custom_questions(guild_id.to_string())
+        .await?;
+    for imported in pool.questions {
+        // Pool files can be edited by hand, questions still have to fit in a post
+        if question_length_error(&imported).is_some() {
+            skipped += 1;
+            continue;
+        }
+
+        let duplicate = existing_questions.iter().find(|row| {
+            let existing: String = row.get(2);
+            normalize_question(&existing) == normalize_question(&imported)
+        });
+
+        let choice = match duplicate {
+            Some(_) if skip_rest => MergeChoice::Skip,
+            Some(row) => {
+                ask_merge_choice(ctx, invocation, &row.get::<_, String>(2), &imported, false)
+                    .await?
+            }
+            None => MergeChoice::KeepBoth,
+        };
+
+        match (choice, duplicate) {
+            (MergeChoice::Replace, Some(row)) => {
+                store
+                    .update_custom_question(guild_id.to_string(), row.get(0), imported)
+                    .await?;
+                replaced += 1;
+            }
+            (MergeChoice::KeepBoth, _) => {
+                if store.question_is_under_limit(guild_id.to_string()).await? {
+                    if store
+                        .add_custom_question(guild_id.to_string(), imported, None, None, false)
+                        .await?
+                        > 0
+                    {
+                        added += 1;
+                    } else {
+                        skipped += 1;
+                    }
+                } else {
+                    over_limit += 1;
+                }
+            }
+            (MergeChoice::SkipRest, _) => {
+                skip_rest = true;
+                skipped += 1;
+            }
+            _ => {
+                skipped += 1;
+            }
+        }
+    }
+
+    // Polls, duplicates are matched on the poll question
+    let existing_polls = store.get_list_of_custom_polls(guild_id.to_string()).await?;
+    for imported in pool.polls {
+        if imported.len() != 3 {
+            skipped += 1;
+            continue;
+        }
+
+        let duplicate = existing_polls.iter().find(|row| {
+            let existing: Vec<String> = row.get(2);
+            normalize_question(&existing[0]) == normalize_question(&imported[0])
+        });
+
+        let choice = match duplicate {
+            Some(_) if skip_rest => MergeChoice::Skip,
+            Some(row) => {
+                let existing: Vec<String> = row.get(2);
+                ask_merge_choice(
+                    ctx,
+                    invocation,
+                    &existing.join("\n"),
+                    &imported.join("\n"),
+                    true,
+                )
+                .await?
+            }
+            None => MergeChoice::KeepBoth,
+        };
+
+        match (choice, duplicate) {
+            (MergeChoice::Replace, Some(row)) => {
+                store
+                    .update_custom_poll(guild_id.to_string(), row.get(0), imported)
+                    .await?;
+                replaced += 1;
+            }
+            (MergeChoice::KeepBoth, _) => {
+                if store.poll_is_under_limit(guild_id.to_string()).await? {
+                    store
+                        .add_custom_poll(guild_id.to_string(), imported, None, None, false)
+                        .await?;
+                    added += 1;
+                } else {
+                    over_limit += 1;
+                }
+            }
+            (MergeChoice::SkipRest, _) => {
+                skip_rest = true;
+                skipped += 1;
+            }
+            _ => {
+                skipped += 1;
+            }
+        }
+    }
+
+    invocation
+        .reply(
+            ctx,
+            format!(
+                "Merge done! Added {}, replaced {}, skipped {}, {} didn't fit under the limit.",
+                added, replaced, skipped, over_limit
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn answer_window(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("hours");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("off") {
+            store.set_answer_window(guild_id.to_string(), None).await?;
+            invocation.reply(ctx, "Answer threads turned off!").await?;
+            return Ok(());
+        }
+
+        // A week is the longest a thread stays open before auto archiving
+        match parameter.parse::<i32>() {
+            Ok(hours) if (1..=168).contains(&hours) => {
+                store
+                    .set_answer_window(guild_id.to_string(), Some(hours))
+                    .await?;
+                invocation
+                    .reply(
+                        ctx,
+                        format!(
+                            "Questions will get an answer thread that closes after {} hours!",
+                            hours
+                        ),
+                    )
+                    .await?;
+            }
+            _ => {
+                invocation
+                    .reply(ctx, "Please enter an amount of hours between 1 and 168!")
+                    .await?;
+            }
+        }
+    } else {
+        match store.get_answer_window(guild_id.to_string()).await? {
+            Some(hours) => {
+                invocation
+                    .reply(ctx, format!("Answer threads close after {} hours.", hours))
+                    .await?;
+            }
+            None => {
+                invocation.reply(ctx, "Answer threads are off!").await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn answer_filter(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let usage = "Usage: answer_filter min <characters> or answer_filter limit <answers/off>";
+
+    let (mut min_length, mut member_limit) = store.get_answer_filter(guild_id.to_string()).await?;
+
+    if !invocation.args.is_empty() {
+        let setting = invocation.args.text("setting");
+        let value = invocation.args.text("value");
+
+        match setting.to_lowercase().as_str() {
+            "min" => match value.parse::<i32>() {
+                Ok(length) if (0..=500).contains(&length) => min_length = length,
+                _ => {
+                    invocation
+                        .reply(ctx, "Please enter a length between 0 and 500 characters!")
+                        .await?;
+                    return Ok(());
+                }
+            },
+            "limit" if value.eq_ignore_ascii_case("off") => member_limit = None,
+            "limit" => match value.parse::<i32>() {
+                Ok(limit) if (1..=100).contains(&limit) => member_limit = Some(limit),
+                _ => {
+                    invocation
+                        .reply(
+                            ctx,
+                            "Please enter a limit between 1 and 100 answers, or off!",
+                        )
+                        .await?;
+                    return Ok(());
+                }
+            },
+            _ => {
+                invocation.reply(ctx, usage).await?;
+                return Ok(());
+            }
+        }
+
+        store
+            .set_answer_filter(guild_id.to_string(), min_length, member_limit)
+            .await?;
+    }
+
+    invocation.reply(
+        ctx,
+        format!(
+            "Answers count from {} characters, {}. Repeated answers from the same member never count.",
+            min_length,
+            format_member_limit(member_limit)
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Formats the answer member limit from get_answer_filter for showing it in a message
+fn format_member_limit(member_limit: Option<i32>) -> String {
+    match member_limit {
+        Some(1) => String::from("1 answer per member"),
+        Some(limit) => format!("{} answers per member", limit),
+        None => String::from("no limit per member"),
+    }
+}
+
+async fn fair_rotation(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store.set_fair_rotation(guild_id.to_string(), true).await?;
+            invocation
+                .reply(
+                    ctx,
+                    "Custom questions now rotate fairly between submitters!",
+                )
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store.set_fair_rotation(guild_id.to_string(), false).await?;
+            invocation
+                .reply(ctx, "Custom questions are now picked evenly at random!")
+                .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_fair_rotation(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                "Fair rotation between submitters is {}. Use fair_rotation <on/off> to change it.",
+                current_setting
+            ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn custom_only(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store.set_custom_only(guild_id.to_string(), true).await?;
+            invocation
+                .reply(
+                    ctx,
+                    "Questions are now picked only from the custom questions of the server!",
+                )
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store.set_custom_only(guild_id.to_string(), false).await?;
+            invocation
+                .reply(
+                    ctx,
+                    "Questions are now picked from the default questions too!",
+                )
+                .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_custom_only(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Custom questions only is {}. Use custom_only <on/off> to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn recommend(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store.set_recommend(guild_id.to_string(), true).await?;
+            invocation
+                .reply(
+                    ctx,
+                    "Questions like the ones members engage with most are now preferred!",
+                )
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store.set_recommend(guild_id.to_string(), false).await?;
+            invocation
+                .reply(ctx, "Questions are now picked without recommendations!")
+                .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_recommend(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        // Showing what the recommendations go by, so admins can tell why questions get picked
+        let liked = match engagement_profile(
+            &store
+                .get_engaged_questions(guild_id.to_string(), 50)
+                .await?,
+        ) {
+            Some(profile) => {
+                let mut tags: Vec<(String, f64)> = profile.tags.into_iter().collect();
+                tags.sort_by(|a, b| b.1.total_cmp(&a.1));
+                if tags.is_empty() {
+                    String::from("no tagged questions yet")
+                } else {
+                    tags.iter()
+                        .take(5)
+                        .map(|(tag, share)| format!("`{}` {:.0}%", tag, share * 100.0))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                }
+            }
+            None => String::from("nothing answered or saved yet"),
+        };
+        invocation.reply(
+            ctx,
+            format!(
+                "Recommendations are {}. Use recommend <on/off> to change it.\nMost engaged tags: {}",
+                current_setting, liked
+            ),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn seasonal(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store.set_seasonal(guild_id.to_string(), true).await?;
+            invocation
+                .reply(ctx, "Questions for the season are now preferred!")
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store.set_seasonal(guild_id.to_string(), false).await?;
+            invocation
+                .reply(ctx, "Questions are now picked the same way all year round!")
+                .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_seasonal(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        let seasons: Vec<String> = SEASONS
+            .iter()
+            .map(|(season, (start_month, start_day), (end_month, end_day))| {
+                format!(
+                    "{} ({}/{} - {}/{})",
+                    season, start_day, start_month, end_day, end_month
+                )
+            })
+            .collect();
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Seasonal questions are {}. Use seasonal <on/off> to change it. Seasons: {}",
+                    current_setting,
+                    seasons.join(", ")
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Formats the question mix of a guild for showing, like 70% default / 30% custom
+fn format_question_mix(custom_share: Option<i32>) -> String {
+    match custom_share {
+        Some(custom_share) => format!("{}% default / {}% custom", 100 - custom_share, custom_share),
+        None => "Off".to_string(),
+    }
+}
+
+async fn mix(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let default_share = invocation.args.text("default");
+    let parameter = match invocation.args.integer("custom") {
+        Some(custom_share) => format!("{} {}", default_share, custom_share),
+        None => default_share.to_string(),
+    };
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("off") {
+            store.set_question_mix(guild_id.to_string(), None).await?;
+            invocation.reply(ctx, "Question mix turned off!").await?;
+            return Ok(());
+        }
+
+        let shares: Vec<i32> = parameter
+            .split_whitespace()
+            .map(|share| share.trim_end_matches('%').parse::<i32>())
+            .collect::<Result<_, _>>()
+            .unwrap_or_default();
+        match shares[..] {
+            [default_share, custom_share]
+                if default_share >= 0
+                    && custom_share >= 0
+                    && default_share + custom_share == 100 =>
+            {
+                store
+                    .set_question_mix(guild_id.to_string(), Some(custom_share))
+                    .await?;
+                invocation
+                    .reply(
+                        ctx,
+                        format!(
+                            "Questions are now picked {}!",
+                            format_question_mix(Some(custom_share))
+                        ),
+                    )
+                    .await?;
+            }
+            _ => {
+                invocation.reply(
+                    ctx,
+                    "Please use mix <default %> <custom %> adding up to 100, like mix 70 30, or mix off!",
+                )
+                .await?;
+            }
+        }
+    } else {
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Question mix is {}. Use mix <default %> <custom %> or mix off to change it.",
+                    format_question_mix(store.get_question_mix(guild_id.to_string()).await?)
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn fallback(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("text");
+    if !parameter.is_empty() {
+        let fallback_text = if parameter.eq_ignore_ascii_case("default") {
+            None
+        } else {
+            Some(parameter.to_string())
+        };
+
+        match store
+            .set_fallback_question(guild_id.to_string(), fallback_text)
+            .await
+        {
+            Ok(_) => {
+                invocation.reply(ctx, "Fallback text updated!").await?;
+            }
+            Err(e) => {
+                println!("{}", e);
+                invocation.reply(ctx, "Something went wrong!").await?;
+            }
+        }
+    } else {
+        let current_fallback = store.get_fallback_question(guild_id.to_string()).await?;
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "When there are no questions to pick from, this is posted instead: \n{}",
+                    current_fallback
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn schedule(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("schedule").trim_matches('"').trim();
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("off") {
+            store
+                .set_post_schedule(guild_id.to_string(), None, None)
+                .await?;
+            invocation
+                .reply(ctx, "Scheduled questions turned off!")
+                .await?;
+            return Ok(());
+        }
+
+        let schedule = match parse_schedule_parameter(parameter) {
+            Some(schedule) => schedule,
+            None => {
+                invocation.reply(
+                    ctx,
+                    "Please enter a time in HH:MM format, or a cron schedule like \"0 9 * * MON,WED,FRI\"!",
+                )
+                .await?;
+                return Ok(());
+            }
+        };
+
+        let timezone = store.get_guild_timezone(guild_id.to_string()).await?;
+        let skip_days = store.get_skip_days(guild_id.to_string()).await?;
+        let next_post_at = match next_post_time(&schedule, timezone, &skip_days, Utc::now()) {
+            Some(next_post_at) => next_post_at,
+            None => {
+                invocation
+                    .reply(ctx, "That schedule never posts anything!")
+                    .await?;
+                return Ok(());
+            }
+        };
+        store
+            .set_post_schedule(
+                guild_id.to_string(),
+                Some(schedule.clone()),
+                Some(next_post_at),
+            )
+            .await?;
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Questions will be posted {}! The first one is <t:{}:R>.",
+                    describe_schedule(&schedule, timezone),
+                    next_post_at.timestamp()
+                ),
+            )
+            .await?;
+    } else {
+        match store.get_post_schedule(guild_id.to_string()).await? {
+            Some((schedule, next_post_at)) => {
+                let timezone = store.get_guild_timezone(guild_id.to_string()).await?;
+                let next = next_post_at
+                    .map(|next| format!(" The next one is <t:{}:R>.", next.timestamp()))
+                    .unwrap_or_default();
+                invocation
+                    .reply(
+                        ctx,
+                        format!(
+                            "Questions are posted {}.{}",
+                            describe_schedule(&schedule, timezone),
+                            next
+                        ),
+                    )
+                    .await?;
+            }
+            None => {
+                invocation
+                    .reply(ctx, "Scheduled questions are off!")
+                    .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn teaser(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("minutes");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("off") {
+            store.set_teaser_minutes(guild_id.to_string(), None).await?;
+            invocation.reply(ctx, "Teasers turned off!").await?;
+            return Ok(());
+        }
+
+        match parameter.parse::<i32>() {
+            Ok(minutes) if (1..=720).contains(&minutes) => {
+                store
+                    .set_teaser_minutes(guild_id.to_string(), Some(minutes))
+                    .await?;
+                invocation
+                    .reply(
+                        ctx,
+                        format!(
+                            "A teaser will be posted {} before the scheduled question!",
+                            format_lead_time(minutes)
+                        ),
+                    )
+                    .await?;
+            }
+            _ => {
+                invocation
+                    .reply(ctx, "Please enter an amount of minutes between 1 and 720!")
+                    .await?;
+            }
+        }
+    } else {
+        match store.get_teaser_minutes(guild_id.to_string()).await? {
+            Some(minutes) => {
+                invocation
+                    .reply(
+                        ctx,
+                        format!(
+                            "Teasers are posted {} before the scheduled question.",
+                            format_lead_time(minutes)
+                        ),
+                    )
+                    .await?;
+            }
+            None => {
+                invocation.reply(ctx, "Teasers are off!").await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats a guild id as its name if the bot can see the guild
+fn guild_display_name(ctx: &Context, guild_id: &str) -> String {
+    guild_id
+        .parse::<u64>()
+        .ok()
+        .and_then(|id| {
+            ctx.cache
+                .guild_field(GuildId(id), |guild| guild.name.clone())
+        })
+        .unwrap_or_else(|| guild_id.to_string())
+}
+
+async fn share_pool(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let parameters: Vec<&str> = [invocation.args.text("action"), invocation.args.text("code")]
+        .into_iter()
+        .filter(|parameter| !parameter.is_empty())
+        .collect();
+
+    match parameters.as_slice() {
+        ["invite"] => match store.create_pool_invite(guild_id.to_string()).await {
+            Ok(code) => {
+                invocation.reply(
+                    ctx,
+                    format!(
+                        "Invite code: **{}** \nAn admin of the partner server can join with share_pool join {} within a day.",
+                        code, code
+                    ),
+                )
+                .await?;
+            }
+            Err(e) => {
+                println!("{}", e);
+                invocation.reply(ctx, "Something went wrong!").await?;
+            }
+        },
+        ["join", code] => {
+            if store.is_in_pool(guild_id.to_string()).await? {
+                invocation
+                    .reply(ctx, "This server already shares a pool! Leave it first.")
+                    .await?;
+                return Ok(());
+            }
+
+            let pool_id = match store.get_pool_invite(code.to_string()).await? {
+                Some(pool_id) => pool_id,
+                None => {
+                    invocation
+                        .reply(ctx, "Invite code not found or expired!")
+                        .await?;
+                    return Ok(());
+                }
+            };
+
+            // The invite is the confirmation of the inviting server, this is the confirmation of this one
+            let mut prompt = invocation
+                .channel_id
+                .send_message(ctx, |m| {
+                    m.content(format!(
+                        "<@{}> Joining shares this server's custom questions with the partner servers, and theirs with this one. Confirm?",
+                        invocation.author.id
+                    ))
+                    .components(|c| {
+                        c.create_action_row(|row| {
+                            row.create_button(|b| {
+                                b.custom_id("pool_confirm")
+                                    .label("Confirm")
+                                    .style(ButtonStyle::Success)
+                            })
+                            .create_button(|b| {
+                                b.custom_id("pool_cancel")
+                                    .label("Cancel")
+                                    .style(ButtonStyle::Secondary)
+                            })
+                        })
+                    })
+                })
+                .await?;
+
+            let interaction = waiting_on_user(
+                prompt
+                    .await_component_interaction(ctx)
+                    .author_id(invocation.author.id)
+                    .timeout(Duration::from_secs(60)),
+            )
+            .await;
+
+            match interaction {
+                Some(interaction) if interaction.data.custom_id == "pool_confirm" => {
+                    store
+                        .join_pool(guild_id.to_string(), pool_id, code.to_string())
+                        .await?;
+                    interaction
+                        .create_interaction_response(ctx, |r| {
+                            r.kind(InteractionResponseType::UpdateMessage)
+                                .interaction_response_data(|d| {
+                                    d.content("Joined the shared pool!").components(|c| c)
+                                })
+                        })
+                        .await?;
+                }
+                Some(interaction) => {
+                    interaction
+                        .create_interaction_response(ctx, |r| {
+                            r.kind(InteractionResponseType::UpdateMessage)
+                                .interaction_response_data(|d| {
+                                    d.content("Not joining the shared pool.").components(|c| c)
+                                })
+                        })
+                        .await?;
+                }
+                None => {
+                    prompt
+                        .edit(ctx, |m| {
+                            m.content("No confirmation, not joining the shared pool.")
+                                .components(|c| c)
+                        })
+                        .await?;
+                }
+            }
+        }
+        ["leave"] => {
+            if store.leave_pool(guild_id.to_string()).await? > 0 {
+                invocation
+                    .reply(
+                        ctx,
+                        "Left the shared pool! Your own questions stay with you.",
+                    )
+                    .await?;
+            } else {
+                invocation
+                    .reply(ctx, "This server isn't sharing a pool!")
+                    .await?;
+            }
+        }
+        [] => {
+            let partners = store.get_pool_partners(guild_id.to_string()).await?;
+            if partners.is_empty() {
+                invocation.reply(
+                    ctx,
+                    "This server isn't sharing a pool! Create an invite with share_pool invite.",
+                )
+                .await?;
+            } else {
+                let partner_names: Vec<String> = partners
+                    .iter()
+                    .map(|partner| guild_display_name(ctx, partner))
+                    .collect();
+                invocation
+                    .reply(
+                        ctx,
+                        format!(
+                            "Sharing custom questions with: {}",
+                            partner_names.join(", ")
+                        ),
+                    )
+                    .await?;
+            }
+        }
+        _ => {
+            invocation
+                .reply(ctx, "Usage: share_pool <invite/join <code>/leave>")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn strictness(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("level").to_lowercase();
+    if !parameter.is_empty() {
+        if ["off", "low", "high"].contains(&parameter.as_str()) {
+            store
+                .set_moderation_strictness(guild_id.to_string(), parameter.clone())
+                .await?;
+            invocation
+                .reply(ctx, format!("Submission strictness set to {}!", parameter))
+                .await?;
+        } else {
+            invocation
+                .reply(ctx, "Please use off, low or high!")
+                .await?;
+        }
+    } else {
+        let current_setting = store
+            .get_moderation_strictness(guild_id.to_string())
+            .await?;
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Submission strictness is {}. Use strictness <off/low/high> to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn edit_question(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    let question = invocation.args.text("question");
+
+    let question_id = match invocation.args.id("id") {
+        Some(question_id) => question_id,
+        None => {
+            invocation
+                .reply(ctx, "Usage: edit_question <id> <new question>")
+                .await?;
+            return Ok(());
+        }
+    };
+    if question.is_empty() {
+        invocation
+            .reply(ctx, "Please enter the new question!")
+            .await?;
+        return Ok(());
+    }
+    if let Some(error) = question_length_error(question) {
+        invocation.reply(ctx, error).await?;
+        return Ok(());
+    }
+
+    match store(ctx)
+        .await
+        .edit_custom_question(
+            guild_id.to_string(),
+            question_id,
+            question.to_string(),
+            invocation.author.id.to_string(),
+        )
+        .await
+    {
+        Ok(QuestionEdit::Edited(previous)) => {
+            let detail = format!("{}\n\nIt was: {}", question, previous);
+            let event = QuestionEvent {
+                kind: "edited",
+                question_ids: vec![question_id],
+                by: Some(invocation.author.id),
+                detail: &detail,
+            };
+            emit_question_event(ctx, guild_id.to_string(), event).await;
+            invocation
+                .reply(
+                    ctx,
+                    format!("Question {} edited! It was: {}", question_id, previous),
+                )
+                .await?;
+        }
+        Ok(QuestionEdit::NotFound) => {
+            invocation.reply(ctx, "Question not found!").await?;
+        }
+        Ok(QuestionEdit::Duplicate) => {
+            invocation
+                .reply(ctx, "This question is already saved!")
+                .await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn note(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    if !invocation.args.is_empty() {
+        // Quotes around the note are optional
+        let note_text = invocation.args.text("note").trim_matches('"').trim();
+
+        let question_id = match invocation.args.id("id") {
+            Some(question_id) => question_id,
+            None => {
+                invocation.reply(ctx, "Please enter a valid ID!").await?;
+                return Ok(());
+            }
+        };
+
+        let note_to_set = if note_text.is_empty() {
+            invocation
+                .reply(ctx, "Please add a note, or use clear to remove it!")
+                .await?;
+            return Ok(());
+        } else if note_text.eq_ignore_ascii_case("clear") {
+            None
+        } else {
+            Some(note_text.to_string())
+        };
+        let clearing = note_to_set.is_none();
+
+        match store(ctx)
+            .await
+            .set_question_note(guild_id.to_string(), question_id, note_to_set)
+            .await
+        {
+            Ok(0) if !clearing => {
+                invocation.reply(ctx, "Question not found!").await?;
+            }
+            Ok(_) => {
+                if clearing {
+                    invocation.reply(ctx, "Note removed!").await?;
+                } else {
+                    invocation.reply(ctx, "Note saved!").await?;
+                }
+            }
+            Err(e) => {
+                println!("{}", e);
+                invocation.reply(ctx, "Something went wrong!").await?;
+            }
+        }
+    } else {
+        invocation
+            .reply(ctx, "Usage: note <id> <note/clear>")
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn build_poll(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if !store.poll_is_under_limit(guild_id.to_string()).await? {
+        invocation
+            .reply(
+                ctx,
+                "Too many custom polls saved! Please delete some before adding more!",
+            )
+            .await?;
+        return Ok(());
+    }
+
+    // Titles of embeds and labels of buttons have length limits
+    let question = match ask_builder_text(ctx, invocation, "What's the poll question?", 256).await?
+    {
+        Some(question) => question,
+        None => return Ok(()),
+    };
+    let option_a = match ask_builder_text(ctx, invocation, "What's the first option?", 80).await? {
+        Some(option) => option,
+        None => return Ok(()),
+    };
+    let option_b = match ask_builder_text(ctx, invocation, "What's the second option?", 80).await? {
+        Some(option) => option,
+        None => return Ok(()),
+    };
+
+    let emoji_sets = [["🟠", "🔵"], ["👍", "👎"], ["✅", "❌"]];
+    let emoji_labels = ["🟠 🔵", "👍 👎", "✅ ❌"];
+    let emoji = match ask_builder_choice(
+        ctx,
+        invocation,
+        "Which emoji should the options use?",
+        &emoji_labels,
+    )
+    .await?
+    {
+        Some(picked) => emoji_sets[picked],
+        None => return Ok(()),
+    };
+
+    let durations = [None, Some(1), Some(24), Some(168)];
+    let duration_labels = ["No time limit", "1 hour", "1 day", "1 week"];
+    let duration_hours = match ask_builder_choice(
+        ctx,
+        invocation,
+        "How long should the poll stay open?",
+        &duration_labels,
+    )
+    .await?
+    {
+        Some(picked) => durations[picked],
+        None => return Ok(()),
+    };
+
+    let anonymous = match ask_builder_choice(
+        ctx,
+        invocation,
+        "How should members vote?",
+        &["Reactions", "Anonymous buttons"],
+    )
+    .await?
+    {
+        Some(picked) => picked == 1,
+        None => return Ok(()),
+    };
+
+    let summary = format!(
+        "**{}**\n{} - {}\n{} - {}\nOpen for: {}\nVotes: {}\nSave this poll?",
+        question,
+        emoji[0],
+        option_a,
+        emoji[1],
+        option_b,
+        duration_hours.map_or(String::from("no time limit"), |hours| format!(
+            "{} hours",
+            hours
+        )),
+        if anonymous { "anonymous" } else { "public" }
+    );
+    match ask_builder_choice(ctx, invocation, &summary, &["Save", "Cancel"]).await? {
+        Some(0) => {}
+        Some(_) => {
+            invocation.reply(ctx, "Poll builder cancelled!").await?;
+            return Ok(());
+        }
+        None => return Ok(()),
+    }
+
+    match store
+        .add_custom_poll(
+            guild_id.to_string(),
+            vec![question, option_a, option_b],
+            Some(emoji.iter().map(|e| e.to_string()).collect()),
+            duration_hours,
+            anonymous,
+        )
+        .await
+    {
+        Ok(_) => {
+            invocation
+                .reply(ctx, "Poll saved! Post it with custom_poll.")
+                .await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn stream(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    // No parameters lists the streams of the guild
+    if invocation.args.is_empty() {
+        let default_stream = store.get_default_stream(guild_id.to_string()).await?;
+        let mut pretty_list = format!(
+            "**daily** - {} - {}\n",
+            default_stream
+                .channel
+                .map_or(String::from("no channel"), |channel| format!(
+                    "<#{}>",
+                    channel
+                )),
+            format_ping_role(&default_stream.ping_role)
+        );
+        for row in store.get_list_of_streams(guild_id.to_string()).await? {
+            let name: String = row.get(0);
+            let channel_id: String = row.get(1);
+            let ping_role: String = row.get(2);
+            pretty_list = format!(
+                "{}**{}** - <#{}> - {}\n",
+                pretty_list,
+                name,
+                channel_id,
+                format_ping_role(&ping_role)
+            )
+        }
+
+        invocation.channel_id
+            .send_message(ctx, |m| {
+                m.content(format!(
+                    "<@{}> Set up a stream with stream <name> channel <#channel>. Templates can use {{role}} and {{stream}}.",
+                    invocation.author.id
+                ))
+                .embed(|embed| {
+                    embed
+                        .title("Streams")
+                        .description(pretty_list)
+                        .color(Color::DARK_BLUE)
+                })
+            })
+            .await?;
+        return Ok(());
+    }
+
+    let name = invocation.args.text("name").to_lowercase();
+    let setting = invocation.args.text("setting").to_lowercase();
+    let value = invocation.args.text("value");
+
+    if name.is_empty()
+        || name.len() > 32
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        invocation
+            .reply(ctx, "Stream names can only have letters, numbers, - and _!")
+            .await?;
+        return Ok(());
+    }
+    let is_default = name == "daily";
+
+    // Rows changed is 0 when the stream doesn't exist
+    let changed = match setting.as_str() {
+        "channel" => {
+            let channel_id = match parse_channel(value) {
+                Some(channel_id) => channel_id,
+                None => {
+                    invocation.reply(ctx, "Not a valid channel!").await?;
+                    return Ok(());
+                }
+            };
+            let guild_channels = ctx
+                .cache
+                .guild_channels(guild_id)
+                .ok_or("Command not being called from a guild?")?;
+            if !guild_channels.contains_key(&ChannelId(channel_id)) {
+                invocation
+                    .reply(ctx, "Channel not found on this server!")
+                    .await?;
+                return Ok(());
+            }
+
+            if is_default {
+                store
+                    .set_ping_channel_id(
+                        channel_id.to_string(),
+                        guild_id.to_string(),
+                        invocation.author.id.to_string(),
+                    )
+                    .await
+            } else {
+                store
+                    .set_stream_channel(guild_id.to_string(), name.clone(), channel_id.to_string())
+                    .await
+            }
+        }
+        "role" => {
+            let ping_role = if value == "0" || value == "1" {
+                value.to_string()
+            } else {
+                match parse_role(value) {
+                    Some(role) => role.to_string(),
+                    None => {
+                        invocation
+                            .reply(
+                                ctx,
+                                "Not a valid role! Use a role, 1 for everyone or 0 for off.",
+                            )
+                            .await?;
+                        return Ok(());
+                    }
+                }
+            };
+
+            // Switching to @everyone can need a second admin to approve it
+            if ping_role == "1"
+                && !everyone_ping_approved(ctx, invocation, "set a stream ping to @everyone")
+                    .await?
+            {
+                return Ok(());
+            }
+
+            if is_default {
+                store.set_ping_role(guild_id.to_string(), ping_role).await
+            } else {
+                store
+                    .set_stream_ping_role(guild_id.to_string(), name.clone(), ping_role)
+                    .await
+            }
+        }
+        "template" => {
+            let template = if value.is_empty() || value.eq_ignore_ascii_case("default") {
+                None
+            } else {
+                Some(value.to_string())
+            };
+
+            // A template pinging everyone goes through the same approval as setting the ping to @everyone
+            if template.as_deref().is_some_and(mentions_everyone)
+                && !everyone_ping_approved(
+                    ctx,
+                    invocation,
+                    "set a stream mention template with an @everyone or @here ping",
+                )
+                .await?
+            {
+                return Ok(());
+            }
+
+            if is_default {
+                store
+                    .set_mention_template(guild_id.to_string(), template)
+                    .await
+            } else {
+                store
+                    .set_stream_template(guild_id.to_string(), name.clone(), template)
+                    .await
+            }
+        }
+        "remove" if !is_default => {
+            store
+                .delete_stream(guild_id.to_string(), name.clone())
+                .await
+        }
+        "remove" => {
+            invocation
+                .reply(ctx, "The daily stream can't be removed!")
+                .await?;
+            return Ok(());
+        }
+        _ => {
+            invocation
+                .reply(
+                    ctx,
+                    "Usage: stream <name> <channel/role/template/remove> <value>",
+                )
+                .await?;
+            return Ok(());
+        }
+    };
+
+    match changed {
+        Ok(0) => {
+            invocation
+                .reply(ctx, "Stream not found! Set its channel first.")
+                .await?;
+        }
+        Ok(_) => {
+            invocation.reply(ctx, "Stream updated!").await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn cleanup(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if !invocation.args.is_empty() {
+        let parameters: Vec<String> = [
+            invocation.args.text("seconds"),
+            invocation.args.text("commands"),
+        ]
+        .into_iter()
+        .filter(|parameter| !parameter.is_empty())
+        .map(|parameter| parameter.to_lowercase())
+        .collect();
+
+        match parameters
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<&str>>()
+            .as_slice()
+        {
+            ["off"] => {
+                store.set_cleanup(guild_id.to_string(), None, false).await?;
+                invocation
+                    .reply(ctx, "Replies are no longer cleaned up!")
+                    .await?;
+            }
+            [seconds] | [seconds, "commands"] => match seconds.parse::<i32>() {
+                // An hour is the longest, replies are meant to be short lived
+                Ok(seconds) if (1..=3600).contains(&seconds) => {
+                    let delete_command = parameters.len() == 2;
+                    store
+                        .set_cleanup(guild_id.to_string(), Some(seconds), delete_command)
+                        .await?;
+                    let cleaned = if delete_command {
+                        "Replies and commands"
+                    } else {
+                        "Replies"
+                    };
+                    invocation
+                        .reply(
+                            ctx,
+                            format!("{} are cleaned up after {} seconds!", cleaned, seconds),
+                        )
+                        .await?;
+                }
+                _ => {
+                    invocation
+                        .reply(ctx, "Please enter an amount of seconds between 1 and 3600!")
+                        .await?;
+                }
+            },
+            _ => {
+                invocation
+                    .reply(ctx, "Usage: cleanup <seconds/off> [commands]")
+                    .await?;
+            }
+        }
+    } else {
+        let current_setting = match store.get_cleanup(guild_id.to_string()).await? {
+            (Some(seconds), true) => {
+                format!("replies and commands are deleted after {} seconds", seconds)
+            }
+            (Some(seconds), false) => format!("replies are deleted after {} seconds", seconds),
+            (None, _) => String::from("off"),
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Cleanup is {}. Use cleanup <seconds/off> [commands] to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn set_timezone(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("timezone");
+    if !parameter.is_empty() {
+        let timezone = match parameter.parse::<Tz>() {
+            Ok(timezone) => timezone,
+            Err(_) => {
+                invocation.reply(
+                    ctx,
+                    "Not a valid timezone! Use a name from the tz database, like Europe/Helsinki.",
+                )
+                .await?;
+                return Ok(());
+            }
+        };
+
+        let stored = if timezone == Tz::UTC {
+            None
+        } else {
+            Some(timezone.name().to_string())
+        };
+        store
+            .set_guild_timezone(guild_id.to_string(), stored)
+            .await?;
+
+        // Moving the schedule to the new timezone
+        reschedule_guild(guild_id.to_string(), ctx).await?;
+
+        invocation
+            .reply(ctx, format!("Timezone set to {}!", timezone))
+            .await?;
+    } else {
+        let timezone = store.get_guild_timezone(guild_id.to_string()).await?;
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "The schedule runs in {}. Use set_timezone <tz> to change it.",
+                    timezone
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn accessible(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store
+                .set_accessible_posts(guild_id.to_string(), true)
+                .await?;
+            invocation
+                .reply(ctx, "Questions and polls are now posted as plain text!")
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store
+                .set_accessible_posts(guild_id.to_string(), false)
+                .await?;
+            invocation
+                .reply(ctx, "Questions and polls are now posted as embeds!")
+                .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_accessible_posts(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Accessibility mode is {}. Use accessible <on/off> to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn image_card(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let usage = "Usage: image_card <on/off/preview> or image_card color <#hex/default> or image_card template <plain/stripe/frame>";
+
+    let setting = invocation.args.text("setting");
+    let value = invocation.args.text("value");
+
+    match setting.to_lowercase().as_str() {
+        "on" => {
+            store.set_image_cards(guild_id.to_string(), true).await?;
+            invocation.reply(ctx, "Questions of the day are now posted as image cards! Questions too long for a card, or with characters it can't draw, are posted as usual.")
+                .await?;
+        }
+        "off" => {
+            store.set_image_cards(guild_id.to_string(), false).await?;
+            invocation
+                .reply(ctx, "Questions of the day are now posted as embeds!")
+                .await?;
+        }
+        "color" => {
+            let color = if value.eq_ignore_ascii_case("default") {
+                None
+            } else if parse_card_color(value).is_some() {
+                Some(format!("#{}", value.trim_start_matches('#').to_uppercase()))
+            } else {
+                invocation
+                    .reply(ctx, "Please use a hex color like #FF8800, or default!")
+                    .await?;
+                return Ok(());
+            };
+            store.set_card_color(guild_id.to_string(), color).await?;
+            invocation
+                .reply(ctx, "Card color set! Use image_card preview to see it.")
+                .await?;
+        }
+        "template" => {
+            let template = value.to_lowercase();
+            if CARD_TEMPLATES.contains(&template.as_str()) {
+                store
+                    .set_card_template(guild_id.to_string(), template)
+                    .await?;
+                invocation
+                    .reply(ctx, "Card template set! Use image_card preview to see it.")
+                    .await?;
+            } else {
+                invocation
+                    .reply(
+                        ctx,
+                        format!("Please use one of {}!", CARD_TEMPLATES.join(", ")),
+                    )
+                    .await?;
+            }
+        }
+        "preview" => {
+            let style = store.get_card_style(guild_id.to_string()).await?;
+            let card = get_image_card(
+                ctx,
+                "Question",
+                "What is a small thing that made your day better recently?",
+                style,
+            )
+            .await;
+            match card {
+                Some(card) => {
+                    Post::card("Preview of the image card", card, None, Color::FABLED_PINK)
+                        .send(ctx, invocation.channel_id)
+                        .await?;
+                }
+                None => {
+                    invocation.reply(ctx, "Something went wrong!").await?;
+                }
+            }
+        }
+        "" => {
+            let style = store.get_card_style(guild_id.to_string()).await?;
+            let current_setting = if store.get_image_cards(guild_id.to_string()).await? {
+                "on"
+            } else {
+                "off"
+            };
+            invocation
+                .reply(
+                    ctx,
+                    format!(
+                        "Image cards are {}, with the color {} and the {} template.\n{}",
+                        current_setting, style.color, style.template, usage
+                    ),
+                )
+                .await?;
+        }
+        _ => {
+            invocation.reply(ctx, usage).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn pause(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if store.get_paused(guild_id.to_string()).await? {
+        invocation
+            .reply(ctx, "Scheduled posting is already paused!")
+            .await?;
+        return Ok(());
+    }
+
+    store
+        .set_paused(guild_id.to_string(), true, None, None)
+        .await?;
+    invocation.reply(
+        ctx,
+        "Scheduled posting paused! Questions can still be posted by hand. Use resume to continue.",
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn resume(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if !store.get_paused(guild_id.to_string()).await? {
+        invocation
+            .reply(ctx, "Scheduled posting isn't paused!")
+            .await?;
+        return Ok(());
+    }
+
+    // Continuing from now, so the runs missed while paused aren't posted all at once
+    let timezone = store.get_guild_timezone(guild_id.to_string()).await?;
+    let skip_days = store.get_skip_days(guild_id.to_string()).await?;
+    let schedule = store.get_post_schedule(guild_id.to_string()).await?;
+    let next_post_at = schedule
+        .as_ref()
+        .and_then(|(schedule, _)| next_post_time(schedule, timezone, &skip_days, Utc::now()));
+    let poll_schedule = store.get_poll_schedule(guild_id.to_string()).await?;
+    let next_poll_at = poll_schedule
+        .as_ref()
+        .and_then(|(schedule, _, _)| next_post_time(schedule, timezone, &skip_days, Utc::now()));
+    store
+        .set_paused(guild_id.to_string(), false, next_post_at, next_poll_at)
+        .await?;
+
+    match next_post_at.or(next_poll_at) {
+        Some(next_post_at) => {
+            invocation
+                .reply(
+                    ctx,
+                    format!(
+                        "Scheduled posting resumed! The next post is <t:{}:R>.",
+                        next_post_at.timestamp()
+                    ),
+                )
+                .await?;
+        }
+        None => {
+            invocation.reply(
+                ctx,
+                "Scheduled posting resumed! Set a schedule with schedule or poll_schedule to get posts.",
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats a channel from get_ping_channel_id, get_archive_channel_id or get_backup_channel_id for showing it in a message
+fn format_setting_channel(channel: &str) -> String {
+    if channel == "0" {
+        String::from("not set")
+    } else {
+        channel.to_string()
+    }
+}
+
+/// Formats an on/off setting for showing it in a message
+fn format_toggle(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+async fn config(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let guild = guild_id.to_string();
+
+    let timezone = store.get_guild_timezone(guild.clone()).await?;
+    let paused = store.get_paused(guild.clone()).await?;
+    let schedule = match store.get_post_schedule(guild.clone()).await? {
+        Some((schedule, next_post_at)) => {
+            let next = match next_post_at {
+                Some(next) if !paused => format!(", next <t:{}:R>", next.timestamp()),
+                _ => String::new(),
+            };
+            format!("{}{}", describe_schedule(&schedule, timezone), next)
+        }
+        None => String::from("off"),
+    };
+    let poll_schedule = match store.get_poll_schedule(guild.clone()).await? {
+        Some((schedule, source, next_poll_at)) => {
+            let next = match next_poll_at {
+                Some(next) if !paused => format!(", next <t:{}:R>", next.timestamp()),
+                _ => String::new(),
+            };
+            format!(
+                "{} polls {}{}",
+                source,
+                describe_schedule(&schedule, timezone),
+                next
+            )
+        }
+        None => String::from("off"),
+    };
+    // Pausing is shown first when it's on, it's the usual reason nothing is posting
+    let (schedule_state, poll_schedule_state) = if paused {
+        (
+            format!("**PAUSED** - {}", schedule),
+            format!("**PAUSED** - {}", poll_schedule),
+        )
+    } else {
+        (schedule, poll_schedule)
+    };
+    let teaser = store
+        .get_teaser_minutes(guild.clone())
+        .await?
+        .map_or(String::from("off"), format_lead_time);
+    let answer_window = store
+        .get_answer_window(guild.clone())
+        .await?
+        .map_or(String::from("off"), |hours| format!("{} hours", hours));
+    let (min_length, member_limit) = store.get_answer_filter(guild.clone()).await?;
+    let answer_filter = format!(
+        "from {} characters, {}",
+        min_length,
+        format_member_limit(member_limit)
+    );
+    let cleanup = match store.get_cleanup(guild.clone()).await? {
+        (Some(seconds), true) => format!("{} seconds, commands too", seconds),
+        (Some(seconds), false) => format!("{} seconds", seconds),
+        (None, _) => String::from("off"),
+    };
+    let (poll_intro, poll_title) = store.get_poll_texts(guild.clone()).await?;
+    let image_cards = if store.get_image_cards(guild.clone()).await? {
+        let style = store.get_card_style(guild.clone()).await?;
+        format!("on ({}, {})", style.color, style.template)
+    } else {
+        String::from("off")
+    };
+    let modlog = format_modlog_target(&store.get_modlog_target(guild.clone()).await?);
+    let modlog = match store.get_modlog_events(guild.clone()).await? {
+        Some(events) if modlog != "off" => format!("{} ({})", modlog, events.join(", ")),
+        _ => modlog,
+    };
+    let question_filter = match store.get_question_filter(guild.clone()).await? {
+        Some(tags) => format_tags(&tags),
+        None => String::from("off"),
+    };
+    let admin_contacts = store.get_admin_contacts(guild.clone()).await?;
+    let admin_contacts = if admin_contacts.is_empty() {
+        String::from("the admin who set the channel")
+    } else {
+        admin_contacts
+            .iter()
+            .map(|user_id| format!("<@{}>", user_id))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    let settings = format!(
+        "**Channel:** {}\n\
+        **Channel fallback:** {}\n\
+        **Ping role:** {}\n\
+        **Schedule:** {}\n\
+        **Poll schedule:** {}\n\
+        **Timezone:** {}\n\
+        **Skip days:** {}\n\
+        **Double days:** {}\n\
+        **Teaser:** {}\n\
+        **Answer window:** {}\n\
+        **Answer filter:** {}\n\
+        **Trivia mode:** {}\n\
+        **Archive:** {}\n\
+        **Backup channel:** {}\n\
+        **Modlog:** {}\n\
+        **Admin contacts:** {}\n\
+        **Notify policy:** {}\n\
+        **Question cooldown:** {} days\n\
+        **Question filter:** {}\n\
+        **Fair rotation:** {}\n\
+        **Custom questions only:** {}\n\
+        **Question mix:** {}\n\
+        **Seasonal questions:** {}\n\
+        **Family friendly:** {}\n\
+        **Recommendations:** {}\n\
+        **Attribution:** {}\n\
+        **Submitter credit:** {}\n\
+        **Submitter mention:** {}\n\
+        **Save emoji:** {}\n\
+        **@everyone approval:** {}\n\
+        **Submission strictness:** {}\n\
+        **Accessibility mode:** {}\n\
+        **Image cards:** {}\n\
+        **Poll buttons:** {}\n\
+        **Poll role weights:** {}\n\
+        **Poll nudge:** {}\n\
+        **Poll intro:** {}\n\
+        **Poll title:** {}\n\
+        **Cleanup:** {}\n\
+        **Ephemeral replies:** {}\n\
+        **Removal export:** {}\n\
+        **Command channel:** {}\n\
+        **Fallback:** {}",
+        format_setting_channel(&store.get_ping_channel_id(guild.clone()).await?),
+        format_toggle(store.get_channel_fallback(guild.clone()).await?),
+        format_ping_role(&store.get_ping_role(guild.clone()).await?),
+        schedule_state,
+        poll_schedule_state,
+        timezone,
+        format_skip_days(&store.get_skip_days(guild.clone()).await?),
+        format_skip_days(&store.get_double_days(guild.clone()).await?),
+        teaser,
+        answer_window,
+        answer_filter,
+        format_toggle(store.get_trivia_mode(guild.clone()).await?),
+        format_setting_channel(&store.get_archive_channel_id(guild.clone()).await?),
+        format_setting_channel(&store.get_backup_channel_id(guild.clone()).await?),
+        modlog,
+        admin_contacts,
+        store.get_notify_policy(guild.clone()).await?,
+        store.get_question_cooldown(guild.clone()).await?,
+        question_filter,
+        format_toggle(store.get_fair_rotation(guild.clone()).await?),
+        format_toggle(store.get_custom_only(guild.clone()).await?),
+        format_question_mix(store.get_question_mix(guild.clone()).await?),
+        format_toggle(store.get_seasonal(guild.clone()).await?),
+        format_toggle(store.get_family_friendly(guild.clone()).await?),
+        format_toggle(store.get_recommend(guild.clone()).await?),
+        format_toggle(store.get_show_attribution(guild.clone()).await?),
+        format_toggle(store.get_show_submitter(guild.clone()).await?),
+        format_toggle(store.get_mention_submitter(guild.clone()).await?),
+        store.get_save_emoji(guild.clone()).await?,
+        format_toggle(store.get_everyone_approval(guild.clone()).await?),
+        store.get_moderation_strictness(guild.clone()).await?,
+        format_toggle(store.get_accessible_posts(guild.clone()).await?),
+        image_cards,
+        format_toggle(store.get_poll_buttons(guild.clone()).await?),
+        format_role_weights(&store.get_poll_role_weights(guild.clone()).await?),
+        store.get_poll_nudge(guild.clone()).await?,
+        poll_intro,
+        poll_title,
+        cleanup,
+        format_toggle(store.get_ephemeral_replies(guild.clone()).await?),
+        format_toggle(store.get_removal_export(guild.clone()).await?),
+        format_setting_channel(&store.get_command_channel_id(guild.clone()).await?),
+        store.get_fallback_question(guild).await?,
+    );
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
+                .embed(|embed| {
+                    embed
+                        .title("Server settings")
+                        .description(settings)
+                        .color(Color::DARK_BLUE)
+                })
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn poll_nudge(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("mode").to_lowercase();
+    if !parameter.is_empty() {
+        let reply = match parameter.as_str() {
+            "off" => "Poll nudges turned off!",
+            "ping" => "Members with the ping role who haven't voted will be mentioned halfway through anonymous polls!",
+            "dm" => "Members with the ping role who haven't voted will get a DM halfway through anonymous polls!",
+            _ => {
+                invocation.reply(ctx, "Please use off, ping or dm!").await?;
+                return Ok(());
+            }
+        };
+
+        store
+            .set_poll_nudge(guild_id.to_string(), parameter)
+            .await?;
+        invocation.reply(ctx, reply).await?;
+    } else {
+        let current_setting = store.get_poll_nudge(guild_id.to_string()).await?;
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Poll nudge is {}. Use poll_nudge <off/ping/dm> to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn poll_schedule(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if !invocation.args.is_empty() {
+        let poll_source = match invocation.args.text("source") {
+            "custom" => "custom",
+            _ => "default",
+        };
+        let parameter = invocation.args.text("schedule").trim_matches('"').trim();
+
+        if parameter.eq_ignore_ascii_case("off") {
+            store
+                .set_poll_schedule(guild_id.to_string(), None, String::from("default"), None)
+                .await?;
+            invocation.reply(ctx, "Scheduled polls turned off!").await?;
+            return Ok(());
+        }
+
+        let schedule = match parse_schedule_parameter(parameter) {
+            Some(schedule) => schedule,
+            None => {
+                invocation.reply(
+                    ctx,
+                    "Please enter a time in HH:MM format, or a cron schedule like \"0 18 * * FRI\"!",
+                )
+                .await?;
+                return Ok(());
+            }
+        };
+
+        let timezone = store.get_guild_timezone(guild_id.to_string()).await?;
+        let skip_days = store.get_skip_days(guild_id.to_string()).await?;
+        let next_poll_at = match next_post_time(&schedule, timezone, &skip_days, Utc::now()) {
+            Some(next_poll_at) => next_poll_at,
+            None => {
+                invocation
+                    .reply(ctx, "That schedule never posts anything!")
+                    .await?;
+                return Ok(());
+            }
+        };
+        store
+            .set_poll_schedule(
+                guild_id.to_string(),
+                Some(schedule.clone()),
+                poll_source.to_string(),
+                Some(next_poll_at),
+            )
+            .await?;
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "{} polls will be posted {}! The first one is <t:{}:R>.",
+                    if poll_source == "custom" {
+                        "Custom"
+                    } else {
+                        "Random"
+                    },
+                    describe_schedule(&schedule, timezone),
+                    next_poll_at.timestamp()
+                ),
+            )
+            .await?;
+    } else {
+        match store.get_poll_schedule(guild_id.to_string()).await? {
+            Some((schedule, poll_source, next_poll_at)) => {
+                let timezone = store.get_guild_timezone(guild_id.to_string()).await?;
+                let next = next_poll_at
+                    .map(|next| format!(" The next one is <t:{}:R>.", next.timestamp()))
+                    .unwrap_or_default();
+                invocation
+                    .reply(
+                        ctx,
+                        format!(
+                            "{} polls are posted {}.{}",
+                            if poll_source == "custom" {
+                                "Custom"
+                            } else {
+                                "Random"
+                            },
+                            describe_schedule(&schedule, timezone),
+                            next
+                        ),
+                    )
+                    .await?;
+            }
+            None => {
+                invocation.reply(ctx, "Scheduled polls are off!").await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Hashes an API token for storing and looking it up
+fn hash_api_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Generates a new random API token
+fn generate_api_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let random: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("qotd_{}", random)
+}
+
+async fn api_token(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameters: Vec<String> = [
+        invocation.args.text("action"),
+        invocation.args.text("scope"),
+    ]
+    .into_iter()
+    .filter(|parameter| !parameter.is_empty())
+    .map(|parameter| parameter.to_lowercase())
+    .collect();
+
+    match parameters
+        .iter()
+        .map(|parameter| parameter.as_str())
+        .collect::<Vec<&str>>()
+        .as_slice()
+    {
+        ["create", scope] if *scope == "read" || *scope == "manage" => {
+            // Creating a token with a scope that already has one rotates it, the old token stops working
+            let token = generate_api_token();
+            store
+                .set_api_token(
+                    guild_id.to_string(),
+                    scope.to_string(),
+                    hash_api_token(&token),
+                )
+                .await?;
+
+            let sent = invocation
+                .author
+                .direct_message(ctx, |m| {
+                    m.content(format!(
+                        "Your {} API token, it's only shown this once:\n`{}`",
+                        scope, token
+                    ))
+                })
+                .await;
+
+            match sent {
+                Ok(_) => {
+                    invocation
+                        .reply(ctx, format!("New {} token sent in DMs!", scope))
+                        .await?;
+                }
+                Err(_) => {
+                    // Nobody has the token, so it shouldn't stay valid either
+                    store
+                        .revoke_api_tokens(guild_id.to_string(), Some(scope.to_string()))
+                        .await?;
+                    invocation
+                        .reply(ctx, "Couldn't send you a DM, open your DMs and try again!")
+                        .await?;
+                }
+            }
+        }
+        ["revoke", scope] if *scope == "read" || *scope == "manage" || *scope == "all" => {
+            let scope = if *scope == "all" {
+                None
+            } else {
+                Some(scope.to_string())
+            };
+            match store.revoke_api_tokens(guild_id.to_string(), scope).await? {
+                0 => {
+                    invocation.reply(ctx, "No tokens to revoke!").await?;
+                }
+                revoked => {
+                    invocation
+                        .reply(ctx, format!("Revoked {} token(s)!", revoked))
+                        .await?;
+                }
+            }
+        }
+        [] => {
+            let tokens = store.get_api_tokens(guild_id.to_string()).await?;
+            if tokens.is_empty() {
+                invocation
+                    .reply(
+                        ctx,
+                        "No API tokens. Use api_token create <read/manage> to make one.",
+                    )
+                    .await?;
+            } else {
+                let mut pretty_list = String::from("API tokens:\n");
+                for row in &tokens {
+                    let scope: String = row.get(0);
+                    let created: String = row.get(1);
+                    pretty_list = format!("{}{} - created {}\n", pretty_list, scope, created);
+                }
+                invocation.reply(ctx, pretty_list).await?;
+            }
+        }
+        _ => {
+            invocation
+                .reply(
+                    ctx,
+                    "Usage: api_token <create <read/manage>/revoke <read/manage/all>>",
+                )
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+// Guild of the token a request to the HTTP API was made with
+#[derive(Clone)]
+struct ApiGuild {
+    guild_id: String,
+}
+
+#[derive(Serialize)]
+struct ApiQuestion {
+    id: i32,
+    question: String,
+    status: String,
+    in_use: bool,
+}
+
+#[derive(Deserialize)]
+struct ApiNewQuestion {
+    question: String,
+    #[serde(default)]
+    mature: bool,
+    source_url: Option<String>,
+    license: Option<String>,
+}
+
+// Range of the owner API stats, in days back from today
+#[derive(Deserialize)]
+struct OwnerQuery {
+    days: Option<i64>,
+    limit: Option<i64>,
+}
+
+impl OwnerQuery {
+    fn days(&self) -> i64 {
+        self.days.unwrap_or(30).clamp(1, 365)
+    }
+
+    fn limit(&self) -> i64 {
+        self.limit.unwrap_or(10).clamp(1, 100)
+    }
+}
+
+#[derive(Serialize)]
+struct OwnerStats {
+    guilds: usize,
+    questions_posted: i64,
+    commands_used: i64,
+    command_failures: i64,
+    error_rate: f64,
+}
+
+#[derive(Serialize)]
+struct OwnerTopQuestion {
+    question: String,
+    posts: i64,
+    guilds: i64,
+}
+
+#[derive(Serialize)]
+struct OwnerCommandVolume {
+    command: String,
+    uses: i64,
+    failures: i64,
+    error_rate: f64,
+}
+
+/// Share of the uses that failed, 0 without uses
+fn error_rate(uses: i64, failures: i64) -> f64 {
+    if uses == 0 {
+        0.0
+    } else {
+        failures as f64 / uses as f64
+    }
+}
+
+/// Serves the HTTP API on the address until the bot shuts down
+async fn run_api(address: String, ctx: Context) {
+    let address = match address.parse() {
+        Ok(address) => address,
+        Err(e) => {
+            println!("Not a valid API address {}: {}", address, e);
+            return;
+        }
+    };
+
+    let mut app = Router::new()
+        .route("/questions", get(api_list_questions).post(api_add_question))
+        .route("/questions/:id", delete(api_delete_question))
+        .route_layer(middleware::from_fn_with_state(ctx.clone(), api_auth));
+
+    // The owner endpoints only exist if an owner token is set
+    if let Ok(token) = env::var("OWNER_API_TOKEN") {
+        let owner = Router::new()
+            .route("/owner/stats", get(owner_stats))
+            .route("/owner/top_questions", get(owner_top_questions))
+            .route("/owner/commands", get(owner_commands))
+            .route_layer(middleware::from_fn_with_state(
+                hash_api_token(&token),
+                owner_auth,
+            ));
+        app = app.merge(owner);
+    }
+
+    let app = app.with_state(ctx);
+
+    if let Err(e) = axum::Server::bind(&address)
+        .serve(app.into_make_service())
+        .await
+    {
+        println!("HTTP API stopped: {}", e);
+    }
+}
+
+/// Checks the bearer token of every API request.
+/// Read tokens can only make GET requests, manage tokens can make any request to their own guild.
+async fn api_auth<B>(
+    State(ctx): State<Context>,
+    mut request: Request<B>,
+    next: Next<B>,
+) -> Result<Response, StatusCode> {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let (guild_id, scope) = match store(&ctx)
+        .await
+        .get_api_token_guild(hash_api_token(token))
+        .await
+    {
+        Ok(Some(found)) => found,
+        Ok(None) => return Err(StatusCode::UNAUTHORIZED),
+        Err(e) => {
+            println!("Checking API token failed: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    if scope != "manage" && request.method() != axum::http::Method::GET {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    request.extensions_mut().insert(ApiGuild { guild_id });
+    Ok(next.run(request).await)
+}
+
+/// Checks the bearer token of owner API requests against OWNER_API_TOKEN
+async fn owner_auth<B>(
+    State(token_hash): State<String>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Result<Response, StatusCode> {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    // Comparing hashes so the comparison doesn't leak how much of the token matched
+    if hash_api_token(token) != token_hash {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    Ok(next.run(request).await)
+}
+
+/// GET /owner/stats, guild count, questions posted and command totals across every guild
+async fn owner_stats(
+    State(ctx): State<Context>,
+    Query(query): Query<OwnerQuery>,
+) -> Result<Json<OwnerStats>, StatusCode> {
+    let questions_posted = store(&ctx).await.get_posted_question_count().await;
+    let volumes = store(&ctx).await.get_command_volumes(query.days()).await;
+
+    match (questions_posted, volumes) {
+        (Ok(questions_posted), Ok(rows)) => {
+            let commands_used = rows.iter().map(|row| row.get::<_, i64>("uses")).sum();
+            let command_failures = rows.iter().map(|row| row.get::<_, i64>("failures")).sum();
+            Ok(Json(OwnerStats {
+                guilds: ctx.cache.guild_count(),
+                questions_posted,
+                commands_used,
+                command_failures,
+                error_rate: error_rate(commands_used, command_failures),
+            }))
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            println!("Getting owner stats failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /owner/top_questions, the questions posted the most across every guild
+async fn owner_top_questions(
+    State(ctx): State<Context>,
+    Query(query): Query<OwnerQuery>,
+) -> Result<Json<Vec<OwnerTopQuestion>>, StatusCode> {
+    match store(&ctx)
+        .await
+        .get_top_questions_globally(query.days(), query.limit())
+        .await
+    {
+        Ok(rows) => Ok(Json(
+            rows.iter()
+                .map(|row| OwnerTopQuestion {
+                    question: row.get("question_string"),
+                    posts: row.get("posts"),
+                    guilds: row.get("guilds"),
+                })
+                .collect(),
+        )),
+        Err(e) => {
+            println!("Getting top questions failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /owner/commands, uses and error rates of every command across every guild
+async fn owner_commands(
+    State(ctx): State<Context>,
+    Query(query): Query<OwnerQuery>,
+) -> Result<Json<Vec<OwnerCommandVolume>>, StatusCode> {
+    match store(&ctx).await.get_command_volumes(query.days()).await {
+        Ok(rows) => Ok(Json(
+            rows.iter()
+                .map(|row| {
+                    let uses = row.get("uses");
+                    let failures = row.get("failures");
+                    OwnerCommandVolume {
+                        command: row.get("command"),
+                        uses,
+                        failures,
+                        error_rate: error_rate(uses, failures),
+                    }
+                })
+                .collect(),
+        )),
+        Err(e) => {
+            println!("Getting command volumes failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// GET /questions, lists the custom questions of the guild
+async fn api_list_questions(
+    State(ctx): State<Context>,
+    Extension(guild): Extension<ApiGuild>,
+) -> Result<Json<Vec<ApiQuestion>>, StatusCode> {
+    match store(&ctx)
+        .await
+        .get_list_custom_questions(guild.guild_id)
+        .await
+    {
+        Ok(rows) => Ok(Json(
+            rows.iter()
+                .map(|row| ApiQuestion {
+                    id: row.get("question_id"),
+                    question: row.get("question_string"),
+                    status: row.get("status"),
+                    in_use: row.get("in_use"),
+                })
+                .collect(),
+        )),
+        Err(e) => {
+            println!("Listing API questions failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// POST /questions, adds a custom question to the guild.
+/// Submissions go through the same rating as submit_qotd.
+async fn api_add_question(
+    State(ctx): State<Context>,
+    Extension(guild): Extension<ApiGuild>,
+    Json(new_question): Json<ApiNewQuestion>,
+) -> Response {
+    let store = store(&ctx).await;
+    if new_question.question.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "Question is empty").into_response();
+    }
+    if let Some(error) = question_length_error(&new_question.question) {
+        return (StatusCode::BAD_REQUEST, error).into_response();
+    }
+    match store.question_is_under_limit(guild.guild_id.clone()).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return (StatusCode::CONFLICT, "Too many custom questions saved").into_response()
+        }
+        Err(e) => {
+            println!("Checking the question limit failed: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    }
+
+    let flag_reason =
+        match rate_question(guild.guild_id.clone(), &new_question.question, &ctx).await {
+            Ok(flag_reason) => flag_reason,
+            Err(e) => {
+                println!("Rating an API question failed: {}", e);
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+    let detail = match &flag_reason {
+        Some(reason) => format!(
+            "{}\n\nHeld for moderation ({})",
+            new_question.question, reason
+        ),
+        None => new_question.question.clone(),
+    };
+    let source = QuestionSource {
+        url: new_question.source_url,
+        license: new_question.license,
+    };
+    match store
+        .add_custom_question(
+            guild.guild_id.clone(),
+            new_question.question.clone(),
+            None,
+            flag_reason,
+            new_question.mature,
+        )
+        .await
+    {
+        Ok(0) => (StatusCode::CONFLICT, "Question is already saved").into_response(),
+        Ok(_) => {
+            if !source.is_empty() {
+                if let Err(e) = store
+                    .set_question_source(guild.guild_id.clone(), new_question.question, source)
+                    .await
+                {
+                    println!("Saving the source of an API question failed: {}", e);
+                }
+            }
+            let event = QuestionEvent {
+                kind: "submitted",
+                question_ids: Vec::new(),
+                by: None,
+                detail: &detail,
+            };
+            emit_question_event(&ctx, guild.guild_id, event).await;
+            StatusCode::CREATED.into_response()
+        }
+        Err(e) => {
+            println!("{}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// DELETE /questions/:id, deletes a custom question of the guild
+async fn api_delete_question(
+    State(ctx): State<Context>,
+    Extension(guild): Extension<ApiGuild>,
+    Path(question_id): Path<i32>,
+) -> StatusCode {
+    match store(&ctx)
+        .await
+        .delete_custom_question(guild.guild_id.clone(), question_id, None)
+        .await
+    {
+        Ok(1) => {
+            let detail = format!("Question {} deleted through the API", question_id);
+            let event = QuestionEvent {
+                kind: "deleted",
+                question_ids: vec![question_id],
+                by: None,
+                detail: &detail,
+            };
+            emit_question_event(&ctx, guild.guild_id, event).await;
+            StatusCode::NO_CONTENT
+        }
+        Ok(_) => StatusCode::NOT_FOUND,
+        Err(e) => {
+            println!("Deleting an API question failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+async fn queue_question(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let timezone = store.get_guild_timezone(guild_id.to_string()).await?;
+    let today = Utc::now().with_timezone(&timezone).date_naive();
+
+    let parameters: Vec<&str> = [invocation.args.text("id"), invocation.args.text("date")]
+        .into_iter()
+        .flat_map(str::split_whitespace)
+        .collect();
+
+    match parameters.as_slice() {
+        [] => {
+            let queued = store
+                .get_queued_questions(guild_id.to_string(), today)
+                .await?;
+            if queued.is_empty() {
+                invocation
+                    .reply(
+                        ctx,
+                        "No questions queued! Use queue_question <id> <YYYY-MM-DD> to queue one.",
+                    )
+                    .await?;
+            } else {
+                let mut pretty_list = String::from("Date - ID - Question\n");
+                for row in &queued {
+                    let date: String = row.get(0);
+                    let question_id: i32 = row.get(1);
+                    let question: String = row.get(2);
+                    pretty_list =
+                        format!("{}{} - {} - {}\n", pretty_list, date, question_id, question);
+                }
+                invocation
+                    .channel_id
+                    .send_message(ctx, |m| {
+                        m.content(format!(
+                            "<@{}> Here are the queued questions",
+                            invocation.author.id
+                        ))
+                        .embed(|embed| {
+                            embed
+                                .title("Queued questions")
+                                .description(pretty_list)
+                                .color(Color::DARK_GREEN)
+                        })
+                    })
+                    .await?;
+            }
+        }
+        [id, date] => {
+            let post_date = match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                Ok(post_date) if post_date >= today => post_date,
+                Ok(_) => {
+                    invocation
+                        .reply(ctx, "That date has already passed!")
+                        .await?;
+                    return Ok(());
+                }
+                Err(_) => {
+                    invocation
+                        .reply(ctx, "Please enter the date in YYYY-MM-DD format!")
+                        .await?;
+                    return Ok(());
+                }
+            };
+
+            if id.eq_ignore_ascii_case("remove") {
+                match store
+                    .unqueue_custom_question(guild_id.to_string(), post_date)
+                    .await?
+                {
+                    0 => {
+                        invocation
+                            .reply(ctx, "Nothing queued for that date!")
+                            .await?
+                    }
+                    _ => invocation.reply(ctx, "Queued question removed!").await?,
+                };
+                return Ok(());
+            }
+
+            let question_id = match id.parse::<i32>() {
+                Ok(question_id) => question_id,
+                Err(_) => {
+                    invocation.reply(ctx, "Please enter a valid ID!").await?;
+                    return Ok(());
+                }
+            };
+
+            match store
+                .queue_custom_question(guild_id.to_string(), question_id, post_date)
+                .await
+            {
+                Ok(0) => {
+                    invocation.reply(ctx, "Question not found!").await?;
+                }
+                Ok(_) => {
+                    invocation
+                        .reply(
+                            ctx,
+                            format!(
+                                "Question {} will be posted on {} instead of a random one!",
+                                question_id, post_date
+                            ),
+                        )
+                        .await?;
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    invocation.reply(ctx, "Something went wrong!").await?;
+                }
+            }
+        }
+        _ => {
+            invocation.reply(
+                ctx,
+                "Usage: queue_question <id> <YYYY-MM-DD>, or queue_question remove <YYYY-MM-DD>",
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses question ids given to a command, separated by commas or spaces, with ranges like 3-9.
+/// Returns None if any part isn't an id or a range, or the range is too long.
+fn parse_id_list(parameter: &str) -> Option<Vec<i32>> {
+    let mut ids = Vec::new();
+    for part in parameter.split(|c: char| c == ',' || c.is_whitespace()) {
+        if part.is_empty() {
+            continue;
+        }
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (start.parse::<i32>().ok()?, end.parse::<i32>().ok()?);
+                // A guild can't have more questions than this saved anyway
+                if start > end || end - start > 1000 {
+                    return None;
+                }
+                ids.extend(start..=end);
+            }
+            None => ids.push(part.parse::<i32>().ok()?),
+        }
+    }
+
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.is_empty() {
+        None
+    } else {
+        Some(ids)
+    }
+}
+
+async fn approve(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    let ids = parse_id_list(invocation.args.text("ids"));
+
+    match ids {
+        Some(ids) => {
+            let requested = ids.len();
+            match store(ctx)
+                .await
+                .approve_queued_questions(guild_id.to_string(), Some(ids.clone()))
+                .await
+            {
+                Ok(approved) => {
+                    if approved > 0 {
+                        let detail = format!("Approved {} queued question(s)", approved);
+                        let event = QuestionEvent {
+                            kind: "approved",
+                            question_ids: ids,
+                            by: Some(invocation.author.id),
+                            detail: &detail,
+                        };
+                        emit_question_event(ctx, guild_id.to_string(), event).await;
+                    }
+                    invocation
+                        .reply(
+                            ctx,
+                            format_moderation_summary("Approved", approved, requested),
+                        )
+                        .await?;
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    invocation.reply(ctx, "Something went wrong!").await?;
+                }
+            }
+        }
+        None => {
+            invocation
+                .reply(ctx, "Usage: approve <id>, approve 3-9 or approve 3,5,7")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn reject(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    let ids = parse_id_list(invocation.args.text("ids"));
+
+    match ids {
+        Some(ids) => {
+            let requested = ids.len();
+            match store(ctx)
+                .await
+                .reject_queued_questions(guild_id.to_string(), Some(ids.clone()))
+                .await
+            {
+                Ok(rejected) => {
+                    if rejected > 0 {
+                        let detail = format!("Rejected {} queued question(s)", rejected);
+                        let event = QuestionEvent {
+                            kind: "rejected",
+                            question_ids: ids,
+                            by: Some(invocation.author.id),
+                            detail: &detail,
+                        };
+                        emit_question_event(ctx, guild_id.to_string(), event).await;
+                    }
+                    invocation
+                        .reply(
+                            ctx,
+                            format_moderation_summary("Rejected", rejected, requested),
+                        )
+                        .await?;
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    invocation.reply(ctx, "Something went wrong!").await?;
+                }
+            }
+        }
+        None => {
+            invocation
+                .reply(ctx, "Usage: reject <id>, reject 3-9 or reject 3,5,7")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn approve_all(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    match store(ctx)
+        .await
+        .approve_queued_questions(guild_id.to_string(), None)
+        .await
+    {
+        Ok(0) => {
+            invocation
+                .reply(ctx, "No questions waiting for moderation!")
+                .await?;
+        }
+        Ok(approved) => {
+            let detail = format!("Approved the whole queue, {} question(s)", approved);
+            let event = QuestionEvent {
+                kind: "approved",
+                question_ids: Vec::new(),
+                by: Some(invocation.author.id),
+                detail: &detail,
+            };
+            emit_question_event(ctx, guild_id.to_string(), event).await;
+            invocation
+                .reply(ctx, format!("Approved {} question(s)!", approved))
+                .await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn reject_all(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    match store(ctx)
+        .await
+        .reject_queued_questions(guild_id.to_string(), None)
+        .await
+    {
+        Ok(0) => {
+            invocation
+                .reply(ctx, "No questions waiting for moderation!")
+                .await?;
+        }
+        Ok(rejected) => {
+            let detail = format!("Rejected the whole queue, {} question(s)", rejected);
+            let event = QuestionEvent {
+                kind: "rejected",
+                question_ids: Vec::new(),
+                by: Some(invocation.author.id),
+                detail: &detail,
+            };
+            emit_question_event(ctx, guild_id.to_string(), event).await;
+            invocation
+                .reply(ctx, format!("Rejected {} question(s)!", rejected))
+                .await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats the reply for approving or rejecting a list of questions.
+/// Ids that weren't in the moderation queue are counted as skipped.
+fn format_moderation_summary(action: &str, changed: u64, requested: usize) -> String {
+    let skipped = requested as u64 - changed.min(requested as u64);
+    if skipped == 0 {
+        format!("{} {} question(s)!", action, changed)
+    } else {
+        format!(
+            "{} {} question(s)! {} of the ids weren't in the moderation queue.",
+            action, changed, skipped
+        )
+    }
+}
+
+/// Formats skip days for showing them in a message
+fn format_skip_days(skip_days: &[Weekday]) -> String {
+    if skip_days.is_empty() {
+        String::from("none")
+    } else {
+        skip_days
+            .iter()
+            .map(|day| day.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+async fn skip_days(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("days");
+    if !parameter.is_empty() {
+        let mut days: Vec<Weekday> = Vec::new();
+        if !parameter.eq_ignore_ascii_case("off") {
+            for day in parameter.split(|c: char| c == ',' || c.is_whitespace()) {
+                if day.is_empty() {
+                    continue;
+                }
+                match day.parse::<Weekday>() {
+                    Ok(day) if !days.contains(&day) => days.push(day),
+                    Ok(_) => {}
+                    Err(_) => {
+                        invocation
+                            .reply(
+                                ctx,
+                                format!("{} isn't a weekday! Use names like mon or saturday.", day),
+                            )
+                            .await?;
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        if days.len() == 7 {
+            invocation
+                .reply(
+                    ctx,
+                    "Skipping every day would stop posting, use pause for that instead!",
+                )
+                .await?;
+            return Ok(());
+        }
+
+        days.sort_by_key(|day| day.num_days_from_monday());
+        let stored = days
+            .iter()
+            .map(|day| day.to_string().to_lowercase())
+            .collect();
+        store.set_skip_days(guild_id.to_string(), stored).await?;
+        // The next posts might be on a day that's skipped now
+        reschedule_guild(guild_id.to_string(), ctx).await?;
+
+        if days.is_empty() {
+            invocation
+                .reply(ctx, "Scheduled posts go out on every day again!")
+                .await?;
+        } else {
+            invocation
+                .reply(
+                    ctx,
+                    format!("Scheduled posts skip {}!", format_skip_days(&days)),
+                )
+                .await?;
+        }
+    } else {
+        let days = store.get_skip_days(guild_id.to_string()).await?;
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Skipped days: {}. Use skip_days <days/off> to change them.",
+                    format_skip_days(&days)
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn double_days(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("days");
+    if !parameter.is_empty() {
+        let mut days: Vec<Weekday> = Vec::new();
+        if !parameter.eq_ignore_ascii_case("off") {
+            for day in parameter.split(|c: char| c == ',' || c.is_whitespace()) {
+                if day.is_empty() {
+                    continue;
+                }
+                match day.parse::<Weekday>() {
+                    Ok(day) if !days.contains(&day) => days.push(day),
+                    Ok(_) => {}
+                    Err(_) => {
+                        invocation
+                            .reply(
+                                ctx,
+                                format!("{} isn't a weekday! Use names like mon or saturday.", day),
+                            )
+                            .await?;
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        days.sort_by_key(|day| day.num_days_from_monday());
+        let stored = days
+            .iter()
+            .map(|day| day.to_string().to_lowercase())
+            .collect();
+        store.set_double_days(guild_id.to_string(), stored).await?;
+
+        if days.is_empty() {
+            invocation
+                .reply(ctx, "Scheduled questions are posted one at a time again!")
+                .await?;
+        } else {
+            invocation.reply(
+                ctx,
+                format!(
+                    "Two scheduled questions are posted side by side on {}, members react to pick one!",
+                    format_skip_days(&days)
+                ),
+            )
+            .await?;
+        }
+    } else {
+        let days = store.get_double_days(guild_id.to_string()).await?;
+        let scores = style_scores(
+            &store
+                .get_double_post_votes(guild_id.to_string(), 100)
+                .await?,
+        );
+        let mut styles: Vec<(String, f64)> = scores.into_iter().collect();
+        styles.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let styles = if styles.is_empty() {
+            String::from("no votes yet")
+        } else {
+            styles
+                .iter()
+                .take(5)
+                .map(|(tag, score)| format!("`{}` {:.0}%", tag, score * 100.0))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        invocation.reply(
+            ctx,
+            format!(
+                "Double days: {}. Use double_days <days/off> to change them.\nStyles members pick most: {}",
+                format_skip_days(&days),
+                styles
+            ),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn next(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let next_post_at = match store.get_post_schedule(guild_id.to_string()).await? {
+        Some((_, Some(next_post_at))) => next_post_at,
+        _ => {
+            invocation
+                .reply(ctx, "Scheduled questions are off!")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    // A question queued for the day of the next post goes out instead of the drawn one
+    let timezone = store.get_guild_timezone(guild_id.to_string()).await?;
+    let post_date = next_post_at.with_timezone(&timezone).date_naive();
+    let queued = store
+        .get_queued_questions(guild_id.to_string(), post_date)
+        .await?;
+    let queued_question = queued.first().and_then(|row| {
+        let date: String = row.get(0);
+        if date == post_date.format("%Y-%m-%d").to_string() {
+            let question: String = row.get(2);
+            Some(question)
+        } else {
+            None
+        }
+    });
+
+    let (question, source) = match queued_question {
+        Some(question) => (question, "queued for the day"),
+        None => {
+            let drawn = match store.get_drawn_question(guild_id.to_string()).await? {
+                Some((question, attribution))
+                    if is_drawn_question_eligible(ctx, guild_id, &question).await? =>
+                {
+                    Some((question, attribution))
+                }
+                _ => {
+                    // Nothing drawn yet, or the drawn one can't be picked anymore.
+                    // Drawing now so the preview is what gets posted
+                    let drawn =
+                        get_random_question_for_guild(guild_id.to_string(), None, ctx).await?;
+                    store
+                        .set_drawn_question(guild_id.to_string(), drawn.clone())
+                        .await?;
+                    drawn
+                }
+            };
+            match drawn {
+                Some((question, _)) => (question, "random"),
+                None => (
+                    store.get_fallback_question(guild_id.to_string()).await?,
+                    "fallback, there are no questions to pick from",
+                ),
+            }
+        }
+    };
+
+    let paused = if store.get_paused(guild_id.to_string()).await? {
+        "\nScheduled posting is paused, it won't be posted until you resume."
+    } else {
+        ""
+    };
+
+    // Sent in DMs so the question isn't spoiled for the rest of the server
+    let sent = invocation
+        .author
+        .direct_message(ctx, |m| {
+            m.content(format!(
+                "Next scheduled question <t:{}:R> ({}):\n{}{}",
+                next_post_at.timestamp(),
+                source,
+                question,
+                paused
+            ))
+        })
+        .await;
+
+    match sent {
+        Ok(_) => {
+            invocation
+                .reply(ctx, "Sent you the next question in DMs!")
+                .await?;
+        }
+        Err(_) => {
+            invocation
+                .reply(ctx, "Couldn't send you a DM, open your DMs and try again!")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn retire(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    if invocation.args.get("id").is_some() {
+        match invocation.args.id("id") {
+            Some(question_id) => {
+                match store(ctx)
+                    .await
+                    .set_custom_question_in_use(guild_id.to_string(), question_id, false)
+                    .await
+                {
+                    Ok(0) => {
+                        invocation.reply(ctx, "Question not found!").await?;
+                    }
+                    Ok(_) => {
+                        invocation
+                            .reply(ctx, "Question retired! Use unretire to bring it back.")
+                            .await?;
+                    }
+                    Err(e) => {
+                        println!("{}", e);
+                        invocation.reply(ctx, "Something went wrong!").await?;
+                    }
+                }
+            }
+            None => {
+                invocation.reply(ctx, "Please enter a valid ID!").await?;
+            }
+        }
+    } else {
+        invocation.reply(ctx, "Usage: retire <id>").await?;
+    }
+
+    Ok(())
+}
+
+/// Parses tags given to a command, separated by spaces or commas.
+/// Tags are lowercase letters, numbers, - and _, a leading # is dropped.
+fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    for tag in text.split(|c: char| c.is_whitespace() || c == ',') {
+        let tag = tag.trim_start_matches('#').to_lowercase();
+        let valid = !tag.is_empty()
+            && tag.chars().count() <= 20
+            && tag
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+        if valid && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Splits the tags of the tag commands into the ones to add and the ones to remove, marked with a leading -
+fn parse_tag_changes(text: &str) -> (Vec<String>, Vec<String>) {
+    let (remove, add): (Vec<&str>, Vec<&str>) = text
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|tag| !tag.is_empty())
+        .partition(|tag| tag.starts_with('-'));
+    let remove: Vec<&str> = remove
+        .iter()
+        .map(|tag| tag.trim_start_matches('-'))
+        .collect();
+    (parse_tags(&add.join(" ")), parse_tags(&remove.join(" ")))
+}
+
+/// Formats the tags of a question for a reply
+fn format_tags(tags: &[String]) -> String {
+    if tags.is_empty() {
+        String::from("no tags")
+    } else {
+        format!("`{}`", tags.join("` `"))
+    }
+}
+
+async fn tag(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+    let usage = "Usage: tag <id> <tags>, a tag starting with - is removed";
+
+    let question_id = match invocation.args.id("id") {
+        Some(question_id) => question_id,
+        None => {
+            invocation.reply(ctx, usage).await?;
+            return Ok(());
+        }
+    };
+    let (add, remove) = parse_tag_changes(invocation.args.text("tags"));
+    if add.is_empty() && remove.is_empty() {
+        invocation.reply(ctx, usage).await?;
+        return Ok(());
+    }
+
+    match store(ctx)
+        .await
+        .update_custom_question_tags(guild_id.to_string(), question_id, add, remove)
+        .await
+    {
+        Ok(Some(tags)) => {
+            invocation
+                .reply(
+                    ctx,
+                    format!("Question {} now has {}", question_id, format_tags(&tags)),
+                )
+                .await?;
+        }
+        Ok(None) => {
+            invocation.reply(ctx, "Question not found!").await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the options of mature and mature_default, an id and on or off.
+/// Returns None if either is missing.
+fn mature_parameters(args: &CommandArgs) -> Option<(i32, bool)> {
+    let mature = match args.text("setting") {
+        setting if setting.eq_ignore_ascii_case("on") => true,
+        setting if setting.eq_ignore_ascii_case("off") => false,
+        _ => return None,
+    };
+    Some((args.id("id")?, mature))
+}
+
+async fn mature(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    let (question_id, mature) = match mature_parameters(&invocation.args) {
+        Some(parsed) => parsed,
+        None => {
+            invocation.reply(ctx, "Usage: mature <id> <on/off>").await?;
+            return Ok(());
+        }
+    };
+
+    match store(ctx)
+        .await
+        .set_custom_question_mature(guild_id.to_string(), question_id, mature)
+        .await
+    {
+        Ok(0) => {
+            invocation.reply(ctx, "Question does not exist!").await?;
+        }
+        Ok(_) if mature => {
+            invocation.reply(ctx, "Question flagged as mature!").await?;
+        }
+        Ok(_) => {
+            invocation
+                .reply(ctx, "Question no longer flagged as mature!")
+                .await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn family_friendly(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store
+                .set_family_friendly(guild_id.to_string(), true)
+                .await?;
+            invocation
+                .reply(ctx, "Mature questions won't be picked anymore!")
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store
+                .set_family_friendly(guild_id.to_string(), false)
+                .await?;
+            invocation
+                .reply(ctx, "Mature questions can be picked again!")
+                .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_family_friendly(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Family friendly mode is {}. Use family_friendly <on/off> to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn question_filter(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("tags");
+    if !parameter.is_empty() {
+        let tags = if parameter.eq_ignore_ascii_case("off") {
+            None
+        } else {
+            match parse_tags(parameter) {
+                tags if tags.is_empty() => {
+                    invocation.reply(ctx, "Please enter tags or off!").await?;
+                    return Ok(());
+                }
+                tags => Some(tags),
+            }
+        };
+        let reply = match &tags {
+            Some(tags) => format!(
+                "Only questions tagged {} are picked now!",
+                format_tags(tags)
+            ),
+            None => String::from("Questions are picked from every tag again!"),
+        };
+        store
+            .set_question_filter(guild_id.to_string(), tags)
+            .await?;
+        invocation.reply(ctx, reply).await?;
+    } else {
+        let reply = match store.get_question_filter(guild_id.to_string()).await? {
+            Some(tags) => format!(
+                "Only questions tagged {} are picked. Use question_filter off to pick from every question.",
+                format_tags(&tags)
+            ),
+            None => String::from(
+                "Questions are picked from every tag. Use question_filter <tags> to limit them.",
+            ),
+        };
+        invocation.reply(ctx, reply).await?;
+    }
+
+    Ok(())
+}
+
+async fn unretire(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    if invocation.args.get("id").is_some() {
+        match invocation.args.id("id") {
+            Some(question_id) => {
+                match store(ctx)
+                    .await
+                    .set_custom_question_in_use(guild_id.to_string(), question_id, true)
+                    .await
+                {
+                    Ok(0) => {
+                        invocation.reply(ctx, "Question not found!").await?;
+                    }
+                    Ok(_) => {
+                        invocation.reply(ctx, "Question is back in use!").await?;
+                    }
+                    Err(e) => {
+                        println!("{}", e);
+                        invocation.reply(ctx, "Something went wrong!").await?;
+                    }
+                }
+            }
+            None => {
+                invocation.reply(ctx, "Please enter a valid ID!").await?;
+            }
+        }
+    } else {
+        invocation.reply(ctx, "Usage: unretire <id>").await?;
+    }
+
+    Ok(())
+}
+
+// Discord application command option types used by the slash commands
+const OPTION_STRING: u8 = 3;
+const OPTION_INTEGER: u8 = 4;
+const OPTION_USER: u8 = 6;
+const OPTION_CHANNEL: u8 = 7;
+const OPTION_ROLE: u8 = 8;
+const OPTION_ATTACHMENT: u8 = 11;
+
+// Option of a slash command, the given values are joined back into the arguments of the prefix command
+struct SlashOption {
+    name: &'static str,
+    description: &'static str,
+    kind: u8,
+    required: bool,
+    // Shown name and the value given to the command
+    choices: &'static [(&'static str, &'static str)],
+    // Where the suggestions come from while typing an id
+    autocomplete: Option<IdSource>,
+}
+
+// Entries of the guild suggested for id options
+#[derive(Clone, Copy)]
+enum IdSource {
+    Questions,
+    Polls,
+}
+
+// Slash command running the prefix command with the same name
+struct SlashCommand {
+    name: &'static str,
+    description: &'static str,
+    options: &'static [SlashOption],
+    // What the option values are joined with, submit_poll takes its parts on separate lines
+    separator: &'static str,
+    // Commands outside the General group work in DMs too
+    dm: bool,
+}
+
+const fn option(
+    name: &'static str,
+    description: &'static str,
+    kind: u8,
+    required: bool,
+) -> SlashOption {
+    SlashOption {
+        name,
+        description,
+        kind,
+        required,
+        choices: &[],
+        autocomplete: None,
+    }
+}
+
+const fn id_option(
+    name: &'static str,
+    description: &'static str,
+    required: bool,
+    source: IdSource,
+) -> SlashOption {
+    SlashOption {
+        name,
+        description,
+        kind: OPTION_INTEGER,
+        required,
+        choices: &[],
+        autocomplete: Some(source),
+    }
+}
+
+const fn choice_option(
+    name: &'static str,
+    description: &'static str,
+    required: bool,
+    choices: &'static [(&'static str, &'static str)],
+) -> SlashOption {
+    SlashOption {
+        name,
+        description,
+        kind: OPTION_STRING,
+        required,
+        choices,
+        autocomplete: None,
+    }
+}
+
+const fn slash(
+    name: &'static str,
+    description: &'static str,
+    options: &'static [SlashOption],
+) -> SlashCommand {
+    SlashCommand {
+        name,
+        description,
+        options,
+        separator: " ",
+        dm: false,
+    }
+}
+
+const ON_OFF: &[(&str, &str)] = &[("On", "on"), ("Off", "off")];
+
+const SLASH_COMMANDS: &[SlashCommand] = &[
+    slash(
+        "help",
+        "Shows the commands of the bot",
+        &[choice_option(
+            "section",
+            "Section of commands to show",
+            false,
+            &[
+                ("Questions", "questions"),
+                ("Polls", "polls"),
+                ("Config", "config"),
+            ],
+        )],
+    ),
+    slash(
+        "qotd",
+        "Sends a random question of the day!",
+        &[option(
+            "stream",
+            "Stream to post to, the daily stream by default",
+            OPTION_STRING,
+            false,
+        )],
+    ),
+    slash(
+        "custom_qotd",
+        "Sends a question of the day from the custom questions",
+        &[id_option(
+            "id",
+            "Id of the question, random by default",
+            false,
+            IdSource::Questions,
+        )],
+    ),
+    slash(
+        "submit_qotd",
+        "Submits a custom question",
+        &[
+            choice_option(
+                "mature",
+                "Flags the question as mature",
+                false,
+                &[("Mature", "--mature")],
+            ),
+            option(
+                "question",
+                "The question, leave out to type it in a form",
+                OPTION_STRING,
+                false,
+            ),
+        ],
+    ),
+    slash(
+        "delete_question",
+        "Deletes a custom question",
+        &[id_option(
+            "id",
+            "Id of the question, pick from a menu by default",
+            false,
+            IdSource::Questions,
+        )],
+    ),
+    slash(
+        "delete_questions",
+        "Deletes many custom questions at once",
+        &[option(
+            "ids",
+            "Ids of the questions, like 3 5 7 or 10-20",
+            OPTION_STRING,
+            true,
+        )],
+    ),
+    slash(
+        "clear_questions",
+        "Deletes every custom question, after confirming",
+        &[],
+    ),
+    slash(
+        "edit_question",
+        "Fixes the text of a custom question, keeping its id",
+        &[
+            id_option("id", "Id of the question", true, IdSource::Questions),
+            option("question", "The new question", OPTION_STRING, true),
+        ],
+    ),
+    slash(
+        "restore_question",
+        "Brings back a deleted custom question, or lists the deleted questions",
+        &[option("id", "Id of the question", OPTION_INTEGER, false)],
+    ),
+    slash("list_qotd", "Lists all custom questions of the server", &[]),
+    slash(
+        "note",
+        "Adds a private note to a custom question",
+        &[
+            option("id", "Id of the question", OPTION_INTEGER, true),
+            option(
+                "note",
+                "The note, or clear to remove it",
+                OPTION_STRING,
+                true,
+            ),
+        ],
+    ),
+    slash(
+        "tag",
+        "Tags a custom question, tags starting with - are removed",
+        &[
+            option("id", "Id of the question", OPTION_INTEGER, true),
+            option(
+                "tags",
+                "Tags like deep fun, or -deep to remove",
+                OPTION_STRING,
+                true,
+            ),
+        ],
+    ),
+    slash(
+        "mature",
+        "Flags a custom question as mature, or unflags it",
+        &[
+            option("id", "Id of the question", OPTION_INTEGER, true),
+            choice_option("setting", "On or off", true, ON_OFF),
+        ],
+    ),
+    slash(
+        "family_friendly",
+        "Never picks questions flagged as mature",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "question_filter",
+        "Only picks questions with one of the tags, or shows the filter",
+        &[option(
+            "tags",
+            "Tags like deep fun, or off",
+            OPTION_STRING,
+            false,
+        )],
+    ),
+    SlashCommand {
+        name: "draft",
+        description: "Saves a question or poll privately for later, or lists your drafts",
+        options: &[
+            choice_option(
+                "kind",
+                "What to do, lists your drafts by default",
+                false,
+                &[
+                    ("Question", "question"),
+                    ("Poll", "poll"),
+                    ("Delete", "delete"),
+                ],
+            ),
+            option(
+                "text",
+                "The question, the poll question or the id of the draft to delete",
+                OPTION_STRING,
+                false,
+            ),
+            option(
+                "option_a",
+                "The first option of a poll",
+                OPTION_STRING,
+                false,
+            ),
+            option(
+                "option_b",
+                "The second option of a poll",
+                OPTION_STRING,
+                false,
+            ),
+        ],
+        separator: "\n",
+        dm: false,
+    },
+    slash(
+        "publish_draft",
+        "Adds one of your drafts to the custom questions or polls",
+        &[option("id", "Id of the draft", OPTION_INTEGER, true)],
+    ),
+    slash(
+        "trivia",
+        "Grades the answer threads of questions with an answer",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "trivia_answer",
+        "Sets the answer a custom question is graded against in trivia mode",
+        &[
+            id_option("id", "Id of the question", true, IdSource::Questions),
+            option(
+                "answer",
+                "The answer with alternatives separated by |, or clear to remove it",
+                OPTION_STRING,
+                true,
+            ),
+        ],
+    ),
+    slash(
+        "trivia_winner",
+        "Overrides who won the trivia question of this answer thread",
+        &[option(
+            "winner",
+            "The member, or none to take the win back",
+            OPTION_STRING,
+            true,
+        )],
+    ),
+    slash(
+        "block",
+        "Stops a member from submitting questions and polls, or lists the blocked members",
+        &[option("member", "The member to block", OPTION_USER, false)],
+    ),
+    slash(
+        "unblock",
+        "Lets a blocked member submit questions and polls again",
+        &[option("member", "The member to unblock", OPTION_USER, true)],
+    ),
+    slash(
+        "import",
+        "Adds the questions of a CSV or JSON file to the custom questions",
+        &[option(
+            "file",
+            "CSV or JSON file of questions",
+            OPTION_ATTACHMENT,
+            true,
+        )],
+    ),
+    slash(
+        "export",
+        "Sends the custom questions, polls and settings of the server as a JSON file",
+        &[],
+    ),
+    slash(
+        "removal_export",
+        "Sends the server owner an export if the bot is removed from the server",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "restrict",
+        "Only allows bot commands in the channel",
+        &[
+            option("channel", "The command channel", OPTION_CHANNEL, false),
+            choice_option(
+                "setting",
+                "Allows commands everywhere",
+                false,
+                &[("Off", "off")],
+            ),
+        ],
+    ),
+    slash(
+        "harvest",
+        "Imports past messages of a channel as custom questions, picked in batches",
+        &[
+            option("channel", "Channel to go through", OPTION_CHANNEL, true),
+            option(
+                "count",
+                "How many past messages to go through, 100 by default",
+                OPTION_INTEGER,
+                false,
+            ),
+        ],
+    ),
+    slash(
+        "retire",
+        "Stops a custom question from being picked without deleting it",
+        &[option("id", "Id of the question", OPTION_INTEGER, true)],
+    ),
+    slash(
+        "unretire",
+        "Brings a retired custom question back",
+        &[option("id", "Id of the question", OPTION_INTEGER, true)],
+    ),
+    slash(
+        "cooldown",
+        "Sets how many days before a custom question can be picked again",
+        &[option(
+            "days",
+            "Days, 0 disables the cooldown",
+            OPTION_INTEGER,
+            true,
+        )],
+    ),
+    slash(
+        "question_cooldown",
+        "Overrides the cooldown of a single custom question",
+        &[
+            option("id", "Id of the question", OPTION_INTEGER, true),
+            option("days", "Days, or default", OPTION_STRING, true),
+        ],
+    ),
+    slash(
+        "fair_rotation",
+        "Gives every submitter the same chance of having their question picked",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "custom_only",
+        "Picks questions only from the custom questions, leaving out the default ones",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "recommend",
+        "Picks questions like the ones members answered and saved most more often",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "seasonal",
+        "Prefers default questions tagged for the season",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "mix",
+        "Sets how often questions are picked from the default or custom questions",
+        &[
+            option(
+                "default",
+                "Percentage of default questions, or off",
+                OPTION_STRING,
+                false,
+            ),
+            option(
+                "custom",
+                "Percentage of custom questions",
+                OPTION_INTEGER,
+                false,
+            ),
+        ],
+    ),
+    slash(
+        "strictness",
+        "Sets how strictly submissions are checked",
+        &[choice_option(
+            "level",
+            "Strictness",
+            false,
+            &[("Off", "off"), ("Low", "low"), ("High", "high")],
+        )],
+    ),
+    slash("pending", "Lists the questions waiting for approval", &[]),
+    slash(
+        "approve",
+        "Approves questions held for moderation",
+        &[option(
+            "ids",
+            "Ids like 3, 3-9 or 3,5,7",
+            OPTION_STRING,
+            true,
+        )],
+    ),
+    slash(
+        "reject",
+        "Rejects questions held for moderation",
+        &[option(
+            "ids",
+            "Ids like 3, 3-9 or 3,5,7",
+            OPTION_STRING,
+            true,
+        )],
+    ),
+    slash(
+        "approve_all",
+        "Approves every question held for moderation",
+        &[],
+    ),
+    slash(
+        "reject_all",
+        "Rejects every question held for moderation",
+        &[],
+    ),
+    slash(
+        "share_pool",
+        "Shares one custom question pool with partner servers",
+        &[
+            choice_option(
+                "action",
+                "What to do",
+                true,
+                &[("Invite", "invite"), ("Join", "join"), ("Leave", "leave")],
+            ),
+            option("code", "Invite code for joining", OPTION_STRING, false),
+        ],
+    ),
+    slash(
+        "merge_pool",
+        "Merges an exported pool of questions and polls into this server",
+        &[option(
+            "file",
+            "Exported pool file",
+            OPTION_ATTACHMENT,
+            true,
+        )],
+    ),
+    slash(
+        "queue_question",
+        "Reserves a custom question for the scheduled post on a date",
+        &[
+            option("id", "Id of the question, or remove", OPTION_STRING, false),
+            option("date", "Date in YYYY-MM-DD format", OPTION_STRING, false),
+        ],
+    ),
+    slash("poll", "Sends a random poll of the day!", &[]),
+    slash(
+        "custom_poll",
+        "Sends a poll of the day from the custom polls",
+        &[id_option(
+            "id",
+            "Id of the poll, random by default",
+            false,
+            IdSource::Polls,
+        )],
+    ),
+    SlashCommand {
+        name: "submit_poll",
+        description: "Submits a custom poll",
+        options: &[
+            option("question", "The question", OPTION_STRING, true),
+            option("option_a", "The first option", OPTION_STRING, true),
+            option("option_b", "The second option", OPTION_STRING, true),
+        ],
+        separator: "\n",
+        dm: false,
+    },
+    slash("build_poll", "Builds a custom poll step by step", &[]),
+    slash(
+        "delete_poll",
+        "Deletes a custom poll",
+        &[option("id", "Id of the poll", OPTION_INTEGER, true)],
+    ),
+    slash("list_polls", "Lists all custom polls of the server", &[]),
+    slash(
+        "poll_history",
+        "Shows how the results of a custom poll changed every time it was posted",
+        &[option("id", "Id of the poll", OPTION_INTEGER, true)],
+    ),
+    slash(
+        "amend_poll",
+        "Adds a third option to a live poll",
+        &[
+            option("link", "Link to the poll message", OPTION_STRING, true),
+            option("option", "The option to add", OPTION_STRING, true),
+        ],
+    ),
+    slash(
+        "poll_buttons",
+        "Votes on every poll with buttons showing live tallies",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "poll_weight",
+        "Sets how many votes members with a role count as on weighted polls",
+        &[
+            option("role", "The role", OPTION_ROLE, false),
+            option("weight", "Votes from 1 to 10, or off", OPTION_STRING, false),
+        ],
+    ),
+    slash(
+        "weighted_poll",
+        "Weighs the votes on a custom poll by role",
+        &[
+            option("id", "The poll id", OPTION_INTEGER, true),
+            choice_option("setting", "On or off", true, ON_OFF),
+        ],
+    ),
+    slash(
+        "poll_nudge",
+        "Reminds members who haven't voted halfway through anonymous polls",
+        &[choice_option(
+            "mode",
+            "How members are reminded",
+            false,
+            &[("Off", "off"), ("Ping", "ping"), ("DM", "dm")],
+        )],
+    ),
+    slash(
+        "poll_text",
+        "Sets the line posted with polls and the title of the poll embed",
+        &[
+            choice_option(
+                "part",
+                "Which text to set",
+                false,
+                &[("Intro line", "intro"), ("Embed title", "title")],
+            ),
+            option(
+                "text",
+                "The text with {date} and {question}, or default",
+                OPTION_STRING,
+                false,
+            ),
+        ],
+    ),
+    slash(
+        "channel_fallback",
+        "Posts in the channel commands are used in while no channel is set",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash("config", "Shows all the settings of the server", &[]),
+    slash(
+        "diagnose",
+        "Checks the bot has the permissions it needs in every configured channel",
+        &[],
+    ),
+    slash(
+        "set_channel",
+        "Sets the channel used for questions of the day",
+        &[option("channel", "The channel", OPTION_CHANNEL, true)],
+    ),
+    slash(
+        "channel",
+        "Shows the channel used for questions of the day",
+        &[],
+    ),
+    slash(
+        "ping_role",
+        "Sets who is pinged with questions of the day",
+        &[
+            choice_option(
+                "setting",
+                "No ping or @everyone",
+                false,
+                &[("No ping", "0"), ("@everyone", "1")],
+            ),
+            option("role", "Role to ping", OPTION_ROLE, false),
+        ],
+    ),
+    slash(
+        "stream",
+        "Sets up extra posting streams, each with its own ping",
+        &[
+            option("name", "Name of the stream", OPTION_STRING, true),
+            choice_option(
+                "setting",
+                "Setting to change",
+                false,
+                &[
+                    ("Channel", "channel"),
+                    ("Role", "role"),
+                    ("Template", "template"),
+                    ("Remove", "remove"),
+                ],
+            ),
+            option("value", "New value of the setting", OPTION_STRING, false),
+        ],
+    ),
+    slash(
+        "schedule",
+        "Posts questions automatically at a time or on a cron schedule",
+        &[option(
+            "schedule",
+            "HH:MM, a cron schedule, or off",
+            OPTION_STRING,
+            false,
+        )],
+    ),
+    slash(
+        "poll_schedule",
+        "Posts polls automatically at a time or on a cron schedule",
+        &[
+            option(
+                "schedule",
+                "HH:MM, a cron schedule, or off",
+                OPTION_STRING,
+                false,
+            ),
+            choice_option(
+                "source",
+                "Where the polls are picked from",
+                false,
+                &[("Random", "default"), ("Custom", "custom")],
+            ),
+        ],
+    ),
+    slash(
+        "skip_days",
+        "Leaves weekdays out of scheduled posting",
+        &[option(
+            "days",
+            "Weekdays like sat sun, or off",
+            OPTION_STRING,
+            false,
+        )],
+    ),
+    slash(
+        "double_days",
+        "Posts two scheduled questions side by side on weekdays",
+        &[option(
+            "days",
+            "Weekdays like fri sat, or off",
+            OPTION_STRING,
+            false,
+        )],
+    ),
+    slash(
+        "calendar",
+        "Shows what is scheduled for the next 14 days",
+        &[],
+    ),
+    slash("pause", "Pauses scheduled questions and polls", &[]),
+    slash("resume", "Resumes scheduled questions and polls", &[]),
+    slash("next", "Sends you the next scheduled question in DMs", &[]),
+    slash(
+        "set_timezone",
+        "Sets the timezone of the schedule",
+        &[option(
+            "timezone",
+            "Timezone like Europe/Helsinki",
+            OPTION_STRING,
+            false,
+        )],
+    ),
+    slash(
+        "teaser",
+        "Posts a teaser ping before the scheduled question",
+        &[option(
+            "minutes",
+            "Minutes before the question, or off",
+            OPTION_STRING,
+            false,
+        )],
+    ),
+    slash(
+        "fallback",
+        "Sets the text posted when there are no questions to pick from",
+        &[option("text", "The text, or default", OPTION_STRING, false)],
+    ),
+    slash(
+        "answer_window",
+        "Opens an answer thread on questions and locks it after a while",
+        &[option("hours", "Hours, or off", OPTION_STRING, false)],
+    ),
+    slash(
+        "answer_filter",
+        "Sets which answers count in answer threads",
+        &[
+            choice_option(
+                "setting",
+                "Shortest answer or answers per member",
+                false,
+                &[("Min length", "min"), ("Member limit", "limit")],
+            ),
+            option(
+                "value",
+                "Characters, or answers per member or off",
+                OPTION_STRING,
+                false,
+            ),
+        ],
+    ),
+    slash(
+        "set_archive",
+        "Sets a channel where posted questions and polls are archived",
+        &[
+            option("channel", "The archive channel", OPTION_CHANNEL, false),
+            choice_option("setting", "Turns the archive off", false, &[("Off", "off")]),
+        ],
+    ),
+    slash(
+        "backup_channel",
+        "Sets a channel questions and polls are posted to when posting to their channel fails",
+        &[
+            option("channel", "The backup channel", OPTION_CHANNEL, false),
+            choice_option(
+                "setting",
+                "Turns the backup channel off",
+                false,
+                &[("Off", "off")],
+            ),
+        ],
+    ),
+    slash("audit_log", "Shows the latest events of the server", &[]),
+    slash("usage", "Shows what the server used of its quotas", &[]),
+    slash(
+        "checklist",
+        "Posts the setup checklist and pins it, kept updated as settings change",
+        &[
+            option(
+                "channel",
+                "Channel for the checklist, this one by default",
+                OPTION_CHANNEL,
+                false,
+            ),
+            choice_option("off", "Removes the checklist", false, &[("Off", "off")]),
+        ],
+    ),
+    slash(
+        "outbox",
+        "Shows whether the latest scheduled posts went out",
+        &[],
+    ),
+    slash(
+        "modlog",
+        "Sends question events to a channel or webhook, or picks the events",
+        &[option(
+            "target",
+            "Channel, webhook URL, off, or events followed by the events",
+            OPTION_STRING,
+            false,
+        )],
+    ),
+    slash(
+        "admin_contact",
+        "Adds or removes an admin told about problems, or lists the admin contacts",
+        &[
+            choice_option(
+                "action",
+                "Add or remove",
+                false,
+                &[("Add", "add"), ("Remove", "remove")],
+            ),
+            option("member", "The admin", OPTION_USER, false),
+        ],
+    ),
+    slash(
+        "notify_policy",
+        "Sends notifications to every admin contact or to one at a time",
+        &[choice_option(
+            "policy",
+            "All or rotate",
+            false,
+            &[("All", "all"), ("Rotate", "rotate")],
+        )],
+    ),
+    slash(
+        "save_emoji",
+        "Sets the emoji members react with to save a question",
+        &[option("emoji", "The emoji", OPTION_STRING, false)],
+    ),
+    slash(
+        "attribution",
+        "Shows or hides the pack or submitter of a question",
+        &[
+            choice_option(
+                "setting",
+                "On or off for packs, or submitter or mention",
+                false,
+                &[
+                    ("On", "on"),
+                    ("Off", "off"),
+                    ("Submitter", "submitter"),
+                    ("Mention", "mention"),
+                ],
+            ),
+            choice_option("value", "On or off for submitter or mention", false, ON_OFF),
+        ],
+    ),
+    slash(
+        "accessible",
+        "Posts questions and polls as plain text for screen readers",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "image_card",
+        "Posts questions of the day rendered onto an image card",
+        &[
+            choice_option(
+                "setting",
+                "What to change",
+                false,
+                &[
+                    ("On", "on"),
+                    ("Off", "off"),
+                    ("Preview", "preview"),
+                    ("Color", "color"),
+                    ("Template", "template"),
+                ],
+            ),
+            option(
+                "value",
+                "Hex color or default, or plain, stripe or frame",
+                OPTION_STRING,
+                false,
+            ),
+        ],
+    ),
+    slash(
+        "ephemeral_replies",
+        "Shows the replies to configuration slash commands only to you",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "cleanup",
+        "Deletes the replies of the bot after a while",
+        &[
+            option("seconds", "Seconds, or off", OPTION_STRING, false),
+            choice_option(
+                "commands",
+                "Deletes the commands too",
+                false,
+                &[("Delete commands too", "commands")],
+            ),
+        ],
+    ),
+    slash(
+        "everyone_approval",
+        "Requires a second admin to approve @everyone pings",
+        &[choice_option("setting", "On or off", false, ON_OFF)],
+    ),
+    slash(
+        "api_token",
+        "Creates or revokes a token for the HTTP API",
+        &[
+            choice_option(
+                "action",
+                "What to do",
+                false,
+                &[("Create", "create"), ("Revoke", "revoke")],
+            ),
+            choice_option(
+                "scope",
+                "Scope of the token",
+                false,
+                &[("Read", "read"), ("Manage", "manage"), ("All", "all")],
+            ),
+        ],
+    ),
+    SlashCommand {
+        name: "saved",
+        description: "Sends you a DM with all the questions you have saved",
+        options: &[],
+        separator: " ",
+        dm: true,
+    },
+    slash(
+        "notify",
+        "Chooses how you're notified of questions of the day",
+        &[choice_option(
+            "mode",
+            "How you're notified",
+            false,
+            &[
+                ("Ping", "ping"),
+                ("DM", "dm"),
+                ("None", "none"),
+                ("Server default", "default"),
+            ],
+        )],
+    ),
+    slash(
+        "answer",
+        "Answers the trivia question of this answer thread",
+        &[option("answer", "Your answer", OPTION_STRING, true)],
+    ),
+    slash(
+        "trivia_scores",
+        "Shows the members with the most trivia points",
+        &[],
+    ),
+    slash(
+        "suggest",
+        "Suggests a question to the server, posted once an admin approves it",
+        &[option("question", "Your question", OPTION_STRING, true)],
+    ),
+    slash(
+        "history",
+        "Shows the latest questions posted in the server",
+        &[option(
+            "count",
+            "How many questions, up to 25",
+            OPTION_INTEGER,
+            false,
+        )],
+    ),
+    slash(
+        "top_questions",
+        "Shows the questions members rated best, or worst",
+        &[choice_option(
+            "order",
+            "Best or worst rated",
+            false,
+            &[("Best", "best"), ("Worst", "worst")],
+        )],
+    ),
+];
+
+// Owner commands, only registered as slash commands in slash only mode where there are no prefix commands.
+// They're hidden from members without a permission override and the framework only runs them for the owner
+const OWNER_SLASH_COMMANDS: &[SlashCommand] = &[
+    SlashCommand {
+        name: "growth",
+        description: "Servers joined and left, retention and the most active servers",
+        options: &[],
+        separator: " ",
+        dm: true,
+    },
+    SlashCommand {
+        name: "tag_default",
+        description: "Tags a default question, tags starting with - are removed",
+        options: &[
+            option("id", "Id of the default question", OPTION_INTEGER, true),
+            option(
+                "tags",
+                "Tags like deep fun, or -deep to remove",
+                OPTION_STRING,
+                true,
+            ),
+        ],
+        separator: " ",
+        dm: true,
+    },
+    SlashCommand {
+        name: "mature_default",
+        description: "Flags a default question as mature, or unflags it",
+        options: &[
+            option("id", "Id of the default question", OPTION_INTEGER, true),
+            choice_option("setting", "On or off", true, ON_OFF),
+        ],
+        separator: " ",
+        dm: true,
+    },
+    SlashCommand {
+        name: "quota",
+        description: "Sets a quota of a server in place of the configured one",
+        options: &[
+            option("server", "Id of the server", OPTION_STRING, true),
+            choice_option(
+                "quota",
+                "Quota to set",
+                true,
+                &[
+                    ("Commands a day", "commands"),
+                    ("DM fan-outs a month", "dm_fanouts"),
+                    ("Imports a month", "imports"),
+                ],
+            ),
+            option(
+                "amount",
+                "Amount, or default for the configured quota",
+                OPTION_STRING,
+                true,
+            ),
+        ],
+        separator: " ",
+        dm: true,
+    },
+    SlashCommand {
+        name: "seed_starter",
+        description: "Adds the bundled starter pack to the default questions",
+        options: &[],
+        separator: " ",
+        dm: true,
+    },
+    SlashCommand {
+        name: "sources",
+        description: "Lists default questions missing their source or license",
+        options: &[],
+        separator: " ",
+        dm: true,
+    },
+    SlashCommand {
+        name: "source_pack",
+        description: "Sets the source and license of a pack of default questions",
+        options: &[
+            option("pack", "Name of the pack", OPTION_STRING, true),
+            option("source", "Link to the source, or none", OPTION_STRING, true),
+            option(
+                "license",
+                "License of the pack, or none",
+                OPTION_STRING,
+                true,
+            ),
+        ],
+        separator: "\n",
+        dm: true,
+    },
+];
+
+// Discord takes up to 100 slash commands, related commands are registered as subcommands of a group instead.
+// The prefix commands keep their own names, /settings cooldown runs q!cooldown
+struct SlashGroup {
+    name: &'static str,
+    description: &'static str,
+    commands: &'static [&'static str],
+}
+
+const SLASH_GROUPS: &[SlashGroup] = &[
+    SlashGroup {
+        name: "settings",
+        description: "Changes how the bot picks, posts and credits questions",
+        commands: &[
+            "cooldown",
+            "question_cooldown",
+            "fair_rotation",
+            "custom_only",
+            "recommend",
+            "seasonal",
+            "mix",
+            "strictness",
+            "family_friendly",
+            "question_filter",
+            "channel_fallback",
+            "teaser",
+            "fallback",
+            "answer_window",
+            "answer_filter",
+            "admin_contact",
+            "notify_policy",
+            "save_emoji",
+            "attribution",
+            "accessible",
+            "image_card",
+            "ephemeral_replies",
+            "everyone_approval",
+        ],
+    },
+    SlashGroup {
+        name: "poll_admin",
+        description: "Manages custom polls and how polls are posted",
+        commands: &[
+            "delete_poll",
+            "amend_poll",
+            "poll_history",
+            "poll_buttons",
+            "poll_weight",
+            "weighted_poll",
+            "poll_nudge",
+            "poll_text",
+        ],
+    },
+    SlashGroup {
+        name: "owner",
+        description: "Commands for the owner of the bot",
+        commands: &[
+            "growth",
+            "tag_default",
+            "mature_default",
+            "quota",
+            "seed_starter",
+            "sources",
+            "source_pack",
+        ],
+    },
+];
+
+// Most top level slash commands an application can register
+const SLASH_COMMAND_LIMIT: usize = 100;
+
+// Admin commands replying privately when run as slash commands, unless ephemeral replies are turned off
+const EPHEMERAL_COMMANDS: &[&str] = &[
+    "set_channel",
+    "ping_role",
+    "delete_question",
+    "ephemeral_replies",
+    "modlog",
+    "trivia_answer",
+];
+
+// Name of the message context menu command adding the message as a custom question
+const SAVE_AS_QOTD: &str = "Save as QOTD";
+
+/// Builds the registration of every slash command for Discord.
+/// Commands in SLASH_GROUPS are registered as subcommands of their group.
+fn slash_command_registrations() -> Value {
+    let owner_commands = if slash_only() {
+        OWNER_SLASH_COMMANDS
+    } else {
+        &[]
+    };
+    let mut commands: Vec<Value> = Vec::new();
+    let mut groups: Vec<Value> = Vec::new();
+    for command in SLASH_COMMANDS.iter().chain(owner_commands) {
+        let group = SLASH_GROUPS
+            .iter()
+            .find(|group| group.commands.contains(&command.name));
+        let mut registration = json!({
+            "name": command.name,
+            "description": command.description,
+            "type": 1,
+            "options": slash_option_registrations(command),
+        });
+
+        match group {
+            Some(group) => {
+                // The group is registered with its first subcommand, later ones are added to it
+                match groups
+                    .iter_mut()
+                    .find(|registered| registered["name"] == group.name)
+                {
+                    Some(registered) => registered["options"]
+                        .as_array_mut()
+                        .expect("Group options are an array")
+                        .push(registration),
+                    None => {
+                        let mut group_registration = json!({
+                            "name": group.name,
+                            "description": group.description,
+                            "type": 1,
+                            "options": [registration],
+                            "dm_permission": command.dm,
+                        });
+                        if is_owner_command(command.name) {
+                            group_registration["default_member_permissions"] = json!("0");
+                        }
+                        groups.push(group_registration);
+                    }
+                }
+            }
+            None => {
+                registration["dm_permission"] = json!(command.dm);
+                // /qotd can be used when the bot is installed to a user so it works in DMs and group chats too.
+                // integration_types: guild install, user install
+                // contexts: guild, bot DM, private channel
+                if command.name == "qotd" {
+                    registration["integration_types"] = json!([0, 1]);
+                    registration["contexts"] = json!([0, 1, 2]);
+                    registration["dm_permission"] = json!(true);
+                }
+                // Nobody but server admins sees owner commands unless a server allows them
+                if is_owner_command(command.name) {
+                    registration["default_member_permissions"] = json!("0");
+                }
+                commands.push(registration);
+            }
+        }
+    }
+    commands.append(&mut groups);
+
+    // Discord rejects the whole registration past the limit, so a new command has to go into a group
+    assert!(
+        commands.len() <= SLASH_COMMAND_LIMIT,
+        "{} slash commands registered, Discord takes up to {}",
+        commands.len(),
+        SLASH_COMMAND_LIMIT
+    );
+
+    // Right click menu on messages, type 3 is a message command
+    commands.push(json!({
+        "name": SAVE_AS_QOTD,
+        "type": 3,
+        "dm_permission": false,
+    }));
+
+    Value::Array(commands)
+}
+
+/// Builds the registration of the options of a slash command
+fn slash_option_registrations(command: &SlashCommand) -> Vec<Value> {
+    command
+        .options
+        .iter()
+        .map(|option| {
+            let mut registration = json!({
+                "name": option.name,
+                "description": option.description,
+                "type": option.kind,
+                "required": option.required,
+            });
+            if !option.choices.is_empty() {
+                registration["choices"] = option
+                    .choices
+                    .iter()
+                    .map(|(name, value)| json!({"name": name, "value": value}))
+                    .collect();
+            }
+            if option.autocomplete.is_some() {
+                registration["autocomplete"] = json!(true);
+            }
+            registration
+        })
+        .collect()
+}
+
+/// Finds the definition of the slash command that was run and the options given to it.
+/// Subcommands of a group in SLASH_GROUPS come in as the only option of the group, with their options inside it.
+fn slash_command_definition<'a>(
+    name: &str,
+    options: &'a [CommandDataOption],
+) -> Option<(&'static SlashCommand, &'a [CommandDataOption])> {
+    match SLASH_GROUPS.iter().find(|group| group.name == name) {
+        Some(group) => {
+            let subcommand = options
+                .iter()
+                .find(|option| option.kind == CommandOptionType::SubCommand)?;
+            if !group.commands.contains(&subcommand.name.as_str()) {
+                return None;
+            }
+            let definition = command_definition(&subcommand.name)?;
+            Some((definition, &subcommand.options))
+        }
+        None => Some((command_definition(name)?, options)),
+    }
+}
+
+/// Finds the command a message starts with, q! and the command name are case insensitive.
+/// Returns the definition of the command and what was written after its name.
+fn find_prefix_command(content: &str) -> Option<(&'static SlashCommand, &str)> {
+    let written = content
+        .get(..2)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("q!"))
+        .map(|_| &content[2..])?;
+    let name_end = written.find(char::is_whitespace).unwrap_or(written.len());
+    let definition = command_definition(&written[..name_end].to_lowercase())?;
+    Some((definition, &written[name_end..]))
+}
+
+/// Gets the definition of the command with the name
+fn command_definition(name: &str) -> Option<&'static SlashCommand> {
+    SLASH_COMMANDS
+        .iter()
+        .chain(OWNER_SLASH_COMMANDS)
+        .find(|definition| definition.name == name)
+}
+
+fn is_owner_command(name: &str) -> bool {
+    OWNER_SLASH_COMMANDS
+        .iter()
+        .any(|definition| definition.name == name)
+}
+
+/// Whether the option takes any text, instead of a choice, a number or a mention
+fn is_text_option(option: &SlashOption) -> bool {
+    option.kind == OPTION_STRING && option.choices.is_empty()
+}
+
+/// Reads a word written for the option of a prefix command.
+/// Returns None if the option doesn't take the word, choices are matched ignoring case.
+fn prefix_option_value(option: &SlashOption, word: &str) -> Option<ArgValue> {
+    if !option.choices.is_empty() {
+        return option
+            .choices
+            .iter()
+            .find(|(_, value)| value.eq_ignore_ascii_case(word))
+            .map(|(_, value)| ArgValue::Text(value.to_string()));
+    }
+
+    match option.kind {
+        OPTION_STRING => Some(ArgValue::Text(word.to_string())),
+        OPTION_INTEGER => word.parse::<i64>().ok().map(ArgValue::Integer),
+        OPTION_CHANNEL => parse_channel(word).map(|cid| ArgValue::Channel(ChannelId(cid))),
+        OPTION_ROLE => parse_role(word).map(|rid| ArgValue::Role(RoleId(rid))),
+        OPTION_USER => parse_member(word).map(ArgValue::User),
+        _ => None,
+    }
+}
+
+/// Splits what was written after the name of a prefix command into the options of its definition.
+/// Options are written in order. An option that doesn't take the next word is left out if a later option takes it,
+/// so optional options can be skipped, and the word is kept as text if no option takes it.
+/// Text options end at the separator of the command, the last one takes the rest as written
+/// minus the words the options after it take. Attachments of the message go to the attachment option.
+fn parse_prefix_arguments(
+    definition: &SlashCommand,
+    written: &str,
+    attachments: &[Attachment],
+) -> CommandArgs {
+    let mut args = CommandArgs::default();
+    let mut rest = written.trim();
+
+    for (index, option) in definition.options.iter().enumerate() {
+        if option.kind == OPTION_ATTACHMENT {
+            if let Some(attachment) = attachments.first() {
+                args.set(option.name, ArgValue::Attachment(attachment.clone()));
+            }
+            continue;
+        }
+        if rest.is_empty() {
+            continue;
+        }
+        let later: Vec<&SlashOption> = definition.options[index + 1..]
+            .iter()
+            .filter(|later| later.kind != OPTION_ATTACHMENT)
+            .collect();
+
+        if is_text_option(option) {
+            let (value, after) = if later.iter().any(|later| is_text_option(later)) {
+                let split = if definition.separator == " " {
+                    rest.split_once(char::is_whitespace)
+                } else {
+                    rest.split_once(definition.separator)
+                };
+                split.unwrap_or((rest, ""))
+            } else {
+                // Words at the end go to the options after this one if they take them
+                let mut value = rest;
+                for later in later.iter().rev() {
+                    let start = match value.rfind(char::is_whitespace) {
+                        Some(start) => start,
+                        None => break,
+                    };
+                    if prefix_option_value(later, value[start..].trim()).is_none() {
+                        break;
+                    }
+                    value = value[..start].trim_end();
+                }
+                (value, &rest[value.len()..])
+            };
+            args.set(option.name, ArgValue::Text(value.trim().to_string()));
+            rest = after.trim();
+            continue;
+        }
+
+        let (word, after) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        match prefix_option_value(option, word) {
+            Some(value) => args.set(option.name, value),
+            None if later
+                .iter()
+                .any(|later| prefix_option_value(later, word).is_some()) =>
+            {
+                continue
+            }
+            None => args.set(option.name, ArgValue::Text(word.to_string())),
+        }
+        rest = after.trim();
+    }
+
+    args
+}
+
+/// Runs a slash command with the options given to it.
+/// Admin commands in EPHEMERAL_COMMANDS reply privately if the guild has ephemeral replies on.
+async fn dispatch_slash_command(ctx: Context, command: ApplicationCommandInteraction) {
+    let (definition, options) =
+        match slash_command_definition(&command.data.name, &command.data.options) {
+            Some(found) => found,
+            None => return,
+        };
+
+    let ephemeral = match command.guild_id {
+        Some(guild_id) if EPHEMERAL_COMMANDS.contains(&definition.name) => logged(
+            store(&ctx)
+                .await
+                .get_ephemeral_replies(guild_id.to_string())
+                .await,
+        )
+        .unwrap_or(false),
+        _ => false,
+    };
+    let args = CommandArgs::from_options(options);
+    let invocation = Invocation::from_command(definition.name, args, command, ephemeral);
+    run_interaction(ctx, invocation).await;
+}
+
+/// Runs a command from an interaction through the command framework, so it's checked and timed like a prefix command
+async fn run_interaction(ctx: Context, invocation: Invocation) {
+    let framework = {
+        let read = ctx.data.read().await;
+        read.get::<DataFramework>()
+            .expect("Framework error")
+            .clone()
+    };
+    framework.run(ctx, invocation).await;
+}
+
+/// Adds the message right clicked with Save as QOTD to the custom questions with submit_qotd
+async fn save_message_as_question(ctx: Context, command: ApplicationCommandInteraction) {
+    let target = command
+        .data
+        .target_id
+        .and_then(|target| command.data.resolved.messages.get(&target.to_message_id()));
+    let question = match target {
+        Some(target) => target.content.trim().to_string(),
+        None => return,
+    };
+
+    if question.is_empty() {
+        let response = command
+            .create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| {
+                        d.content("That message has no text to save!")
+                            .ephemeral(true)
+                    })
+            })
+            .await;
+        if let Err(e) = response {
+            println!("Responding to {} failed: {}", SAVE_AS_QOTD, e);
+        }
+        return;
+    }
+
+    let mut args = CommandArgs::default();
+    args.set("question", ArgValue::Text(question));
+    let invocation = Invocation::from_command("submit_qotd", args, command, false);
+    run_interaction(ctx, invocation).await;
+}
+
+/// Runs the handler of the command
+async fn run_command(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    match invocation.name {
+        "help" => help(ctx, invocation).await,
+        "set_channel" => set_channel(ctx, invocation).await,
+        "channel" => channel(ctx, invocation).await,
+        "qotd" => qotd(ctx, invocation).await,
+        "custom_qotd" => custom_qotd(ctx, invocation).await,
+        "submit_qotd" => submit_qotd(ctx, invocation).await,
+        "delete_question" => delete_question(ctx, invocation).await,
+        "delete_questions" => delete_questions(ctx, invocation).await,
+        "clear_questions" => clear_questions(ctx, invocation).await,
+        "edit_question" => edit_question(ctx, invocation).await,
+        "list_qotd" => list_qotd(ctx, invocation).await,
+        "ping_role" => ping_role(ctx, invocation).await,
+        "poll" => poll(ctx, invocation).await,
+        "submit_poll" => submit_poll(ctx, invocation).await,
+        "custom_poll" => custom_poll(ctx, invocation).await,
+        "list_polls" => list_polls(ctx, invocation).await,
+        "delete_poll" => delete_poll(ctx, invocation).await,
+        "cooldown" => cooldown(ctx, invocation).await,
+        "question_cooldown" => question_cooldown(ctx, invocation).await,
+        "set_archive" => set_archive(ctx, invocation).await,
+        "save_emoji" => save_emoji(ctx, invocation).await,
+        "everyone_approval" => everyone_approval(ctx, invocation).await,
+        "attribution" => attribution(ctx, invocation).await,
+        "poll_history" => poll_history(ctx, invocation).await,
+        "amend_poll" => amend_poll(ctx, invocation).await,
+        "merge_pool" => merge_pool(ctx, invocation).await,
+        "answer_window" => answer_window(ctx, invocation).await,
+        "answer_filter" => answer_filter(ctx, invocation).await,
+        "fair_rotation" => fair_rotation(ctx, invocation).await,
+        "custom_only" => custom_only(ctx, invocation).await,
+        "mix" => mix(ctx, invocation).await,
+        "seasonal" => seasonal(ctx, invocation).await,
+        "recommend" => recommend(ctx, invocation).await,
+        "fallback" => fallback(ctx, invocation).await,
+        "schedule" => schedule(ctx, invocation).await,
+        "teaser" => teaser(ctx, invocation).await,
+        "share_pool" => share_pool(ctx, invocation).await,
+        "strictness" => strictness(ctx, invocation).await,
+        "note" => note(ctx, invocation).await,
+        "build_poll" => build_poll(ctx, invocation).await,
+        "stream" => stream(ctx, invocation).await,
+        "cleanup" => cleanup(ctx, invocation).await,
+        "set_timezone" => set_timezone(ctx, invocation).await,
+        "accessible" => accessible(ctx, invocation).await,
+        "pause" => pause(ctx, invocation).await,
+        "resume" => resume(ctx, invocation).await,
+        "config" => config(ctx, invocation).await,
+        "poll_nudge" => poll_nudge(ctx, invocation).await,
+        "poll_schedule" => poll_schedule(ctx, invocation).await,
+        "api_token" => api_token(ctx, invocation).await,
+        "queue_question" => queue_question(ctx, invocation).await,
+        "pending" => pending(ctx, invocation).await,
+        "approve" => approve(ctx, invocation).await,
+        "reject" => reject(ctx, invocation).await,
+        "approve_all" => approve_all(ctx, invocation).await,
+        "reject_all" => reject_all(ctx, invocation).await,
+        "skip_days" => skip_days(ctx, invocation).await,
+        "double_days" => double_days(ctx, invocation).await,
+        "next" => next(ctx, invocation).await,
+        "retire" => retire(ctx, invocation).await,
+        "unretire" => unretire(ctx, invocation).await,
+        "calendar" => calendar(ctx, invocation).await,
+        "poll_buttons" => poll_buttons(ctx, invocation).await,
+        "poll_weight" => poll_weight(ctx, invocation).await,
+        "weighted_poll" => weighted_poll(ctx, invocation).await,
+        "ephemeral_replies" => ephemeral_replies(ctx, invocation).await,
+        "diagnose" => diagnose(ctx, invocation).await,
+        "poll_text" => poll_text(ctx, invocation).await,
+        "channel_fallback" => channel_fallback(ctx, invocation).await,
+        "draft" => draft(ctx, invocation).await,
+        "publish_draft" => publish_draft(ctx, invocation).await,
+        "trivia" => trivia(ctx, invocation).await,
+        "trivia_answer" => trivia_answer(ctx, invocation).await,
+        "trivia_winner" => trivia_winner(ctx, invocation).await,
+        "harvest" => harvest(ctx, invocation).await,
+        "block" => block(ctx, invocation).await,
+        "unblock" => unblock(ctx, invocation).await,
+        "restore_question" => restore_question(ctx, invocation).await,
+        "export" => export(ctx, invocation).await,
+        "removal_export" => removal_export(ctx, invocation).await,
+        "restrict" => restrict(ctx, invocation).await,
+        "backup_channel" => backup_channel(ctx, invocation).await,
+        "audit_log" => audit_log(ctx, invocation).await,
+        "outbox" => outbox(ctx, invocation).await,
+        "usage" => usage(ctx, invocation).await,
+        "checklist" => checklist(ctx, invocation).await,
+        "modlog" => modlog(ctx, invocation).await,
+        "import" => import(ctx, invocation).await,
+        "admin_contact" => admin_contact(ctx, invocation).await,
+        "notify_policy" => notify_policy(ctx, invocation).await,
+        "image_card" => image_card(ctx, invocation).await,
+        "tag" => tag(ctx, invocation).await,
+        "mature" => mature(ctx, invocation).await,
+        "family_friendly" => family_friendly(ctx, invocation).await,
+        "question_filter" => question_filter(ctx, invocation).await,
+        "saved" => saved(ctx, invocation).await,
+        "notify" => notify(ctx, invocation).await,
+        "answer" => answer(ctx, invocation).await,
+        "trivia_scores" => trivia_scores(ctx, invocation).await,
+        "history" => history(ctx, invocation).await,
+        "top_questions" => top_questions(ctx, invocation).await,
+        "suggest" => suggest(ctx, invocation).await,
+        "growth" => growth(ctx, invocation).await,
+        "tag_default" => tag_default(ctx, invocation).await,
+        "mature_default" => mature_default(ctx, invocation).await,
+        "seed_starter" => seed_starter(ctx, invocation).await,
+        "quota" => quota(ctx, invocation).await,
+        "sources" => sources(ctx, invocation).await,
+        "source_pack" => source_pack(ctx, invocation).await,
+        _ => Ok(()),
+    }
+}
+
+/// Suggests the questions or polls of the guild matching what has been typed into an id option so far
+async fn suggest_ids(ctx: Context, autocomplete: AutocompleteInteraction) {
+    let guild_id = match autocomplete.guild_id {
+        Some(guild_id) => guild_id,
+        None => return,
+    };
+    let (definition, options) =
+        match slash_command_definition(&autocomplete.data.name, &autocomplete.data.options) {
+            Some(found) => found,
+            None => return,
+        };
+    let focused = match options.iter().find(|option| option.focused) {
+        Some(focused) => focused,
+        None => return,
+    };
+    let source = definition
+        .options
+        .iter()
+        .find(|option| option.name == focused.name)
+        .and_then(|option| option.autocomplete);
+    let source = match source {
+        Some(source) => source,
+        None => return,
+    };
+
+    // Partly typed values come in as text
+    let typed = match &focused.value {
+        Some(Value::String(typed)) => typed.trim().to_string(),
+        Some(Value::Number(typed)) => typed.to_string(),
+        _ => String::new(),
+    };
+
+    let suggestions = match store(&ctx)
+        .await
+        .get_id_suggestions(guild_id.to_string(), source, typed)
+        .await
+    {
+        Ok(suggestions) => suggestions,
+        Err(e) => {
+            println!("Getting id suggestions failed: {}", e);
+            Vec::new()
+        }
+    };
+
+    let response = autocomplete
+        .create_autocomplete_response(&ctx.http, |response| {
+            for (id, preview) in &suggestions {
+                // Choice names can be up to 100 characters
+                let mut name = format!("{} - {}", id, preview);
+                if name.chars().count() > 100 {
+                    name = format!("{}...", name.chars().take(97).collect::<String>());
+                }
+                response.add_int_choice(name, i64::from(*id));
+            }
+            response
+        })
+        .await;
+    if let Err(e) = response {
+        println!("Responding to autocomplete failed: {}", e);
+    }
+}
+
+/// Opens the modal for typing in a question to submit
+async fn show_submission_modal(ctx: Context, command: ApplicationCommandInteraction) {
+    let response = command
+        .create_interaction_response(&ctx.http, |r| {
+            r.kind(InteractionResponseType::Modal)
+                .interaction_response_data(|d| {
+                    d.custom_id("submit_qotd")
+                        .title("Submit a question")
+                        .components(|c| {
+                            c.create_action_row(|row| {
+                                row.create_input_text(|input| {
+                                    input
+                                        .custom_id("question")
+                                        .label("Question")
+                                        .style(InputTextStyle::Paragraph)
+                                        .max_length(1000)
+                                        .required(true)
+                                })
+                            })
+                        })
+                })
+        })
+        .await;
+    if let Err(e) = response {
+        println!("Opening the submission modal failed: {}", e);
+    }
+}
+
+/// Submits the question typed into the submission modal with submit_qotd.
+/// Only the submitter sees the reply, so the question isn't shown in the channel before it's posted.
+async fn submit_from_modal(ctx: Context, submit: ModalSubmitInteraction) {
+    let question = submit
+        .data
+        .components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == "question" => {
+                Some(input.value.trim().to_string())
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    // The question is given as typed, so lines and spacing stay the way they were written
+    let mut args = CommandArgs::default();
+    args.set("question", ArgValue::Text(question));
+    let invocation = Invocation::from_modal("submit_qotd", args, submit, true);
+    run_interaction(ctx, invocation).await;
+}
+
+/// Formats the times something is posted on a day for the calendar, with the count if there are many
+fn format_day_times(times: &[&DateTime<Tz>]) -> String {
+    match times {
+        [] => String::new(),
+        [time] => time.format("%H:%M").to_string(),
+        [first, second] => format!("{}, {}", first.format("%H:%M"), second.format("%H:%M")),
+        [first, rest @ ..] => format!("{} +{} more", first.format("%H:%M"), rest.len()),
+    }
+}
+
+async fn calendar(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let guild = guild_id.to_string();
+
+    let timezone = store.get_guild_timezone(guild.clone()).await?;
+    let skip_days = store.get_skip_days(guild.clone()).await?;
+    let now = Utc::now();
+    let today = now.with_timezone(&timezone).date_naive();
+    let until = now + chrono::Duration::days(14);
+
+    let question_times = match store.get_post_schedule(guild.clone()).await? {
+        Some((schedule, _)) => post_times_between(&schedule, timezone, &skip_days, now, until, 200),
+        None => Vec::new(),
+    };
+    let poll_times = match store.get_poll_schedule(guild.clone()).await? {
+        Some((schedule, _, _)) => {
+            post_times_between(&schedule, timezone, &skip_days, now, until, 200)
+        }
+        None => Vec::new(),
+    };
+    let queued = store.get_queued_questions(guild.clone(), today).await?;
+
+    let mut days = String::new();
+    for offset in 0..14 {
+        let day = today + chrono::Duration::days(offset);
+        let date = day.format("%Y-%m-%d").to_string();
+        let mut entries = Vec::new();
+
+        let questions: Vec<&DateTime<Tz>> = question_times
+            .iter()
+            .filter(|time| time.date_naive() == day)
+            .collect();
+        if !questions.is_empty() {
+            entries.push(format!("❓ {}", format_day_times(&questions)));
+        }
+        if let Some(row) = queued.iter().find(|row| row.get::<_, String>(0) == date) {
+            let question_id: i32 = row.get(1);
+            entries.push(format!("📌 queued #{}", question_id));
+        }
+        let polls: Vec<&DateTime<Tz>> = poll_times
+            .iter()
+            .filter(|time| time.date_naive() == day)
+            .collect();
+        if !polls.is_empty() {
+            entries.push(format!("📊 {}", format_day_times(&polls)));
+        }
+        if skip_days.contains(&day.weekday()) {
+            entries.push(String::from("⏭️ skipped"));
+        }
+        if entries.is_empty() {
+            entries.push(String::from("-"));
+        }
+
+        days = format!(
+            "{}**{}** {} - {}\n",
+            days,
+            day.format("%a"),
+            day.format("%d.%m."),
+            entries.join(" · ")
+        );
+    }
+
+    if store.get_paused(guild).await? {
+        days = format!(
+            "**Scheduled posting is paused, nothing is posted until resume.**\n\n{}",
+            days
+        );
+    }
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
+                .embed(|embed| {
+                    embed
+                        .title("Next 14 days")
+                        .description(days)
+                        .footer(|footer| {
+                            footer.text(format!(
+                                "Times in {}. ❓ questions, 📌 queued questions, 📊 polls",
+                                timezone
+                            ))
+                        })
+                        .color(Color::DARK_GREEN)
+                })
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Formats the rows of get_poll_role_weights for showing, like <@&role> 2x
+fn format_role_weights(rows: &[Row]) -> String {
+    if rows.is_empty() {
+        return String::from("none");
+    }
+
+    rows.iter()
+        .map(|row| format!("<@&{}> {}x", row.get::<_, String>(0), row.get::<_, i32>(1)))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+async fn poll_weight(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let usage = "Please use poll_weight <role> <weight from 1 to 10/off>!";
+
+    if !invocation.args.is_empty() {
+        let role_id = match invocation.args.role("role") {
+            Some(role_id) => role_id,
+            None => {
+                invocation.reply(ctx, usage).await?;
+                return Ok(());
+            }
+        };
+        let weight = invocation.args.text("weight");
+        let weight = if weight.eq_ignore_ascii_case("off") {
+            None
+        } else {
+            match weight.parse::<i32>() {
+                Ok(weight) if (1..=10).contains(&weight) => Some(weight),
+                _ => {
+                    invocation.reply(ctx, usage).await?;
+                    return Ok(());
+                }
+            }
+        };
+
+        store
+            .set_poll_role_weight(guild_id.to_string(), role_id.to_string(), weight)
+            .await?;
+        let reply = match weight {
+            Some(weight) => format!(
+                "Votes from {} now count {} times on weighted polls!",
+                role_id.mention(),
+                weight
+            ),
+            None => format!("Votes from {} count once again!", role_id.mention()),
+        };
+        invocation.reply_without_pings(ctx, reply).await?;
+    } else {
+        let weights = store.get_poll_role_weights(guild_id.to_string()).await?;
+        invocation
+            .reply_without_pings(
+                ctx,
+                format!(
+                    "Poll role weights: {}. Use poll_weight <role> <weight/off> to change them.",
+                    format_role_weights(&weights)
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn weighted_poll(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let usage = "Please use weighted_poll <id> <on/off>!";
+
+    let weighted = match invocation.args.text("setting") {
+        setting if setting.eq_ignore_ascii_case("on") => true,
+        setting if setting.eq_ignore_ascii_case("off") => false,
+        _ => {
+            invocation.reply(ctx, usage).await?;
+            return Ok(());
+        }
+    };
+    let poll_id = match invocation.args.id("id") {
+        Some(poll_id) => poll_id,
+        None => {
+            invocation.reply(ctx, usage).await?;
+            return Ok(());
+        }
+    };
+
+    match store
+        .set_custom_poll_weighted(guild_id.to_string(), poll_id, weighted)
+        .await
+    {
+        Ok(0) => {
+            invocation.reply(ctx, "Poll does not exist!").await?;
+        }
+        Ok(_) if weighted => {
+            invocation
+                .reply(
+                    ctx,
+                    "Votes on the poll are now weighted by role, it's voted on with buttons!",
+                )
+                .await?;
+        }
+        Ok(_) => {
+            invocation
+                .reply(ctx, "Every vote on the poll counts once again!")
+                .await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn poll_buttons(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store.set_poll_buttons(guild_id.to_string(), true).await?;
+            invocation
+                .reply(ctx, "Polls are now voted on with buttons!")
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store.set_poll_buttons(guild_id.to_string(), false).await?;
+            invocation.reply(
+                ctx,
+                "Polls are now voted on with reactions, anonymous custom polls still use buttons!",
+            )
+            .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_poll_buttons(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Button voting is {}. Use poll_buttons <on/off> to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn notify(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = match invocation.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            invocation
+                .reply(
+                    ctx,
+                    "Notifications are set per server, use this in a server!",
+                )
+                .await?;
+            return Ok(());
+        }
+    };
+    let user_id = invocation.author.id.to_string();
+
+    let parameter = invocation.args.text("mode").to_lowercase();
+
+    if parameter.is_empty() {
+        let current = match store
+            .get_notify_preference(guild_id.to_string(), user_id)
+            .await?
+        {
+            Some(mode) => format!("You're notified of questions of the day by {}.", mode),
+            None => String::from("You're only notified by the server's ping role."),
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "{} Use notify <ping/dm/none/default> to change it.",
+                    current
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    let (mode, reply) = match parameter.as_str() {
+        "ping" => (
+            Some(parameter.clone()),
+            "You'll be mentioned for every question of the day, unless the server's ping role already pings you!",
+        ),
+        "dm" => (
+            Some(parameter.clone()),
+            "You'll get questions of the day in your DMs!",
+        ),
+        "none" => (
+            Some(parameter.clone()),
+            "The bot won't mention or DM you for questions of the day anymore! The server's ping role still reaches you while you have the role.",
+        ),
+        "default" => (
+            None,
+            "You're only notified by the server's ping role again!",
+        ),
+        _ => {
+            invocation
+                .reply(ctx, "Usage: notify <ping/dm/none/default>")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    // Asking for DMs again gives members unsubscribed for closed DMs another chance
+    if parameter == "dm" {
+        store.clear_dm_failures(user_id.clone()).await?;
+    }
+
+    match store
+        .set_notify_preference(guild_id.to_string(), user_id, mode)
+        .await
+    {
+        Ok(_) => {
+            invocation.reply(ctx, reply).await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+// Starter pack of default questions bundled into the bot, one question per line
+const STARTER_PACK: &str = include_str!("../starter_pack.txt");
+// Where the starter pack comes from, its license is set with source_pack by whoever runs the bot
+const STARTER_PACK_SOURCE: &str = "https://github.com/Juusohel/Easy-QOTD";
+
+async fn seed_starter(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let questions: Vec<String> = STARTER_PACK
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect();
+    let total = questions.len();
+
+    match store(ctx)
+        .await
+        .add_default_questions(
+            questions,
+            String::from("Starter pack"),
+            String::from("Easy-QOTD"),
+            QuestionSource {
+                url: Some(String::from(STARTER_PACK_SOURCE)),
+                license: None,
+            },
+        )
+        .await
+    {
+        Ok(added) => {
+            invocation.reply(
+                ctx,
+                format!(
+                    "Added {} of the {} starter questions to the default questions, the rest were there already.",
+                    added, total
+                ),
+            )
+            .await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+// Packs shown by sources, the biggest ones first
+const SOURCES_SHOWN: usize = 20;
+
+async fn sources(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let packs = store(ctx).await.get_unattributed_packs().await?;
+    if packs.is_empty() {
+        invocation
+            .reply(ctx, "Every default question has a source and a license!")
+            .await?;
+        return Ok(());
+    }
+
+    let mut report = String::new();
+    for pack in packs.iter().take(SOURCES_SHOWN) {
+        report.push_str(&format!(
+            "**{}** - {} questions from id {}, {} without a source and {} without a license\n",
+            pack.pack_name.as_deref().unwrap_or("No pack"),
+            pack.questions,
+            pack.first_id,
+            pack.missing_source,
+            pack.missing_license
+        ));
+    }
+    if packs.len() > SOURCES_SHOWN {
+        report.push_str(&format!(
+            "...and {} more packs\n",
+            packs.len() - SOURCES_SHOWN
+        ));
+    }
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.embed(|embed| {
+                embed
+                    .title("Default questions missing attribution")
+                    .description(report)
+                    .footer(|footer| footer.text("Credit a pack with source_pack"))
+                    .color(Color::FABLED_PINK)
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn source_pack(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let usage = "Usage: source_pack <pack name>\n<source url or none>\n<license or none>";
+
+    let pack_name = invocation.args.text("pack");
+    let url = invocation.args.text("source");
+    let license = invocation.args.text("license");
+    if pack_name.is_empty() {
+        invocation.reply(ctx, usage).await?;
+        return Ok(());
+    }
+    let optional = |value: &str| {
+        Some(value.to_string())
+            .filter(|value| !value.is_empty() && !value.eq_ignore_ascii_case("none"))
+    };
+    let source = QuestionSource {
+        url: optional(url),
+        license: optional(license),
+    };
+    if let Some(url) = &source.url {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            invocation
+                .reply(ctx, "The source has to be a link, or none!")
+                .await?;
+            return Ok(());
+        }
+    }
+
+    let reply = match store(ctx)
+        .await
+        .set_pack_source(pack_name.to_string(), source)
+        .await?
+    {
+        0 => format!("No default questions are in a pack called {}!", pack_name),
+        updated => format!(
+            "Set the source of the {} questions of {}!",
+            updated, pack_name
+        ),
+    };
+    invocation.reply(ctx, reply).await?;
+
+    Ok(())
+}
+
+async fn quota(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let usage = "Usage: quota <server id> <commands/dm_fanouts/imports> <amount/default>";
+
+    let parameters = [
+        invocation.args.text("server"),
+        invocation.args.text("quota"),
+        invocation.args.text("amount"),
+    ];
+    let (guild_id, quota, amount) = match parameters {
+        [guild_id, quota, amount]
+            if guild_id.parse::<u64>().is_ok()
+                && QUOTA_COLUMNS.iter().any(|(name, _)| *name == quota) =>
+        {
+            (guild_id, quota, amount)
+        }
+        _ => {
+            invocation.reply(ctx, usage).await?;
+            return Ok(());
+        }
+    };
+    let amount = if amount.eq_ignore_ascii_case("default") {
+        None
+    } else {
+        match amount.parse::<i32>() {
+            Ok(amount) if amount >= 0 => Some(amount),
+            _ => {
+                invocation.reply(ctx, usage).await?;
+                return Ok(());
+            }
+        }
+    };
+
+    store(ctx)
+        .await
+        .set_quota(guild_id.to_string(), quota, amount)
+        .await?;
+    let reply = match amount {
+        Some(amount) => format!("The {} quota of {} is now {}!", quota, guild_id, amount),
+        None => format!(
+            "The {} quota of {} is back to the configured one!",
+            quota, guild_id
+        ),
+    };
+    invocation.reply(ctx, reply).await?;
+
+    Ok(())
+}
+
+async fn mature_default(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let (question_id, mature) = match mature_parameters(&invocation.args) {
+        Some(parsed) => parsed,
+        None => {
+            invocation
+                .reply(ctx, "Usage: mature_default <id> <on/off>")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    match store(ctx)
+        .await
+        .set_default_question_mature(question_id, mature)
+        .await
+    {
+        Ok(0) => {
+            invocation.reply(ctx, "Question not found!").await?;
+        }
+        Ok(_) => {
+            invocation
+                .reply(
+                    ctx,
+                    format!(
+                        "Default question {} is {} flagged as mature",
+                        question_id,
+                        if mature { "now" } else { "no longer" }
+                    ),
+                )
+                .await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn tag_default(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let usage = "Usage: tag_default <id> <tags>, a tag starting with - is removed";
+
+    let question_id = match invocation.args.id("id") {
+        Some(question_id) => question_id,
+        None => {
+            invocation.reply(ctx, usage).await?;
+            return Ok(());
+        }
+    };
+    let (add, remove) = parse_tag_changes(invocation.args.text("tags"));
+    if add.is_empty() && remove.is_empty() {
+        invocation.reply(ctx, usage).await?;
+        return Ok(());
+    }
+
+    match store(ctx)
+        .await
+        .update_default_question_tags(question_id, add, remove)
+        .await
+    {
+        Ok(Some(tags)) => {
+            invocation
+                .reply(
+                    ctx,
+                    format!(
+                        "Default question {} now has {}",
+                        question_id,
+                        format_tags(&tags)
+                    ),
+                )
+                .await?;
+        }
+        Ok(None) => {
+            invocation.reply(ctx, "Question not found!").await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn growth(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let weeks = match store.get_weekly_growth().await {
+        Ok(weeks) => weeks,
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+            return Ok(());
+        }
+    };
+
+    // Bars are scaled to the largest guild count of the weeks shown
+    let largest = weeks
+        .iter()
+        .map(|row| row.get::<_, i64>(3))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let mut chart = String::new();
+    for row in &weeks {
+        let week: String = row.get(0);
+        let joins: i64 = row.get(1);
+        let leaves: i64 = row.get(2);
+        let total: i64 = row.get(3);
+        let bar = "█".repeat((total.max(0) * 12 / largest) as usize);
+        chart = format!(
+            "{}`{}` {} {} (+{} / -{})\n",
+            chart, week, bar, total, joins, leaves
+        );
+    }
+
+    let retention = match store.get_guild_retention().await {
+        Ok((0, _)) => String::from("No servers joined over 30 days ago yet"),
+        Ok((joined, stayed)) => format!(
+            "{}% of {} servers kept the bot for 30 days",
+            stayed * 100 / joined,
+            joined
+        ),
+        Err(e) => {
+            println!("{}", e);
+            String::from("Unavailable")
+        }
+    };
+
+    let mut active = String::new();
+    match store.get_most_active_guilds(10).await {
+        Ok(guilds) => {
+            for (guild, uses) in guilds {
+                let name = guild
+                    .parse::<u64>()
+                    .ok()
+                    .and_then(|id| ctx.cache.guild_field(GuildId(id), |g| g.name.clone()))
+                    .unwrap_or_else(|| guild.clone());
+                active = format!("{}{} - {} commands\n", active, name, uses);
+            }
+        }
+        Err(e) => println!("{}", e),
+    }
+    if active.is_empty() {
+        active = String::from("No commands used in the last 30 days");
+    }
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.embed(|embed| {
+                embed
+                    .title("Growth")
+                    .description(format!(
+                        "Serving {} servers\n\n{}",
+                        ctx.cache.guild_count(),
+                        chart
+                    ))
+                    .field("Retention", retention, false)
+                    .field("Most active in the last 30 days", active, false)
+                    .color(Color::DARK_GREEN)
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn ephemeral_replies(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store
+                .set_ephemeral_replies(guild_id.to_string(), true)
+                .await?;
+            invocation
+                .reply(ctx, "Configuration slash commands now reply only to you!")
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store
+                .set_ephemeral_replies(guild_id.to_string(), false)
+                .await?;
+            invocation
+                .reply(
+                    ctx,
+                    "Configuration slash commands now reply in the channel!",
+                )
+                .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_ephemeral_replies(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Ephemeral replies are {}. Use ephemeral_replies <on/off> to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+// A channel checked by diagnose, with the permissions the bot needs there and what needs them
+struct ChannelCheck {
+    title: String,
+    channel: Option<ChannelId>,
+    fix: &'static str,
+    needs: Vec<(Permissions, &'static str)>,
+}
+
+/// Tells if pinging the ping role needs the Mention Everyone permission.
+/// @everyone and roles that aren't mentionable by everyone do, missing roles are counted in.
+fn ping_needs_mention_everyone(ctx: &Context, guild_id: GuildId, ping_role: &str) -> bool {
+    match ping_role {
+        "0" => false,
+        "1" => true,
+        role => role
+            .parse::<u64>()
+            .ok()
+            .and_then(|role_id| ctx.cache.role(guild_id, RoleId(role_id)))
+            .is_none_or(|role| !role.mentionable),
+    }
+}
+
+/// Lists what posting questions and polls in a channel needs
+fn posting_needs(
+    mentions: bool,
+    threads: bool,
+    reactions: bool,
+) -> Vec<(Permissions, &'static str)> {
+    let mut needs = vec![
+        (Permissions::VIEW_CHANNEL, "seeing the channel"),
+        (Permissions::SEND_MESSAGES, "posting"),
+        (Permissions::EMBED_LINKS, "question and poll embeds"),
+    ];
+    if reactions {
+        needs.push((Permissions::ADD_REACTIONS, "poll votes"));
+    }
+    if mentions {
+        needs.push((Permissions::MENTION_EVERYONE, "pinging the ping role"));
+    }
+    if threads {
+        needs.push((Permissions::CREATE_PUBLIC_THREADS, "opening answer threads"));
+        needs.push((
+            Permissions::SEND_MESSAGES_IN_THREADS,
+            "answer thread summaries",
+        ));
+        needs.push((Permissions::READ_MESSAGE_HISTORY, "counting answers"));
+        needs.push((Permissions::MANAGE_THREADS, "locking answer threads"));
+    }
+    needs
+}
+
+/// Runs the checks of one channel for diagnose, returning the checklist and whether all passed
+fn run_channel_check(ctx: &Context, check: &ChannelCheck) -> (String, bool) {
+    let channel_id = match check.channel {
+        Some(channel_id) => channel_id,
+        None => return (format!("❌ Not set\n**Fix:** {}", check.fix), false),
+    };
+    let channel = match ctx.cache.guild_channel(channel_id) {
+        Some(channel) => channel,
+        None => {
+            return (
+                format!(
+                    "❌ <#{}> was not found, it may have been deleted\n**Fix:** {}",
+                    channel_id, check.fix
+                ),
+                false,
+            )
+        }
+    };
+    let permissions = match channel.permissions_for_user(ctx, ctx.cache.current_user_id()) {
+        Ok(permissions) => permissions,
+        Err(e) => {
+            println!("Checking permissions failed: {}", e);
+            return (
+                format!("❌ Couldn't read the permissions in <#{}>", channel_id),
+                false,
+            );
+        }
+    };
+
+    let mut lines = format!("<#{}>\n", channel_id);
+    let mut passed = true;
+    for (permission, needed_for) in &check.needs {
+        let name = permission.get_permission_names().join(", ");
+        if permissions.contains(*permission) {
+            lines = format!("{}✅ {} - {}\n", lines, name, needed_for);
+        } else {
+            lines = format!("{}❌ {} - {}\n", lines, name, needed_for);
+            passed = false;
+        }
+    }
+    // Webhooks aren't used for posting yet, so missing them is only a heads up
+    if !permissions.contains(Permissions::MANAGE_WEBHOOKS) {
+        lines = format!("{}⚠️ Manage Webhooks - optional for now\n", lines);
+    }
+    if !passed {
+        lines = format!(
+            "{}**Fix:** In the channel settings under Permissions, allow the ❌ permissions for the bots role.",
+            lines
+        );
+    }
+    (lines, passed)
+}
+
+async fn diagnose(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let guild = guild_id.to_string();
+
+    let threads = store.get_answer_window(guild.clone()).await?.is_some()
+        || store.get_trivia_mode(guild.clone()).await?;
+    let reactions = !store.get_poll_buttons(guild.clone()).await?;
+    let ping_role = store.get_ping_role(guild.clone()).await?;
+
+    let mut checks = vec![ChannelCheck {
+        title: String::from("Question channel"),
+        channel: parse_channel(store.get_ping_channel_id(guild.clone()).await?).map(ChannelId),
+        fix: "Use set_channel in the channel for questions of the day.",
+        needs: posting_needs(
+            ping_needs_mention_everyone(ctx, guild_id, &ping_role),
+            threads,
+            reactions,
+        ),
+    }];
+    for row in store.get_list_of_streams(guild.clone()).await? {
+        let name: String = row.get("name");
+        let channel_id: String = row.get("channel_id");
+        let stream_ping_role: String = row.get("ping_role");
+        checks.push(ChannelCheck {
+            title: format!("Stream {}", name),
+            channel: channel_id.parse::<u64>().ok().map(ChannelId),
+            fix: "Use stream <name> channel <channel> to set its channel.",
+            needs: posting_needs(
+                ping_needs_mention_everyone(ctx, guild_id, &stream_ping_role),
+                threads,
+                false,
+            ),
+        });
+    }
+    let archive = store.get_archive_channel_id(guild.clone()).await?;
+    if archive != "0" {
+        checks.push(ChannelCheck {
+            title: String::from("Archive channel"),
+            channel: parse_channel(archive).map(ChannelId),
+            fix: "Use set_archive with a channel the bot can post in, or set_archive off.",
+            needs: posting_needs(false, false, false),
+        });
+    }
+    let backup = store.get_backup_channel_id(guild.clone()).await?;
+    if backup != "0" {
+        checks.push(ChannelCheck {
+            title: String::from("Backup channel"),
+            channel: parse_channel(backup).map(ChannelId),
+            fix: "Use backup_channel with a channel the bot can post in, or backup_channel off.",
+            needs: posting_needs(
+                ping_needs_mention_everyone(ctx, guild_id, &ping_role),
+                threads,
+                reactions,
+            ),
+        });
+    }
+    // Deleting the commands happens in the channels they are used in, checking this one
+    if let (Some(_), true) = store.get_cleanup(guild).await? {
+        checks.push(ChannelCheck {
+            title: String::from("Command cleanup"),
+            channel: Some(invocation.channel_id),
+            fix: "Use cleanup without commands to leave the commands in place.",
+            needs: vec![(Permissions::MANAGE_MESSAGES, "deleting commands")],
+        });
+    }
+
+    let results: Vec<(String, String, bool)> = checks
+        .iter()
+        .map(|check| {
+            let (lines, passed) = run_channel_check(ctx, check);
+            (check.title.clone(), lines, passed)
+        })
+        .collect();
+    let failed = results.iter().filter(|(_, _, passed)| !passed).count();
+    let summary = if failed == 0 {
+        String::from("Everything is set up correctly!")
+    } else {
+        format!("{} of {} checks failed.", failed, results.len())
+    };
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
+                .embed(|embed| {
+                    embed.title("Diagnose").description(summary);
+                    // Embeds fit 25 fields
+                    for (title, lines, passed) in results.iter().take(25) {
+                        let icon = if *passed { "✅" } else { "❌" };
+                        embed.field(format!("{} {}", icon, title), lines, false);
+                    }
+                    embed.color(if failed == 0 {
+                        Color::DARK_GREEN
+                    } else {
+                        Color::RED
+                    })
+                })
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn poll_text(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if !invocation.args.is_empty() {
+        let part = invocation.args.text("part");
+        let text = invocation.args.text("text").trim_matches('"').trim();
+
+        if text.is_empty() {
+            invocation
+                .reply(ctx, "Usage: poll_text <intro/title> <text/default>")
+                .await?;
+            return Ok(());
+        }
+        // Embed titles are cut off by Discord after 256 characters
+        if text.chars().count() > 200 {
+            invocation
+                .reply(ctx, "Please keep the text under 200 characters!")
+                .await?;
+            return Ok(());
+        }
+        let new_text = if text.eq_ignore_ascii_case("default") {
+            None
+        } else {
+            Some(text.to_string())
+        };
+
+        let updated = if part.eq_ignore_ascii_case("intro") {
+            store.set_poll_intro(guild_id.to_string(), new_text).await
+        } else if part.eq_ignore_ascii_case("title") {
+            store.set_poll_title(guild_id.to_string(), new_text).await
+        } else {
+            invocation
+                .reply(ctx, "Please choose intro or title!")
+                .await?;
+            return Ok(());
+        };
+
+        match updated {
+            Ok(_) => {
+                invocation.reply(ctx, "Poll text updated!").await?;
+            }
+            Err(e) => {
+                println!("{}", e);
+                invocation.reply(ctx, "Something went wrong!").await?;
+            }
+        }
+    } else {
+        let (intro, title) = store.get_poll_texts(guild_id.to_string()).await?;
+        invocation.reply(
+            ctx,
+            format!(
+                "Polls are posted with: {}\nThe poll title is: {}\nUse poll_text <intro/title> <text/default> to change them, {{date}} and {{question}} are filled in.",
+                intro, title
+            ),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn channel_fallback(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store
+                .set_channel_fallback(guild_id.to_string(), true)
+                .await?;
+            invocation.reply(
+                ctx,
+                "Without a channel set, questions and polls are now posted where the command is used!",
+            )
+            .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store
+                .set_channel_fallback(guild_id.to_string(), false)
+                .await?;
+            invocation
+                .reply(
+                    ctx,
+                    "Questions and polls are now only posted once a channel is set!",
+                )
+                .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_channel_fallback(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Channel fallback is {}. Use channel_fallback <on/off> to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn draft(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let user_id = invocation.author.id.to_string();
+    let drafts = store
+        .get_drafts(guild_id.to_string(), user_id.clone())
+        .await?;
+
+    let kind = invocation.args.text("kind");
+    let text = invocation.args.text("text");
+
+    match kind.to_lowercase().as_str() {
+        // Listing the drafts of the user, only they see them with ephemeral replies on
+        "" => {
+            if drafts.is_empty() {
+                invocation
+                    .reply(
+                        ctx,
+                        "You have no drafts! Save one with draft <question/poll> <text>.",
+                    )
+                    .await?;
+                return Ok(());
+            }
+
+            let mut pretty_list = "ID - Draft\n".to_string();
+            for row in &drafts {
+                let draft_id: i32 = row.get(0);
+                let kind: String = row.get(1);
+                let content: Vec<String> = row.get(2);
+                pretty_list = format!(
+                    "{}{} - {}: {} \n",
+                    pretty_list,
+                    draft_id,
+                    kind,
+                    content.join(" / ")
+                )
+            }
+            invocation.reply(ctx, pretty_list).await?;
+        }
+        "delete" => match text.parse::<i32>() {
+            Ok(draft_id) => {
+                match store
+                    .delete_draft(guild_id.to_string(), user_id, draft_id)
+                    .await
+                {
+                    Ok(0) => {
+                        invocation.reply(ctx, "Draft not found!").await?;
+                    }
+                    Ok(_) => {
+                        invocation.reply(ctx, "Draft deleted!").await?;
+                    }
+                    Err(e) => {
+                        println!("{}", e);
+                        invocation.reply(ctx, "Something went wrong!").await?;
+                    }
+                }
+            }
+            Err(_) => {
+                invocation.reply(ctx, "Please enter a valid ID!").await?;
+            }
+        },
+        "question" | "poll" => {
+            let kind = kind.to_lowercase();
+            // Polls are drafted like submit_poll, the question and both options
+            let content: Vec<String> = if kind == "poll" {
+                [
+                    text,
+                    invocation.args.text("option_a"),
+                    invocation.args.text("option_b"),
+                ]
+                .iter()
+                .map(|part| part.to_string())
+                .collect()
+            } else {
+                vec![text.to_string()]
+            };
+            if content.iter().any(String::is_empty) {
+                invocation.reply(
+                    ctx,
+                    "Usage: draft question <question>, or draft poll <question> with the two options on the next lines",
+                )
+                .await?;
+                return Ok(());
+            }
+            if drafts.len() >= 25 {
+                invocation
+                    .reply(
+                        ctx,
+                        "Too many drafts saved! Please publish or delete some before adding more!",
+                    )
+                    .await?;
+                return Ok(());
+            }
+
+            match store
+                .add_draft(guild_id.to_string(), user_id, &kind, content)
+                .await
+            {
+                Ok(draft_id) => {
+                    invocation
+                        .reply(
+                            ctx,
+                            format!(
+                                "Draft {} saved! Publish it with publish_draft {}.",
+                                draft_id, draft_id
+                            ),
+                        )
+                        .await?;
+                }
+                Err(e) => {
+                    println!("{}", e);
+                    invocation.reply(ctx, "Something went wrong!").await?;
+                }
+            }
+        }
+        _ => {
+            invocation
+                .reply(ctx, "Usage: draft <question/poll/delete> <text/id>")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn publish_draft(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let user_id = invocation.author.id.to_string();
+
+    let draft_id = if invocation.args.get("id").is_some() {
+        match invocation.args.id("id") {
+            Some(draft_id) => draft_id,
+            None => {
+                invocation.reply(ctx, "Please enter a valid ID!").await?;
+                return Ok(());
+            }
+        }
+    } else {
+        invocation.reply(ctx, "Usage: publish_draft <id>").await?;
+        return Ok(());
+    };
+
+    let (kind, content) = match store
+        .get_draft(guild_id.to_string(), user_id.clone(), draft_id)
+        .await?
+    {
+        Some(draft) => draft,
+        None => {
+            invocation.reply(ctx, "Draft not found!").await?;
+            return Ok(());
+        }
+    };
+
+    // Published drafts go through the same checks as submitted questions and polls
+    let published = if kind == "poll" {
+        if !store.poll_is_under_limit(guild_id.to_string()).await? {
+            invocation
+                .reply(
+                    ctx,
+                    "Too many custom polls saved! Please delete some before adding more!",
+                )
+                .await?;
+            return Ok(());
+        }
+        store
+            .add_custom_poll(guild_id.to_string(), content, None, None, false)
+            .await
+            .map(|_| String::from("Draft published to the custom polls!"))
+    } else {
+        let question = &content[0];
+        if let Some(error) = question_length_error(question) {
+            invocation.reply(ctx, error).await?;
+            return Ok(());
+        }
+        if store
+            .question_is_duplicate(guild_id.to_string(), question)
+            .await?
+        {
+            invocation
+                .reply(ctx, "This question is already saved!")
+                .await?;
+            return Ok(());
+        }
+        if !store.question_is_under_limit(guild_id.to_string()).await? {
+            invocation
+                .reply(
+                    ctx,
+                    "Too many custom questions saved! Please delete some before adding more!",
+                )
+                .await?;
+            return Ok(());
+        }
+        let flag_reason = rate_question(guild_id.to_string(), question, ctx).await?;
+        let added = store
+            .add_custom_question(
+                guild_id.to_string(),
+                question.clone(),
+                Some(user_id.clone()),
+                flag_reason.clone(),
+                false,
+            )
+            .await;
+        if added.as_ref().is_ok_and(|added| *added > 0) {
+            let detail = match &flag_reason {
+                Some(reason) => format!("{}\n\nHeld for moderation ({})", question, reason),
+                None => question.clone(),
+            };
+            let event = QuestionEvent {
+                kind: "submitted",
+                question_ids: Vec::new(),
+                by: Some(invocation.author.id),
+                detail: &detail,
+            };
+            emit_question_event(ctx, guild_id.to_string(), event).await;
+        }
+        added.map(|added| match flag_reason {
+            _ if added == 0 => String::from("This question is already saved!"),
+            Some(reason) => format!("Draft held for moderation ({})", reason),
+            None => String::from("Draft published to the custom questions!"),
+        })
+    };
+
+    match published {
+        Ok(reply) => {
+            store
+                .delete_draft(guild_id.to_string(), user_id, draft_id)
+                .await?;
+            invocation.reply(ctx, reply).await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn trivia(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store.set_trivia_mode(guild_id.to_string(), true).await?;
+            invocation
+                .reply(
+                    ctx,
+                    "Trivia mode on! Questions with an answer are graded in their answer thread.",
+                )
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store.set_trivia_mode(guild_id.to_string(), false).await?;
+            invocation.reply(ctx, "Trivia mode off!").await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_trivia_mode(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Trivia mode is {}. Use trivia <on/off> to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn trivia_answer(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    if !invocation.args.is_empty() {
+        let answer = invocation.args.text("answer");
+
+        let question_id = match invocation.args.id("id") {
+            Some(question_id) => question_id,
+            None => {
+                invocation.reply(ctx, "Please enter a valid ID!").await?;
+                return Ok(());
+            }
+        };
+
+        let answer_to_set = if answer.is_empty() {
+            invocation
+                .reply(ctx, "Please add an answer, or use clear to remove it!")
+                .await?;
+            return Ok(());
+        } else if answer.eq_ignore_ascii_case("clear") {
+            None
+        } else {
+            Some(answer.to_string())
+        };
+        let clearing = answer_to_set.is_none();
+
+        match store(ctx)
+            .await
+            .set_question_answer(guild_id.to_string(), question_id, answer_to_set)
+            .await
+        {
+            Ok(0) => {
+                invocation.reply(ctx, "Question not found!").await?;
+            }
+            Ok(_) => {
+                if clearing {
+                    invocation.reply(ctx, "Answer removed!").await?;
+                } else {
+                    invocation.reply(ctx, "Answer saved! With trivia on, the answer thread of the question is graded against it.",
+                    )
+                    .await?;
+                }
+            }
+            Err(e) => {
+                println!("{}", e);
+                invocation.reply(ctx, "Something went wrong!").await?;
+            }
+        }
+
+        // The answer isn't left in the channel for everyone to read, slash command options aren't shown
+        if !clearing {
+            if let Some(msg) = invocation.message() {
+                if let Err(e) = msg.delete(ctx).await {
+                    println!("Deleting trivia answer failed: {}", e);
+                }
+            }
+        }
+    } else {
+        invocation
+            .reply(ctx, "Usage: trivia_answer <id> <answer/clear>")
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn trivia_winner(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+    let thread_id = invocation.channel_id.to_string();
+
+    let previous = match store.get_trivia_round(thread_id.clone()).await? {
+        Some((_, previous)) => previous,
+        None => {
+            invocation
+                .reply(ctx, "Use this in the answer thread of a trivia question!")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let parameter = invocation.args.text("winner");
+    if parameter.is_empty() {
+        invocation
+            .reply(ctx, "Usage: trivia_winner <member/none>")
+            .await?;
+        return Ok(());
+    }
+    let winner = if parameter.eq_ignore_ascii_case("none") {
+        None
+    } else {
+        match parse_member(parameter) {
+            Some(user_id) => Some(user_id.to_string()),
+            None => {
+                invocation
+                    .reply(ctx, "Please mention a member, or use none!")
+                    .await?;
+                return Ok(());
+            }
+        }
+    };
+
+    if winner == previous {
+        invocation.reply(ctx, "Nothing to change!").await?;
+        return Ok(());
+    }
+
+    store
+        .set_trivia_winner(thread_id, winner.clone(), true)
+        .await?;
+    // The point moves from the winner marked before to the new one
+    if let Some(previous) = previous {
+        store
+            .add_trivia_points(guild_id.to_string(), previous, -1)
+            .await?;
+    }
+    match winner {
+        Some(winner) => {
+            let points = store
+                .add_trivia_points(guild_id.to_string(), winner.clone(), 1)
+                .await?;
+            invocation
+                .reply(
+                    ctx,
+                    format!(
+                        "<@{}> is the winner now, with {}!",
+                        winner,
+                        format_points(points)
+                    ),
+                )
+                .await?;
+        }
+        None => {
+            invocation
+                .reply(ctx, "Win taken back! The next correct answer wins.")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn answer(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guess = invocation.args.text("answer");
+
+    let (guild_id, round) = match invocation.guild_id {
+        Some(guild_id) => (
+            guild_id,
+            store(ctx)
+                .await
+                .get_trivia_round(invocation.channel_id.to_string())
+                .await?,
+        ),
+        None => {
+            invocation
+                .reply(ctx, "Answers go in the answer thread of a trivia question!")
+                .await?;
+            return Ok(());
+        }
+    };
+    match round {
+        None => {
+            invocation
+                .reply(ctx, "Answers go in the answer thread of a trivia question!")
+                .await?;
+        }
+        Some((_, Some(_))) => {
+            invocation
+                .reply(ctx, "This question has been answered already!")
+                .await?;
+        }
+        Some(_) if guess.is_empty() => {
+            invocation.reply(ctx, "Please enter your answer!").await?;
+        }
+        Some(_) => {
+            let graded = grade_trivia_answer(
+                ctx,
+                guild_id,
+                invocation.channel_id,
+                invocation.author.id,
+                guess,
+            )
+            .await?;
+            match graded {
+                Some(announcement) => {
+                    if let Some(msg) = invocation.message() {
+                        msg.react(ctx, Unicode(String::from("✅"))).await?;
+                    }
+                    invocation.reply(ctx, announcement).await?;
+                }
+                None => {
+                    invocation.reply(ctx, "Not quite, try again!").await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn trivia_scores(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = match invocation.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            invocation
+                .reply(
+                    ctx,
+                    "Trivia points are kept per server, use this in a server!",
+                )
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let scores = store(ctx)
+        .await
+        .get_trivia_scores(guild_id.to_string())
+        .await?;
+
+    let mut pretty_list = String::new();
+    for (place, row) in scores.iter().enumerate() {
+        let user_id: String = row.get(0);
+        let points: i32 = row.get(1);
+        pretty_list = format!(
+            "{}{}. <@{}> - {}\n",
+            pretty_list,
+            place + 1,
+            user_id,
+            format_points(points)
+        );
+    }
+    if scores.is_empty() {
+        pretty_list =
+            String::from("No trivia points yet! Answer a trivia question first to get one.");
+    }
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
+                .embed(|embed| {
+                    embed
+                        .title("Trivia scores")
+                        .description(pretty_list)
+                        .color(Color::DARK_GREEN)
+                })
+        })
+        .await?;
+
+    Ok(())
+}
+
+// Candidates shown at once when harvesting a channel
+const HARVEST_BATCH: usize = 10;
+
+// The most past messages harvest goes through
+const HARVEST_LIMIT: u64 = 1000;
+
+/// Fetches up to limit past messages of the channel, newest first, 100 at a time
+async fn fetch_past_messages(
+    ctx: &Context,
+    channel_id: ChannelId,
+    limit: u64,
+) -> Result<Vec<Message>, SerenityError> {
+    let mut messages: Vec<Message> = Vec::new();
+
+    while (messages.len() as u64) < limit {
+        let page_size = (limit - messages.len() as u64).min(100);
+        let before = messages.last().map(|message| message.id);
+        let page = channel_id
+            .messages(ctx, |retriever| {
+                if let Some(before) = before {
+                    retriever.before(before);
+                }
+                retriever.limit(page_size)
+            })
+            .await?;
+
+        let reached_start = (page.len() as u64) < page_size;
+        messages.extend(page);
+        if reached_start {
+            break;
+        }
+    }
+
+    Ok(messages)
+}
+
+/// Builds the picker of a batch of harvested candidates
+fn create_harvest_menu<'a>(
+    components: &'a mut CreateComponents,
+    batch: &[(String, UserId)],
+    picked: &[usize],
+) -> &'a mut CreateComponents {
+    components
+        .create_action_row(|row| {
+            row.create_select_menu(|menu| {
+                menu.custom_id("harvest_pick")
+                    .placeholder("Questions to import")
+                    .min_values(0)
+                    .max_values(batch.len() as u64)
+                    .options(|options| {
+                        for (index, (question, _)) in batch.iter().enumerate() {
+                            // Option labels can be up to 100 characters
+                            let mut label = format!("{}. {}", index + 1, question);
+                            if label.chars().count() > 100 {
+                                label =
+                                    format!("{}...", label.chars().take(97).collect::<String>());
+                            }
+                            options.create_option(|option| {
+                                option
+                                    .label(label)
+                                    .value(index)
+                                    .default_selection(picked.contains(&index))
+                            });
+                        }
+                        options
+                    })
+            })
+        })
+        .create_action_row(|row| {
+            row.create_button(|b| {
+                b.custom_id("harvest_import")
+                    .label(format!("Import picked ({})", picked.len()))
+                    .style(ButtonStyle::Success)
+            })
+            .create_button(|b| {
+                b.custom_id("harvest_all")
+                    .label("Import all")
+                    .style(ButtonStyle::Primary)
+            })
+            .create_button(|b| {
+                b.custom_id("harvest_skip")
+                    .label("Skip batch")
+                    .style(ButtonStyle::Secondary)
+            })
+            .create_button(|b| {
+                b.custom_id("harvest_stop")
+                    .label("Stop")
+                    .style(ButtonStyle::Danger)
+            })
+        })
+}
+
+/// Lists a batch of harvested candidates for the admin to pick from
+fn describe_harvest_batch(batch: &[(String, UserId)], number: usize, batches: usize) -> String {
+    let mut description = format!("Batch {}/{}, pick the ones to import:\n", number, batches);
+    for (index, (question, author)) in batch.iter().enumerate() {
+        // Keeping the whole batch in one message
+        let mut shown = question.clone();
+        if shown.chars().count() > 300 {
+            shown = format!("{}...", shown.chars().take(297).collect::<String>());
+        }
+        description = format!(
+            "{}\n**{}.** {} - <@{}>",
+            description,
+            index + 1,
+            shown,
+            author
+        );
+    }
+    description
+}
+
+async fn harvest(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    // Past messages have no content without the message content intent
+    if slash_only() {
+        invocation.reply(
+            ctx,
+            "Harvesting needs to read messages, which this bot doesn't. Add messages with Apps > Save as QOTD instead!",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let channel_id = match invocation.args.channel("channel") {
+        Some(channel_id) => channel_id,
+        None => {
+            invocation
+                .reply(ctx, "Usage: harvest <channel> [count]")
+                .await?;
+            return Ok(());
+        }
+    };
+    let count = match invocation.args.get("count") {
+        None => 100,
+        Some(_) => match invocation
+            .args
+            .integer("count")
+            .and_then(|count| u64::try_from(count).ok())
+        {
+            Some(count) if (1..=HARVEST_LIMIT).contains(&count) => count,
+            _ => {
+                invocation
+                    .reply(
+                        ctx,
+                        format!("Please enter a count between 1 and {}!", HARVEST_LIMIT),
+                    )
+                    .await?;
+                return Ok(());
+            }
+        },
+    };
+
+    let messages = match fetch_past_messages(ctx, channel_id, count).await {
+        Ok(messages) => messages,
+        Err(e) => {
+            println!("Harvesting {} failed: {}", channel_id, e);
+            invocation.reply(
+                ctx,
+                "Couldn't read that channel! Check the bot can view it and read its message history.",
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    // Messages of members who aren't blocked that fit in a post, each question once and none the server already has
+    let blocked: HashSet<String> = store
+        .get_blocked_users(guild_id.to_string())
+        .await?
+        .into_iter()
+        .collect();
+    let mut seen = HashSet::new();
+    let mut candidates: Vec<(String, UserId)> = Vec::new();
+    for message in messages {
+        let question = message.content.trim();
+        if message.author.bot
+            || blocked.contains(&message.author.id.to_string())
+            || question.is_empty()
+            || question.to_lowercase().starts_with("q!")
+            || question_length_error(question).is_some()
+            || !seen.insert(normalize_question(question))
+            || store
+                .question_is_duplicate(guild_id.to_string(), question)
+                .await?
+        {
+            continue;
+        }
+        candidates.push((question.to_string(), message.author.id));
+    }
+
+    if candidates.is_empty() {
+        invocation
+            .reply(ctx, "No new questions found in that channel!")
+            .await?;
+        return Ok(());
+    }
+
+    let batches: Vec<&[(String, UserId)]> = candidates.chunks(HARVEST_BATCH).collect();
+    let (mut imported, mut held, mut full) = (0, 0, false);
+    let mut picked: Vec<usize> = Vec::new();
+    let mut number = 0;
+
+    let mut prompt = invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
+                .embed(|embed| {
+                    embed
+                        .title("Harvest")
+                        .description(describe_harvest_batch(
+                            batches[number],
+                            number + 1,
+                            batches.len(),
+                        ))
+                        .color(Color::DARK_GREEN)
+                })
+                .components(|c| create_harvest_menu(c, batches[number], &picked))
+        })
+        .await?;
+
+    loop {
+        let interaction = waiting_on_user(
+            prompt
+                .await_component_interaction(ctx)
+                .author_id(invocation.author.id)
+                .timeout(Duration::from_secs(300)),
+        )
+        .await;
+        let interaction = match interaction {
+            Some(interaction) => interaction,
+            None => {
+                prompt
+                    .edit(ctx, |m| {
+                        m.content(format!(
+                            "Harvest timed out. Imported {} questions, {} held for moderation.",
+                            imported, held
+                        ))
+                        .set_embeds(Vec::new())
+                        .components(|c| c)
+                    })
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let batch = batches[number];
+        let mut stopped = false;
+        let accepted: Vec<usize> = match interaction.data.custom_id.as_str() {
+            "harvest_pick" => {
+                picked = interaction
+                    .data
+                    .values
+                    .iter()
+                    .filter_map(|index| index.parse::<usize>().ok())
+                    .collect();
+                interaction
+                    .create_interaction_response(ctx, |r| {
+                        r.kind(InteractionResponseType::UpdateMessage)
+                            .interaction_response_data(|d| {
+                                d.components(|c| create_harvest_menu(c, batch, &picked))
+                            })
+                    })
+                    .await?;
+                continue;
+            }
+            "harvest_import" => picked.clone(),
+            "harvest_all" => (0..batch.len()).collect(),
+            "harvest_skip" => Vec::new(),
+            _ => {
+                stopped = true;
+                Vec::new()
+            }
+        };
+
+        // Imported ones go through the same checks as submitted questions
+        for index in accepted {
+            if !store.question_is_under_limit(guild_id.to_string()).await? {
+                full = true;
+                break;
+            }
+            let (question, author) = &batch[index];
+            let flag_reason = rate_question(guild_id.to_string(), question, ctx).await?;
+            if flag_reason.is_some() {
+                held += 1;
+            } else {
+                imported += 1;
+            }
+            store
+                .add_custom_question(
+                    guild_id.to_string(),
+                    question.clone(),
+                    Some(author.to_string()),
+                    flag_reason,
+                    false,
                 )
                 .await?;
+        }
+
+        number += 1;
+        picked.clear();
+        if stopped || full || number >= batches.len() {
+            let mut summary = format!(
+                "Harvest done! Imported {} questions, {} held for moderation.",
+                imported, held
+            );
+            if full {
+                summary = format!(
+                    "{} The custom question limit was reached, delete some to import more.",
+                    summary
+                );
             }
-        } else {
-            msg.channel_id
-                .send_message(ctx, |message| {
-                    message
-                        .content(format!(
-                            "<@{}> Follow this format when submitting new questions!",
-                            msg.author.id
-                        ))
-                        .embed(|embed| {
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|d| {
+                            d.content(summary).set_embeds(Vec::new()).components(|c| c)
+                        })
+                })
+                .await?;
+            return Ok(());
+        }
+
+        interaction
+            .create_interaction_response(ctx, |r| {
+                r.kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|d| {
+                        d.embed(|embed| {
                             embed
-                                .title("Custom poll format")
-                                .description("submit_poll Question\nOption1\nOption2")
-                                .color(Color::DARK_BLUE)
+                                .title("Harvest")
+                                .description(describe_harvest_batch(
+                                    batches[number],
+                                    number + 1,
+                                    batches.len(),
+                                ))
+                                .color(Color::DARK_GREEN)
                         })
+                        .components(|c| create_harvest_menu(c, batches[number], &picked))
+                    })
+            })
+            .await?;
+    }
+}
+
+/// Parses a member given to a command, a mention or a user id
+fn parse_member(parameter: &str) -> Option<UserId> {
+    parse_username(parameter)
+        .or_else(|| parameter.parse::<u64>().ok())
+        .map(UserId)
+}
+
+async fn block(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if invocation.args.is_empty() {
+        let blocked = store.get_blocked_users(guild_id.to_string()).await?;
+        let reply = if blocked.is_empty() {
+            String::from("Nobody is blocked from submitting.")
+        } else {
+            format!(
+                "Blocked from submitting: {}",
+                blocked
+                    .iter()
+                    .map(|user_id| format!("<@{}>", user_id))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+        invocation.reply(ctx, reply).await?;
+        return Ok(());
+    }
+
+    let user_id = match invocation.args.user("member") {
+        Some(user_id) => user_id,
+        None => {
+            invocation
+                .reply(ctx, "Please mention the member to block!")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    match store
+        .block_user(
+            guild_id.to_string(),
+            user_id.to_string(),
+            invocation.author.id.to_string(),
+        )
+        .await
+    {
+        Ok(0) => {
+            invocation
+                .reply(ctx, format!("<@{}> is blocked already!", user_id))
+                .await?;
+        }
+        Ok(_) => {
+            invocation
+                .reply(
+                    ctx,
+                    format!(
+                        "<@{}> can no longer submit questions or polls in this server.",
+                        user_id
+                    ),
+                )
+                .await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn unblock(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    let user_id = match invocation.args.user("member") {
+        Some(user_id) => user_id,
+        None => {
+            invocation.reply(ctx, "Usage: unblock <member>").await?;
+            return Ok(());
+        }
+    };
+
+    match store(ctx)
+        .await
+        .unblock_user(guild_id.to_string(), user_id.to_string())
+        .await
+    {
+        Ok(0) => {
+            invocation
+                .reply(ctx, format!("<@{}> isn't blocked!", user_id))
+                .await?;
+        }
+        Ok(_) => {
+            invocation
+                .reply(
+                    ctx,
+                    format!("<@{}> can submit questions and polls again!", user_id),
+                )
+                .await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn restore_question(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    if invocation.args.is_empty() {
+        let deleted = store
+            .get_deleted_custom_questions(guild_id.to_string())
+            .await?;
+        if deleted.is_empty() {
+            invocation
+                .reply(ctx, "No deleted questions to restore.")
+                .await?;
+            return Ok(());
+        }
+
+        let mut response = format!(
+            "Deleted questions, restorable for {} days after deletion:\n",
+            RESTORE_DAYS
+        );
+        for row in &deleted {
+            let id: i32 = row.get(0);
+            let question: String = row.get(1);
+            let deleted_on: String = row.get(2);
+            response.push_str(&format!("{} - {} (deleted {})\n", id, question, deleted_on));
+        }
+        for part in split_text(&response, CONTENT_LIMIT) {
+            invocation.channel_id.say(ctx, part).await?;
+        }
+        return Ok(());
+    }
+
+    let question_id = match invocation.args.id("id") {
+        Some(question_id) => question_id,
+        None => {
+            invocation.reply(ctx, "Please enter a valid ID!").await?;
+            return Ok(());
+        }
+    };
+
+    if !store.question_is_under_limit(guild_id.to_string()).await? {
+        invocation
+            .reply(
+                ctx,
+                "Too many custom questions saved! Please delete some before restoring one!",
+            )
+            .await?;
+        return Ok(());
+    }
+
+    match store
+        .restore_custom_question(guild_id.to_string(), question_id)
+        .await
+    {
+        Ok(0) => {
+            invocation
+                .reply(
+                    ctx,
+                    "No deleted question with that ID, or the same question is saved again!",
+                )
+                .await?;
+        }
+        Ok(_) => {
+            invocation.reply(ctx, "Question restored!").await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the JSON export of the guild with its custom questions, polls and settings.
+/// Returns the file with the amounts of questions and polls in it.
+async fn build_guild_export(
+    guild_id: GuildId,
+    ctx: &Context,
+) -> CommandResult<(Vec<u8>, usize, usize)> {
+    let store = store(ctx).await;
+    let guild = guild_id.to_string();
+
+    // Questions and polls are laid out like merge_pool takes them, so the file can be merged into another server
+    let questions: Vec<String> = store
+        .get_list_custom_questions(guild.clone())
+        .await?
+        .iter()
+        .map(|row| row.get(2))
+        .collect();
+    let polls: Vec<Vec<String>> = store
+        .get_list_of_custom_polls(guild.clone())
+        .await?
+        .iter()
+        .map(|row| row.get(2))
+        .collect();
+
+    let (cleanup_seconds, cleanup_commands) = store.get_cleanup(guild.clone()).await?;
+    let (answer_min_length, answer_member_limit) = store.get_answer_filter(guild.clone()).await?;
+    let (poll_intro, poll_title) = store.get_poll_texts(guild.clone()).await?;
+    let post_schedule = store
+        .get_post_schedule(guild.clone())
+        .await?
+        .map(|(schedule, _)| schedule);
+    let (poll_schedule, poll_source) = match store.get_poll_schedule(guild.clone()).await? {
+        Some((schedule, source, _)) => (Some(schedule), Some(source)),
+        None => (None, None),
+    };
+    let settings = json!({
+        "channel_id": store.get_ping_channel_id(guild.clone()).await?,
+        "channel_fallback": store.get_channel_fallback(guild.clone()).await?,
+        "ping_role": store.get_ping_role(guild.clone()).await?,
+        "mention_template": store.get_mention_template(guild.clone()).await?,
+        "post_schedule": post_schedule,
+        "poll_schedule": poll_schedule,
+        "poll_source": poll_source,
+        "timezone": store.get_guild_timezone(guild.clone()).await?.to_string(),
+        "skip_days": store
+            .get_skip_days(guild.clone())
+            .await?
+            .iter()
+            .map(|day| day.to_string())
+            .collect::<Vec<String>>(),
+        "double_days": store
+            .get_double_days(guild.clone())
+            .await?
+            .iter()
+            .map(|day| day.to_string())
+            .collect::<Vec<String>>(),
+        "teaser_minutes": store.get_teaser_minutes(guild.clone()).await?,
+        "answer_window_hours": store.get_answer_window(guild.clone()).await?,
+        "answer_min_length": answer_min_length,
+        "answer_member_limit": answer_member_limit,
+        "trivia_mode": store.get_trivia_mode(guild.clone()).await?,
+        "archive_channel_id": store.get_archive_channel_id(guild.clone()).await?,
+        "backup_channel_id": store.get_backup_channel_id(guild.clone()).await?,
+        // Webhook URLs work like passwords, they aren't exported
+        "modlog_channel_id": store
+            .get_modlog_target(guild.clone())
+            .await?
+            .filter(|target| !target.starts_with("https://")),
+        "modlog_events": store.get_modlog_events(guild.clone()).await?,
+        "admin_contacts": store.get_admin_contacts(guild.clone()).await?,
+        "notify_policy": store.get_notify_policy(guild.clone()).await?,
+        "question_cooldown_days": store.get_question_cooldown(guild.clone()).await?,
+        "question_tags": store.get_question_filter(guild.clone()).await?,
+        "fair_rotation": store.get_fair_rotation(guild.clone()).await?,
+        "custom_only": store.get_custom_only(guild.clone()).await?,
+        "custom_question_share": store.get_question_mix(guild.clone()).await?,
+        "seasonal": store.get_seasonal(guild.clone()).await?,
+        "family_friendly": store.get_family_friendly(guild.clone()).await?,
+        "recommend": store.get_recommend(guild.clone()).await?,
+        "show_attribution": store.get_show_attribution(guild.clone()).await?,
+        "show_submitter": store.get_show_submitter(guild.clone()).await?,
+        "mention_submitter": store.get_mention_submitter(guild.clone()).await?,
+        "save_emoji": store.get_save_emoji(guild.clone()).await?,
+        "everyone_approval": store.get_everyone_approval(guild.clone()).await?,
+        "moderation_strictness": store.get_moderation_strictness(guild.clone()).await?,
+        "accessible_posts": store.get_accessible_posts(guild.clone()).await?,
+        "image_cards": store.get_image_cards(guild.clone()).await?,
+        "poll_buttons": store.get_poll_buttons(guild.clone()).await?,
+        "poll_role_weights": store
+            .get_poll_role_weights(guild.clone())
+            .await?
+            .iter()
+            .map(|row| json!({ "role_id": row.get::<_, String>(0), "weight": row.get::<_, i32>(1) }))
+            .collect::<Vec<_>>(),
+        "poll_nudge": store.get_poll_nudge(guild.clone()).await?,
+        "poll_intro": poll_intro,
+        "poll_title": poll_title,
+        "cleanup_seconds": cleanup_seconds,
+        "cleanup_commands": cleanup_commands,
+        "ephemeral_replies": store.get_ephemeral_replies(guild.clone()).await?,
+        "removal_export": store.get_removal_export(guild.clone()).await?,
+        "command_channel_id": store.get_command_channel_id(guild.clone()).await?,
+        "fallback_question": store.get_fallback_question(guild.clone()).await?,
+        "paused": store.get_paused(guild.clone()).await?,
+    });
+
+    let export = json!({
+        "guild_id": guild,
+        "exported_at": Utc::now().to_rfc3339(),
+        "questions": questions,
+        "polls": polls,
+        "settings": settings,
+    });
+    let data = serde_json::to_vec_pretty(&export)?;
+
+    Ok((data, questions.len(), polls.len()))
+}
+
+async fn export(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+    let (data, questions, polls) = build_guild_export(guild_id, ctx).await?;
+    let filename = format!("qotd_export_{}.json", guild_id);
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!(
+                "<@{}> Exported {} questions and {} polls with the server settings",
+                invocation.author.id, questions, polls
+            ))
+            .add_file((data.as_slice(), filename.as_str()))
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn removal_export(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("setting");
+    if !parameter.is_empty() {
+        if parameter.eq_ignore_ascii_case("on") {
+            store.set_removal_export(guild_id.to_string(), true).await?;
+            invocation
+                .reply(
+                    ctx,
+                    "The owner will be sent an export of the server if the bot is removed!",
+                )
+                .await?;
+        } else if parameter.eq_ignore_ascii_case("off") {
+            store
+                .set_removal_export(guild_id.to_string(), false)
+                .await?;
+            invocation
+                .reply(
+                    ctx,
+                    "The owner won't be sent an export of the server if the bot is removed!",
+                )
+                .await?;
+        } else {
+            invocation.reply(ctx, "Please use on or off!").await?;
+        }
+    } else {
+        let current_setting = if store.get_removal_export(guild_id.to_string()).await? {
+            "on"
+        } else {
+            "off"
+        };
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Removal export is {}. Use removal_export <on/off> to change it.",
+                    current_setting
+                ),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn backup_channel(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    // If message is a valid message
+    if !invocation.args.is_empty() {
+        // Turning the backup channel off
+        if invocation.args.text("setting") == "off" {
+            store
+                .set_backup_channel_id(guild_id.to_string(), None)
+                .await?;
+            invocation.reply(ctx, "Backup channel turned off!").await?;
+            return Ok(());
+        }
+
+        match invocation.args.channel("channel").map(|channel| channel.0) {
+            Some(cid) => {
+                // Checking that the channel is in the server.
+                let guild_channels = ctx
+                    .cache
+                    .guild_channels(guild_id)
+                    .ok_or("Command not being called from a guild?")?;
+                let channel_id = ChannelId(cid);
+
+                if guild_channels.contains_key(&channel_id) {
+                    store
+                        .set_backup_channel_id(guild_id.to_string(), Some(cid.to_string()))
+                        .await?;
+                    invocation.reply(ctx, "Backup channel set!").await?;
+                } else {
+                    invocation
+                        .reply(ctx, "Channel not found on this server!")
+                        .await?;
+                }
+            }
+            None => {
+                invocation.reply(ctx, "Not a valid channel!").await?;
+            }
+        }
+    }
+    // No parameters, showing the current backup channel
+    else {
+        let backup_channel = store.get_backup_channel_id(guild_id.to_string()).await?;
+        match parse_channel(&backup_channel) {
+            Some(_cid) => {
+                invocation
+                    .reply(ctx, format!("Backup channel is set to {}", backup_channel))
+                    .await?;
+            }
+            None => {
+                invocation.reply(ctx, "Backup channel not set!").await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Describes where the question events of the guild go, webhook URLs aren't shown as they work like passwords
+fn format_modlog_target(target: &Option<String>) -> String {
+    match target {
+        Some(target) if target.starts_with("https://") => String::from("a webhook"),
+        Some(target) => format!("<#{}>", target),
+        None => String::from("off"),
+    }
+}
+
+async fn modlog(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("target");
+
+    if parameter.is_empty() {
+        let target = store.get_modlog_target(guild_id.to_string()).await?;
+        let events = match store.get_modlog_events(guild_id.to_string()).await? {
+            Some(events) => events.join(", "),
+            None => String::from("every event"),
+        };
+        invocation.reply(
+            ctx,
+            format!(
+                "Question events go to {} ({}). Use modlog <channel/webhook URL/off> and modlog events <events/all>.",
+                format_modlog_target(&target),
+                events
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if let Some(events) = parameter.strip_prefix("events") {
+        let events: Vec<String> = events
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|event| !event.is_empty())
+            .map(|event| event.to_lowercase())
+            .collect();
+        if events.iter().any(|event| event == "all") {
+            store.set_modlog_events(guild_id.to_string(), None).await?;
+            invocation
+                .reply(ctx, "Every question event goes to the modlog!")
+                .await?;
+        } else if events.is_empty()
+            || events
+                .iter()
+                .any(|event| !MODLOG_EVENTS.contains(&event.as_str()))
+        {
+            invocation
+                .reply(
+                    ctx,
+                    format!("Pick events out of {}, or all!", MODLOG_EVENTS.join(", ")),
+                )
+                .await?;
+        } else {
+            let reply = format!("Only {} events go to the modlog now!", events.join(", "));
+            store
+                .set_modlog_events(guild_id.to_string(), Some(events))
+                .await?;
+            invocation.reply(ctx, reply).await?;
+        }
+        return Ok(());
+    }
+
+    if parameter.eq_ignore_ascii_case("off") {
+        store.set_modlog_target(guild_id.to_string(), None).await?;
+        invocation
+            .reply(ctx, "Question events aren't sent anymore!")
+            .await?;
+        return Ok(());
+    }
+
+    if parameter.starts_with("https://") {
+        // Fetching the webhook checks that it exists before events are sent to it
+        if let Err(e) = Webhook::from_url(ctx, parameter).await {
+            println!("Checking modlog webhook failed: {}", e);
+            invocation
+                .reply(ctx, "That webhook doesn't work, check the URL!")
+                .await?;
+            return Ok(());
+        }
+        store
+            .set_modlog_target(guild_id.to_string(), Some(parameter.to_string()))
+            .await?;
+        // The URL lets anyone post through the webhook, so it isn't left in the channel
+        match invocation.message() {
+            // Slash command options aren't shown in the channel
+            None => {
+                invocation.reply(ctx, "Modlog webhook set!").await?;
+            }
+            Some(msg) if msg.delete(ctx).await.is_ok() => {
+                invocation
+                    .channel_id
+                    .say(
+                        ctx,
+                        format!("<@{}> Modlog webhook set!", invocation.author.id),
+                    )
+                    .await?;
+            }
+            Some(_) => {
+                invocation
+                    .reply(
+                        ctx,
+                        "Modlog webhook set! Delete your message, the URL works like a password.",
+                    )
+                    .await?;
+            }
+        }
+        return Ok(());
+    }
+
+    match parse_channel(parameter) {
+        Some(cid) => {
+            // Checking that the channel is in the server.
+            let guild_channels = ctx
+                .cache
+                .guild_channels(guild_id)
+                .ok_or("Command not being called from a guild?")?;
+
+            if guild_channels.contains_key(&ChannelId(cid)) {
+                store
+                    .set_modlog_target(guild_id.to_string(), Some(cid.to_string()))
+                    .await?;
+                invocation.reply(ctx, "Modlog channel set!").await?;
+            } else {
+                invocation
+                    .reply(ctx, "Channel not found on this server!")
+                    .await?;
+            }
+        }
+        None => {
+            invocation
+                .reply(ctx, "Usage: modlog <channel/webhook URL/off>")
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn audit_log(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    let entries = store(ctx)
+        .await
+        .get_audit_log(guild_id.to_string(), 20)
+        .await?;
+    if entries.is_empty() {
+        invocation
+            .reply(ctx, "Nothing in the audit log yet.")
+            .await?;
+        return Ok(());
+    }
+
+    let mut log = String::new();
+    for row in &entries {
+        let action: String = row.get(0);
+        let detail: String = row.get(1);
+        let logged_at: DateTime<Utc> = row.get(2);
+        log.push_str(&format!(
+            "<t:{}:f> **{}** - {}\n",
+            logged_at.timestamp(),
+            action,
+            detail
+        ));
+    }
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
+                .embed(|embed| {
+                    embed
+                        .title("Audit log")
+                        .description(log)
+                        .color(Color::DARK_BLUE)
+                })
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn outbox(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = invocation.guild_id.unwrap();
+
+    let entries = store(ctx)
+        .await
+        .get_outbox(guild_id.to_string(), 20)
+        .await?;
+    if entries.is_empty() {
+        invocation.reply(ctx, "No scheduled posts yet.").await?;
+        return Ok(());
+    }
+
+    let mut log = String::new();
+    for row in &entries {
+        let kind: String = row.get(0);
+        let due: DateTime<Utc> = row.get(1);
+        let status: String = row.get(2);
+        let attempts: i32 = row.get(3);
+        let channel_id: Option<String> = row.get(4);
+        let message_id: Option<String> = row.get(5);
+        let outcome = match (status.as_str(), channel_id, message_id) {
+            ("sent", Some(channel_id), Some(message_id)) => format!(
+                "sent as https://discord.com/channels/{}/{}/{}",
+                guild_id, channel_id, message_id
+            ),
+            ("failed", ..) => format!("failed after {} tries", attempts),
+            _ if attempts > 0 => format!("waiting, tried {} times", attempts),
+            _ => String::from("waiting"),
+        };
+        log.push_str(&format!(
+            "<t:{}:f> **{}** - {}\n",
+            due.timestamp(),
+            kind,
+            outcome
+        ));
+    }
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
+                .embed(|embed| {
+                    embed
+                        .title("Scheduled posts")
+                        .description(log)
+                        .color(Color::DARK_BLUE)
+                })
+        })
+        .await?;
+
+    Ok(())
+}
+
+// Quotas every guild has unless the owner set its own, by the environment variable they're read from and how they're shown.
+// In the order of QUOTA_COLUMNS, a quota that isn't configured is unlimited
+const QUOTAS: [(&str, &str); 3] = [
+    ("QUOTA_COMMANDS", "Commands today"),
+    ("QUOTA_DM_FANOUTS", "DM fan-outs this month"),
+    ("QUOTA_IMPORTS", "Imports this month"),
+];
+
+/// Formats what was used of a quota, with the share of it if there is one
+fn format_usage(label: &str, used: i64, quota: Option<i64>) -> String {
+    match quota {
+        Some(quota) => format!(
+            "**{}:** {} of {} ({}%)\n",
+            label,
+            used,
+            quota,
+            used * 100 / quota.max(1)
+        ),
+        None => format!("**{}:** {}, unlimited\n", label, used),
+    }
+}
+
+async fn usage(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let used = store.get_guild_usage(guild_id.to_string()).await?;
+    let quotas = store.get_quotas(guild_id.to_string()).await?;
+    let mut report = String::new();
+    for (((variable, label), quota), used) in QUOTAS.iter().zip(quotas).zip(used) {
+        let quota = quota
+            .map(i64::from)
+            .or_else(|| env::var(variable).ok()?.parse().ok());
+        report.push_str(&format_usage(label, used, quota));
+    }
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
+                .embed(|embed| {
+                    embed
+                        .title("Usage")
+                        .description(report)
+                        .color(Color::DARK_BLUE)
                 })
-                .await?;
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn checklist(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    // A checklist posted before is removed when it's turned off or moved
+    if let Some((channel_id, message_id)) =
+        store.get_checklist_message(guild_id.to_string()).await?
+    {
+        if let (Some(channel_id), Ok(message_id)) =
+            (parse_channel(&channel_id), message_id.parse::<u64>())
+        {
+            if let Err(e) = ChannelId(channel_id)
+                .delete_message(ctx, MessageId(message_id))
+                .await
+            {
+                println!("Removing old checklist failed: {}", e);
+            }
         }
+        store
+            .set_checklist_message(guild_id.to_string(), None)
+            .await?;
+    }
+
+    if invocation.args.text("off") == "off" {
+        invocation.reply(ctx, "Checklist removed!").await?;
+        return Ok(());
+    }
+
+    let channel_id = if invocation.args.get("channel").is_none() {
+        invocation.channel_id
     } else {
-        msg.channel_id
-            .send_message(ctx, |message| {
-                message
-                    .content(format!("<@{}> Please use correct format!", msg.author.id))
-                    .embed(|embed| {
-                        embed
-                            .title("Custom poll format")
-                            .description("submit_poll Question\nOption1\nOption2")
-                            .color(Color::DARK_BLUE)
-                    })
-            })
+        let guild_channels = ctx
+            .cache
+            .guild_channels(guild_id)
+            .ok_or("Command not being called from a guild?")?;
+        match invocation.args.channel("channel") {
+            Some(channel_id) if guild_channels.contains_key(&channel_id) => channel_id,
+            _ => {
+                invocation
+                    .reply(ctx, "Channel not found on this server!")
+                    .await?;
+                return Ok(());
+            }
+        }
+    };
+
+    post_checklist(guild_id, channel_id, ctx).await?;
+    if channel_id != invocation.channel_id {
+        invocation
+            .reply(
+                ctx,
+                format!("Checklist posted in {}!", channel_id.mention()),
+            )
             .await?;
     }
 
     Ok(())
 }
 
-#[command]
-async fn custom_poll(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
-    let custom_poll;
-    let channel_id = get_ping_channel_id(guild_id.to_string(), ctx).await;
-    let ping_role = get_ping_role(guild_id.to_string(), ctx).await;
+// Most questions the history command shows at once
+const HISTORY_LIMIT: i64 = 25;
 
-    if msg.content.len() >= 14 {
-        match &msg.content[14..].parse::<i32>() {
-            Ok(id_to_use) => {
-                let id_to_use = *id_to_use;
-                custom_poll = get_specific_custom_poll(guild_id.to_string(), id_to_use, ctx).await;
-            }
+async fn history(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = match invocation.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            invocation
+                .reply(ctx, "History is kept per server, use this in a server!")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let count = if invocation.args.get("count").is_some() {
+        match invocation.args.integer("count") {
+            Some(count) if (1..=HISTORY_LIMIT).contains(&count) => count,
             _ => {
-                msg.reply(ctx, "Not a valid question ID").await?;
+                invocation
+                    .reply(
+                        ctx,
+                        format!("Please use a count from 1 to {}!", HISTORY_LIMIT),
+                    )
+                    .await?;
                 return Ok(());
             }
         }
     } else {
-        custom_poll = get_random_custom_poll(guild_id.to_string(), ctx).await;
-    }
+        10
+    };
 
-    if custom_poll.len() < 3 {
-        msg.reply(ctx, "No custom polls saved!\nAdd some with submit_poll!")
+    let posts = store(ctx)
+        .await
+        .get_post_history(guild_id.to_string(), count)
+        .await?;
+    if posts.is_empty() {
+        invocation
+            .reply(ctx, "No questions have been posted in this server yet!")
             .await?;
         return Ok(());
     }
-    let message_string = format_string_for_pings(ping_role, String::from("Poll of the day!")).await;
-
-    match parse_channel(&channel_id) {
-        Some(channel) => {
-            // Sending message to the channel assigned to the server
-            let channel = ChannelId(channel);
-            let message = channel
-                .send_message(ctx, |message| {
-                    message.content(message_string).embed(|embed| {
-                        embed
-                            .title(&custom_poll[0])
-                            .description(format!(
-                                "🟠 - {}\n🔵 - {}",
-                                &custom_poll[1], custom_poll[2]
-                            ))
-                            .color(Color::DARK_MAGENTA)
-                    })
-                })
-                .await?;
 
-            // Orange circle unicode
-            message.react(ctx, Unicode(String::from("🟠"))).await?;
-            // Blue circle unicode
-            message.react(ctx, Unicode(String::from("🔵"))).await?;
-        }
-        None => {
-            msg.reply(ctx, "Channel not set!").await?;
+    let mut pretty_list = String::new();
+    for row in &posts {
+        let question: String = row.get(0);
+        let channel_id: String = row.get(1);
+        let message_id: String = row.get(2);
+        let posted_at: DateTime<Utc> = row.get(3);
+        let line = format!(
+            "<t:{}:d> [{}](https://discord.com/channels/{}/{}/{})\n",
+            posted_at.timestamp(),
+            question,
+            guild_id,
+            channel_id,
+            message_id
+        );
+        // Embed descriptions fit 4096 characters, long questions cut the list short
+        if pretty_list.len() + line.len() > 4096 {
+            break;
         }
+        pretty_list.push_str(&line);
     }
 
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
+                .embed(|embed| {
+                    embed
+                        .title("Question history")
+                        .description(pretty_list)
+                        .color(Color::FABLED_PINK)
+                })
+        })
+        .await?;
+
     Ok(())
 }
 
-#[command]
-async fn list_polls(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
-    // Getting all questions
-    let polls_list = get_list_of_custom_polls(guild_id.to_string(), ctx).await;
+async fn top_questions(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let guild_id = match invocation.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            invocation
+                .reply(ctx, "Ratings are kept per server, use this in a server!")
+                .await?;
+            return Ok(());
+        }
+    };
 
-    // If there are custom questions saved
-    if !polls_list.is_empty() {
-        // Formatting vector for printing
-        let length = polls_list.len();
+    let worst = invocation.args.text("order") == "worst";
+    let questions = store(ctx)
+        .await
+        .get_rated_questions(guild_id.to_string(), worst, 10)
+        .await?;
+    if questions.is_empty() {
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "No questions have been rated yet, react to a question with {} or {}!",
+                    RATING_EMOJI[0], RATING_EMOJI[1]
+                ),
+            )
+            .await?;
+        return Ok(());
+    }
 
-        let mut pretty_list = "ID - Poll Question\n".to_string();
-        // Putting the questions onto the list
-        for i in 0..length {
-            let poll_id: i32 = polls_list[i].get(0);
-            let poll_full: Vec<String> = polls_list[i].get(2);
-            let poll_question_string = &poll_full[0];
-            pretty_list = format!("{}{} - {} \n", pretty_list, poll_id, poll_question_string)
+    let mut pretty_list = String::new();
+    for row in &questions {
+        let question: String = row.get(0);
+        let up: i64 = row.get(1);
+        let down: i64 = row.get(2);
+        let line = format!(
+            "{} {} {} {} - {}\n",
+            RATING_EMOJI[0], up, RATING_EMOJI[1], down, question
+        );
+        // Embed descriptions fit 4096 characters, long questions cut the list short
+        if pretty_list.len() + line.len() > 4096 {
+            break;
         }
-        // Listing questions in message
-        msg.channel_id
-            .send_message(ctx, |m| {
-                m.content(format!(
-                    "<@{}> Here's a list of all saved custom polls",
-                    msg.author.id
-                ))
+        pretty_list.push_str(&line);
+    }
+
+    invocation
+        .channel_id
+        .send_message(ctx, |m| {
+            m.content(format!("<@{}>", invocation.author.id))
                 .embed(|embed| {
                     embed
-                        .title("Polls")
+                        .title(if worst {
+                            "Worst rated questions"
+                        } else {
+                            "Top rated questions"
+                        })
                         .description(pretty_list)
-                        .color(Color::RED)
+                        .color(Color::FABLED_PINK)
                 })
-            })
-            .await?;
-    } else {
-        msg.reply(ctx, "No custom polls found!").await?;
-    }
+        })
+        .await?;
 
     Ok(())
 }
 
-#[command]
-async fn delete_poll(ctx: &Context, msg: &Message) -> CommandResult {
-    let guild_id = msg.guild_id.unwrap();
-
-    if msg.content.len() >= 14 {
-        // Parsing id from the message
-        match &msg.content[14..].parse::<i32>() {
-            Ok(id_to_delete) => {
-                let id_to_delete = id_to_delete;
-                let test = delete_custom_poll(guild_id.to_string(), *id_to_delete, ctx).await;
-                if test == 1 {
-                    msg.reply(ctx, "Poll deleted!").await?;
-                } else {
-                    msg.reply(ctx, "Poll not found!").await?;
-                }
+/// Splits CSV text into records of fields, quoted fields can hold commas, quotes as "" and line breaks
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let (mut record, mut field) = (Vec::new(), String::new());
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
             }
-            _ => {
-                msg.reply(ctx, "Please enter a valid ID!").await?;
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
             }
+            '\r' if !quoted => {}
+            c => field.push(c),
         }
-    } else {
-        // Getting all polls
-        let polls_list = get_list_of_custom_polls(guild_id.to_string(), ctx).await;
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
 
-        // If there are custom questions saved
-        if !polls_list.is_empty() {
-            // Formatting vector for printing
-            let length = polls_list.len();
+    records
+}
 
-            let mut pretty_list = "ID - Poll\n".to_string();
-            // Putting the polls onto the list
-            for i in 0..length {
-                let poll_id: i32 = polls_list[i].get(0);
-                let poll_full: Vec<String> = polls_list[i].get(2);
-                let poll_question_string = &poll_full[0];
-                pretty_list = format!("{}{} - {} \n", pretty_list, poll_id, poll_question_string)
-            }
-            // Listing questions in message
-            msg.channel_id
-                .send_message(ctx, |m| {
-                    m.content(format!(
-                        "<@{}> Please specify the ID of poll",
-                        msg.author.id
-                    ))
-                    .embed(|embed| {
-                        embed
-                            .title("Questions")
-                            .description(pretty_list)
-                            .color(Color::DARK_BLUE)
-                    })
+/// Reads the questions out of a file attached to import, with the source they were taken from.
+/// JSON can be a list of questions, a list of objects with a question field, or a file from export.
+/// CSV with a question column in its header takes that column, otherwise every line is a question.
+/// Objects and CSV with a header can give a source_url and license for each question.
+/// Returns None if the file isn't valid JSON or text.
+fn parse_import_file(filename: &str, data: &[u8]) -> Option<Vec<(String, QuestionSource)>> {
+    // Blank fields are the same as missing ones
+    let field = |value: Option<&str>| {
+        value
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(String::from)
+    };
+
+    if filename.to_lowercase().ends_with(".json") {
+        let value: Value = serde_json::from_slice(data).ok()?;
+        let entries = match &value {
+            Value::Array(entries) => entries,
+            Value::Object(export) => export.get("questions")?.as_array()?,
+            _ => return None,
+        };
+        // Entries that aren't questions are kept as empty, so they are counted as skipped
+        return Some(
+            entries
+                .iter()
+                .map(|entry| match entry {
+                    Value::String(question) => (question.clone(), QuestionSource::default()),
+                    Value::Object(row) => (
+                        row.get("question")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string(),
+                        QuestionSource {
+                            url: field(row.get("source_url").and_then(Value::as_str)),
+                            license: field(row.get("license").and_then(Value::as_str)),
+                        },
+                    ),
+                    _ => (String::new(), QuestionSource::default()),
                 })
+                .collect(),
+        );
+    }
+
+    let text = std::str::from_utf8(data).ok()?;
+    let text = text.trim_start_matches('\u{feff}');
+    let records = parse_csv(text);
+    let column = |wanted: &str| {
+        records.first().and_then(|header| {
+            header
+                .iter()
+                .position(|name| name.trim().eq_ignore_ascii_case(wanted))
+        })
+    };
+    match column("question") {
+        Some(question) => {
+            let (url, license) = (column("source_url"), column("license"));
+            Some(
+                records
+                    .iter()
+                    .skip(1)
+                    .map(|record| {
+                        let get = |column: Option<usize>| {
+                            field(
+                                column
+                                    .and_then(|column| record.get(column))
+                                    .map(String::as_str),
+                            )
+                        };
+                        (
+                            get(Some(question)).unwrap_or_default(),
+                            QuestionSource {
+                                url: get(url),
+                                license: get(license),
+                            },
+                        )
+                    })
+                    .collect(),
+            )
+        }
+        None => Some(
+            text.lines()
+                .map(|line| (line.to_string(), QuestionSource::default()))
+                .collect(),
+        ),
+    }
+}
+
+async fn import(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let attachment = match invocation.args.attachment("file") {
+        Some(attachment) => attachment,
+        None => {
+            invocation
+                .reply(ctx, "Attach a CSV or JSON file of questions to import!")
+                .await?;
+            return Ok(());
+        }
+    };
+    let rows = match parse_import_file(&attachment.filename, &attachment.download().await?) {
+        Some(rows) => rows,
+        None => {
+            invocation
+                .reply(ctx, "Couldn't read the file, it has to be CSV or JSON!")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    // Each question once and none the server already has, the limit is checked when adding
+    let existing = store
+        .get_list_custom_questions(guild_id.to_string())
+        .await?;
+    let mut seen: HashSet<String> = existing
+        .iter()
+        .map(|row| normalize_question(&row.get::<_, String>(2)))
+        .collect();
+    let (mut invalid, mut too_long, mut duplicates, mut over_limit) = (0, 0, 0, 0);
+    let mut questions = Vec::new();
+    for (row, source) in rows {
+        let question = row.trim();
+        if question.is_empty() || question.to_lowercase().starts_with("q!") {
+            invalid += 1;
+        } else if question_length_error(question).is_some() {
+            too_long += 1;
+        } else if !seen.insert(normalize_question(question)) {
+            duplicates += 1;
+        } else {
+            questions.push((question.to_string(), source));
+        }
+    }
+
+    // Questions past the limit are left out by add_custom_questions, so imports at the same time can't overfill it
+    let to_add = questions.len() as u64;
+    let added = if questions.is_empty() {
+        0
+    } else {
+        match store
+            .add_custom_questions(
+                guild_id.to_string(),
+                questions,
+                Some(invocation.author.id.to_string()),
+            )
+            .await
+        {
+            Ok(added) => {
+                over_limit += to_add - added;
+                added
+            }
+            Err(e) => {
+                println!("{}", e);
+                invocation
+                    .reply(ctx, "Something went wrong, nothing was imported!")
+                    .await?;
+                return Ok(());
+            }
+        }
+    };
+
+    let mut report = format!("Imported {} questions.", added);
+    if invalid > 0 {
+        report.push_str(&format!(" Skipped {} empty or invalid rows.", invalid));
+    }
+    if too_long > 0 {
+        report.push_str(&format!(
+            " Skipped {} longer than {} characters.",
+            too_long, QUESTION_LENGTH_LIMIT
+        ));
+    }
+    if duplicates > 0 {
+        report.push_str(&format!(" Skipped {} duplicates.", duplicates));
+    }
+    if over_limit > 0 {
+        report.push_str(&format!(
+            " Skipped {} over the limit of {} custom questions.",
+            over_limit,
+            storage::CUSTOM_QUESTION_LIMIT
+        ));
+    }
+    invocation.reply(ctx, report).await?;
+
+    Ok(())
+}
+
+async fn admin_contact(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    // No parameters, listing the contacts
+    if invocation.args.is_empty() {
+        let contacts = store.get_admin_contacts(guild_id.to_string()).await?;
+        let reply = if contacts.is_empty() {
+            String::from(
+                "No admin contacts set, notifications go to the admin who set the channel.",
+            )
+        } else {
+            format!(
+                "Admin contacts: {}\nNotify policy: {}",
+                contacts
+                    .iter()
+                    .map(|user_id| format!("<@{}>", user_id))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                store.get_notify_policy(guild_id.to_string()).await?
+            )
+        };
+        invocation.reply(ctx, reply).await?;
+        return Ok(());
+    }
+
+    let action = invocation.args.text("action");
+    let user_id = match invocation.args.user("member") {
+        Some(user_id) => user_id,
+        None => {
+            invocation
+                .reply(ctx, "Usage: admin_contact <add/remove> <member>")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    let reply = match action.to_lowercase().as_str() {
+        "add" => store
+            .add_admin_contact(guild_id.to_string(), user_id.to_string())
+            .await
+            .map(|added| match added {
+                0 => format!("<@{}> is an admin contact already!", user_id),
+                _ => format!("<@{}> is told about problems from now on!", user_id),
+            }),
+        "remove" => store
+            .remove_admin_contact(guild_id.to_string(), user_id.to_string())
+            .await
+            .map(|removed| match removed {
+                0 => format!("<@{}> isn't an admin contact!", user_id),
+                _ => format!("<@{}> is no longer told about problems.", user_id),
+            }),
+        _ => {
+            invocation
+                .reply(ctx, "Usage: admin_contact <add/remove> <member>")
+                .await?;
+            return Ok(());
+        }
+    };
+
+    match reply {
+        Ok(reply) => {
+            invocation.reply(ctx, reply).await?;
+        }
+        Err(e) => {
+            println!("{}", e);
+            invocation.reply(ctx, "Something went wrong!").await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn notify_policy(ctx: &Context, invocation: &Invocation) -> CommandResult {
+    let store = store(ctx).await;
+    let guild_id = invocation.guild_id.unwrap();
+
+    let parameter = invocation.args.text("policy").to_lowercase();
+    if !parameter.is_empty() {
+        if ["all", "rotate"].contains(&parameter.as_str()) {
+            store
+                .set_notify_policy(guild_id.to_string(), parameter.clone())
                 .await?;
+            let reply = match parameter.as_str() {
+                "all" => "Notifications go to every admin contact!",
+                _ => "Notifications rotate between the admin contacts!",
+            };
+            invocation.reply(ctx, reply).await?;
         } else {
-            msg.reply(ctx, "No custom questions found!").await?;
+            invocation.reply(ctx, "Please use all or rotate!").await?;
         }
+    } else {
+        let policy = store.get_notify_policy(guild_id.to_string()).await?;
+        invocation
+            .reply(
+                ctx,
+                format!(
+                    "Notify policy is {}. Use notify_policy <all/rotate> to change it.",
+                    policy
+                ),
+            )
+            .await?;
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn questions(rows: &[(String, QuestionSource)]) -> Vec<&str> {
+        rows.iter().map(|(question, _)| question.as_str()).collect()
+    }
+
+    #[test]
+    fn csv_quoted_fields_keep_commas() {
+        assert_eq!(
+            parse_csv("\"Tea, or coffee?\",b\n"),
+            vec![vec!["Tea, or coffee?", "b"]]
+        );
+    }
+
+    #[test]
+    fn csv_doubled_quotes_are_escaped_quotes() {
+        assert_eq!(
+            parse_csv("\"What does \"\"home\"\" mean to you?\""),
+            vec![vec!["What does \"home\" mean to you?"]]
+        );
+    }
+
+    #[test]
+    fn csv_quoted_fields_keep_line_breaks() {
+        assert_eq!(
+            parse_csv("\"First line\nsecond line\",x\r\nnext,y\r\n"),
+            vec![vec!["First line\nsecond line", "x"], vec!["next", "y"]]
+        );
+    }
+
+    #[test]
+    fn csv_without_trailing_line_break_keeps_last_record() {
+        assert_eq!(parse_csv("a,b\nc,"), vec![vec!["a", "b"], vec!["c", ""]]);
+    }
+
+    #[test]
+    fn import_with_header_reads_question_and_source_columns() {
+        let file = "license,Question,source_url\nCC0,\"Tea, or coffee?\",https://example.com\n,Best pet?,\n";
+        let rows = parse_import_file("questions.csv", file.as_bytes()).unwrap();
+        assert_eq!(questions(&rows), vec!["Tea, or coffee?", "Best pet?"]);
+        assert_eq!(rows[0].1.url.as_deref(), Some("https://example.com"));
+        assert_eq!(rows[0].1.license.as_deref(), Some("CC0"));
+        assert!(rows[1].1.is_empty());
+    }
+
+    #[test]
+    fn import_with_header_keeps_multi_line_questions() {
+        let file = "question\n\"Pick one:\n- summer\n- winter\"\n";
+        let rows = parse_import_file("questions.csv", file.as_bytes()).unwrap();
+        assert_eq!(questions(&rows), vec!["Pick one:\n- summer\n- winter"]);
+    }
+
+    #[test]
+    fn import_without_header_takes_every_line() {
+        let file = "Tea, or coffee?\r\nBest pet?\n";
+        let rows = parse_import_file("questions.txt", file.as_bytes()).unwrap();
+        assert_eq!(questions(&rows), vec!["Tea, or coffee?", "Best pet?"]);
+        assert!(rows.iter().all(|(_, source)| source.is_empty()));
+    }
+
+    #[test]
+    fn import_skips_byte_order_mark() {
+        let file = "\u{feff}question\nBest pet?\n";
+        let rows = parse_import_file("questions.csv", file.as_bytes()).unwrap();
+        assert_eq!(questions(&rows), vec!["Best pet?"]);
+
+        let file = "\u{feff}Best pet?\n";
+        let rows = parse_import_file("questions.csv", file.as_bytes()).unwrap();
+        assert_eq!(questions(&rows), vec!["Best pet?"]);
+    }
+
+    #[test]
+    fn import_rejects_files_that_arent_text() {
+        assert!(parse_import_file("questions.csv", &[0xff, 0xfe, 0x00]).is_none());
+        assert!(parse_import_file("questions.json", b"not json").is_none());
+    }
+
+    #[test]
+    fn slash_commands_fit_discords_limits() {
+        // Registering asserts the limit itself, this covers the owner commands of slash only mode too
+        slash_command_registrations();
+        let ungrouped = SLASH_COMMANDS
+            .iter()
+            .chain(OWNER_SLASH_COMMANDS)
+            .filter(|command| {
+                !SLASH_GROUPS
+                    .iter()
+                    .any(|group| group.commands.contains(&command.name))
+            })
+            .count();
+        assert!(ungrouped + SLASH_GROUPS.len() <= SLASH_COMMAND_LIMIT);
+
+        // A group takes up to 25 subcommands, each has to be a defined command
+        for group in SLASH_GROUPS {
+            assert!(group.commands.len() <= 25);
+            assert!(group
+                .commands
+                .iter()
+                .all(|name| command_definition(name).is_some()));
+        }
+    }
+
+    fn parse_prefix(content: &str) -> (&'static str, CommandArgs) {
+        let (definition, written) = find_prefix_command(content).unwrap();
+        (
+            definition.name,
+            parse_prefix_arguments(definition, written, &[]),
+        )
+    }
+
+    #[test]
+    fn prefix_command_name_ignores_case() {
+        let (definition, written) = find_prefix_command("Q!Submit_QOTD Best pet?").unwrap();
+        assert_eq!(definition.name, "submit_qotd");
+        assert_eq!(written, " Best pet?");
+
+        assert!(find_prefix_command("q!not_a_command").is_none());
+        assert!(find_prefix_command("qq!help").is_none());
+        assert!(find_prefix_command("q!").is_none());
+    }
+
+    #[test]
+    fn prefix_text_keeps_lines_and_markdown() {
+        let (name, args) = parse_prefix("q!submit_qotd **Best** pet?\n> and _why_?");
+        assert_eq!(name, "submit_qotd");
+        assert!(args.get("mature").is_none());
+        assert_eq!(args.text("question"), "**Best** pet?\n> and _why_?");
+    }
+
+    #[test]
+    fn prefix_choices_match_ignoring_case() {
+        let (_, args) = parse_prefix("q!submit_qotd --MATURE Worst date?");
+        assert_eq!(args.text("mature"), "--mature");
+        assert_eq!(args.text("question"), "Worst date?");
+
+        let (_, args) = parse_prefix("q!help Polls");
+        assert_eq!(args.text("section"), "polls");
+    }
+
+    #[test]
+    fn prefix_skips_optional_options_not_given() {
+        let (_, args) = parse_prefix("q!harvest 50");
+        assert_eq!(args.channel("channel"), None);
+        assert_eq!(args.integer("count"), Some(50));
+
+        let (_, args) = parse_prefix("q!harvest <#123> 50");
+        assert_eq!(args.channel("channel"), Some(ChannelId(123)));
+        assert_eq!(args.integer("count"), Some(50));
+
+        let (_, args) = parse_prefix("q!custom_qotd");
+        assert!(args.get("id").is_none());
+    }
+
+    #[test]
+    fn prefix_text_options_split_on_separator() {
+        let (_, args) = parse_prefix("q!submit_poll Cats or dogs?\nBig cats\nSmall dogs");
+        assert_eq!(args.text("question"), "Cats or dogs?");
+        assert_eq!(args.text("option_a"), "Big cats");
+        assert_eq!(args.text("option_b"), "Small dogs");
+
+        let (_, args) = parse_prefix("q!stream daily template {role} it's time!");
+        assert_eq!(args.text("name"), "daily");
+        assert_eq!(args.text("setting"), "template");
+        assert_eq!(args.text("value"), "{role} it's time!");
+    }
+
+    #[test]
+    fn prefix_trailing_words_go_to_later_options() {
+        let (_, args) = parse_prefix("q!cleanup 30 commands");
+        assert_eq!(args.text("seconds"), "30");
+        assert_eq!(args.text("commands"), "commands");
+
+        let (_, args) = parse_prefix("q!question_cooldown 12 default");
+        assert_eq!(args.id("id"), Some(12));
+        assert_eq!(args.text("days"), "default");
+    }
+
+    fn words(count: usize) -> String {
+        vec!["word"; count].join(" ")
+    }
+
+    #[test]
+    fn split_text_breaks_at_spaces_and_lines() {
+        assert_eq!(
+            split_text("  Best pet?  ", CONTENT_LIMIT),
+            vec!["Best pet?"]
+        );
+        assert!(split_text("   ", CONTENT_LIMIT).is_empty());
+
+        let text = words(900);
+        let parts = split_text(&text, CONTENT_LIMIT);
+        assert_eq!(parts.len(), 3);
+        assert!(parts
+            .iter()
+            .all(|part| part.chars().count() <= CONTENT_LIMIT && !part.contains("wo rd")));
+        assert_eq!(parts.join(" "), text);
+
+        let text = format!("{}\n{}", "a ".repeat(10).trim(), "b".repeat(15));
+        assert_eq!(
+            split_text(&text, 20),
+            vec!["a a a a a a a a a a", "bbbbbbbbbbbbbbb"]
+        );
+    }
+
+    #[test]
+    fn split_text_cuts_words_longer_than_the_limit_by_characters() {
+        let text = "é".repeat(2500);
+        let parts = split_text(&text, CONTENT_LIMIT);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].chars().count(), CONTENT_LIMIT);
+        assert_eq!(parts[1].chars().count(), 500);
+    }
+
+    #[test]
+    fn plain_post_sends_the_rest_as_follow_ups() {
+        let post = Post::plain(&words(900));
+        assert!(post.embed.is_none());
+        assert!(post.content.chars().count() <= CONTENT_LIMIT);
+        assert_eq!(post.follow_ups.len(), 2);
+    }
+
+    #[test]
+    fn embed_post_fits_content_and_description_limits() {
+        let post = Post::embed(
+            &words(500),
+            "Question",
+            &words(1000),
+            None,
+            Color::FABLED_PINK,
+        );
+        let embed = post.embed.unwrap();
+        assert!(post.content.chars().count() <= CONTENT_LIMIT);
+        assert!(embed.description.chars().count() <= EMBED_DESCRIPTION_LIMIT);
+        assert_eq!(embed.title.as_deref(), Some("Question"));
+        // One follow up for the content and one for the end of the description
+        assert_eq!(post.follow_ups.len(), 2);
+        assert!(post
+            .follow_ups
+            .iter()
+            .all(|follow_up| follow_up.chars().count() <= CONTENT_LIMIT));
+    }
+
+    #[test]
+    fn embed_post_moves_long_title_into_description() {
+        let title = "t".repeat(EMBED_TITLE_LIMIT + 1);
+        let post = Post::embed("", &title, "Why?", None, Color::FABLED_PINK);
+        let embed = post.embed.unwrap();
+        assert!(embed.title.is_none());
+        assert_eq!(embed.description, format!("**{}**\nWhy?", title));
+
+        let title = "t".repeat(EMBED_TITLE_LIMIT);
+        let post = Post::embed("", &title, "Why?", None, Color::FABLED_PINK);
+        assert_eq!(post.embed.unwrap().title, Some(title));
+    }
+
+    #[test]
+    fn embed_post_cuts_footer_short() {
+        let footer = "f".repeat(EMBED_FOOTER_LIMIT + 10);
+        let post = Post::embed("", "Question", "Why?", Some(&footer), Color::FABLED_PINK);
+        let cut = post.embed.unwrap().footer.unwrap();
+        assert_eq!(cut.chars().count(), EMBED_FOOTER_LIMIT);
+        assert!(cut.ends_with('…'));
+
+        let post = Post::embed("", "Question", "Why?", Some("Pack"), Color::FABLED_PINK);
+        assert_eq!(post.embed.unwrap().footer.as_deref(), Some("Pack"));
+
+        // The whole embed stays under its limit with a full title and description
+        let title = "t".repeat(EMBED_TITLE_LIMIT);
+        let description = "d".repeat(EMBED_DESCRIPTION_LIMIT);
+        let post = Post::embed("", &title, &description, Some(&footer), Color::FABLED_PINK);
+        let embed = post.embed.unwrap();
+        let total = EMBED_TITLE_LIMIT
+            + embed.description.chars().count()
+            + embed.footer.unwrap().chars().count();
+        assert_eq!(total, EMBED_TOTAL_LIMIT);
+    }
+
+    #[test]
+    fn posts_only_ping_the_configured_role() {
+        let pings = Pings::for_ping_role("0");
+        assert!(!pings.everyone && pings.roles.is_empty() && pings.users.is_empty());
+
+        let pings = Pings::for_ping_role("1");
+        assert!(pings.everyone && pings.roles.is_empty());
+
+        let pings = Pings::for_ping_role("1234");
+        assert!(!pings.everyone);
+        assert_eq!(pings.roles, vec![RoleId(1234)]);
+
+        // Mentions written in a question don't ping anyone
+        let post = Post::plain("@everyone <@&1234> what's up?");
+        assert!(!post.pings.everyone && post.pings.roles.is_empty());
+    }
+
+    #[test]
+    fn mention_chunks_stay_under_the_allowed_mentions_limit() {
+        let users: Vec<UserId> = (1..=250).map(UserId).collect();
+        let chunks = chunk_mentions(&users);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks
+            .iter()
+            .all(|(mentions, pinged)| pinged.len() <= 100 && mentions.len() <= 1900));
+        assert_eq!(
+            chunks.iter().map(|(_, pinged)| pinged.len()).sum::<usize>(),
+            250
+        );
+    }
+
+    #[tokio::test]
+    async fn mention_template_fills_role_and_stream() {
+        let filled = format_string_for_pings(
+            String::from("1234"),
+            String::from("Question of the day!"),
+            Some("{role} time for {stream}!"),
+            "evening",
+        )
+        .await;
+        assert_eq!(filled, "<@&1234> time for evening!");
+
+        assert!(mentions_everyone("@everyone {role}"));
+        assert!(mentions_everyone("hey @here"));
+        assert!(!mentions_everyone("{role} @someone"));
+    }
+}
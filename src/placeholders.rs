@@ -0,0 +1,110 @@
+// Placeholders questions can be written with, filled in when the question is posted
+// Anything else in braces is left as written, so questions using braces of their own aren't changed
+
+// Placeholders a question can use, with what they're filled with
+pub const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("{server}", "the name of the server"),
+    ("{random_member}", "the name of a random member"),
+    ("{month}", "the current month"),
+    ("{weekday}", "the current day of the week"),
+];
+
+// What the placeholders of a question are filled with when it's posted
+pub struct PlaceholderValues {
+    pub server: String,
+    pub random_member: String,
+    pub month: String,
+    pub weekday: String,
+}
+
+impl PlaceholderValues {
+    fn get(&self, placeholder: &str) -> &str {
+        match placeholder {
+            "{server}" => &self.server,
+            "{random_member}" => &self.random_member,
+            "{month}" => &self.month,
+            _ => &self.weekday,
+        }
+    }
+}
+
+/// Checks whether the text uses any of PLACEHOLDERS, so the values are only looked up for questions needing them
+pub fn has_placeholders(text: &str) -> bool {
+    PLACEHOLDERS
+        .iter()
+        .any(|(placeholder, _)| text.contains(placeholder))
+}
+
+/// Fills the PLACEHOLDERS of the text in a single pass, so a value containing a placeholder isn't filled again
+pub fn fill_placeholders(text: &str, values: &PlaceholderValues) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match PLACEHOLDERS
+            .iter()
+            .find(|(placeholder, _)| rest.starts_with(placeholder))
+        {
+            Some((placeholder, _)) => {
+                filled.push_str(values.get(placeholder));
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                filled.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> PlaceholderValues {
+        PlaceholderValues {
+            server: String::from("Cafe {random_member}"),
+            random_member: String::from("Sam"),
+            month: String::from("March"),
+            weekday: String::from("Friday"),
+        }
+    }
+
+    #[test]
+    fn fills_every_placeholder() {
+        assert_eq!(
+            fill_placeholders(
+                "{random_member}, what is {weekday} like in {month}?",
+                &values()
+            ),
+            "Sam, what is Friday like in March?"
+        );
+    }
+
+    #[test]
+    fn doesnt_fill_placeholders_in_values() {
+        assert_eq!(
+            fill_placeholders("Welcome to {server}!", &values()),
+            "Welcome to Cafe {random_member}!"
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_braces() {
+        assert_eq!(
+            fill_placeholders("Is {} or {year} or {server a set?", &values()),
+            "Is {} or {year} or {server a set?"
+        );
+        assert_eq!(fill_placeholders("{{month}}", &values()), "{March}");
+    }
+
+    #[test]
+    fn finds_placeholders() {
+        assert!(has_placeholders("Happy {weekday}!"));
+        assert!(!has_placeholders("Is {} a set?"));
+        assert!(!has_placeholders("No {Server} here"));
+    }
+}
@@ -0,0 +1,8101 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+
+use serenity::async_trait;
+use serenity::model::id::{ChannelId, UserId};
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+use serde_json::json;
+
+use deadpool_postgres::{Manager, ManagerConfig, Object, Pool, PoolError, RecyclingMethod};
+use tokio_postgres::types::ToSql;
+use tokio_postgres::NoTls;
+
+use regex::Regex;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::types::{ToSqlOutput, Value as SqliteValue};
+use sha2::{Digest, Sha256};
+
+use crate::card::{CardStyle, DEFAULT_CARD_COLOR};
+use crate::{
+    format_attribution, normalize_question, parse_channel, parse_skip_days, parse_timezone,
+    record_query_time, IdSource, Stream, DM_FAILURE_LIMIT, DM_FANOUT_STAT, DM_LEASE_SECONDS,
+    DM_QUEUE_LIMIT, DM_RATE, IMPORT_COMMANDS,
+};
+
+// Schema migrations built into the bot, applied in order of their version on startup
+// New schema changes are added as a new file, applied migrations are never edited
+const POSTGRES_MIGRATIONS: &[(i32, &str, &str)] = &[
+    (
+        1,
+        "initial_schema",
+        include_str!("../migrations/0001_initial_schema.sql"),
+    ),
+    (
+        2,
+        "pre_migration_features",
+        include_str!("../migrations/0002_pre_migration_features.sql"),
+    ),
+    (
+        3,
+        "question_hashes",
+        include_str!("../migrations/0003_question_hashes.sql"),
+    ),
+    (
+        4,
+        "channel_fallback",
+        include_str!("../migrations/0004_channel_fallback.sql"),
+    ),
+    (
+        5,
+        "add_seconds",
+        include_str!("../migrations/0005_add_seconds.sql"),
+    ),
+    (6, "drafts", include_str!("../migrations/0006_drafts.sql")),
+    (7, "trivia", include_str!("../migrations/0007_trivia.sql")),
+    (
+        8,
+        "blocked_users",
+        include_str!("../migrations/0008_blocked_users.sql"),
+    ),
+    (
+        9,
+        "soft_delete",
+        include_str!("../migrations/0009_soft_delete.sql"),
+    ),
+    (
+        10,
+        "backup_channel",
+        include_str!("../migrations/0010_backup_channel.sql"),
+    ),
+    (
+        11,
+        "poll_amendments",
+        include_str!("../migrations/0011_poll_amendments.sql"),
+    ),
+    (
+        12,
+        "admin_contacts",
+        include_str!("../migrations/0012_admin_contacts.sql"),
+    ),
+    (
+        13,
+        "image_cards",
+        include_str!("../migrations/0013_image_cards.sql"),
+    ),
+    (
+        14,
+        "question_tags",
+        include_str!("../migrations/0014_question_tags.sql"),
+    ),
+    (
+        15,
+        "dm_queue",
+        include_str!("../migrations/0015_dm_queue.sql"),
+    ),
+    (
+        16,
+        "question_history",
+        include_str!("../migrations/0016_question_history.sql"),
+    ),
+    (17, "modlog", include_str!("../migrations/0017_modlog.sql")),
+    (
+        18,
+        "command_failures",
+        include_str!("../migrations/0018_command_failures.sql"),
+    ),
+    (
+        19,
+        "removal_export",
+        include_str!("../migrations/0019_removal_export.sql"),
+    ),
+    (
+        20,
+        "double_days",
+        include_str!("../migrations/0020_double_days.sql"),
+    ),
+    (
+        21,
+        "submitter_attribution",
+        include_str!("../migrations/0021_submitter_attribution.sql"),
+    ),
+    (
+        22,
+        "answer_filter",
+        include_str!("../migrations/0022_answer_filter.sql"),
+    ),
+    (
+        23,
+        "mention_submitter",
+        include_str!("../migrations/0023_mention_submitter.sql"),
+    ),
+    (
+        24,
+        "custom_only",
+        include_str!("../migrations/0024_custom_only.sql"),
+    ),
+    (
+        25,
+        "question_mix",
+        include_str!("../migrations/0025_question_mix.sql"),
+    ),
+    (
+        26,
+        "command_channel",
+        include_str!("../migrations/0026_command_channel.sql"),
+    ),
+    (
+        27,
+        "seasonal",
+        include_str!("../migrations/0027_seasonal.sql"),
+    ),
+    (
+        28,
+        "poll_role_weights",
+        include_str!("../migrations/0028_poll_role_weights.sql"),
+    ),
+    (
+        29,
+        "mature_questions",
+        include_str!("../migrations/0029_mature_questions.sql"),
+    ),
+    (
+        30,
+        "recommendations",
+        include_str!("../migrations/0030_recommendations.sql"),
+    ),
+    (31, "outbox", include_str!("../migrations/0031_outbox.sql")),
+    (
+        32,
+        "question_ratings",
+        include_str!("../migrations/0032_question_ratings.sql"),
+    ),
+    (
+        33,
+        "unique_custom_questions",
+        include_str!("../migrations/0033_unique_custom_questions.sql"),
+    ),
+    (34, "quotas", include_str!("../migrations/0034_quotas.sql")),
+    (
+        35,
+        "onboarding_checklist",
+        include_str!("../migrations/0035_onboarding_checklist.sql"),
+    ),
+    (
+        36,
+        "question_sources",
+        include_str!("../migrations/0036_question_sources.sql"),
+    ),
+];
+
+// The SQLite schema starts from everything the Postgres migrations above add up to
+// Schema changes from here on get a migration in both lists
+const SQLITE_MIGRATIONS: &[(i32, &str, &str)] = &[
+    (
+        1,
+        "initial_schema",
+        include_str!("../migrations/sqlite/0001_initial_schema.sql"),
+    ),
+    (
+        2,
+        "drafts",
+        include_str!("../migrations/sqlite/0002_drafts.sql"),
+    ),
+    (
+        3,
+        "trivia",
+        include_str!("../migrations/sqlite/0003_trivia.sql"),
+    ),
+    (
+        4,
+        "blocked_users",
+        include_str!("../migrations/sqlite/0004_blocked_users.sql"),
+    ),
+    (
+        5,
+        "soft_delete",
+        include_str!("../migrations/sqlite/0005_soft_delete.sql"),
+    ),
+    (
+        6,
+        "backup_channel",
+        include_str!("../migrations/sqlite/0006_backup_channel.sql"),
+    ),
+    (
+        7,
+        "poll_amendments",
+        include_str!("../migrations/sqlite/0007_poll_amendments.sql"),
+    ),
+    (
+        8,
+        "admin_contacts",
+        include_str!("../migrations/sqlite/0008_admin_contacts.sql"),
+    ),
+    (
+        9,
+        "image_cards",
+        include_str!("../migrations/sqlite/0009_image_cards.sql"),
+    ),
+    (
+        10,
+        "question_tags",
+        include_str!("../migrations/sqlite/0010_question_tags.sql"),
+    ),
+    (
+        11,
+        "dm_queue",
+        include_str!("../migrations/sqlite/0011_dm_queue.sql"),
+    ),
+    (
+        12,
+        "question_history",
+        include_str!("../migrations/sqlite/0012_question_history.sql"),
+    ),
+    (
+        13,
+        "modlog",
+        include_str!("../migrations/sqlite/0013_modlog.sql"),
+    ),
+    (
+        14,
+        "command_failures",
+        include_str!("../migrations/sqlite/0014_command_failures.sql"),
+    ),
+    (
+        15,
+        "removal_export",
+        include_str!("../migrations/sqlite/0015_removal_export.sql"),
+    ),
+    (
+        16,
+        "double_days",
+        include_str!("../migrations/sqlite/0016_double_days.sql"),
+    ),
+    (
+        17,
+        "submitter_attribution",
+        include_str!("../migrations/sqlite/0017_submitter_attribution.sql"),
+    ),
+    (
+        18,
+        "answer_filter",
+        include_str!("../migrations/sqlite/0018_answer_filter.sql"),
+    ),
+    (
+        19,
+        "mention_submitter",
+        include_str!("../migrations/sqlite/0019_mention_submitter.sql"),
+    ),
+    (
+        20,
+        "custom_only",
+        include_str!("../migrations/sqlite/0020_custom_only.sql"),
+    ),
+    (
+        21,
+        "question_mix",
+        include_str!("../migrations/sqlite/0021_question_mix.sql"),
+    ),
+    (
+        22,
+        "command_channel",
+        include_str!("../migrations/sqlite/0022_command_channel.sql"),
+    ),
+    (
+        23,
+        "seasonal",
+        include_str!("../migrations/sqlite/0023_seasonal.sql"),
+    ),
+    (
+        24,
+        "poll_role_weights",
+        include_str!("../migrations/sqlite/0024_poll_role_weights.sql"),
+    ),
+    (
+        25,
+        "mature_questions",
+        include_str!("../migrations/sqlite/0025_mature_questions.sql"),
+    ),
+    (
+        26,
+        "recommendations",
+        include_str!("../migrations/sqlite/0026_recommendations.sql"),
+    ),
+    (
+        27,
+        "outbox",
+        include_str!("../migrations/sqlite/0027_outbox.sql"),
+    ),
+    (
+        28,
+        "question_ratings",
+        include_str!("../migrations/sqlite/0028_question_ratings.sql"),
+    ),
+    (
+        29,
+        "unique_custom_questions",
+        include_str!("../migrations/sqlite/0029_unique_custom_questions.sql"),
+    ),
+    (
+        30,
+        "quotas",
+        include_str!("../migrations/sqlite/0030_quotas.sql"),
+    ),
+    (
+        31,
+        "onboarding_checklist",
+        include_str!("../migrations/sqlite/0031_onboarding_checklist.sql"),
+    ),
+    (
+        32,
+        "question_sources",
+        include_str!("../migrations/sqlite/0032_question_sources.sql"),
+    ),
+];
+
+// Error from the database, whichever backend it came from
+#[derive(Debug)]
+pub enum DbError {
+    // No connection could be checked out of the pool
+    Pool(PoolError),
+    Postgres(tokio_postgres::Error),
+    Sqlite(rusqlite::Error),
+    // query_one got no row back
+    NoRows,
+}
+
+impl fmt::Display for DbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DbError::Pool(e) => e.fmt(f),
+            DbError::Postgres(e) => e.fmt(f),
+            DbError::Sqlite(e) => e.fmt(f),
+            DbError::NoRows => write!(f, "query returned no rows"),
+        }
+    }
+}
+
+impl std::error::Error for DbError {}
+
+impl From<PoolError> for DbError {
+    fn from(e: PoolError) -> Self {
+        DbError::Pool(e)
+    }
+}
+
+impl From<tokio_postgres::Error> for DbError {
+    fn from(e: tokio_postgres::Error) -> Self {
+        DbError::Postgres(e)
+    }
+}
+
+impl From<rusqlite::Error> for DbError {
+    fn from(e: rusqlite::Error) -> Self {
+        DbError::Sqlite(e)
+    }
+}
+
+// Query parameter that can be bound on either backend
+// SQLite has no arrays, they are bound as JSON text there
+pub trait SqlParam: ToSql + Sync {
+    fn to_sqlite(&self) -> SqliteValue;
+}
+
+/// Converts a value to what rusqlite would bind for it
+fn sqlite_value(value: &dyn rusqlite::ToSql) -> SqliteValue {
+    match value.to_sql() {
+        Ok(ToSqlOutput::Owned(value)) => value,
+        Ok(ToSqlOutput::Borrowed(value)) => value.into(),
+        _ => SqliteValue::Null,
+    }
+}
+
+macro_rules! sql_param {
+    ($($param:ty),*) => {
+        $(
+            impl SqlParam for $param {
+                fn to_sqlite(&self) -> SqliteValue {
+                    sqlite_value(self)
+                }
+            }
+        )*
+    };
+}
+
+sql_param!(String, &str, i32, i64, bool, f64, DateTime<Utc>, NaiveDate);
+
+impl SqlParam for Vec<String> {
+    fn to_sqlite(&self) -> SqliteValue {
+        SqliteValue::Text(json!(self).to_string())
+    }
+}
+
+impl SqlParam for Vec<i32> {
+    fn to_sqlite(&self) -> SqliteValue {
+        SqliteValue::Text(json!(self).to_string())
+    }
+}
+
+impl<T: SqlParam> SqlParam for Option<T> {
+    fn to_sqlite(&self) -> SqliteValue {
+        match self {
+            Some(value) => value.to_sqlite(),
+            None => SqliteValue::Null,
+        }
+    }
+}
+
+// Column of a row, by position or by name
+pub trait ColumnIndex {
+    fn postgres_index(&self, row: &tokio_postgres::Row) -> usize;
+    fn sqlite_index(&self, columns: &[String]) -> usize;
+}
+
+impl ColumnIndex for usize {
+    fn postgres_index(&self, _row: &tokio_postgres::Row) -> usize {
+        *self
+    }
+
+    fn sqlite_index(&self, _columns: &[String]) -> usize {
+        *self
+    }
+}
+
+impl ColumnIndex for &str {
+    fn postgres_index(&self, row: &tokio_postgres::Row) -> usize {
+        row.columns()
+            .iter()
+            .position(|column| column.name() == *self)
+            .unwrap_or_else(|| panic!("No column named {}", self))
+    }
+
+    fn sqlite_index(&self, columns: &[String]) -> usize {
+        columns
+            .iter()
+            .position(|column| column == self)
+            .unwrap_or_else(|| panic!("No column named {}", self))
+    }
+}
+
+// Value that can be read from a column on either backend
+pub trait FromColumn: Sized {
+    fn from_postgres(row: &tokio_postgres::Row, index: usize) -> Self;
+    fn from_sqlite(value: &SqliteValue) -> Self;
+}
+
+macro_rules! from_column {
+    ($($column:ty),*) => {
+        $(
+            impl FromColumn for $column {
+                fn from_postgres(row: &tokio_postgres::Row, index: usize) -> Self {
+                    row.get(index)
+                }
+
+                fn from_sqlite(value: &SqliteValue) -> Self {
+                    rusqlite::types::FromSql::column_result(value.into())
+                        .expect("Unexpected column type")
+                }
+            }
+        )*
+    };
+}
+
+from_column!(
+    String,
+    Option<String>,
+    i32,
+    Option<i32>,
+    i64,
+    Option<i64>,
+    bool,
+    Option<bool>,
+    f64,
+    Option<f64>,
+    DateTime<Utc>,
+    Option<DateTime<Utc>>,
+    NaiveDate,
+    Option<NaiveDate>
+);
+
+impl FromColumn for Option<Vec<String>> {
+    fn from_postgres(row: &tokio_postgres::Row, index: usize) -> Self {
+        row.get(index)
+    }
+
+    fn from_sqlite(value: &SqliteValue) -> Self {
+        match value {
+            SqliteValue::Text(array) => {
+                Some(serde_json::from_str(array).expect("Unexpected column type"))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromColumn for Vec<String> {
+    fn from_postgres(row: &tokio_postgres::Row, index: usize) -> Self {
+        row.get(index)
+    }
+
+    fn from_sqlite(value: &SqliteValue) -> Self {
+        Option::<Vec<String>>::from_sqlite(value).expect("Unexpected column type")
+    }
+}
+
+// Row of a query result, read the same way as a tokio_postgres row
+pub enum Row {
+    Postgres(tokio_postgres::Row),
+    Sqlite {
+        columns: Arc<Vec<String>>,
+        values: Vec<SqliteValue>,
+    },
+}
+
+impl Row {
+    /// Reads a column of the row, panicking when it doesn't exist or has another type
+    pub fn get<I: ColumnIndex, T: FromColumn>(&self, index: I) -> T {
+        match self {
+            Row::Postgres(row) => T::from_postgres(row, index.postgres_index(row)),
+            Row::Sqlite { columns, values } => T::from_sqlite(&values[index.sqlite_index(columns)]),
+        }
+    }
+}
+
+// Connection the helpers run their queries on
+// Queries are written for Postgres, the SQLite backend translates them
+// Both backends prepare a statement once per connection and keep it for the next time it runs
+#[async_trait]
+trait DbConnection: Send + Sync {
+    async fn query(&self, statement: &str, params: &[&dyn SqlParam]) -> Result<Vec<Row>, DbError>;
+
+    async fn execute(&self, statement: &str, params: &[&dyn SqlParam]) -> Result<u64, DbError>;
+
+    /// Starts a transaction, the statements on the connection run in it until it is committed
+    async fn begin(&mut self) -> Result<(), DbError>;
+
+    async fn commit(&mut self) -> Result<(), DbError>;
+
+    /// Rolls the open transaction back without waiting on it, for transactions dropped before they were committed
+    fn abandon(self: Box<Self>);
+}
+
+/// Binds the parameters as tokio_postgres takes them
+fn postgres_params<'a>(params: &[&'a dyn SqlParam]) -> Vec<&'a (dyn ToSql + Sync)> {
+    params
+        .iter()
+        .map(|param| *param as &(dyn ToSql + Sync))
+        .collect()
+}
+
+#[async_trait]
+impl DbConnection for Object {
+    async fn query(&self, statement: &str, params: &[&dyn SqlParam]) -> Result<Vec<Row>, DbError> {
+        let client: &tokio_postgres::Client = self;
+        let prepared = self.prepare_cached(statement).await?;
+        let rows = client.query(&prepared, &postgres_params(params)).await?;
+        Ok(rows.into_iter().map(Row::Postgres).collect())
+    }
+
+    async fn execute(&self, statement: &str, params: &[&dyn SqlParam]) -> Result<u64, DbError> {
+        let client: &tokio_postgres::Client = self;
+        let prepared = self.prepare_cached(statement).await?;
+        Ok(client.execute(&prepared, &postgres_params(params)).await?)
+    }
+
+    async fn begin(&mut self) -> Result<(), DbError> {
+        Ok(self.batch_execute("BEGIN").await?)
+    }
+
+    async fn commit(&mut self) -> Result<(), DbError> {
+        Ok(self.batch_execute("COMMIT").await?)
+    }
+
+    fn abandon(self: Box<Self>) {
+        // The connection goes back to the pool once the rollback is done
+        tokio::spawn(async move {
+            if let Err(e) = self.batch_execute("ROLLBACK").await {
+                println!("Rolling back transaction failed: {}", e);
+            }
+        });
+    }
+}
+
+// Single file database, the queries take turns on its one connection
+// A transaction holds the gate until it is done, the queries of other connections wait for it
+struct SqliteStorage {
+    connection: Arc<std::sync::Mutex<rusqlite::Connection>>,
+    gate: Arc<tokio::sync::Mutex<()>>,
+    transaction: Option<tokio::sync::OwnedMutexGuard<()>>,
+}
+
+impl SqliteStorage {
+    /// Prepares the translated statement and binds the parameters to it.
+    /// Queries on a local file are quick, so they run without leaving the async task.
+    async fn run<T>(
+        &self,
+        statement: &str,
+        params: &[&dyn SqlParam],
+        run: impl FnOnce(&mut rusqlite::Statement, Vec<SqliteValue>) -> rusqlite::Result<T>,
+    ) -> Result<T, DbError> {
+        let _gate = match self.transaction {
+            Some(_) => None,
+            None => Some(self.gate.lock().await),
+        };
+        let params = params.iter().map(|param| param.to_sqlite()).collect();
+        let statement = translated_statement(statement);
+        let connection = self.connection.lock().expect("SQLite connection poisoned");
+        let mut prepared = connection.prepare_cached(&statement)?;
+        Ok(run(&mut prepared, params)?)
+    }
+}
+
+#[async_trait]
+impl DbConnection for SqliteStorage {
+    async fn query(&self, statement: &str, params: &[&dyn SqlParam]) -> Result<Vec<Row>, DbError> {
+        self.run(statement, params, |prepared, params| {
+            let columns: Arc<Vec<String>> = Arc::new(
+                prepared
+                    .column_names()
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            );
+            let mut rows = prepared.query(rusqlite::params_from_iter(params))?;
+            let mut result = Vec::new();
+            while let Some(row) = rows.next()? {
+                let values = (0..columns.len())
+                    .map(|index| row.get(index))
+                    .collect::<rusqlite::Result<_>>()?;
+                result.push(Row::Sqlite {
+                    columns: columns.clone(),
+                    values,
+                });
+            }
+            Ok(result)
+        })
+        .await
+    }
+
+    async fn execute(&self, statement: &str, params: &[&dyn SqlParam]) -> Result<u64, DbError> {
+        self.run(statement, params, |prepared, params| {
+            Ok(prepared.execute(rusqlite::params_from_iter(params))? as u64)
+        })
+        .await
+    }
+
+    async fn begin(&mut self) -> Result<(), DbError> {
+        self.transaction = Some(self.gate.clone().lock_owned().await);
+        self.connection
+            .lock()
+            .expect("SQLite connection poisoned")
+            .execute_batch("BEGIN IMMEDIATE")?;
+        Ok(())
+    }
+
+    async fn commit(&mut self) -> Result<(), DbError> {
+        self.connection
+            .lock()
+            .expect("SQLite connection poisoned")
+            .execute_batch("COMMIT")?;
+        self.transaction = None;
+        Ok(())
+    }
+
+    fn abandon(self: Box<Self>) {
+        let rollback = self
+            .connection
+            .lock()
+            .expect("SQLite connection poisoned")
+            .execute_batch("ROLLBACK");
+        if let Err(e) = rollback {
+            println!("Rolling back transaction failed: {}", e);
+        }
+    }
+}
+
+/// Gets the SQLite translation of a statement, each statement is only translated the first time it runs
+fn translated_statement(statement: &str) -> Arc<str> {
+    static TRANSLATIONS: LazyLock<std::sync::Mutex<HashMap<String, Arc<str>>>> =
+        LazyLock::new(Default::default);
+
+    let mut translations = TRANSLATIONS.lock().expect("SQLite translations poisoned");
+    translations
+        .entry(statement.to_string())
+        .or_insert_with(|| Arc::from(sqlite_statement(statement)))
+        .clone()
+}
+
+/// Rewrites a query written for Postgres into SQLite.
+/// Casts are dropped, $1 turns into ?1 and arrays are read and unnested as JSON, the Postgres functions the
+/// queries use are registered on the connection by register_sqlite_functions.
+fn sqlite_statement(statement: &str) -> String {
+    static RULES: LazyLock<Vec<(Regex, &str)>> = LazyLock::new(|| {
+        [
+            (r"::\w+(\[\])?", ""),
+            (r"\$(\d+)", "?${1}"),
+            (r"IS NOT DISTINCT FROM", "IS"),
+            (r"IS DISTINCT FROM", "IS NOT"),
+            (
+                r"(\S+) = ANY\(([^)]+)\)",
+                "${1} IN (SELECT value FROM json_each(${2}))",
+            ),
+            (r"\bILIKE\b", "LIKE"),
+            (r"\bGREATEST\(", "max("),
+            // Transactions take the whole database on SQLite, rows don't need locking
+            (r"\s+FOR UPDATE\b", ""),
+            (
+                r"unnest\(([^)]+)\) AS (\w+)",
+                "(SELECT value AS ${2} FROM json_each(${1}))",
+            ),
+        ]
+        .into_iter()
+        .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+        .collect()
+    });
+    static ARRAY_INDEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\w+)\[(\d+)\]").unwrap());
+
+    let mut statement = statement.to_string();
+    for (pattern, replacement) in RULES.iter() {
+        statement = pattern.replace_all(&statement, *replacement).into_owned();
+    }
+    // Postgres arrays start from 1, JSON arrays from 0
+    ARRAY_INDEX
+        .replace_all(&statement, |captures: &regex::Captures| {
+            let index: usize = captures[2].parse().unwrap();
+            format!("json_extract({}, '$[{}]')", &captures[1], index - 1)
+        })
+        .into_owned()
+}
+
+/// Formats a timestamp the way rusqlite stores them, so they compare correctly as text
+fn sqlite_timestamp(time: DateTime<Utc>) -> String {
+    time.format("%F %T%.f%:z").to_string()
+}
+
+/// Registers the Postgres functions the queries use that SQLite doesn't have, or has differently
+fn register_sqlite_functions(connection: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let deterministic = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+
+    connection.create_scalar_function("now", 0, FunctionFlags::SQLITE_UTF8, |_| {
+        Ok(sqlite_timestamp(Utc::now()))
+    })?;
+    // SQLite's random() gives an integer, the queries expect a float between 0 and 1
+    connection.create_scalar_function("random", 0, FunctionFlags::SQLITE_UTF8, |_| {
+        Ok(rand::random::<f64>())
+    })?;
+    connection.create_scalar_function("ln", 1, deterministic, |ctx| {
+        Ok(ctx.get::<Option<f64>>(0)?.map(f64::ln))
+    })?;
+    connection.create_scalar_function("floor", 1, deterministic, |ctx| {
+        Ok(ctx.get::<Option<f64>>(0)?.map(f64::floor))
+    })?;
+    connection.create_scalar_function("hash_question", 1, deterministic, |ctx| {
+        Ok(ctx.get::<Option<String>>(0)?.map(|question| {
+            Sha256::digest(normalize_question(&question).as_bytes())
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>()
+        }))
+    })?;
+    // Only the YYYY-MM-DD format is used
+    connection.create_scalar_function("to_char", 2, deterministic, |ctx| {
+        Ok(ctx
+            .get::<Option<DateTime<Utc>>>(0)?
+            .map(|time| time.format("%Y-%m-%d").to_string()))
+    })?;
+    connection.create_scalar_function("array_to_string", 2, deterministic, |ctx| {
+        let separator: String = ctx.get(1)?;
+        match ctx.get::<Option<String>>(0)? {
+            Some(array) => {
+                let array: Vec<String> = serde_json::from_str(&array)
+                    .map_err(|e| rusqlite::Error::UserFunctionError(e.into()))?;
+                Ok(Some(array.join(&separator)))
+            }
+            None => Ok(None),
+        }
+    })?;
+    // Postgres gets the same function from a migration, so queries can use it instead of intervals
+    connection.create_scalar_function("add_seconds", 2, deterministic, |ctx| {
+        let time: Option<DateTime<Utc>> = ctx.get(0)?;
+        let seconds: Option<f64> = ctx.get(1)?;
+        Ok(time.zip(seconds).map(|(time, seconds)| {
+            sqlite_timestamp(time + chrono::Duration::milliseconds((seconds * 1000.0) as i64))
+        }))
+    })?;
+    // Only truncating to the week is used
+    connection.create_scalar_function("date_trunc", 2, deterministic, |ctx| {
+        Ok(ctx.get::<Option<DateTime<Utc>>>(1)?.map(|time| {
+            let monday = time.date_naive()
+                - chrono::Duration::days(time.weekday().num_days_from_monday() as i64);
+            sqlite_timestamp(monday.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        }))
+    })?;
+
+    Ok(())
+}
+
+/// Opens the SQLite database file, creating it when it doesn't exist yet
+fn open_sqlite(path: &str) -> rusqlite::Result<rusqlite::Connection> {
+    let connection = rusqlite::Connection::open(path)?;
+    connection.execute_batch(
+        "PRAGMA foreign_keys = ON;
+        PRAGMA journal_mode = WAL;
+        PRAGMA busy_timeout = 5000;",
+    )?;
+    register_sqlite_functions(&connection)?;
+    // Room for every statement the bot runs, so the hot ones aren't pushed out of the cache
+    connection.set_prepared_statement_cache_capacity(256);
+    Ok(connection)
+}
+
+// Connections to the database, checking out a connection gives a TimedClient
+// The settings are kept for connecting again when the connection to the database breaks
+pub enum ClientPool {
+    Postgres {
+        pool: Pool,
+        config: Box<tokio_postgres::Config>,
+        size: usize,
+    },
+    Sqlite {
+        connection: Arc<std::sync::Mutex<rusqlite::Connection>>,
+        gate: Arc<tokio::sync::Mutex<()>>,
+        path: String,
+    },
+}
+
+impl ClientPool {
+    /// Builds a pool of up to size connections, connections are opened as they are checked out
+    pub fn postgres(config: tokio_postgres::Config, size: usize) -> ClientPool {
+        let manager = Manager::from_config(
+            config.clone(),
+            NoTls,
+            ManagerConfig {
+                recycling_method: RecyclingMethod::Fast,
+            },
+        );
+        let pool = Pool::builder(manager)
+            .max_size(size)
+            .build()
+            .expect("Building database pool failed");
+
+        ClientPool::Postgres {
+            pool,
+            config: Box::new(config),
+            size,
+        }
+    }
+
+    /// Opens the SQLite database at path, shared by every command
+    pub fn sqlite(path: String) -> Result<ClientPool, DbError> {
+        let connection = open_sqlite(&path)?;
+
+        Ok(ClientPool::Sqlite {
+            connection: Arc::new(std::sync::Mutex::new(connection)),
+            gate: Arc::new(tokio::sync::Mutex::new(())),
+            path,
+        })
+    }
+
+    /// Connects again with the same settings, for replacing a broken connection
+    pub fn reopen(&self) -> Result<ClientPool, DbError> {
+        match self {
+            ClientPool::Postgres { config, size, .. } => {
+                Ok(ClientPool::postgres(*config.clone(), *size))
+            }
+            ClientPool::Sqlite { path, .. } => ClientPool::sqlite(path.clone()),
+        }
+    }
+
+    /// Fails the Postgres connections still checked out
+    pub fn close(&self) {
+        if let ClientPool::Postgres { pool, .. } = self {
+            pool.close();
+        }
+    }
+
+    /// Checks the database answers on a connection of the pool, giving up after 10 seconds
+    pub async fn check(&self) -> Result<(), String> {
+        let check = async {
+            let client = self.get().await.map_err(|e| e.to_string())?;
+            client
+                .query("SELECT 1", &[])
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(())
+        };
+        match tokio::time::timeout(Duration::from_secs(10), check).await {
+            Ok(result) => result,
+            Err(_) => Err(String::from("the database didn't answer in time")),
+        }
+    }
+
+    pub async fn get(&self) -> Result<TimedClient, PoolError> {
+        let storage: Box<dyn DbConnection> = match self {
+            ClientPool::Postgres { pool, .. } => Box::new(pool.get().await?),
+            ClientPool::Sqlite {
+                connection, gate, ..
+            } => Box::new(SqliteStorage {
+                connection: connection.clone(),
+                gate: gate.clone(),
+                transaction: None,
+            }),
+        };
+
+        Ok(TimedClient { storage })
+    }
+}
+
+// Key of the Postgres advisory lock held by the process running the scheduled posts
+const LEADER_LOCK_KEY: i64 = 0x716f_7464;
+
+// Lead of the scheduled posts, held for as long as its connection stays open
+pub struct LeaderLock {
+    // None on SQLite, which doesn't need a lock
+    client: Option<tokio_postgres::Client>,
+}
+
+impl LeaderLock {
+    /// Checks the lock is still held, it's released when its connection is lost
+    pub async fn is_held(&self) -> bool {
+        match &self.client {
+            Some(client) => client.simple_query("SELECT 1").await.is_ok(),
+            None => true,
+        }
+    }
+}
+
+impl ClientPool {
+    /// Tries to take the lead of the scheduled posts, for deployments running more than one process.
+    /// The Postgres advisory lock is held on a connection of its own outside the pool, so it's released
+    /// as soon as the process or its connection goes away. Returns None while another process leads.
+    /// A SQLite database belongs to one process, it always leads.
+    pub async fn try_lead(&self) -> Result<Option<LeaderLock>, DbError> {
+        let config = match self {
+            ClientPool::Postgres { config, .. } => config,
+            ClientPool::Sqlite { .. } => return Ok(Some(LeaderLock { client: None })),
+        };
+
+        let (client, connection) = config.connect(NoTls).await?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                println!("Leader lock connection closed: {}", e);
+            }
+        });
+        let locked: bool = client
+            .query_one("SELECT pg_try_advisory_lock($1)", &[&LEADER_LOCK_KEY])
+            .await?
+            .get(0);
+
+        // Without the lock the connection is dropped right away
+        Ok(locked.then_some(LeaderLock {
+            client: Some(client),
+        }))
+    }
+}
+
+// Database connection recording how long the queries of the command being dispatched take
+pub struct TimedClient {
+    storage: Box<dyn DbConnection>,
+}
+
+impl TimedClient {
+    pub async fn query(
+        &self,
+        statement: &str,
+        params: &[&dyn SqlParam],
+    ) -> Result<Vec<Row>, DbError> {
+        let started = Instant::now();
+        let result = self.storage.query(statement, params).await;
+        record_query_time(statement, started.elapsed());
+        result
+    }
+
+    pub async fn query_one(
+        &self,
+        statement: &str,
+        params: &[&dyn SqlParam],
+    ) -> Result<Row, DbError> {
+        let rows = self.query(statement, params).await?;
+        rows.into_iter().next().ok_or(DbError::NoRows)
+    }
+
+    pub async fn execute(&self, statement: &str, params: &[&dyn SqlParam]) -> Result<u64, DbError> {
+        let started = Instant::now();
+        let result = self.storage.execute(statement, params).await;
+        record_query_time(statement, started.elapsed());
+        result
+    }
+
+    /// Starts a transaction on the connection, for steps that have to happen together or not at all
+    pub async fn transaction(mut self) -> Result<Transaction, DbError> {
+        self.storage.begin().await?;
+        Ok(Transaction { client: Some(self) })
+    }
+}
+
+// Transaction on a checked out connection, rolled back if it is dropped before it is committed
+pub struct Transaction {
+    client: Option<TimedClient>,
+}
+
+impl Transaction {
+    fn client(&self) -> &TimedClient {
+        self.client.as_ref().expect("Transaction already finished")
+    }
+
+    pub async fn query(
+        &self,
+        statement: &str,
+        params: &[&dyn SqlParam],
+    ) -> Result<Vec<Row>, DbError> {
+        self.client().query(statement, params).await
+    }
+
+    pub async fn execute(&self, statement: &str, params: &[&dyn SqlParam]) -> Result<u64, DbError> {
+        self.client().execute(statement, params).await
+    }
+
+    /// Commits the transaction, it is rolled back if committing fails
+    pub async fn commit(mut self) -> Result<(), DbError> {
+        let mut client = self.client.take().expect("Transaction already finished");
+        match client.storage.commit().await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                client.storage.abandon();
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            client.storage.abandon();
+        }
+    }
+}
+
+/// Brings the database schema up to date, applying every migration it doesn't have yet in its own transaction.
+pub async fn run_migrations(pool: &ClientPool) -> Result<(), DbError> {
+    match pool {
+        ClientPool::Postgres { pool, .. } => migrate_postgres(pool).await,
+        ClientPool::Sqlite { connection, .. } => {
+            migrate_sqlite(&mut connection.lock().expect("SQLite connection poisoned"))
+        }
+    }
+}
+
+/// Applies the Postgres migrations.
+/// Databases set up by hand with the old setup script before migrations are taken to be on the first one.
+async fn migrate_postgres(pool: &Pool) -> Result<(), DbError> {
+    let mut client = pool.get().await?;
+
+    client
+        .batch_execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version integer PRIMARY KEY,
+                name varchar NOT NULL,
+                applied_at timestamptz NOT NULL DEFAULT now()
+            )",
+        )
+        .await?;
+
+    let mut applied: HashSet<i32> = client
+        .query("SELECT version FROM schema_migrations", &[])
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    if applied.is_empty() {
+        let set_up_by_hand: bool = client
+            .query_one("SELECT to_regclass('channels') IS NOT NULL", &[])
+            .await?
+            .get(0);
+        if set_up_by_hand {
+            let (version, name, _) = POSTGRES_MIGRATIONS[0];
+            client
+                .execute(
+                    "INSERT INTO schema_migrations (version, name) VALUES ($1, $2)",
+                    &[&version, &name],
+                )
+                .await?;
+            applied.insert(version);
+            println!("Found a database set up without migrations, continuing from the first one");
+        }
+    }
+
+    for (version, name, migration) in POSTGRES_MIGRATIONS {
+        if applied.contains(version) {
+            continue;
+        }
+
+        let transaction = client.transaction().await?;
+        transaction.batch_execute(migration).await?;
+        transaction
+            .execute(
+                "INSERT INTO schema_migrations (version, name) VALUES ($1, $2)",
+                &[version, name],
+            )
+            .await?;
+        transaction.commit().await?;
+        println!("Applied database migration {} {}", version, name);
+    }
+
+    Ok(())
+}
+
+/// Applies the SQLite migrations, the same way as on Postgres
+fn migrate_sqlite(connection: &mut rusqlite::Connection) -> Result<(), DbError> {
+    connection.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version integer PRIMARY KEY,
+            name varchar NOT NULL,
+            applied_at timestamptz NOT NULL DEFAULT (now())
+        )",
+    )?;
+
+    let applied: HashSet<i32> = connection
+        .prepare("SELECT version FROM schema_migrations")?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    for (version, name, migration) in SQLITE_MIGRATIONS {
+        if applied.contains(version) {
+            continue;
+        }
+
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(migration)?;
+        transaction.execute(
+            "INSERT INTO schema_migrations (version, name) VALUES (?1, ?2)",
+            rusqlite::params![version, name],
+        )?;
+        transaction.commit()?;
+        println!("Applied database migration {} {}", version, name);
+    }
+
+    Ok(())
+}
+
+/// Marks a custom question as posted by the guild now, starting its cooldown
+async fn mark_custom_question_posted(
+    guild_id: &str,
+    question_id: i32,
+    client: &TimedClient,
+) -> Result<u64, DbError> {
+    client
+        .execute(
+            "INSERT INTO custom_question_posts (guild_id, question_id, last_posted)
+            VALUES ($1, $2, now())
+            ON CONFLICT (guild_id, question_id)
+            DO
+            UPDATE SET last_posted = EXCLUDED.last_posted",
+            &[&guild_id, &question_id],
+        )
+        .await
+}
+
+/// Queues a scheduled post of the guild in the outbox, once per kind and due time
+async fn queue_outbox_entry(
+    guild_id: &str,
+    kind: &str,
+    due: DateTime<Utc>,
+    transaction: &Transaction,
+) -> Result<u64, DbError> {
+    transaction
+        .execute(
+            "INSERT INTO outbox (guild_id, kind, due_at) VALUES ($1, $2, $3)
+            ON CONFLICT (guild_id, kind, due_at) DO NOTHING",
+            &[&guild_id, &kind, &due],
+        )
+        .await
+}
+
+// Quotas a guild can have, by name and the guild_settings column they're set in
+pub const QUOTA_COLUMNS: [(&str, &str); 3] = [
+    ("commands", "command_quota"),
+    ("dm_fanouts", "dm_fanout_quota"),
+    ("imports", "import_quota"),
+];
+
+// Custom questions a guild can have saved at once
+pub const CUSTOM_QUESTION_LIMIT: i64 = 100;
+// Tags a question can have at once
+pub const QUESTION_TAG_LIMIT: usize = 5;
+
+/// Adds and removes tags from the tags of a question, keeping them in the order they were added.
+/// Tags past QUESTION_TAG_LIMIT aren't added.
+fn merge_tags(mut tags: Vec<String>, add: Vec<String>, remove: Vec<String>) -> Vec<String> {
+    tags.retain(|tag| !remove.contains(tag));
+    for tag in add {
+        if tags.len() < QUESTION_TAG_LIMIT && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+// What came of editing a custom question
+pub enum QuestionEdit {
+    // The question was changed, with the text it had before
+    Edited(String),
+    // The guild has no question with the id
+    NotFound,
+    // The guild has another question with the same text
+    Duplicate,
+}
+
+// Where a question was taken from, kept for imported, API and pack questions so they can be credited
+#[derive(Clone, Default)]
+pub struct QuestionSource {
+    pub url: Option<String>,
+    pub license: Option<String>,
+}
+
+impl QuestionSource {
+    pub fn is_empty(&self) -> bool {
+        self.url.is_none() && self.license.is_none()
+    }
+}
+
+// Default questions of a pack missing their source or license, None is questions without a pack
+pub struct UnattributedPack {
+    pub pack_name: Option<String>,
+    pub questions: i64,
+    pub missing_source: i64,
+    pub missing_license: i64,
+    // Lowest id of the questions, to look them up with
+    pub first_id: i32,
+}
+
+// Everything the commands store, bundled so they can take one store
+pub trait Store: QuestionStore + PollStore + SettingsStore {}
+
+impl<T: QuestionStore + PollStore + SettingsStore> Store for T {}
+
+// Default questions, the custom questions of guilds and what they have posted
+#[async_trait]
+pub trait QuestionStore: Send + Sync {
+    /// Gets up to count different random questions from the database, with their pack attributions and tags.
+    /// Every question that can be picked is as likely to be, each is read at a random offset of the ones that can.
+    /// With tags, only questions with at least one of them are picked.
+    /// With a guild, questions in the question history of the guild are skipped,
+    /// and so are mature questions if the guild is family friendly.
+    async fn get_random_question_candidates(
+        &self,
+        count: i64,
+        tags: Option<Vec<String>>,
+        unused_in: Option<String>,
+    ) -> Result<Vec<(String, Option<String>, Vec<String>)>, DbError>;
+
+    /// Gets the tags of a question, looked up from the default questions and the custom questions of the guild.
+    /// Returns no tags if the question isn't found.
+    async fn get_question_tags(
+        &self,
+        guild_id: String,
+        question: String,
+    ) -> Result<Vec<String>, DbError>;
+
+    /// Records two questions posted side by side with their tags, for tallying which one members pick.
+    /// Both questions go into the question history of the guild like record_posted_question.
+    async fn record_double_post(
+        &self,
+        guild_id: String,
+        message_id: String,
+        questions: [String; 2],
+        tags: [Vec<String>; 2],
+    ) -> Result<u64, DbError>;
+
+    /// Changes the votes of one of the questions of a double post by change, choice 0 is the first question.
+    /// Does nothing if the message isn't a double post.
+    async fn update_double_post_tally(
+        &self,
+        message_id: String,
+        choice: i32,
+        change: i32,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the latest double posts of the guild, newest first.
+    /// Rows are the tags and votes of the first question, then the tags and votes of the second.
+    async fn get_double_post_votes(
+        &self,
+        guild_id: String,
+        limit: i64,
+    ) -> Result<Vec<Row>, DbError>;
+
+    /// Forgets which default questions the guild has posted, so they can be picked again.
+    /// With tags, only questions with at least one of them are forgotten.
+    async fn clear_question_history(
+        &self,
+        guild_id: String,
+        tags: Option<Vec<String>>,
+    ) -> Result<u64, DbError>;
+
+    /// Adds questions to the default pool from a pack, skipping ones the pool already has.
+    /// Every question of the pack is given the source of the pack.
+    /// Returns the amount of questions added.
+    async fn add_default_questions(
+        &self,
+        questions: Vec<String>,
+        pack_name: String,
+        pack_author: String,
+        source: QuestionSource,
+    ) -> Result<u64, DbError>;
+
+    /// Sets the source of every default question of the pack.
+    /// Returns the amount of questions updated, 0 if there is no pack with the name.
+    async fn set_pack_source(
+        &self,
+        pack_name: String,
+        source: QuestionSource,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the default questions in use missing their source or license, grouped by pack with the biggest first.
+    async fn get_unattributed_packs(&self) -> Result<Vec<UnattributedPack>, DbError>;
+
+    /// Adds and removes tags of a default question.
+    /// Returns the tags the question has after the change, None if there is no question with the id.
+    async fn update_default_question_tags(
+        &self,
+        question_id: i32,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Option<Vec<String>>, DbError>;
+
+    /// Adds and removes tags of a custom question of the guild, in a transaction so concurrent changes aren't lost.
+    /// Returns the tags the question has after the change, None if the guild has no question with the id.
+    async fn update_custom_question_tags(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Option<Vec<String>>, DbError>;
+
+    /// Flags a default question as mature or not.
+    /// Returns the amount of rows updated, 0 if there is no question with the id.
+    async fn set_default_question_mature(
+        &self,
+        question_id: i32,
+        mature: bool,
+    ) -> Result<u64, DbError>;
+
+    /// Flags a custom question of the guild as mature or not.
+    /// Returns the amount of rows updated, 0 if the guild has no question with the id.
+    async fn set_custom_question_mature(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        mature: bool,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the questions a random question shouldn't repeat in the guild.
+    /// These are the custom questions the guild can post, including shared pools, and the last 30 posts in the guild.
+    async fn get_taken_questions(&self, guild_id: String) -> Result<Vec<String>, DbError>;
+
+    /// Adds a custom question to the database with the associated guild_id
+    /// submitted_by is the id of the user who submitted the question, None for imported questions.
+    /// Questions with a flag_reason go into the moderation queue instead of the active pool.
+    /// Mature questions are never picked in family friendly guilds.
+    /// Returns 0 if the guild already has the same question saved, ignoring case, spacing and punctuation at the end.
+    async fn add_custom_question(
+        &self,
+        guild_id: String,
+        question: String,
+        submitted_by: Option<String>,
+        flag_reason: Option<String>,
+        mature: bool,
+    ) -> Result<u64, DbError>;
+
+    /// Adds many custom questions to the guild at once, like add_custom_question without moderation.
+    /// Done in one transaction with the limit check and an audit log entry, so either every question is added or none.
+    /// Questions past CUSTOM_QUESTION_LIMIT and ones the guild already has are left out, returns the amount of questions added.
+    /// Questions are added with their sources, a question the guild already had keeps its own.
+    async fn add_custom_questions(
+        &self,
+        guild_id: String,
+        questions: Vec<(String, QuestionSource)>,
+        submitted_by: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Sets the source of the guilds custom question with the text, for questions added through the API.
+    /// Returns the amount of rows updated, 0 if the guild has no such question.
+    async fn set_question_source(
+        &self,
+        guild_id: String,
+        question: String,
+        source: QuestionSource,
+    ) -> Result<u64, DbError>;
+
+    /// Deletes a specified question from the database.
+    /// Using the guild_id provided, the function checks ownership of the question matches the ID.
+    /// If match, the question is marked deleted, it can be restored until it's purged.
+    /// The deletion is recorded in the audit log in the same transaction, deleted_by is None for deletions through the API.
+    /// Returns 1 on successful deletion
+    /// Returns 0 if the guild has no such question.
+    async fn delete_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        deleted_by: Option<String>,
+    ) -> Result<i32, DbError>;
+
+    /// Deletes many custom questions of the guild at once, None deletes every question of the guild.
+    /// Only questions of the guild are deleted, the same as deleting one, and they can be restored until they're purged.
+    /// The deletion is recorded in the audit log in the same transaction.
+    /// Returns the ids of the deleted questions.
+    async fn delete_custom_questions(
+        &self,
+        guild_id: String,
+        question_ids: Option<Vec<i32>>,
+        deleted_by: String,
+    ) -> Result<Vec<i32>, DbError>;
+
+    /// Changes the text of a custom question, keeping its id.
+    /// Using the guild_id provided, the function checks ownership of the question matches the ID, the same as deleting.
+    /// The edit is recorded in the audit log in the same transaction.
+    async fn edit_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        question: String,
+        edited_by: String,
+    ) -> Result<QuestionEdit, DbError>;
+
+    /// Brings back a deleted custom question of the guild that hasn't been purged yet.
+    /// Returns the amount of rows changed, 0 if the guild has no deleted question with the id
+    /// or has the same question saved again since.
+    async fn restore_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the deleted custom questions of the guild that can still be restored, latest deletion first.
+    /// Rows contain the question id, question string and the day it was deleted.
+    async fn get_deleted_custom_questions(&self, guild_id: String) -> Result<Vec<Row>, DbError>;
+
+    /// Permanently removes custom questions deleted more than days ago, from every guild.
+    /// Returns the amount of questions purged.
+    async fn purge_deleted_questions(&self, days: i32) -> Result<u64, DbError>;
+
+    /// Approves questions of the guild waiting in the moderation queue, moving them to the active pool.
+    /// None approves the whole queue. Done in one statement, so either every question is approved or none.
+    /// Returns the amount of questions approved.
+    async fn approve_queued_questions(
+        &self,
+        guild_id: String,
+        question_ids: Option<Vec<i32>>,
+    ) -> Result<u64, DbError>;
+
+    /// Rejects questions of the guild waiting in the moderation queue, deleting them.
+    /// None rejects the whole queue. Done in one statement, so either every question is rejected or none.
+    /// Returns the amount of questions rejected.
+    async fn reject_queued_questions(
+        &self,
+        guild_id: String,
+        question_ids: Option<Vec<i32>>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets all the questions submitted by the guild_id and returns vector of rows
+    /// The admin note of each question is joined in as the note column.
+    async fn get_list_custom_questions(&self, guild_id: String) -> Result<Vec<Row>, DbError>;
+
+    /// Attaches a private admin note to a custom question of the guild, None removes the note.
+    /// Returns the amount of rows changed, 0 if the guild has no question with the id.
+    async fn set_question_note(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        note: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Sets the answer a custom question of the guild is graded against in trivia mode, None removes it.
+    /// Returns the amount of rows changed, 0 if the guild has no question with the id.
+    async fn set_question_answer(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        answer: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the trivia answer of a posted question, looked up from the custom questions the guild can pick from.
+    /// Returns None if the question has no answer.
+    async fn get_question_answer(
+        &self,
+        guild_id: String,
+        question: &str,
+    ) -> Result<Option<String>, DbError>;
+
+    /// Gets the id of the member who submitted a posted question, looked up from the custom questions the guild can pick from.
+    /// Returns None for questions without a submitter, like default and imported questions.
+    async fn get_question_submitter(
+        &self,
+        guild_id: String,
+        question: &str,
+    ) -> Result<Option<String>, DbError>;
+
+    /// Queries the database for a custom question
+    /// Guilds sharing a pool pick from each others questions too, but keep track of what they posted separately.
+    /// Questions still on cooldown are skipped. The cooldown is the per question override if one is set,
+    /// otherwise the guild default from guild_settings.
+    /// The selected question is marked as posted so the cooldown starts from now.
+    /// With fair rotation on, every submitter has the same chance of being picked no matter how many questions they submitted.
+    /// With tags, only questions with at least one of them are picked.
+    /// Mature questions are skipped if the guild is family friendly.
+    /// Returns the question string and the pack attribution, if the question came from a pack.
+    /// Returns None if no questions are available.
+    async fn get_random_custom_question(
+        &self,
+        guild_id: String,
+        tags: Option<Vec<String>>,
+    ) -> Result<Option<(String, Option<String>)>, DbError>;
+
+    /// Gets a specific custom question from the database based on id
+    /// Picking a question by id ignores the cooldown, but still marks the question as posted.
+    /// Questions from guilds sharing a pool can be picked too.
+    /// Returns the question string and the pack attribution, if the question came from a pack.
+    /// Returns None if the question doesn't exist.
+    async fn get_specific_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+    ) -> Result<Option<(String, Option<String>)>, DbError>;
+
+    /// Checks whether the question could still be picked for the guild, for a question drawn ahead of its post.
+    /// A deleted, retired or edited question can't, and neither can one the tags, custom_only or family friendly setting leave out.
+    /// The cooldown isn't checked, it started when the question was drawn.
+    async fn is_question_eligible(
+        &self,
+        guild_id: String,
+        question: String,
+        tags: Option<Vec<String>>,
+        custom_only: bool,
+    ) -> Result<bool, DbError>;
+
+    /// Reserves a custom question for the scheduled post on the date, replacing anything queued for it before.
+    /// Returns the amount of rows changed, 0 if the guild can't post a question with the id.
+    async fn queue_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        post_date: NaiveDate,
+    ) -> Result<u64, DbError>;
+
+    /// Removes the question queued for the date.
+    /// Returns the amount of rows deleted, 0 if nothing was queued.
+    async fn unqueue_custom_question(
+        &self,
+        guild_id: String,
+        post_date: NaiveDate,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the questions queued for today or later, soonest first.
+    /// Rows contain the date, question id and question string.
+    async fn get_queued_questions(
+        &self,
+        guild_id: String,
+        today: NaiveDate,
+    ) -> Result<Vec<Row>, DbError>;
+
+    /// Marks the question queued for the date consumed and returns its id.
+    /// Returns None if nothing is queued for the date or it was already posted.
+    async fn take_queued_question(
+        &self,
+        guild_id: String,
+        post_date: NaiveDate,
+    ) -> Result<Option<i32>, DbError>;
+
+    /// Retires or brings back a custom question of the guild, like in_use for the global questions.
+    /// Retired questions are never picked but are kept for their history.
+    /// Returns the amount of rows updated, 0 if the guild has no question with the id.
+    async fn set_custom_question_in_use(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        in_use: bool,
+    ) -> Result<u64, DbError>;
+
+    /// Replaces the text of a custom question, keeping its id.
+    /// Only updates the question if it belongs to the guild, returns the amount of rows updated.
+    async fn update_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        question: String,
+    ) -> Result<u64, DbError>;
+
+    /// Sets the cooldown override for a single custom question.
+    /// None clears the override and the question falls back to the guild default.
+    /// Only updates the question if it belongs to the guild, returns the amount of rows updated.
+    async fn set_custom_question_cooldown(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        cooldown_days: Option<i32>,
+    ) -> Result<u64, DbError>;
+
+    /// Records a posted question so it can be looked up later from the message it was posted in.
+    /// Default questions also go into the question history of the guild.
+    async fn record_posted_question(
+        &self,
+        guild_id: String,
+        channel_id: String,
+        message_id: String,
+        question: String,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the latest questions posted in the guild, newest first.
+    /// Rows are the question, channel, message and when it was posted.
+    async fn get_post_history(&self, guild_id: String, limit: i64) -> Result<Vec<Row>, DbError>;
+
+    /// Adds change to the thumbs up or down of a posted question, by the message it was posted as
+    async fn update_question_rating(
+        &self,
+        message_id: String,
+        up: bool,
+        change: i32,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the questions of the guild with the most thumbs up over thumbs down, or the fewest if worst is set,
+    /// counting every time they were posted. Rows contain the question, its thumbs up and its thumbs down.
+    async fn get_rated_questions(
+        &self,
+        guild_id: String,
+        worst: bool,
+        limit: i64,
+    ) -> Result<Vec<Row>, DbError>;
+
+    /// Gets the thumbs up and down of the questions across every guild, for the ones that were rated
+    async fn get_question_ratings(
+        &self,
+        questions: Vec<String>,
+    ) -> Result<HashMap<String, (i64, i64)>, DbError>;
+
+    /// Records how many answers the answer thread of a posted question got when it closed
+    async fn record_post_answers(&self, message_id: String, answers: i32) -> Result<u64, DbError>;
+
+    /// Gets the posts of the guild members engaged with most, counting answers and saves, most engaged first.
+    /// Rows contain the question, its tags if it's still a default or custom question, and the engagement.
+    /// Posts nobody answered or saved are left out.
+    async fn get_engaged_questions(
+        &self,
+        guild_id: String,
+        limit: i64,
+    ) -> Result<Vec<Row>, DbError>;
+
+    /// Gets the amount of questions posted across every guild
+    async fn get_posted_question_count(&self) -> Result<i64, DbError>;
+
+    /// Checks whether the guild already has the question saved, ignoring case, spacing and punctuation at the end
+    /// Matched on the stored question hash, so the questions don't have to be loaded.
+    async fn question_is_duplicate(
+        &self,
+        guild_id: String,
+        question: &str,
+    ) -> Result<bool, DbError>;
+
+    /// Checks whether the amount of custom question entries in the database is under the limit imposed by the function.
+    /// Returns true if the current count is under the limit
+    /// Returns false if the current count is over the limit
+    async fn question_is_under_limit(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Saves the question posted in the message to the users favorites.
+    /// Does nothing if the message isn't a posted question or the user already saved it.
+    async fn save_question_for_user(
+        &self,
+        user_id: String,
+        message_id: String,
+    ) -> Result<u64, DbError>;
+
+    /// Gets all questions saved by the user, oldest first.
+    /// Rows contain the question string and the date it was saved.
+    async fn get_saved_questions(&self, user_id: String) -> Result<Vec<Row>, DbError>;
+
+    /// Saves the answer thread of a posted trivia question, answers in the thread are graded against the answer
+    async fn add_trivia_round(
+        &self,
+        guild_id: String,
+        thread_id: String,
+        answer: String,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the answer of the trivia question the thread was opened for, and who won it.
+    /// Returns None if the thread isn't the answer thread of a trivia question.
+    async fn get_trivia_round(
+        &self,
+        thread_id: String,
+    ) -> Result<Option<(String, Option<String>)>, DbError>;
+
+    /// Sets the winner of a trivia round, None takes the win back.
+    /// Only sets a winner if nobody has won yet unless overriding, so two answers at once can't both win.
+    /// Returns true if the winner was set.
+    async fn set_trivia_winner(
+        &self,
+        thread_id: String,
+        winner_id: Option<String>,
+        overriding: bool,
+    ) -> Result<bool, DbError>;
+
+    /// Adds trivia points to the member, negative points take them away.
+    /// Returns the points the member has now.
+    async fn add_trivia_points(
+        &self,
+        guild_id: String,
+        user_id: String,
+        points: i32,
+    ) -> Result<i32, DbError>;
+
+    /// Gets the ten members of the guild with the most trivia points.
+    /// Rows contain the user id and their points.
+    async fn get_trivia_scores(&self, guild_id: String) -> Result<Vec<Row>, DbError>;
+
+    /// Creates an invite code for joining the guilds shared pool, valid for a day.
+    /// The guild gets a new pool if it isn't sharing one yet.
+    async fn create_pool_invite(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Gets the pool an invite code is for.
+    /// Returns None if the code doesn't exist or has expired.
+    async fn get_pool_invite(&self, code: String) -> Result<Option<i32>, DbError>;
+
+    /// Adds the guild to the shared pool and uses up the invite code
+    async fn join_pool(&self, guild_id: String, pool_id: i32, code: String)
+        -> Result<u64, DbError>;
+
+    /// Takes the guild out of its shared pool. Its own questions stay with it.
+    async fn leave_pool(&self, guild_id: String) -> Result<u64, DbError>;
+
+    /// Gets the ids of the other guilds sharing a pool with the guild.
+    /// Returns an empty vector if the guild isn't sharing a pool.
+    async fn get_pool_partners(&self, guild_id: String) -> Result<Vec<String>, DbError>;
+
+    /// Checks whether the guild is part of a shared pool
+    async fn is_in_pool(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Gets up to 25 custom questions or polls of the guild whose id starts with, or text contains, what was typed.
+    /// Returns the id and text of each, poll options are joined after the question.
+    async fn get_id_suggestions(
+        &self,
+        guild_id: String,
+        source: IdSource,
+        typed: String,
+    ) -> Result<Vec<(i32, String)>, DbError>;
+
+    /// Saves a draft of a question or poll for the user, kind is question or poll.
+    /// content is the question alone, or the question and the two options of a poll.
+    /// Returns the id of the draft.
+    async fn add_draft(
+        &self,
+        guild_id: String,
+        user_id: String,
+        kind: &str,
+        content: Vec<String>,
+    ) -> Result<i32, DbError>;
+
+    /// Gets the drafts the user has saved in the guild, oldest first.
+    /// Rows are the id, kind and content of the draft.
+    async fn get_drafts(&self, guild_id: String, user_id: String) -> Result<Vec<Row>, DbError>;
+
+    /// Gets a draft of the user by id, drafts of other users can't be read.
+    /// Returns the kind and content of the draft, None if the user has no draft with the id.
+    async fn get_draft(
+        &self,
+        guild_id: String,
+        user_id: String,
+        draft_id: i32,
+    ) -> Result<Option<(String, Vec<String>)>, DbError>;
+
+    /// Deletes a draft of the user.
+    /// Returns the amount of rows deleted, 0 if the user has no draft with the id.
+    async fn delete_draft(
+        &self,
+        guild_id: String,
+        user_id: String,
+        draft_id: i32,
+    ) -> Result<u64, DbError>;
+}
+
+// Default polls, the custom polls of guilds and the polls they have posted
+#[async_trait]
+pub trait PollStore: Send + Sync {
+    /// Checking whether the server has reached its limit on polls submitted to the database
+    /// Returns true if server is under the limit
+    /// Returns false if server is over limit
+    async fn poll_is_under_limit(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Gets a random poll from the database and returns its id and the poll.
+    /// Returns None if there are no polls in use.
+    async fn get_random_poll(&self) -> Result<Option<(i32, Vec<String>)>, DbError>;
+
+    /// Inserts a custom poll into the database and associates it with a guild_id
+    /// emoji of None uses the default 🟠 and 🔵, duration_hours of None keeps the poll open.
+    /// Anonymous polls are voted on with buttons instead of reactions.
+    async fn add_custom_poll(
+        &self,
+        guild_id: String,
+        new_poll: Vec<String>,
+        emoji: Option<Vec<String>>,
+        duration_hours: Option<i32>,
+        anonymous: bool,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the emoji, duration, anonymity and role weighting of a custom poll.
+    /// Returns the defaults, 🟠 and 🔵 with no time limit, public votes and no weighting, if the poll has none set.
+    async fn get_custom_poll_settings(
+        &self,
+        poll_id: i32,
+    ) -> Result<(Vec<String>, Option<i32>, bool, bool), DbError>;
+
+    /// Turns weighting the votes by role on or off for a custom poll of the guild.
+    /// Returns the amount of rows updated, 0 if the poll doesn't belong to the guild.
+    async fn set_custom_poll_weighted(
+        &self,
+        guild_id: String,
+        poll_id: i32,
+        weighted: bool,
+    ) -> Result<u64, DbError>;
+
+    /// Sets how many votes a member with the role counts as on weighted polls of the guild.
+    /// None removes the weight, the role counts as one vote again.
+    async fn set_poll_role_weight(
+        &self,
+        guild_id: String,
+        role_id: String,
+        weight: Option<i32>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the role weights of the guild, heaviest first.
+    /// Rows contain the role id and its weight.
+    async fn get_poll_role_weights(&self, guild_id: String) -> Result<Vec<Row>, DbError>;
+
+    /// Returns a random custom poll from the list of polls saved in the database for the guild.
+    /// Returns the id of the poll alongside the poll.
+    /// Returns an empty array if no custom polls are saved
+    async fn get_random_custom_poll(&self, guild_id: String)
+        -> Result<(i32, Vec<String>), DbError>;
+
+    /// Returns a custom poll from the database using a specified id
+    /// Returns the id of the poll alongside the poll, an empty array if the poll doesn't exist
+    async fn get_specific_custom_poll(
+        &self,
+        guild_id: String,
+        poll_id: i32,
+    ) -> Result<(i32, Vec<String>), DbError>;
+
+    /// Returns a vector of rows containing all the custom polls saved for the server
+    /// Returns and empty vector if no polls exist.
+    async fn get_list_of_custom_polls(&self, guild_id: String) -> Result<Vec<Row>, DbError>;
+
+    /// Records a posted poll so the votes on it can be tallied and kept after the poll is done.
+    /// custom tells whether poll_id refers to custom_polls or the default polls.
+    /// Votes on weighted polls count with the weight of the voters heaviest role.
+    #[allow(clippy::too_many_arguments)]
+    async fn record_posted_poll(
+        &self,
+        guild_id: String,
+        poll_id: i32,
+        custom: bool,
+        channel_id: String,
+        message_id: String,
+        emoji: Vec<String>,
+        anonymous: bool,
+        weighted: bool,
+    ) -> Result<u64, DbError>;
+
+    /// Counts a button vote on an anonymous poll, changing the users earlier vote if they had one.
+    /// A user only ever has one vote on a poll. On weighted polls the vote weighs as much as the heaviest of role_ids.
+    /// Returns the new tallies, or None if the message isn't an open anonymous poll.
+    async fn cast_anonymous_vote(
+        &self,
+        message_id: String,
+        user_id: String,
+        option: i32,
+        role_ids: Vec<String>,
+    ) -> Result<Option<(i32, i32)>, DbError>;
+
+    /// Gets what the votes on a weighted poll are made of.
+    /// Rows contain the option, the role the votes were weighted by, None for members without a weighted role,
+    /// the weight and the amount of voters, heaviest first for each option.
+    /// Returns no rows if the poll isn't weighted.
+    async fn get_poll_vote_breakdown(&self, message_id: String) -> Result<Vec<Row>, DbError>;
+
+    /// Gets the question and options of a posted poll, and the emoji of the options.
+    /// Returns None if the poll isn't found, or the custom poll was deleted after posting.
+    async fn get_posted_poll(
+        &self,
+        message_id: String,
+    ) -> Result<Option<(Vec<String>, Vec<String>)>, DbError>;
+
+    /// Gets the ids of the users who have voted on an anonymous poll.
+    /// Returns None if the poll is closed or isn't an anonymous poll.
+    async fn get_open_poll_voters(
+        &self,
+        message_id: String,
+    ) -> Result<Option<Vec<String>>, DbError>;
+
+    /// Marks a posted poll closed so it stops counting votes.
+    /// Returns the final tallies, or None if the poll was already closed.
+    async fn close_poll_post(&self, message_id: String) -> Result<Option<(i32, i32)>, DbError>;
+
+    /// Adds change to the tally of the option the emoji stands for on a posted poll.
+    /// Does nothing if the message isn't an open poll voted on with reactions, or the emoji isn't one of its options.
+    async fn update_poll_tally(
+        &self,
+        message_id: String,
+        emoji: String,
+        change: i32,
+    ) -> Result<u64, DbError>;
+
+    /// Adds an option to an open poll posted in the guild, keeping the tallies from before it was added.
+    /// A poll can only be amended once.
+    /// Returns the amount of rows changed, 0 if the message isn't an open poll of the guild or it was amended already.
+    async fn amend_poll(
+        &self,
+        guild_id: String,
+        message_id: String,
+        option: String,
+        emoji: String,
+        amended_by: String,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the option added to a posted poll.
+    /// Rows contain the option, its emoji, its votes, the tallies of the first two options when it was added
+    /// and when it was added.
+    /// Returns None if the poll hasn't been amended.
+    async fn get_poll_amendment(&self, message_id: String) -> Result<Option<Row>, DbError>;
+
+    /// Gets the results of every time the custom poll has been posted in the guild, oldest first.
+    /// Rows contain the date posted and the tallies for both options.
+    async fn get_poll_history(&self, guild_id: String, poll_id: i32) -> Result<Vec<Row>, DbError>;
+
+    /// Replaces a custom poll, keeping its id.
+    /// Only updates the poll if it belongs to the guild, returns the amount of rows updated.
+    async fn update_custom_poll(
+        &self,
+        guild_id: String,
+        poll_id: i32,
+        poll: Vec<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Deletes a custom poll based on a ID
+    /// Checks guild_id of the requesting command against the guild_id associated with the poll
+    /// The deletion is recorded in the audit log in the same transaction.
+    async fn delete_custom_poll(
+        &self,
+        guild_id: String,
+        id_to_delete: i32,
+        deleted_by: String,
+    ) -> Result<i32, DbError>;
+}
+
+// Per guild settings, the channel and ping role included
+#[async_trait]
+pub trait SettingsStore: Send + Sync {
+    /// Setting the channel id from the database for the server id in question
+    /// guild_id is from parsed within the command.
+    /// channel_id: String - Channel id to be set in the database
+    /// set_by: String - Id of the admin setting the channel, told when posting to it fails
+    async fn set_ping_channel_id(
+        &self,
+        channel_id: String,
+        guild_id: String,
+        set_by: String,
+    ) -> Result<u64, DbError>;
+
+    /// Pulls channel id formatted for parse_channel() from the database using the guild id.
+    /// Returns "0" if no result
+    async fn get_ping_channel_id(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Gets the id of the admin who set the channel of the guild.
+    /// Returns None if no channel is set or it was set before this was kept.
+    async fn get_channel_set_by(&self, guild_id: String) -> Result<Option<String>, DbError>;
+
+    /// Sets the channel questions and polls are posted to when posting to their own channel fails.
+    /// None turns the backup channel off.
+    async fn set_backup_channel_id(
+        &self,
+        guild_id: String,
+        channel_id: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Pulls the backup channel id formatted for parse_channel() from the database using the guild id.
+    /// Returns "0" if no backup channel is set
+    async fn get_backup_channel_id(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Sets the channel and message of the guilds pinned setup checklist, None forgets it
+    async fn set_checklist_message(
+        &self,
+        guild_id: String,
+        message: Option<(String, String)>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the channel and message of the guilds pinned setup checklist.
+    /// Returns None if the guild has none.
+    async fn get_checklist_message(
+        &self,
+        guild_id: String,
+    ) -> Result<Option<(String, String)>, DbError>;
+
+    /// Adds an admin to the contacts told about operational problems in the guild.
+    /// Returns 0 if they are already a contact.
+    async fn add_admin_contact(&self, guild_id: String, user_id: String) -> Result<u64, DbError>;
+
+    /// Removes an admin from the contacts of the guild, returns the amount of rows deleted.
+    async fn remove_admin_contact(&self, guild_id: String, user_id: String)
+        -> Result<u64, DbError>;
+
+    /// Gets the ids of the admin contacts of the guild, in the order they were added.
+    async fn get_admin_contacts(&self, guild_id: String) -> Result<Vec<String>, DbError>;
+
+    /// Sets whether notifications go to all admin contacts or rotate between them.
+    async fn set_notify_policy(&self, guild_id: String, policy: String) -> Result<u64, DbError>;
+
+    /// Gets the notification policy of the guild.
+    /// Returns "all" if none is set.
+    async fn get_notify_policy(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Moves the rotation on to the next admin contact, returns how many notifications were rotated before this one.
+    async fn take_notify_turn(&self, guild_id: String) -> Result<i32, DbError>;
+
+    /// Turns posting in the invoking channel on or off, for guilds that haven't set a channel yet
+    async fn set_channel_fallback(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether commands post in the invoking channel while no channel is set, on by default
+    async fn get_channel_fallback(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Turns trivia mode on or off, questions with an answer get graded in their answer thread
+    async fn set_trivia_mode(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether trivia mode is on, off by default
+    async fn get_trivia_mode(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Sets how strictly question submissions are rated in the guild.
+    async fn set_moderation_strictness(
+        &self,
+        guild_id: String,
+        strictness: String,
+    ) -> Result<u64, DbError>;
+
+    /// Gets how strictly question submissions are rated in the guild, off, low or high.
+    /// Returns "off" if the setting has never been changed.
+    async fn get_moderation_strictness(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Turns showing the pack attribution in posted questions on or off
+    async fn set_show_attribution(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether the guild shows pack attribution in posted questions.
+    /// Returns true if the setting has never been changed.
+    async fn get_show_attribution(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Turns showing who submitted a custom question in posted questions on or off
+    async fn set_show_submitter(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether the guild shows who submitted custom questions in posted questions.
+    /// Returns false if the setting has never been changed.
+    async fn get_show_submitter(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Turns pinging the submitter of a posted custom question on or off
+    async fn set_mention_submitter(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether the guild pings the submitter of a posted custom question.
+    /// Returns false if the setting has never been changed.
+    async fn get_mention_submitter(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Sets the default cooldown in days for the guilds custom questions.
+    /// A question can't be randomly picked again until the cooldown has passed since it was last posted.
+    /// 0 disables the cooldown.
+    async fn set_question_cooldown(
+        &self,
+        guild_id: String,
+        cooldown_days: i32,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the default cooldown in days for the guilds custom questions.
+    /// Returns 0 if no cooldown has been set.
+    async fn get_question_cooldown(&self, guild_id: String) -> Result<i32, DbError>;
+
+    /// Turns fair rotation between submitters on or off for the guilds custom questions
+    async fn set_fair_rotation(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether custom questions rotate fairly between submitters in the guild.
+    /// Returns false if the setting has never been changed.
+    async fn get_fair_rotation(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Turns picking questions only from the guilds custom questions on or off
+    async fn set_custom_only(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether random and scheduled questions of the guild come only from its custom questions.
+    /// Returns false if the setting has never been changed.
+    async fn get_custom_only(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Sets the percentage of random and scheduled questions picked from the guilds custom questions.
+    /// None turns the mix off.
+    async fn set_question_mix(
+        &self,
+        guild_id: String,
+        custom_share: Option<i32>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the percentage of random and scheduled questions picked from the guilds custom questions.
+    /// Returns None if no mix has been set.
+    async fn get_question_mix(&self, guild_id: String) -> Result<Option<i32>, DbError>;
+
+    /// Turns preferring default questions in season on or off for the guild
+    async fn set_seasonal(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether the guild prefers default questions in season and holds back the ones out of season.
+    /// Returns true if it has not been set.
+    async fn get_seasonal(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Sets a quota of the guild, one of QUOTA_COLUMNS by its name.
+    /// None goes back to the quota configured for every guild.
+    async fn set_quota(
+        &self,
+        guild_id: String,
+        quota: &str,
+        amount: Option<i32>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the quotas set for the guild, in the order of QUOTA_COLUMNS.
+    /// A quota that hasn't been set is None.
+    async fn get_quotas(&self, guild_id: String) -> Result<[Option<i32>; 3], DbError>;
+
+    /// Turns leaving mature questions out of picking on or off for the guild
+    async fn set_family_friendly(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether mature questions are left out of picking in the guild.
+    /// Returns false if the setting has never been changed.
+    async fn get_family_friendly(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Turns preferring questions like the most engaged ones on or off for the guild
+    async fn set_recommend(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether the guild prefers questions like the ones its members engaged with most.
+    /// Returns false if the setting has never been changed.
+    async fn get_recommend(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Sets the archive channel for the guild. Posted questions and polls are mirrored there without pings.
+    /// None turns the archive off.
+    async fn set_archive_channel_id(
+        &self,
+        guild_id: String,
+        channel_id: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Pulls the archive channel id formatted for parse_channel() from the database using the guild id.
+    /// Returns "0" if no archive channel is set
+    async fn get_archive_channel_id(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Restricts bot commands in the guild to the channel. None allows them in every channel.
+    async fn set_command_channel_id(
+        &self,
+        guild_id: String,
+        channel_id: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Pulls the channel bot commands are restricted to, formatted for parse_channel(), using the guild id.
+    /// Returns "0" if commands aren't restricted
+    async fn get_command_channel_id(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Sets the emoji members react with to save a posted question
+    async fn set_save_emoji(&self, guild_id: String, emoji: String) -> Result<u64, DbError>;
+
+    /// Gets the emoji members react with to save a posted question.
+    /// Returns 📌 if the guild hasn't set one.
+    async fn get_save_emoji(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Sets how many hours the discussion thread of a posted question stays open.
+    /// None turns answer threads off.
+    async fn set_answer_window(&self, guild_id: String, hours: Option<i32>)
+        -> Result<u64, DbError>;
+
+    /// Gets how many hours the discussion thread of a posted question stays open.
+    /// Returns None if answer threads are off.
+    async fn get_answer_window(&self, guild_id: String) -> Result<Option<i32>, DbError>;
+
+    /// Sets the shortest answer in characters that counts in answer threads,
+    /// and how many answers of one member count at most, None counts every answer.
+    async fn set_answer_filter(
+        &self,
+        guild_id: String,
+        min_length: i32,
+        member_limit: Option<i32>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the shortest answer that counts and how many answers of one member count.
+    /// Returns (3, None) if the filter has never been changed.
+    async fn get_answer_filter(&self, guild_id: String) -> Result<(i32, Option<i32>), DbError>;
+
+    /// Sets how many seconds the bots replies stay before they're cleaned up, None turns cleanup off.
+    /// delete_command also cleans up the command that was replied to.
+    async fn set_cleanup(
+        &self,
+        guild_id: String,
+        seconds: Option<i32>,
+        delete_command: bool,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the cleanup delay in seconds and whether commands are cleaned up too.
+    /// Returns (None, false) if cleanup has never been turned on.
+    async fn get_cleanup(&self, guild_id: String) -> Result<(Option<i32>, bool), DbError>;
+
+    /// Sets the guilds fallback text, posted instead of a question when there is nothing to pick from.
+    /// None goes back to the default text.
+    async fn set_fallback_question(
+        &self,
+        guild_id: String,
+        fallback: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the text posted instead of a question when there is nothing to pick from.
+    /// The default text is used if the guild hasn't set one.
+    async fn get_fallback_question(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Turns accessibility mode on or off, questions and polls are posted as plain text with the options spelled out.
+    async fn set_accessible_posts(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether the guild has accessibility mode on.
+    /// Returns false if the setting has never been changed.
+    async fn get_accessible_posts(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Limits the questions picked for the guild to ones with at least one of the tags, None picks from every question.
+    async fn set_question_filter(
+        &self,
+        guild_id: String,
+        tags: Option<Vec<String>>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the tags the questions picked for the guild are limited to.
+    /// Returns None if there is no filter.
+    async fn get_question_filter(&self, guild_id: String) -> Result<Option<Vec<String>>, DbError>;
+
+    /// Turns posting questions of the day as image cards on or off for the guild.
+    async fn set_image_cards(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether the guild gets its questions of the day as image cards.
+    /// Returns false if it has not been set.
+    async fn get_image_cards(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Sets the background color of the guilds image cards as a hex color, None goes back to the default.
+    async fn set_card_color(&self, guild_id: String, color: Option<String>)
+        -> Result<u64, DbError>;
+
+    /// Sets the template the guilds image cards are drawn with.
+    async fn set_card_template(&self, guild_id: String, template: String) -> Result<u64, DbError>;
+
+    /// Gets the color and template of the guilds image cards.
+    /// The defaults are DEFAULT_CARD_COLOR and the plain template.
+    async fn get_card_style(&self, guild_id: String) -> Result<CardStyle, DbError>;
+
+    /// Sets where the question events of the guild are sent, a channel id or a webhook URL. None stops sending them.
+    async fn set_modlog_target(
+        &self,
+        guild_id: String,
+        target: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the channel id or webhook URL the question events of the guild are sent to.
+    /// Returns None if they aren't sent anywhere.
+    async fn get_modlog_target(&self, guild_id: String) -> Result<Option<String>, DbError>;
+
+    /// Limits the question events sent to the modlog of the guild, None sends every event.
+    async fn set_modlog_events(
+        &self,
+        guild_id: String,
+        events: Option<Vec<String>>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the question events sent to the modlog of the guild.
+    /// Returns None if every event is sent.
+    async fn get_modlog_events(&self, guild_id: String) -> Result<Option<Vec<String>>, DbError>;
+
+    /// Turns voting with buttons on or off for every poll of the guild, instead of reacting.
+    async fn set_poll_buttons(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether the guild votes on every poll with buttons.
+    /// Returns false if it has not been set, polls are voted on with reactions by default.
+    async fn get_poll_buttons(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Turns private replies to admin slash commands on or off for the guild.
+    async fn set_ephemeral_replies(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether admin slash commands reply privately in the guild.
+    /// Returns true if it has not been set, configuration replies don't clutter channels by default.
+    async fn get_ephemeral_replies(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Turns sending the guild owner an export of the guild when the bot is removed on or off.
+    async fn set_removal_export(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether the guild owner is sent an export when the bot is removed.
+    /// Returns true if it has not been set, so question banks aren't lost by accident.
+    async fn get_removal_export(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Sets how members with the ping role who haven't voted are nudged halfway through anonymous polls
+    /// "off" disables the nudge, "ping" mentions them under the poll and "dm" sends them a DM.
+    async fn set_poll_nudge(&self, guild_id: String, mode: String) -> Result<u64, DbError>;
+
+    /// Gets the poll nudge mode of the guild.
+    /// Returns "off" if it has not been set.
+    async fn get_poll_nudge(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Sets the line posted with the guilds polls, None goes back to the default.
+    async fn set_poll_intro(&self, guild_id: String, intro: Option<String>)
+        -> Result<u64, DbError>;
+
+    /// Sets the title template of the guilds poll embeds, None goes back to the default.
+    async fn set_poll_title(&self, guild_id: String, title: Option<String>)
+        -> Result<u64, DbError>;
+
+    /// Gets the intro line and the embed title template of the guilds polls, unfilled.
+    /// The defaults are "Poll of the day!" and the poll question itself.
+    async fn get_poll_texts(&self, guild_id: String) -> Result<(String, String), DbError>;
+
+    /// Pauses or resumes scheduled questions and polls for the guild.
+    /// Resuming moves the next posts to the next times on the schedules, the runs missed while paused are skipped.
+    async fn set_paused(
+        &self,
+        guild_id: String,
+        paused: bool,
+        next_post_at: Option<DateTime<Utc>>,
+        next_poll_at: Option<DateTime<Utc>>,
+    ) -> Result<u64, DbError>;
+
+    /// Checks whether scheduled posting is paused for the guild.
+    /// Returns false if the guild has never been paused.
+    async fn get_paused(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Sets the cron schedule (minute hour day month weekday, local time) the guild gets questions posted on.
+    /// None turns scheduled posting off.
+    /// next_post_at is when the next scheduled question is due.
+    async fn set_post_schedule(
+        &self,
+        guild_id: String,
+        schedule: Option<String>,
+        next_post_at: Option<DateTime<Utc>>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the cron schedule of the guild and when the next scheduled question is due.
+    /// Returns None if scheduled posting is off.
+    async fn get_post_schedule(
+        &self,
+        guild_id: String,
+    ) -> Result<Option<(String, Option<DateTime<Utc>>)>, DbError>;
+
+    /// Gets every guild with a scheduled question, teaser or poll due by the tick, paused guilds are left out.
+    /// Guilds without a due time yet are included so it gets calculated.
+    /// Rows have the schedules of both questions and polls, with the channel, ping role and mention template
+    /// for posting joined in so posting doesn't have to look them up per guild.
+    async fn get_due_schedules(&self, tick: DateTime<Utc>) -> Result<Vec<Row>, DbError>;
+
+    /// Sets the weekdays scheduled questions and polls aren't posted on, stored as weekday names like "sat".
+    async fn set_skip_days(&self, guild_id: String, skip_days: Vec<String>)
+        -> Result<u64, DbError>;
+
+    /// Gets the weekdays scheduled questions and polls aren't posted on.
+    /// Returns no days if none have been set.
+    async fn get_skip_days(&self, guild_id: String) -> Result<Vec<Weekday>, DbError>;
+
+    /// Sets the weekdays two scheduled questions are posted side by side on, stored as weekday names like "sat".
+    async fn set_double_days(
+        &self,
+        guild_id: String,
+        double_days: Vec<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the weekdays two scheduled questions are posted side by side on.
+    /// Returns no days if none have been set.
+    async fn get_double_days(&self, guild_id: String) -> Result<Vec<Weekday>, DbError>;
+
+    /// Gets the question drawn for the guilds next scheduled post and its attribution.
+    /// Returns None if nothing has been drawn yet.
+    async fn get_drawn_question(
+        &self,
+        guild_id: String,
+    ) -> Result<Option<(String, Option<String>)>, DbError>;
+
+    /// Takes the drawn question off the guild for posting it.
+    /// Returns None if nothing was drawn, so the same question is never posted twice.
+    async fn take_drawn_question(
+        &self,
+        guild_id: String,
+    ) -> Result<Option<(String, Option<String>)>, DbError>;
+
+    /// Sets the cron schedule the guild gets polls posted on, in the same format as the question schedule.
+    /// None turns scheduled polls off.
+    /// poll_source is "default" for polls from the global pool or "custom" for the guilds own polls.
+    async fn set_poll_schedule(
+        &self,
+        guild_id: String,
+        schedule: Option<String>,
+        poll_source: String,
+        next_poll_at: Option<DateTime<Utc>>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the poll schedule of the guild, where the polls are picked from and when the next one is due.
+    /// Returns None if scheduled polls are off.
+    async fn get_poll_schedule(
+        &self,
+        guild_id: String,
+    ) -> Result<Option<(String, String, Option<DateTime<Utc>>)>, DbError>;
+
+    /// Moves the guilds next scheduled poll from due to next, queueing the poll due in the outbox.
+    /// Returns false if another check already moved it.
+    async fn claim_scheduled_poll(
+        &self,
+        guild_id: String,
+        due: Option<DateTime<Utc>>,
+        next: Option<DateTime<Utc>>,
+    ) -> Result<bool, DbError>;
+
+    /// Moves the guilds next scheduled question from due to next, queueing the question due in the outbox.
+    /// Returns false if another check already moved it, so a question is never posted twice for the same time.
+    async fn claim_scheduled_post(
+        &self,
+        guild_id: String,
+        due: Option<DateTime<Utc>>,
+        next: Option<DateTime<Utc>>,
+    ) -> Result<bool, DbError>;
+
+    /// Gets the outbox entries waiting to be sent as of now, oldest first,
+    /// with the schedule settings of their guild for posting them
+    async fn get_due_outbox(&self, now: DateTime<Utc>) -> Result<Vec<Row>, DbError>;
+
+    /// Takes an outbox entry for sending, holding it for lease_seconds.
+    /// Returns false if it was taken since it was loaded, so only one attempt runs at a time.
+    async fn claim_outbox_entry(
+        &self,
+        outbox_id: i32,
+        attempts: i32,
+        lease_seconds: i32,
+    ) -> Result<bool, DbError>;
+
+    /// Tries an outbox entry again after the given seconds
+    async fn retry_outbox_entry(&self, outbox_id: i32, seconds: i32) -> Result<u64, DbError>;
+
+    /// Settles an outbox entry as "sent" with the message it was posted as, or as "failed"
+    async fn settle_outbox_entry(
+        &self,
+        outbox_id: i32,
+        status: &str,
+        posted: Option<(String, String)>,
+    ) -> Result<u64, DbError>;
+
+    /// Finds the first post of the kind the guild got since the given time, as its channel and message.
+    /// Used to tell whether an attempt that was cut off posted before it stopped.
+    async fn find_scheduled_post(
+        &self,
+        guild_id: String,
+        kind: String,
+        since: DateTime<Utc>,
+    ) -> Result<Option<(String, String)>, DbError>;
+
+    /// Gets the latest outbox entries of the guild, newest first
+    async fn get_outbox(&self, guild_id: String, limit: i64) -> Result<Vec<Row>, DbError>;
+
+    /// Sets how many minutes before the scheduled question a teaser is posted.
+    /// None turns teasers off.
+    async fn set_teaser_minutes(
+        &self,
+        guild_id: String,
+        minutes: Option<i32>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets how many minutes before the scheduled question a teaser is posted.
+    /// Returns None if teasers are off.
+    async fn get_teaser_minutes(&self, guild_id: String) -> Result<Option<i32>, DbError>;
+
+    /// Marks the teaser for the scheduled question at next_post as posted.
+    /// Returns false if it was already teased.
+    async fn claim_teaser(
+        &self,
+        guild_id: String,
+        next_post: DateTime<Utc>,
+    ) -> Result<bool, DbError>;
+
+    /// Sets the timezone the guilds schedule runs in, None goes back to UTC.
+    async fn set_guild_timezone(
+        &self,
+        guild_id: String,
+        timezone: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the timezone the guilds schedule runs in.
+    /// Returns UTC if the timezone has never been set.
+    async fn get_guild_timezone(&self, guild_id: String) -> Result<Tz, DbError>;
+
+    /// Saves a role id to be used to ping into the database.
+    /// guild_id is the id of the server the command is called from.
+    /// 0 is used for no ping
+    /// 1 is used for EVERYONE
+    /// submitted id is used for specific role
+    async fn set_ping_role(&self, guild_id: String, ping_role: String) -> Result<u64, DbError>;
+
+    /// Gets the role id to be used for pinging based on the guild_id
+    ///  0 is used for no ping
+    /// 1 is used for EVERYONE
+    /// submitted id is used for specific role
+    async fn get_ping_role(&self, guild_id: String) -> Result<String, DbError>;
+
+    /// Sets the mention template of the default stream, None goes back to the default mention.
+    async fn set_mention_template(
+        &self,
+        guild_id: String,
+        template: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the mention template of the default stream.
+    /// Returns None if the guild uses the default mention.
+    async fn get_mention_template(&self, guild_id: String) -> Result<Option<String>, DbError>;
+
+    /// Turns the second admin approval for @everyone pings on or off
+    async fn set_everyone_approval(&self, guild_id: String, enabled: bool) -> Result<u64, DbError>;
+
+    /// Checks whether @everyone pings need approval from a second admin in the guild.
+    /// Returns false if the setting has never been changed.
+    async fn get_everyone_approval(&self, guild_id: String) -> Result<bool, DbError>;
+
+    /// Blocks the member from the submission commands of the guild.
+    /// Returns the amount of rows changed, 0 if the member was blocked already.
+    async fn block_user(
+        &self,
+        guild_id: String,
+        user_id: String,
+        blocked_by: String,
+    ) -> Result<u64, DbError>;
+
+    /// Lets a blocked member use the submission commands again.
+    /// Returns the amount of rows changed, 0 if the member wasn't blocked.
+    async fn unblock_user(&self, guild_id: String, user_id: String) -> Result<u64, DbError>;
+
+    /// Checks whether the member is blocked from the submission commands of the guild
+    async fn is_user_blocked(&self, guild_id: String, user_id: String) -> Result<bool, DbError>;
+
+    /// Gets the members blocked in the guild, oldest block first
+    async fn get_blocked_users(&self, guild_id: String) -> Result<Vec<String>, DbError>;
+
+    /// Persists a deadline that the deadline loop handles once it's due, so it survives restarts.
+    /// kind tells the loop what to do with it, message_id is set for deadlines on a single message.
+    async fn add_deadline(
+        &self,
+        kind: &str,
+        guild_id: String,
+        channel_id: String,
+        message_id: Option<String>,
+        delay_seconds: i32,
+    ) -> Result<u64, DbError>;
+
+    /// Takes the deadline of the kind for a single message off the table, even if it isn't due yet.
+    /// Returns false if there was no such deadline, it was already handled.
+    async fn take_message_deadline(&self, kind: &str, message_id: String) -> Result<bool, DbError>;
+
+    /// Takes every deadline that is due off the table.
+    /// Rows contain the kind, guild_id, channel_id and message_id of the deadline.
+    async fn take_due_deadlines(&self) -> Result<Vec<Row>, DbError>;
+
+    /// Queues a DM to each of the users, sent by run_dm_queue DM_RATE a second.
+    /// Users whose DMs failed DM_FAILURE_LIMIT times in a row are left out,
+    /// and nothing is queued while DM_QUEUE_LIMIT DMs are waiting. Returns how many DMs were queued.
+    async fn queue_dms(
+        &self,
+        users: &[UserId],
+        content: &str,
+        embed: Option<(&str, &str)>,
+    ) -> Result<u64, DbError>;
+
+    /// Takes up to DM_RATE due DMs from the queue, oldest first.
+    /// They are held for DM_LEASE_SECONDS, DMs that weren't finished by then are taken again.
+    async fn take_due_dms(&self) -> Result<Vec<Row>, DbError>;
+
+    /// Takes a DM off the queue once it's sent or given up on
+    async fn remove_queued_dm(&self, dm_id: i32) -> Result<u64, DbError>;
+
+    /// Tries a queued DM again after the given seconds
+    async fn retry_queued_dm(&self, dm_id: i32, seconds: i32) -> Result<u64, DbError>;
+
+    /// Forgets the failed DMs of the user, after a DM goes through or they ask for DMs again
+    async fn clear_dm_failures(&self, user_id: String) -> Result<u64, DbError>;
+
+    /// Counts a DM to the user failing because their DMs are closed.
+    /// At DM_FAILURE_LIMIT failures in a row their DM notifications are turned off in every guild
+    /// and the DMs still queued for them are dropped. Returns whether they were unsubscribed now.
+    async fn record_dm_failure(&self, user_id: String) -> Result<bool, DbError>;
+
+    /// Saves the random question the guild gets on its next scheduled post, so it can be previewed.
+    /// None clears it, the next post is then picked when it's sent.
+    async fn set_drawn_question(
+        &self,
+        guild_id: String,
+        drawn: Option<(String, Option<String>)>,
+    ) -> Result<u64, DbError>;
+
+    /// Saves how the user wants to be notified of posts in the guild, None goes back to the guild default.
+    async fn set_notify_preference(
+        &self,
+        guild_id: String,
+        user_id: String,
+        mode: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the notification preference of the user in the guild, None if they follow the guild default
+    async fn get_notify_preference(
+        &self,
+        guild_id: String,
+        user_id: String,
+    ) -> Result<Option<String>, DbError>;
+
+    /// Gets the notification preferences of every member of the guild who has set one, keyed by user id
+    async fn get_notify_preferences(
+        &self,
+        guild_id: String,
+    ) -> Result<HashMap<String, String>, DbError>;
+
+    /// Records the bot joining or leaving a guild for the growth stats.
+    /// Nothing is recorded if the latest event of the guild is the same, so restarts don't count as joins.
+    async fn record_guild_event(&self, guild_id: String, event: &str) -> Result<u64, DbError>;
+
+    /// Records an event of the guild in the audit log, like posting failing over to the backup channel
+    async fn record_audit_event(
+        &self,
+        guild_id: String,
+        action: &str,
+        detail: &str,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the latest limit entries of the guilds audit log, newest first.
+    /// Rows contain the action, detail and when it was logged.
+    async fn get_audit_log(&self, guild_id: String, limit: i64) -> Result<Vec<Row>, DbError>;
+
+    /// Counts a use of the command in the guild for today, and whether it failed
+    async fn record_command_use(
+        &self,
+        guild_id: String,
+        command: &str,
+        failed: bool,
+    ) -> Result<u64, DbError>;
+
+    /// Gets what the guild used of its quotas from the command stats, in the order of QUOTA_COLUMNS:
+    /// commands today, DM fan-outs this month and imports this month
+    async fn get_guild_usage(&self, guild_id: String) -> Result<[i64; 3], DbError>;
+
+    /// Gets the joins, leaves and the guild count at the end of each of the last 12 weeks, oldest first.
+    /// Rows are the monday of the week, joins, leaves and total.
+    async fn get_weekly_growth(&self) -> Result<Vec<Row>, DbError>;
+
+    /// Gets how many guilds first joined over 30 days ago, and how many of them kept the bot for at least 30 days
+    async fn get_guild_retention(&self) -> Result<(i64, i64), DbError>;
+
+    /// Gets the guilds that used the most commands in the last 30 days, with the amount used
+    async fn get_most_active_guilds(&self, limit: i64) -> Result<Vec<(String, i64)>, DbError>;
+
+    /// Gets the questions posted the most across every guild in the last days.
+    /// Rows are the question, times posted and the amount of guilds that posted it.
+    async fn get_top_questions_globally(&self, days: i64, limit: i64) -> Result<Vec<Row>, DbError>;
+
+    /// Gets the uses and failures of every command across every guild in the last days, most used first
+    async fn get_command_volumes(&self, days: i64) -> Result<Vec<Row>, DbError>;
+
+    /// Gets a stream of the guild by name, daily is the default stream.
+    /// Returns None if the guild has no stream with the name.
+    async fn get_stream(&self, guild_id: String, name: String) -> Result<Option<Stream>, DbError>;
+
+    /// Gets every stream of the guild besides the default one.
+    /// Rows contain the name, channel_id, ping_role and mention_template of the stream.
+    async fn get_list_of_streams(&self, guild_id: String) -> Result<Vec<Row>, DbError>;
+
+    /// Sets the channel of a stream, creating the stream if it doesn't exist yet.
+    async fn set_stream_channel(
+        &self,
+        guild_id: String,
+        name: String,
+        channel_id: String,
+    ) -> Result<u64, DbError>;
+
+    /// Sets the ping role of a stream, same values as set_ping_role.
+    /// Returns 0 if the guild has no stream with the name.
+    async fn set_stream_ping_role(
+        &self,
+        guild_id: String,
+        name: String,
+        ping_role: String,
+    ) -> Result<u64, DbError>;
+
+    /// Sets the mention template of a stream, None goes back to the default mention.
+    /// Returns 0 if the guild has no stream with the name.
+    async fn set_stream_template(
+        &self,
+        guild_id: String,
+        name: String,
+        template: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Removes a stream of the guild.
+    /// Returns 0 if the guild has no stream with the name.
+    async fn delete_stream(&self, guild_id: String, name: String) -> Result<u64, DbError>;
+
+    /// Saves a new API token for the guild, replacing the earlier token with the same scope.
+    /// Only the hash of the token is stored, the token itself is shown once when it is created.
+    async fn set_api_token(
+        &self,
+        guild_id: String,
+        scope: String,
+        token_hash: String,
+    ) -> Result<u64, DbError>;
+
+    /// Revokes the API token of the guild with the scope, None revokes every token of the guild.
+    /// Returns the amount of tokens revoked.
+    async fn revoke_api_tokens(
+        &self,
+        guild_id: String,
+        scope: Option<String>,
+    ) -> Result<u64, DbError>;
+
+    /// Gets the scopes the guild has API tokens for and when they were created.
+    async fn get_api_tokens(&self, guild_id: String) -> Result<Vec<Row>, DbError>;
+
+    /// Looks up the guild and scope of an API token from its hash.
+    /// Returns None if no guild has the token.
+    async fn get_api_token_guild(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<(String, String)>, DbError>;
+
+    /// Gets the default stream of the guild, the channel and ping role set with set_channel and ping_role.
+    async fn get_default_stream(&self, guild_id: String) -> Result<Stream, DbError>;
+}
+
+#[async_trait]
+impl QuestionStore for ClientPool {
+    async fn get_random_question_candidates(
+        &self,
+        count: i64,
+        tags: Option<Vec<String>>,
+        unused_in: Option<String>,
+    ) -> Result<Vec<(String, Option<String>, Vec<String>)>, DbError> {
+        let client = self.get().await?;
+
+        let eligible: i64 = client
+            .query_one(
+                "SELECT count(*) FROM questions
+                WHERE in_use = $1
+                AND ($2::varchar[] IS NULL OR EXISTS (SELECT 1 FROM unnest(tags) AS tag WHERE tag = ANY($2)))
+                AND ($3::varchar IS NULL OR question_id NOT IN (SELECT question_id FROM question_history WHERE guild_id = $3))
+                AND NOT (mature AND EXISTS (SELECT 1 FROM guild_settings WHERE guild_id = $3 AND family_friendly))",
+                &[&true, &tags, &unused_in],
+            )
+            .await?
+            .get(0);
+
+        // Different random offsets into the questions that can be picked, so every question is as likely.
+        // Walking the primary key to an offset reads no more than the rows before it
+        let offsets: Vec<i64> = {
+            let mut rng = rand::thread_rng();
+            rand::seq::index::sample(&mut rng, eligible as usize, count.min(eligible) as usize)
+                .into_iter()
+                .map(|offset| offset as i64)
+                .collect()
+        };
+
+        let mut candidates = Vec::new();
+        for offset in offsets {
+            let rows = client
+                .query(
+                    "SELECT question_string, pack_name, pack_author, tags FROM questions
+                    WHERE in_use = $1
+                    AND ($2::varchar[] IS NULL OR EXISTS (SELECT 1 FROM unnest(tags) AS tag WHERE tag = ANY($2)))
+                    AND ($3::varchar IS NULL OR question_id NOT IN (SELECT question_id FROM question_history WHERE guild_id = $3))
+                    AND NOT (mature AND EXISTS (SELECT 1 FROM guild_settings WHERE guild_id = $3 AND family_friendly))
+                    ORDER BY question_id LIMIT 1 OFFSET $4",
+                    &[&true, &tags, &unused_in, &offset],
+                )
+                .await?;
+
+            // Questions removed since counting leave the offset past the end, that draw is skipped
+            candidates.extend(rows.iter().map(|row| {
+                (
+                    row.get(0),
+                    format_attribution(row.get(1), row.get(2)),
+                    row.get(3),
+                )
+            }));
+        }
+
+        Ok(candidates)
+    }
+
+    async fn get_question_tags(
+        &self,
+        guild_id: String,
+        question: String,
+    ) -> Result<Vec<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT tags FROM questions WHERE question_hash = hash_question($2)
+                UNION ALL
+                SELECT tags FROM custom_questions
+                WHERE guild_id = $1 AND question_hash = hash_question($2) AND deleted_at IS NULL
+                LIMIT 1",
+                &[&guild_id, &question],
+            )
+            .await?;
+
+        Ok(match rows.first() {
+            Some(row) => row.get(0),
+            None => Vec::new(),
+        })
+    }
+
+    async fn record_double_post(
+        &self,
+        guild_id: String,
+        message_id: String,
+        questions: [String; 2],
+        tags: [Vec<String>; 2],
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO double_posts (message_id, guild_id, question_a, question_b, tags_a, tags_b)
+                VALUES ($1, $2, $3, $4, $5, $6)",
+                &[
+                    &message_id,
+                    &guild_id,
+                    &questions[0],
+                    &questions[1],
+                    &tags[0],
+                    &tags[1],
+                ],
+            )
+            .await?;
+        client
+            .execute(
+                "INSERT INTO question_history (guild_id, question_id)
+                SELECT $1, question_id FROM questions WHERE question_hash = hash_question($2) OR question_hash = hash_question($3)
+                ON CONFLICT (guild_id, question_id)
+                DO
+                UPDATE SET posted_at = now()",
+                &[&guild_id, &questions[0], &questions[1]],
+            )
+            .await?;
+
+        Ok(insert)
+    }
+
+    async fn update_double_post_tally(
+        &self,
+        message_id: String,
+        choice: i32,
+        change: i32,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE double_posts SET
+                    votes_a = GREATEST(votes_a + CASE WHEN $2 = 0 THEN $3 ELSE 0 END, 0),
+                    votes_b = GREATEST(votes_b + CASE WHEN $2 = 1 THEN $3 ELSE 0 END, 0)
+                WHERE message_id = $1",
+                &[&message_id, &choice, &change],
+            )
+            .await;
+
+        update
+    }
+
+    async fn get_double_post_votes(
+        &self,
+        guild_id: String,
+        limit: i64,
+    ) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        Ok(client
+            .query(
+                "SELECT tags_a, votes_a, tags_b, votes_b FROM double_posts
+                WHERE guild_id = $1
+                ORDER BY posted_at DESC
+                LIMIT $2",
+                &[&guild_id, &limit],
+            )
+            .await?)
+    }
+
+    async fn clear_question_history(
+        &self,
+        guild_id: String,
+        tags: Option<Vec<String>>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute(
+                "DELETE FROM question_history WHERE guild_id = $1
+                AND ($2::varchar[] IS NULL OR question_id IN (
+                    SELECT question_id FROM questions
+                    WHERE EXISTS (SELECT 1 FROM unnest(tags) AS tag WHERE tag = ANY($2))
+                ))",
+                &[&guild_id, &tags],
+            )
+            .await;
+
+        delete
+    }
+
+    async fn add_default_questions(
+        &self,
+        questions: Vec<String>,
+        pack_name: String,
+        pack_author: String,
+        source: QuestionSource,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO questions (question_string, in_use, pack_name, pack_author, source_url, license)
+                SELECT question, true, $2, $3, $4, $5 FROM unnest($1::varchar[]) AS question
+                WHERE true
+                ON CONFLICT DO NOTHING",
+                &[&questions, &pack_name, &pack_author, &source.url, &source.license],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn set_pack_source(
+        &self,
+        pack_name: String,
+        source: QuestionSource,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE questions SET source_url = $2, license = $3 WHERE pack_name = $1",
+                &[&pack_name, &source.url, &source.license],
+            )
+            .await;
+
+        update
+    }
+
+    async fn get_unattributed_packs(&self) -> Result<Vec<UnattributedPack>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT pack_name, COUNT(*),
+                    SUM(CASE WHEN source_url IS NULL THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN license IS NULL THEN 1 ELSE 0 END),
+                    MIN(question_id)
+                FROM questions
+                WHERE in_use AND (source_url IS NULL OR license IS NULL)
+                GROUP BY pack_name
+                ORDER BY COUNT(*) DESC",
+                &[],
+            )
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| UnattributedPack {
+                pack_name: row.get(0),
+                questions: row.get(1),
+                missing_source: row.get(2),
+                missing_license: row.get(3),
+                first_id: row.get(4),
+            })
+            .collect())
+    }
+
+    async fn set_default_question_mature(
+        &self,
+        question_id: i32,
+        mature: bool,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE questions SET mature = $2 WHERE question_id = $1",
+                &[&question_id, &mature],
+            )
+            .await;
+
+        update
+    }
+
+    async fn set_custom_question_mature(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        mature: bool,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE custom_questions SET mature = $3
+                WHERE guild_id = $1 AND question_id = $2 AND deleted_at IS NULL",
+                &[&guild_id, &question_id, &mature],
+            )
+            .await;
+
+        update
+    }
+
+    async fn update_default_question_tags(
+        &self,
+        question_id: i32,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Option<Vec<String>>, DbError> {
+        let client = self.get().await?;
+        let transaction = client.transaction().await?;
+
+        let rows = transaction
+            .query(
+                "SELECT tags FROM questions WHERE question_id = $1 FOR UPDATE",
+                &[&question_id],
+            )
+            .await?;
+        let tags = match rows.first() {
+            Some(row) => merge_tags(row.get(0), add, remove),
+            None => return Ok(None),
+        };
+        transaction
+            .execute(
+                "UPDATE questions SET tags = $2 WHERE question_id = $1",
+                &[&question_id, &tags],
+            )
+            .await?;
+        transaction.commit().await?;
+
+        Ok(Some(tags))
+    }
+
+    async fn update_custom_question_tags(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Option<Vec<String>>, DbError> {
+        let client = self.get().await?;
+        let transaction = client.transaction().await?;
+
+        let rows = transaction
+            .query(
+                "SELECT tags FROM custom_questions
+                WHERE guild_id = $1 AND question_id = $2 AND deleted_at IS NULL FOR UPDATE",
+                &[&guild_id, &question_id],
+            )
+            .await?;
+        let tags = match rows.first() {
+            Some(row) => merge_tags(row.get(0), add, remove),
+            None => return Ok(None),
+        };
+        transaction
+            .execute(
+                "UPDATE custom_questions SET tags = $2 WHERE question_id = $1",
+                &[&question_id, &tags],
+            )
+            .await?;
+        transaction.commit().await?;
+
+        Ok(Some(tags))
+    }
+
+    async fn get_taken_questions(&self, guild_id: String) -> Result<Vec<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT question_string FROM custom_questions
+                WHERE guild_id IN (
+                    SELECT $1::varchar
+                    UNION
+                    SELECT pm2.guild_id FROM pool_members pm1
+                    JOIN pool_members pm2 ON pm2.pool_id = pm1.pool_id
+                    WHERE pm1.guild_id = $1
+                )
+                AND deleted_at IS NULL
+                UNION ALL
+                SELECT question_string FROM (
+                    SELECT question_string FROM post_history WHERE guild_id = $1 ORDER BY posted_at DESC LIMIT 30
+                ) recent",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    async fn add_custom_question(
+        &self,
+        guild_id: String,
+        question: String,
+        submitted_by: Option<String>,
+        flag_reason: Option<String>,
+        mature: bool,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO custom_questions (guild_id, question_string, submitted_by, submitted_at, status, flag_reason, mature)
+                VALUES ($1, $2, $3, now(), CASE WHEN $4::varchar IS NULL THEN 'active' ELSE 'flagged' END, $4, $5)
+                ON CONFLICT DO NOTHING",
+                &[&guild_id, &question, &submitted_by, &flag_reason, &mature],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn add_custom_questions(
+        &self,
+        guild_id: String,
+        questions: Vec<(String, QuestionSource)>,
+        submitted_by: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+        let transaction = client.transaction().await?;
+
+        // Locking the guilds questions, so imports running at the same time can't both fill the last room
+        let saved = transaction
+            .query(
+                "SELECT question_id FROM custom_questions
+                WHERE guild_id = $1 AND deleted_at IS NULL FOR UPDATE",
+                &[&guild_id],
+            )
+            .await?;
+        let room = (CUSTOM_QUESTION_LIMIT as usize).saturating_sub(saved.len());
+        let (questions, sources): (Vec<String>, Vec<QuestionSource>) =
+            questions.into_iter().take(room).unzip();
+        if questions.is_empty() {
+            return Ok(0);
+        }
+
+        let inserted = transaction
+            .query(
+                "INSERT INTO custom_questions (guild_id, question_string, submitted_by, submitted_at)
+                SELECT $1, question, $3, now() FROM unnest($2::varchar[]) AS question
+                WHERE true
+                ON CONFLICT DO NOTHING
+                RETURNING question_id, question_string",
+                &[&guild_id, &questions, &submitted_by],
+            )
+            .await?;
+        // Only the questions added get a source, the returned text matches the text inserted
+        for row in &inserted {
+            let question: String = row.get(1);
+            let source = match questions.iter().position(|added| *added == question) {
+                Some(index) if !sources[index].is_empty() => &sources[index],
+                _ => continue,
+            };
+            transaction
+                .execute(
+                    "UPDATE custom_questions SET source_url = $2, license = $3 WHERE question_id = $1",
+                    &[&row.get::<_, i32>(0), &source.url, &source.license],
+                )
+                .await?;
+        }
+        let added = inserted.len() as u64;
+        let detail = match &submitted_by {
+            Some(user_id) => format!("{} questions imported by <@{}>.", added, user_id),
+            None => format!("{} questions imported.", added),
+        };
+        transaction
+            .execute(
+                "INSERT INTO audit_log (guild_id, action, detail) VALUES ($1, 'import', $2)",
+                &[&guild_id, &detail],
+            )
+            .await?;
+        transaction.commit().await?;
+
+        Ok(added)
+    }
+
+    async fn set_question_source(
+        &self,
+        guild_id: String,
+        question: String,
+        source: QuestionSource,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE custom_questions SET source_url = $3, license = $4
+                WHERE guild_id = $1 AND question_hash = hash_question($2) AND deleted_at IS NULL",
+                &[&guild_id, &question, &source.url, &source.license],
+            )
+            .await;
+
+        update
+    }
+
+    async fn delete_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        deleted_by: Option<String>,
+    ) -> Result<i32, DbError> {
+        let client = self.get().await?;
+        let transaction = client.transaction().await?;
+
+        // Checking if a question with the guild_id of the requesting server exists, if it exists, delete the question.
+        // This prevents from other servers deleting each others questions.
+        // The row stays locked until the transaction is done, so it can't change between the check and the delete.
+        let rows = transaction
+            .query(
+                "SELECT question_string FROM custom_questions
+                WHERE guild_id = $1 AND question_id = $2 AND deleted_at IS NULL FOR UPDATE",
+                &[&guild_id, &question_id],
+            )
+            .await?;
+        let row = match rows.first() {
+            Some(row) => row,
+            None => return Ok(0),
+        };
+        let question: String = row.get(0);
+        let _delete = transaction
+            .execute(
+                "UPDATE custom_questions SET deleted_at = now() WHERE question_id = $1",
+                &[&question_id],
+            )
+            .await?;
+        let detail = match deleted_by {
+            Some(user_id) => format!(
+                "Question {} deleted by <@{}>: {}",
+                question_id, user_id, question
+            ),
+            None => format!(
+                "Question {} deleted through the API: {}",
+                question_id, question
+            ),
+        };
+        transaction
+            .execute(
+                "INSERT INTO audit_log (guild_id, action, detail) VALUES ($1, 'delete_question', $2)",
+                &[&guild_id, &detail],
+            )
+            .await?;
+        transaction.commit().await?;
+
+        Ok(1)
+    }
+
+    async fn delete_custom_questions(
+        &self,
+        guild_id: String,
+        question_ids: Option<Vec<i32>>,
+        deleted_by: String,
+    ) -> Result<Vec<i32>, DbError> {
+        let client = self.get().await?;
+        let transaction = client.transaction().await?;
+
+        let rows = transaction
+            .query(
+                "UPDATE custom_questions SET deleted_at = now()
+                WHERE guild_id = $1 AND deleted_at IS NULL
+                AND ($2::integer[] IS NULL OR question_id = ANY($2))
+                RETURNING question_id",
+                &[&guild_id, &question_ids],
+            )
+            .await?;
+        let mut deleted: Vec<i32> = rows.iter().map(|row| row.get(0)).collect();
+        deleted.sort_unstable();
+
+        if !deleted.is_empty() {
+            let detail = format!(
+                "{} questions deleted by <@{}>: {}",
+                deleted.len(),
+                deleted_by,
+                deleted
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+            transaction
+                .execute(
+                    "INSERT INTO audit_log (guild_id, action, detail) VALUES ($1, 'delete_questions', $2)",
+                    &[&guild_id, &detail],
+                )
+                .await?;
+        }
+        transaction.commit().await?;
+
+        Ok(deleted)
+    }
+
+    async fn edit_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        question: String,
+        edited_by: String,
+    ) -> Result<QuestionEdit, DbError> {
+        let client = self.get().await?;
+        let transaction = client.transaction().await?;
+
+        // Only questions of the requesting server can be edited, locked so the text can't change in between
+        let rows = transaction
+            .query(
+                "SELECT question_string FROM custom_questions
+                WHERE guild_id = $1 AND question_id = $2 AND deleted_at IS NULL FOR UPDATE",
+                &[&guild_id, &question_id],
+            )
+            .await?;
+        let previous: String = match rows.first() {
+            Some(row) => row.get(0),
+            None => return Ok(QuestionEdit::NotFound),
+        };
+
+        // Fixing the case or punctuation of a question matches its own hash, that isn't a duplicate
+        let duplicates = transaction
+            .query(
+                "SELECT 1 FROM custom_questions
+                WHERE guild_id = $1 AND question_hash = hash_question($2) AND deleted_at IS NULL
+                AND question_id <> $3
+                LIMIT 1",
+                &[&guild_id, &question, &question_id],
+            )
+            .await?;
+        if !duplicates.is_empty() {
+            return Ok(QuestionEdit::Duplicate);
+        }
+
+        transaction
+            .execute(
+                "UPDATE custom_questions SET question_string = $1 WHERE question_id = $2",
+                &[&question, &question_id],
+            )
+            .await?;
+        let detail = format!(
+            "Question {} edited by <@{}>, it was: {}",
+            question_id, edited_by, previous
+        );
+        transaction
+            .execute(
+                "INSERT INTO audit_log (guild_id, action, detail) VALUES ($1, 'edit_question', $2)",
+                &[&guild_id, &detail],
+            )
+            .await?;
+        transaction.commit().await?;
+
+        Ok(QuestionEdit::Edited(previous))
+    }
+
+    async fn restore_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE custom_questions SET deleted_at = NULL
+                WHERE guild_id = $1 AND question_id = $2 AND deleted_at IS NOT NULL
+                AND NOT EXISTS (
+                    SELECT 1 FROM custom_questions saved
+                    WHERE saved.guild_id = $1 AND saved.question_hash = custom_questions.question_hash
+                    AND saved.deleted_at IS NULL
+                )",
+                &[&guild_id, &question_id],
+            )
+            .await;
+
+        update
+    }
+
+    async fn get_deleted_custom_questions(&self, guild_id: String) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT question_id, question_string, to_char(deleted_at, 'YYYY-MM-DD')
+                FROM custom_questions
+                WHERE guild_id = $1 AND deleted_at IS NOT NULL
+                ORDER BY deleted_at DESC",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn purge_deleted_questions(&self, days: i32) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute(
+                "DELETE FROM custom_questions WHERE deleted_at < add_seconds(now(), -86400 * $1)",
+                &[&days],
+            )
+            .await;
+
+        delete
+    }
+
+    async fn approve_queued_questions(
+        &self,
+        guild_id: String,
+        question_ids: Option<Vec<i32>>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE custom_questions SET status = 'active', flag_reason = NULL
+                WHERE guild_id = $1 AND status <> 'active' AND deleted_at IS NULL
+                AND ($2::integer[] IS NULL OR question_id = ANY($2))",
+                &[&guild_id, &question_ids],
+            )
+            .await;
+
+        update
+    }
+
+    async fn reject_queued_questions(
+        &self,
+        guild_id: String,
+        question_ids: Option<Vec<i32>>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute(
+                "DELETE FROM custom_questions
+                WHERE guild_id = $1 AND status <> 'active' AND deleted_at IS NULL
+                AND ($2::integer[] IS NULL OR question_id = ANY($2))",
+                &[&guild_id, &question_ids],
+            )
+            .await;
+
+        delete
+    }
+
+    async fn get_list_custom_questions(&self, guild_id: String) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT cq.*, qn.note FROM custom_questions cq
+                LEFT JOIN question_notes qn ON qn.question_id = cq.question_id
+                WHERE cq.guild_id = $1 AND cq.deleted_at IS NULL
+                ORDER BY cq.question_id",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn set_question_note(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        note: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        match note {
+            Some(note) => {
+                // Selecting through custom_questions so guilds can only note their own questions
+                let upsert = client
+                    .execute(
+                        "INSERT INTO question_notes (question_id, note)
+                        SELECT question_id, $3 FROM custom_questions
+                        WHERE guild_id = $1 AND question_id = $2 AND deleted_at IS NULL
+                        ON CONFLICT (question_id)
+                        DO
+                        UPDATE SET note = EXCLUDED.note",
+                        &[&guild_id, &question_id, &note],
+                    )
+                    .await;
+
+                upsert
+            }
+            None => {
+                let delete = client
+                    .execute(
+                        "DELETE FROM question_notes WHERE question_id IN (
+                            SELECT question_id FROM custom_questions WHERE guild_id = $1 AND question_id = $2
+                        )",
+                        &[&guild_id, &question_id],
+                    )
+                    .await;
+
+                delete
+            }
+        }
+    }
+
+    async fn set_question_answer(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        answer: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE custom_questions SET answer = $3
+                WHERE guild_id = $1 AND question_id = $2 AND deleted_at IS NULL",
+                &[&guild_id, &question_id, &answer],
+            )
+            .await;
+
+        update
+    }
+
+    async fn get_question_answer(
+        &self,
+        guild_id: String,
+        question: &str,
+    ) -> Result<Option<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT answer FROM custom_questions
+                WHERE guild_id IN (
+                    SELECT $1::varchar
+                    UNION
+                    SELECT pm2.guild_id FROM pool_members pm1
+                    JOIN pool_members pm2 ON pm2.pool_id = pm1.pool_id
+                    WHERE pm1.guild_id = $1
+                )
+                AND question_hash = hash_question($2) AND answer IS NOT NULL AND deleted_at IS NULL
+                LIMIT 1",
+                &[&guild_id, &question],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| row.get(0)))
+    }
+
+    async fn get_question_submitter(
+        &self,
+        guild_id: String,
+        question: &str,
+    ) -> Result<Option<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT submitted_by FROM custom_questions
+                WHERE guild_id IN (
+                    SELECT $1::varchar
+                    UNION
+                    SELECT pm2.guild_id FROM pool_members pm1
+                    JOIN pool_members pm2 ON pm2.pool_id = pm1.pool_id
+                    WHERE pm1.guild_id = $1
+                )
+                AND question_hash = hash_question($2) AND submitted_by IS NOT NULL AND deleted_at IS NULL
+                LIMIT 1",
+                &[&guild_id, &question],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| row.get(0)))
+    }
+
+    async fn get_random_custom_question(
+        &self,
+        guild_id: String,
+        tags: Option<Vec<String>>,
+    ) -> Result<Option<(String, Option<String>)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT cq.question_id, cq.question_string, cq.pack_name, cq.pack_author
+                    FROM custom_questions cq
+                    LEFT JOIN guild_settings gs ON gs.guild_id = $1
+                    LEFT JOIN custom_question_posts cqp ON cqp.question_id = cq.question_id AND cqp.guild_id = $1
+                    WHERE cq.guild_id IN (
+                        SELECT $1::varchar
+                        UNION
+                        SELECT pm2.guild_id FROM pool_members pm1
+                        JOIN pool_members pm2 ON pm2.pool_id = pm1.pool_id
+                        WHERE pm1.guild_id = $1
+                    )
+                    AND cq.status = 'active'
+                    AND cq.in_use
+                    AND cq.deleted_at IS NULL
+                    AND (cqp.last_posted IS NULL
+                        OR cqp.last_posted < add_seconds(now(), -86400 * COALESCE(cq.cooldown_days, gs.question_cooldown_days, 0)))
+                    AND ($2::varchar[] IS NULL OR EXISTS (SELECT 1 FROM unnest(cq.tags) AS tag WHERE tag = ANY($2)))
+                    AND NOT (cq.mature AND COALESCE(gs.family_friendly, false))
+                    ORDER BY CASE WHEN COALESCE(gs.fair_rotation, false)
+                        -- Weighted random pick, each question weighs 1 / questions from the same submitter
+                        THEN -ln(1 - random()) * count(*) OVER (PARTITION BY cq.submitted_by)
+                        ELSE random()
+                    END
+                    LIMIT 1",
+                &[&guild_id, &tags],
+            )
+            .await?;
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        if let Err(e) = mark_custom_question_posted(&guild_id, rows[0].get(0), &client).await {
+            println!("Marking custom question as posted failed: {}", e);
+        }
+        Ok(Some((
+            rows[0].get(1),
+            format_attribution(rows[0].get(2), rows[0].get(3)),
+        )))
+    }
+
+    async fn is_question_eligible(
+        &self,
+        guild_id: String,
+        question: String,
+        tags: Option<Vec<String>>,
+        custom_only: bool,
+    ) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT 1 FROM custom_questions cq
+                LEFT JOIN guild_settings gs ON gs.guild_id = $1
+                WHERE cq.guild_id IN (
+                    SELECT $1::varchar
+                    UNION
+                    SELECT pm2.guild_id FROM pool_members pm1
+                    JOIN pool_members pm2 ON pm2.pool_id = pm1.pool_id
+                    WHERE pm1.guild_id = $1
+                )
+                AND cq.question_hash = hash_question($2)
+                AND cq.status = 'active'
+                AND cq.in_use
+                AND cq.deleted_at IS NULL
+                AND ($3::varchar[] IS NULL OR EXISTS (SELECT 1 FROM unnest(cq.tags) AS tag WHERE tag = ANY($3)))
+                AND NOT (cq.mature AND COALESCE(gs.family_friendly, false))
+                UNION ALL
+                SELECT 1 FROM questions q
+                WHERE NOT $4
+                AND q.question_hash = hash_question($2)
+                AND q.in_use
+                AND ($3::varchar[] IS NULL OR EXISTS (SELECT 1 FROM unnest(q.tags) AS tag WHERE tag = ANY($3)))
+                AND NOT (q.mature AND EXISTS (SELECT 1 FROM guild_settings WHERE guild_id = $1 AND family_friendly))",
+                &[&guild_id, &question, &tags, &custom_only],
+            )
+            .await?;
+
+        Ok(!rows.is_empty())
+    }
+
+    async fn get_specific_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+    ) -> Result<Option<(String, Option<String>)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT cq.question_string, cq.pack_name, cq.pack_author
+                    FROM custom_questions cq
+                    WHERE cq.question_id = $2
+                    AND cq.status = 'active'
+                    AND cq.in_use
+                    AND cq.deleted_at IS NULL
+                    AND cq.guild_id IN (
+                        SELECT $1::varchar
+                        UNION
+                        SELECT pm2.guild_id FROM pool_members pm1
+                        JOIN pool_members pm2 ON pm2.pool_id = pm1.pool_id
+                        WHERE pm1.guild_id = $1
+                    )",
+                &[&guild_id, &question_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            mark_custom_question_posted(&guild_id, question_id, &client).await?;
+            Some((
+                rows[0].get(0),
+                format_attribution(rows[0].get(1), rows[0].get(2)),
+            ))
+        } else {
+            None
+        })
+    }
+
+    async fn queue_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        post_date: NaiveDate,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO queued_questions (guild_id, post_date, question_id)
+                SELECT $1, $3, question_id FROM custom_questions
+                WHERE question_id = $2 AND deleted_at IS NULL
+                AND guild_id IN (
+                    SELECT $1::varchar
+                    UNION
+                    SELECT pm2.guild_id FROM pool_members pm1
+                    JOIN pool_members pm2 ON pm2.pool_id = pm1.pool_id
+                    WHERE pm1.guild_id = $1
+                )
+                ON CONFLICT (guild_id, post_date)
+                DO
+                UPDATE SET question_id = EXCLUDED.question_id, consumed = false",
+                &[&guild_id, &question_id, &post_date],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn unqueue_custom_question(
+        &self,
+        guild_id: String,
+        post_date: NaiveDate,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute(
+                "DELETE FROM queued_questions WHERE guild_id = $1 AND post_date = $2 AND NOT consumed",
+                &[&guild_id, &post_date],
+            )
+            .await;
+
+        delete
+    }
+
+    async fn get_queued_questions(
+        &self,
+        guild_id: String,
+        today: NaiveDate,
+    ) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT to_char(qq.post_date, 'YYYY-MM-DD'), qq.question_id, cq.question_string
+                FROM queued_questions qq
+                JOIN custom_questions cq ON cq.question_id = qq.question_id
+                WHERE qq.guild_id = $1 AND qq.post_date >= $2 AND NOT qq.consumed
+                AND cq.deleted_at IS NULL
+                ORDER BY qq.post_date",
+                &[&guild_id, &today],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn take_queued_question(
+        &self,
+        guild_id: String,
+        post_date: NaiveDate,
+    ) -> Result<Option<i32>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "UPDATE queued_questions SET consumed = true
+                WHERE guild_id = $1 AND post_date = $2 AND NOT consumed
+                RETURNING question_id",
+                &[&guild_id, &post_date],
+            )
+            .await?;
+
+        if !rows.is_empty() {
+            Ok(Some(rows[0].get(0)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn set_custom_question_in_use(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        in_use: bool,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE custom_questions SET in_use = $3
+                WHERE guild_id = $1 AND question_id = $2 AND deleted_at IS NULL",
+                &[&guild_id, &question_id, &in_use],
+            )
+            .await;
+
+        update
+    }
+
+    async fn update_custom_question(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        question: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE custom_questions SET question_string = $3
+                WHERE guild_id = $1 AND question_id = $2 AND deleted_at IS NULL",
+                &[&guild_id, &question_id, &question],
+            )
+            .await;
+
+        update
+    }
+
+    async fn set_custom_question_cooldown(
+        &self,
+        guild_id: String,
+        question_id: i32,
+        cooldown_days: Option<i32>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE custom_questions SET cooldown_days = $3
+                WHERE guild_id = $1 AND question_id = $2 AND deleted_at IS NULL",
+                &[&guild_id, &question_id, &cooldown_days],
+            )
+            .await;
+
+        update
+    }
+
+    async fn record_posted_question(
+        &self,
+        guild_id: String,
+        channel_id: String,
+        message_id: String,
+        question: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO post_history (guild_id, channel_id, message_id, question_string) VALUES ($1, $2, $3, $4)",
+                &[&guild_id, &channel_id, &message_id, &question],
+            )
+            .await?;
+        client
+            .execute(
+                "INSERT INTO question_history (guild_id, question_id)
+                SELECT $1, question_id FROM questions WHERE question_hash = hash_question($2)
+                ON CONFLICT (guild_id, question_id)
+                DO
+                UPDATE SET posted_at = now()",
+                &[&guild_id, &question],
+            )
+            .await?;
+
+        Ok(insert)
+    }
+
+    async fn record_post_answers(&self, message_id: String, answers: i32) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE post_history SET answers = $2 WHERE message_id = $1",
+                &[&message_id, &answers],
+            )
+            .await;
+
+        update
+    }
+
+    async fn get_engaged_questions(
+        &self,
+        guild_id: String,
+        limit: i64,
+    ) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT question_string, tags, engagement FROM (
+                    SELECT ph.question_string,
+                        COALESCE(
+                            (SELECT q.tags FROM questions q WHERE q.question_hash = hash_question(ph.question_string) LIMIT 1),
+                            (SELECT cq.tags FROM custom_questions cq
+                            WHERE cq.guild_id = ph.guild_id AND cq.question_hash = hash_question(ph.question_string) LIMIT 1)
+                        ) AS tags,
+                        COALESCE(ph.answers, 0) + (SELECT count(*) FROM saved_questions sq WHERE sq.post_id = ph.post_id) AS engagement
+                    FROM post_history ph
+                    WHERE ph.guild_id = $1
+                ) engaged
+                WHERE engagement > 0
+                ORDER BY engagement DESC
+                LIMIT $2",
+                &[&guild_id, &limit],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn update_question_rating(
+        &self,
+        message_id: String,
+        up: bool,
+        change: i32,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let statement = if up {
+            "UPDATE post_history SET rated_up = GREATEST(rated_up + $2, 0) WHERE message_id = $1"
+        } else {
+            "UPDATE post_history SET rated_down = GREATEST(rated_down + $2, 0) WHERE message_id = $1"
+        };
+        let update = client.execute(statement, &[&message_id, &change]).await;
+
+        update
+    }
+
+    async fn get_rated_questions(
+        &self,
+        guild_id: String,
+        worst: bool,
+        limit: i64,
+    ) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let statement = format!(
+            "SELECT question_string, sum(rated_up) AS up, sum(rated_down) AS down
+            FROM post_history
+            WHERE guild_id = $1
+            GROUP BY question_string
+            HAVING sum(rated_up) + sum(rated_down) > 0
+            ORDER BY sum(rated_up) - sum(rated_down) {}, sum(rated_up) + sum(rated_down) DESC
+            LIMIT $2",
+            if worst { "ASC" } else { "DESC" }
+        );
+        let rows = client.query(&statement, &[&guild_id, &limit]).await?;
+
+        Ok(rows)
+    }
+
+    async fn get_question_ratings(
+        &self,
+        questions: Vec<String>,
+    ) -> Result<HashMap<String, (i64, i64)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT question_string, sum(rated_up), sum(rated_down)
+                FROM post_history
+                WHERE question_string = ANY($1)
+                GROUP BY question_string
+                HAVING sum(rated_up) + sum(rated_down) > 0",
+                &[&questions],
+            )
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get(0), (row.get(1), row.get(2))))
+            .collect())
+    }
+
+    async fn get_post_history(&self, guild_id: String, limit: i64) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        Ok(client
+            .query(
+                "SELECT question_string, channel_id, message_id, posted_at FROM post_history
+                WHERE guild_id = $1
+                ORDER BY posted_at DESC
+                LIMIT $2",
+                &[&guild_id, &limit],
+            )
+            .await?)
+    }
+
+    async fn get_posted_question_count(&self) -> Result<i64, DbError> {
+        let client = self.get().await?;
+
+        let row = client
+            .query_one("SELECT count(*) FROM post_history", &[])
+            .await?;
+
+        Ok(row.get(0))
+    }
+
+    async fn question_is_duplicate(
+        &self,
+        guild_id: String,
+        question: &str,
+    ) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT 1 FROM custom_questions
+                WHERE guild_id = $1 AND question_hash = hash_question($2) AND deleted_at IS NULL
+                LIMIT 1",
+                &[&guild_id, &question],
+            )
+            .await?;
+
+        Ok(!rows.is_empty())
+    }
+
+    async fn question_is_under_limit(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+        let limit: i64 = CUSTOM_QUESTION_LIMIT;
+
+        let rows = client
+            .query(
+                "SELECT COUNT(*) FROM custom_questions WHERE guild_id = $1 AND deleted_at IS NULL",
+                &[&guild_id],
+            )
+            .await?;
+        let count: i64 = rows[0].get(0);
+        Ok(count < limit)
+    }
+
+    async fn save_question_for_user(
+        &self,
+        user_id: String,
+        message_id: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO saved_questions (user_id, post_id)
+                SELECT $1, post_id FROM post_history WHERE message_id = $2
+                ON CONFLICT DO NOTHING",
+                &[&user_id, &message_id],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn get_saved_questions(&self, user_id: String) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT ph.question_string, to_char(sq.saved_at, 'YYYY-MM-DD')
+                FROM saved_questions sq
+                JOIN post_history ph ON ph.post_id = sq.post_id
+                WHERE sq.user_id = $1
+                ORDER BY sq.saved_at",
+                &[&user_id],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn add_trivia_round(
+        &self,
+        guild_id: String,
+        thread_id: String,
+        answer: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO trivia_rounds (thread_id, guild_id, answer) VALUES ($1, $2, $3)",
+                &[&thread_id, &guild_id, &answer],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn get_trivia_round(
+        &self,
+        thread_id: String,
+    ) -> Result<Option<(String, Option<String>)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT answer, winner_id FROM trivia_rounds WHERE thread_id = $1",
+                &[&thread_id],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| (row.get(0), row.get(1))))
+    }
+
+    async fn set_trivia_winner(
+        &self,
+        thread_id: String,
+        winner_id: Option<String>,
+        overriding: bool,
+    ) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE trivia_rounds
+                SET winner_id = $2, won_at = CASE WHEN $2::varchar IS NULL THEN NULL ELSE now() END
+                WHERE thread_id = $1 AND (winner_id IS NULL OR $3)",
+                &[&thread_id, &winner_id, &overriding],
+            )
+            .await?;
+
+        Ok(update == 1)
+    }
+
+    async fn add_trivia_points(
+        &self,
+        guild_id: String,
+        user_id: String,
+        points: i32,
+    ) -> Result<i32, DbError> {
+        let client = self.get().await?;
+
+        let row = client
+            .query_one(
+                "INSERT INTO trivia_scores (guild_id, user_id, points)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id, user_id)
+                DO
+                UPDATE SET points = trivia_scores.points + EXCLUDED.points
+                RETURNING points",
+                &[&guild_id, &user_id, &points],
+            )
+            .await?;
+
+        Ok(row.get(0))
+    }
+
+    async fn get_trivia_scores(&self, guild_id: String) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        Ok(client
+            .query(
+                "SELECT user_id, points FROM trivia_scores
+                WHERE guild_id = $1 AND points > 0
+                ORDER BY points DESC, user_id
+                LIMIT 10",
+                &[&guild_id],
+            )
+            .await?)
+    }
+
+    async fn create_pool_invite(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT pool_id FROM pool_members WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        let pool_id: i32 = if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            let created = client
+                .query_one(
+                    "INSERT INTO shared_pools DEFAULT VALUES RETURNING pool_id",
+                    &[],
+                )
+                .await?;
+            let pool_id: i32 = created.get(0);
+            client
+                .execute(
+                    "INSERT INTO pool_members (guild_id, pool_id) VALUES ($1, $2)",
+                    &[&guild_id, &pool_id],
+                )
+                .await?;
+            pool_id
+        };
+
+        let code = format!("{:08x}", rand::random::<u32>());
+        client
+            .execute(
+                "INSERT INTO pool_invites (code, pool_id, expires_at)
+                VALUES ($1, $2, add_seconds(now(), 86400))",
+                &[&code, &pool_id],
+            )
+            .await?;
+
+        Ok(code)
+    }
+
+    async fn get_pool_invite(&self, code: String) -> Result<Option<i32>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT pool_id FROM pool_invites WHERE code = $1 AND expires_at > now()",
+                &[&code],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            Some(rows[0].get(0))
+        } else {
+            None
+        })
+    }
+
+    async fn join_pool(
+        &self,
+        guild_id: String,
+        pool_id: i32,
+        code: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        client
+            .execute("DELETE FROM pool_invites WHERE code = $1", &[&code])
+            .await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO pool_members (guild_id, pool_id)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET pool_id = EXCLUDED.pool_id",
+                &[&guild_id, &pool_id],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn leave_pool(&self, guild_id: String) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute("DELETE FROM pool_members WHERE guild_id = $1", &[&guild_id])
+            .await;
+
+        delete
+    }
+
+    async fn get_pool_partners(&self, guild_id: String) -> Result<Vec<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT pm2.guild_id FROM pool_members pm1
+                JOIN pool_members pm2 ON pm2.pool_id = pm1.pool_id
+                WHERE pm1.guild_id = $1 AND pm2.guild_id <> $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    async fn is_in_pool(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT pool_id FROM pool_members WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(!rows.is_empty())
+    }
+
+    async fn get_id_suggestions(
+        &self,
+        guild_id: String,
+        source: IdSource,
+        typed: String,
+    ) -> Result<Vec<(i32, String)>, DbError> {
+        let client = self.get().await?;
+
+        let query = match source {
+            IdSource::Questions => {
+                "SELECT question_id, question_string FROM custom_questions
+                WHERE guild_id = $1 AND deleted_at IS NULL
+                AND (question_id::text LIKE $2 || '%' OR question_string ILIKE '%' || $2 || '%')
+                ORDER BY question_id
+                LIMIT 25"
+            }
+            IdSource::Polls => {
+                "SELECT poll_id, array_to_string(poll_string, ' / ') FROM custom_polls
+                WHERE guild_id = $1
+                AND (poll_id::text LIKE $2 || '%' OR array_to_string(poll_string, ' ') ILIKE '%' || $2 || '%')
+                ORDER BY poll_id
+                LIMIT 25"
+            }
+        };
+
+        let rows = client.query(query, &[&guild_id, &typed]).await?;
+
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    async fn add_draft(
+        &self,
+        guild_id: String,
+        user_id: String,
+        kind: &str,
+        content: Vec<String>,
+    ) -> Result<i32, DbError> {
+        let client = self.get().await?;
+
+        let row = client
+            .query_one(
+                "INSERT INTO drafts (guild_id, user_id, kind, content)
+                VALUES ($1, $2, $3, $4)
+                RETURNING draft_id",
+                &[&guild_id, &user_id, &kind, &content],
+            )
+            .await?;
+
+        Ok(row.get(0))
+    }
+
+    async fn get_drafts(&self, guild_id: String, user_id: String) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        Ok(client
+            .query(
+                "SELECT draft_id, kind, content FROM drafts
+                WHERE guild_id = $1 AND user_id = $2
+                ORDER BY draft_id",
+                &[&guild_id, &user_id],
+            )
+            .await?)
+    }
+
+    async fn get_draft(
+        &self,
+        guild_id: String,
+        user_id: String,
+        draft_id: i32,
+    ) -> Result<Option<(String, Vec<String>)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT kind, content FROM drafts
+                WHERE guild_id = $1 AND user_id = $2 AND draft_id = $3",
+                &[&guild_id, &user_id, &draft_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            Some((rows[0].get(0), rows[0].get(1)))
+        } else {
+            None
+        })
+    }
+
+    async fn delete_draft(
+        &self,
+        guild_id: String,
+        user_id: String,
+        draft_id: i32,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute(
+                "DELETE FROM drafts WHERE guild_id = $1 AND user_id = $2 AND draft_id = $3",
+                &[&guild_id, &user_id, &draft_id],
+            )
+            .await;
+
+        delete
+    }
+}
+
+#[async_trait]
+impl PollStore for ClientPool {
+    async fn poll_is_under_limit(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+        let limit: i64 = 100; // CUSTOM QUESTION LIMIT
+
+        let rows = client
+            .query(
+                "SELECT COUNT(*) FROM custom_polls WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        let count: i64 = rows[0].get(0);
+        Ok(count < limit)
+    }
+
+    async fn get_random_poll(&self) -> Result<Option<(i32, Vec<String>)>, DbError> {
+        let client = self.get().await?;
+
+        // A uniformly random offset among the polls in use
+        let rows = client
+            .query(
+                "SELECT poll_id, poll_string FROM polls
+                WHERE in_use = $1
+                ORDER BY poll_id
+                LIMIT 1 OFFSET CAST(floor(random() * (SELECT count(*) FROM polls WHERE in_use = $1)) AS bigint)",
+                &[&true],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| (row.get(0), row.get(1))))
+    }
+
+    async fn add_custom_poll(
+        &self,
+        guild_id: String,
+        new_poll: Vec<String>,
+        emoji: Option<Vec<String>>,
+        duration_hours: Option<i32>,
+        anonymous: bool,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO custom_polls (guild_id, poll_string, emoji, duration_hours, anonymous)
+                VALUES ($1, $2, $3, $4, $5)",
+                &[&guild_id, &new_poll, &emoji, &duration_hours, &anonymous],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn get_custom_poll_settings(
+        &self,
+        poll_id: i32,
+    ) -> Result<(Vec<String>, Option<i32>, bool, bool), DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT emoji, duration_hours, anonymous, weighted FROM custom_polls WHERE poll_id = $1",
+                &[&poll_id],
+            )
+            .await?;
+
+        let default_emoji = vec![String::from("🟠"), String::from("🔵")];
+        Ok(if !rows.is_empty() {
+            let emoji: Option<Vec<String>> = rows[0].get(0);
+            (
+                emoji.unwrap_or(default_emoji),
+                rows[0].get(1),
+                rows[0].get(2),
+                rows[0].get(3),
+            )
+        } else {
+            (default_emoji, None, false, false)
+        })
+    }
+
+    async fn set_custom_poll_weighted(
+        &self,
+        guild_id: String,
+        poll_id: i32,
+        weighted: bool,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE custom_polls SET weighted = $3 WHERE guild_id = $1 AND poll_id = $2",
+                &[&guild_id, &poll_id, &weighted],
+            )
+            .await;
+
+        update
+    }
+
+    async fn set_poll_role_weight(
+        &self,
+        guild_id: String,
+        role_id: String,
+        weight: Option<i32>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        match weight {
+            Some(weight) => {
+                client
+                    .execute(
+                        "INSERT INTO poll_role_weights (guild_id, role_id, weight)
+                        VALUES ($1, $2, $3)
+                        ON CONFLICT (guild_id, role_id)
+                        DO
+                        UPDATE SET weight = EXCLUDED.weight",
+                        &[&guild_id, &role_id, &weight],
+                    )
+                    .await
+            }
+            None => {
+                client
+                    .execute(
+                        "DELETE FROM poll_role_weights WHERE guild_id = $1 AND role_id = $2",
+                        &[&guild_id, &role_id],
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn get_poll_role_weights(&self, guild_id: String) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT role_id, weight FROM poll_role_weights WHERE guild_id = $1 ORDER BY weight DESC",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn get_random_custom_poll(
+        &self,
+        guild_id: String,
+    ) -> Result<(i32, Vec<String>), DbError> {
+        let client = self.get().await?;
+        let rows = client
+            .query(
+                "SELECT poll_id, poll_string FROM custom_polls WHERE guild_id = $1 ORDER BY random() LIMIT 1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            (rows[0].get(0), rows[0].get(1))
+        } else {
+            (0, vec![])
+        })
+    }
+
+    async fn get_specific_custom_poll(
+        &self,
+        guild_id: String,
+        poll_id: i32,
+    ) -> Result<(i32, Vec<String>), DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT poll_id, poll_string FROM custom_polls WHERE guild_id = $1 AND poll_id = $2",
+                &[&guild_id, &poll_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            (rows[0].get(0), rows[0].get(1))
+        } else {
+            (0, vec![])
+        })
+    }
+
+    async fn get_list_of_custom_polls(&self, guild_id: String) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT * FROM custom_polls WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn record_posted_poll(
+        &self,
+        guild_id: String,
+        poll_id: i32,
+        custom: bool,
+        channel_id: String,
+        message_id: String,
+        emoji: Vec<String>,
+        anonymous: bool,
+        weighted: bool,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO poll_posts (guild_id, poll_id, custom, channel_id, message_id, emoji, anonymous, weighted)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+                &[&guild_id, &poll_id, &custom, &channel_id, &message_id, &emoji, &anonymous, &weighted],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn cast_anonymous_vote(
+        &self,
+        message_id: String,
+        user_id: String,
+        option: i32,
+        role_ids: Vec<String>,
+    ) -> Result<Option<(i32, i32)>, DbError> {
+        let client = self.get().await?;
+
+        // The weight is taken when voting, changing role weights later doesn't change votes already cast
+        let voted = client
+            .execute(
+                "INSERT INTO poll_votes (message_id, user_id, option, weight, weight_role)
+                SELECT message_id, $2, $3,
+                    CASE WHEN weighted THEN COALESCE((SELECT max(weight) FROM poll_role_weights
+                        WHERE guild_id = poll_posts.guild_id AND role_id = ANY($4)), 1) ELSE 1 END,
+                    CASE WHEN weighted THEN (SELECT role_id FROM poll_role_weights
+                        WHERE guild_id = poll_posts.guild_id AND role_id = ANY($4)
+                        ORDER BY weight DESC LIMIT 1) END
+                FROM poll_posts WHERE message_id = $1 AND anonymous AND NOT closed
+                ON CONFLICT (message_id, user_id)
+                DO
+                UPDATE SET option = EXCLUDED.option, weight = EXCLUDED.weight, weight_role = EXCLUDED.weight_role",
+                &[&message_id, &user_id, &option, &role_ids],
+            )
+            .await?;
+
+        if voted == 0 {
+            return Ok(None);
+        }
+
+        // Votes on an added option are counted with the amendment
+        client
+            .execute(
+                "UPDATE poll_amendments SET
+                    votes = (SELECT COALESCE(sum(weight), 0) FROM poll_votes WHERE message_id = $1 AND option = 3)
+                WHERE message_id = $1",
+                &[&message_id],
+            )
+            .await?;
+        let rows = client
+            .query(
+                "UPDATE poll_posts SET
+                    votes_a = (SELECT COALESCE(sum(weight), 0) FROM poll_votes WHERE message_id = $1 AND option = 1),
+                    votes_b = (SELECT COALESCE(sum(weight), 0) FROM poll_votes WHERE message_id = $1 AND option = 2)
+                WHERE message_id = $1
+                RETURNING votes_a, votes_b",
+                &[&message_id],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| (row.get(0), row.get(1))))
+    }
+
+    async fn get_poll_vote_breakdown(&self, message_id: String) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT pv.option, pv.weight_role, pv.weight, count(*) FROM poll_votes pv
+                JOIN poll_posts pp ON pp.message_id = pv.message_id
+                WHERE pv.message_id = $1 AND pp.weighted
+                GROUP BY pv.option, pv.weight_role, pv.weight
+                ORDER BY pv.option, pv.weight DESC",
+                &[&message_id],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn get_posted_poll(
+        &self,
+        message_id: String,
+    ) -> Result<Option<(Vec<String>, Vec<String>)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT COALESCE(cp.poll_string, p.poll_string), pp.emoji FROM poll_posts pp
+                LEFT JOIN custom_polls cp ON pp.custom AND cp.poll_id = pp.poll_id
+                LEFT JOIN polls p ON NOT pp.custom AND p.poll_id = pp.poll_id
+                WHERE pp.message_id = $1",
+                &[&message_id],
+            )
+            .await?;
+
+        match rows.first() {
+            Some(row) => {
+                let poll: Option<Vec<String>> = row.get(0);
+                Ok(poll.map(|poll| (poll, row.get(1))))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_open_poll_voters(
+        &self,
+        message_id: String,
+    ) -> Result<Option<Vec<String>>, DbError> {
+        let client = self.get().await?;
+
+        let open = client
+            .query(
+                "SELECT 1 FROM poll_posts WHERE message_id = $1 AND anonymous AND NOT closed",
+                &[&message_id],
+            )
+            .await?;
+
+        if open.is_empty() {
+            return Ok(None);
+        }
+
+        let rows = client
+            .query(
+                "SELECT user_id FROM poll_votes WHERE message_id = $1",
+                &[&message_id],
+            )
+            .await?;
+
+        Ok(Some(rows.iter().map(|row| row.get(0)).collect()))
+    }
+
+    async fn close_poll_post(&self, message_id: String) -> Result<Option<(i32, i32)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "UPDATE poll_posts SET closed = true WHERE message_id = $1 AND NOT closed RETURNING votes_a, votes_b",
+                &[&message_id],
+            )
+            .await?;
+
+        if !rows.is_empty() {
+            Ok(Some((rows[0].get(0), rows[0].get(1))))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update_poll_tally(
+        &self,
+        message_id: String,
+        emoji: String,
+        change: i32,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE poll_posts SET
+                    votes_a = GREATEST(votes_a + CASE WHEN emoji[1] = $2 THEN $3 ELSE 0 END, 0),
+                    votes_b = GREATEST(votes_b + CASE WHEN emoji[2] = $2 THEN $3 ELSE 0 END, 0)
+                WHERE message_id = $1 AND NOT anonymous AND NOT closed AND $2 = ANY(emoji)",
+                &[&message_id, &emoji, &change],
+            )
+            .await?;
+        if update > 0 {
+            return Ok(update);
+        }
+
+        // The emoji may stand for an option added after posting
+        let amended = client
+            .execute(
+                "UPDATE poll_amendments SET votes = GREATEST(votes + $3, 0)
+                WHERE message_id = $1 AND emoji = $2
+                AND message_id IN (SELECT message_id FROM poll_posts WHERE NOT anonymous AND NOT closed)",
+                &[&message_id, &emoji, &change],
+            )
+            .await;
+
+        amended
+    }
+
+    async fn amend_poll(
+        &self,
+        guild_id: String,
+        message_id: String,
+        option: String,
+        emoji: String,
+        amended_by: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO poll_amendments (message_id, option_string, emoji, votes_a_before, votes_b_before, amended_by)
+                SELECT message_id, $3, $4, votes_a, votes_b, $5 FROM poll_posts
+                WHERE guild_id = $1 AND message_id = $2 AND NOT closed
+                ON CONFLICT DO NOTHING",
+                &[&guild_id, &message_id, &option, &emoji, &amended_by],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn get_poll_amendment(&self, message_id: String) -> Result<Option<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT option_string, emoji, votes, votes_a_before, votes_b_before, amended_at
+                FROM poll_amendments WHERE message_id = $1",
+                &[&message_id],
+            )
+            .await?;
+
+        Ok(rows.into_iter().next())
+    }
+
+    async fn get_poll_history(&self, guild_id: String, poll_id: i32) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT to_char(posted_at, 'YYYY-MM-DD'), votes_a, votes_b FROM poll_posts
+                WHERE guild_id = $1 AND custom AND poll_id = $2
+                ORDER BY posted_at",
+                &[&guild_id, &poll_id],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn update_custom_poll(
+        &self,
+        guild_id: String,
+        poll_id: i32,
+        poll: Vec<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE custom_polls SET poll_string = $3 WHERE guild_id = $1 AND poll_id = $2",
+                &[&guild_id, &poll_id, &poll],
+            )
+            .await;
+
+        update
+    }
+
+    async fn delete_custom_poll(
+        &self,
+        guild_id: String,
+        id_to_delete: i32,
+        deleted_by: String,
+    ) -> Result<i32, DbError> {
+        let client = self.get().await?;
+        let transaction = client.transaction().await?;
+
+        // Checking if a poll with the guild_id of the requesting server exists, if it exists, delete the question.
+        // This prevents from other servers deleting each others questions.
+        let rows = transaction
+            .query(
+                "SELECT poll_string FROM custom_polls WHERE guild_id = $1 AND poll_id = $2 FOR UPDATE",
+                &[&guild_id, &id_to_delete],
+            )
+            .await?;
+        let row = match rows.first() {
+            Some(row) => row,
+            None => return Ok(0),
+        };
+        let poll: Vec<String> = row.get(0);
+        let _delete = transaction
+            .execute(
+                "DELETE FROM custom_polls WHERE poll_id = $1",
+                &[&id_to_delete],
+            )
+            .await?;
+        let detail = format!(
+            "Poll {} deleted by <@{}>: {}",
+            id_to_delete,
+            deleted_by,
+            poll.first().cloned().unwrap_or_default()
+        );
+        transaction
+            .execute(
+                "INSERT INTO audit_log (guild_id, action, detail) VALUES ($1, 'delete_poll', $2)",
+                &[&guild_id, &detail],
+            )
+            .await?;
+        transaction.commit().await?;
+
+        Ok(1)
+    }
+}
+
+#[async_trait]
+impl SettingsStore for ClientPool {
+    async fn set_ping_channel_id(
+        &self,
+        channel_id: String,
+        guild_id: String,
+        set_by: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        // Assuming the channel ID is a valid one, parsed at command level
+        // Upserting into the database
+        let upsert = client
+            .execute(
+                "INSERT INTO channels (guild_id, channel_id, set_by)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET channel_id = EXCLUDED.channel_id, set_by = EXCLUDED.set_by",
+                &[&guild_id, &channel_id, &set_by],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_ping_channel_id(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let channel_id: String;
+        let rows = client
+            .query(
+                "SELECT channel_id FROM channels WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+        let channel_string;
+        if !rows.is_empty() {
+            channel_id = rows[0].get(0);
+            channel_string = format!("<#{}>", channel_id);
+        } else {
+            channel_string = String::from("0");
+        }
+        Ok(channel_string)
+    }
+
+    async fn get_channel_set_by(&self, guild_id: String) -> Result<Option<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT set_by FROM channels WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.first().and_then(|row| row.get(0)))
+    }
+
+    async fn set_backup_channel_id(
+        &self,
+        guild_id: String,
+        channel_id: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, backup_channel_id)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET backup_channel_id = EXCLUDED.backup_channel_id",
+                &[&guild_id, &channel_id],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn add_admin_contact(&self, guild_id: String, user_id: String) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO admin_contacts (guild_id, user_id) VALUES ($1, $2)
+                ON CONFLICT DO NOTHING",
+                &[&guild_id, &user_id],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn remove_admin_contact(
+        &self,
+        guild_id: String,
+        user_id: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute(
+                "DELETE FROM admin_contacts WHERE guild_id = $1 AND user_id = $2",
+                &[&guild_id, &user_id],
+            )
+            .await;
+
+        delete
+    }
+
+    async fn get_admin_contacts(&self, guild_id: String) -> Result<Vec<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT user_id FROM admin_contacts WHERE guild_id = $1 ORDER BY added_at, user_id",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    async fn set_notify_policy(&self, guild_id: String, policy: String) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, notify_policy)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET notify_policy = EXCLUDED.notify_policy",
+                &[&guild_id, &policy],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_notify_policy(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT notify_policy FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            String::from("all")
+        })
+    }
+
+    async fn take_notify_turn(&self, guild_id: String) -> Result<i32, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "INSERT INTO guild_settings (guild_id, notify_cursor)
+                VALUES ($1, 1)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET notify_cursor = guild_settings.notify_cursor + 1
+                RETURNING notify_cursor - 1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.first().map_or(0, |row| row.get(0)))
+    }
+
+    async fn set_checklist_message(
+        &self,
+        guild_id: String,
+        message: Option<(String, String)>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let (channel_id, message_id) = message.unzip();
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, checklist_channel_id, checklist_message_id)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET checklist_channel_id = EXCLUDED.checklist_channel_id,
+                    checklist_message_id = EXCLUDED.checklist_message_id",
+                &[&guild_id, &channel_id, &message_id],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_checklist_message(
+        &self,
+        guild_id: String,
+    ) -> Result<Option<(String, String)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT checklist_channel_id, checklist_message_id FROM guild_settings
+                WHERE guild_id = $1 AND checklist_message_id IS NOT NULL",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| (row.get(0), row.get(1))))
+    }
+
+    async fn get_backup_channel_id(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT backup_channel_id FROM guild_settings WHERE guild_id = $1 AND backup_channel_id IS NOT NULL",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            let channel_id: String = rows[0].get(0);
+            format!("<#{}>", channel_id)
+        } else {
+            String::from("0")
+        })
+    }
+
+    async fn set_channel_fallback(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, channel_fallback)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET channel_fallback = EXCLUDED.channel_fallback",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_channel_fallback(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT channel_fallback FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            true
+        })
+    }
+
+    async fn set_trivia_mode(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, trivia_mode)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET trivia_mode = EXCLUDED.trivia_mode",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_trivia_mode(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT trivia_mode FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_moderation_strictness(
+        &self,
+        guild_id: String,
+        strictness: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, moderation_strictness)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET moderation_strictness = EXCLUDED.moderation_strictness",
+                &[&guild_id, &strictness],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_moderation_strictness(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT moderation_strictness FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            "off".to_string()
+        })
+    }
+
+    async fn set_show_attribution(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, show_attribution)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET show_attribution = EXCLUDED.show_attribution",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_show_attribution(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT show_attribution FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            true
+        })
+    }
+
+    async fn set_show_submitter(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, show_submitter)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET show_submitter = EXCLUDED.show_submitter",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_show_submitter(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT show_submitter FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_mention_submitter(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, mention_submitter)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET mention_submitter = EXCLUDED.mention_submitter",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_mention_submitter(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT mention_submitter FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_question_cooldown(
+        &self,
+        guild_id: String,
+        cooldown_days: i32,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, question_cooldown_days)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET question_cooldown_days = EXCLUDED.question_cooldown_days",
+                &[&guild_id, &cooldown_days],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_question_cooldown(&self, guild_id: String) -> Result<i32, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT question_cooldown_days FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() { rows[0].get(0) } else { 0 })
+    }
+
+    async fn set_fair_rotation(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, fair_rotation)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET fair_rotation = EXCLUDED.fair_rotation",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_fair_rotation(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT fair_rotation FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_custom_only(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, custom_only)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET custom_only = EXCLUDED.custom_only",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_custom_only(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT custom_only FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_question_mix(
+        &self,
+        guild_id: String,
+        custom_share: Option<i32>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, custom_question_share)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET custom_question_share = EXCLUDED.custom_question_share",
+                &[&guild_id, &custom_share],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_question_mix(&self, guild_id: String) -> Result<Option<i32>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT custom_question_share FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(match rows.first() {
+            Some(row) => row.get(0),
+            None => None,
+        })
+    }
+
+    async fn set_seasonal(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, seasonal)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET seasonal = EXCLUDED.seasonal",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_seasonal(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT seasonal FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            true
+        })
+    }
+
+    async fn set_quota(
+        &self,
+        guild_id: String,
+        quota: &str,
+        amount: Option<i32>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        // The column comes from QUOTA_COLUMNS, never from what was typed
+        let column = match QUOTA_COLUMNS.iter().find(|(name, _)| *name == quota) {
+            Some((_, column)) => column,
+            None => return Ok(0),
+        };
+        let upsert = client
+            .execute(
+                &format!(
+                    "INSERT INTO guild_settings (guild_id, {0})
+                    VALUES ($1, $2)
+                    ON CONFLICT (guild_id)
+                    DO
+                    UPDATE SET {0} = EXCLUDED.{0}",
+                    column
+                ),
+                &[&guild_id, &amount],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_quotas(&self, guild_id: String) -> Result<[Option<i32>; 3], DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT command_quota, dm_fanout_quota, import_quota FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(match rows.first() {
+            Some(row) => [row.get(0), row.get(1), row.get(2)],
+            None => [None; 3],
+        })
+    }
+
+    async fn set_family_friendly(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, family_friendly)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET family_friendly = EXCLUDED.family_friendly",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_family_friendly(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT family_friendly FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_recommend(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, recommend)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET recommend = EXCLUDED.recommend",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_recommend(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT recommend FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_archive_channel_id(
+        &self,
+        guild_id: String,
+        channel_id: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, archive_channel_id)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET archive_channel_id = EXCLUDED.archive_channel_id",
+                &[&guild_id, &channel_id],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_archive_channel_id(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT archive_channel_id FROM guild_settings WHERE guild_id = $1 AND archive_channel_id IS NOT NULL",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            let channel_id: String = rows[0].get(0);
+            format!("<#{}>", channel_id)
+        } else {
+            String::from("0")
+        })
+    }
+
+    async fn set_command_channel_id(
+        &self,
+        guild_id: String,
+        channel_id: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, command_channel_id)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET command_channel_id = EXCLUDED.command_channel_id",
+                &[&guild_id, &channel_id],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_command_channel_id(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT command_channel_id FROM guild_settings WHERE guild_id = $1 AND command_channel_id IS NOT NULL",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            let channel_id: String = rows[0].get(0);
+            format!("<#{}>", channel_id)
+        } else {
+            String::from("0")
+        })
+    }
+
+    async fn set_save_emoji(&self, guild_id: String, emoji: String) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, save_emoji)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET save_emoji = EXCLUDED.save_emoji",
+                &[&guild_id, &emoji],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_save_emoji(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT save_emoji FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            String::from("📌")
+        })
+    }
+
+    async fn set_answer_window(
+        &self,
+        guild_id: String,
+        hours: Option<i32>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, answer_window_hours)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET answer_window_hours = EXCLUDED.answer_window_hours",
+                &[&guild_id, &hours],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_answer_window(&self, guild_id: String) -> Result<Option<i32>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT answer_window_hours FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            None
+        })
+    }
+
+    async fn set_cleanup(
+        &self,
+        guild_id: String,
+        seconds: Option<i32>,
+        delete_command: bool,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, cleanup_seconds, cleanup_commands)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET cleanup_seconds = EXCLUDED.cleanup_seconds, cleanup_commands = EXCLUDED.cleanup_commands",
+                &[&guild_id, &seconds, &delete_command],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn set_answer_filter(
+        &self,
+        guild_id: String,
+        min_length: i32,
+        member_limit: Option<i32>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, answer_min_length, answer_member_limit)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET answer_min_length = EXCLUDED.answer_min_length, answer_member_limit = EXCLUDED.answer_member_limit",
+                &[&guild_id, &min_length, &member_limit],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_answer_filter(&self, guild_id: String) -> Result<(i32, Option<i32>), DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT answer_min_length, answer_member_limit FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            (rows[0].get(0), rows[0].get(1))
+        } else {
+            (3, None)
+        })
+    }
+
+    async fn get_cleanup(&self, guild_id: String) -> Result<(Option<i32>, bool), DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT cleanup_seconds, cleanup_commands FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            (rows[0].get(0), rows[0].get(1))
+        } else {
+            (None, false)
+        })
+    }
+
+    async fn set_fallback_question(
+        &self,
+        guild_id: String,
+        fallback: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, fallback_question)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET fallback_question = EXCLUDED.fallback_question",
+                &[&guild_id, &fallback],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_fallback_question(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT fallback_question FROM guild_settings WHERE guild_id = $1 AND fallback_question IS NOT NULL",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(match rows.first() {
+            Some(row) => row.get(0),
+            None => String::from("No question today — suggest some with q!submit_qotd!"),
+        })
+    }
+
+    async fn set_accessible_posts(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, accessible_posts)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET accessible_posts = EXCLUDED.accessible_posts",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_accessible_posts(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT accessible_posts FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_question_filter(
+        &self,
+        guild_id: String,
+        tags: Option<Vec<String>>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, question_tags)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET question_tags = EXCLUDED.question_tags",
+                &[&guild_id, &tags],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_question_filter(&self, guild_id: String) -> Result<Option<Vec<String>>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT question_tags FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.first().and_then(|row| row.get(0)))
+    }
+
+    async fn set_image_cards(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, image_cards)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET image_cards = EXCLUDED.image_cards",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_image_cards(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT image_cards FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_card_color(
+        &self,
+        guild_id: String,
+        color: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, card_color)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET card_color = EXCLUDED.card_color",
+                &[&guild_id, &color],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn set_card_template(&self, guild_id: String, template: String) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, card_template)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET card_template = EXCLUDED.card_template",
+                &[&guild_id, &template],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_card_style(&self, guild_id: String) -> Result<CardStyle, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT card_color, card_template FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        let (color, template): (Option<String>, Option<String>) = if !rows.is_empty() {
+            (rows[0].get(0), rows[0].get(1))
+        } else {
+            (None, None)
+        };
+        Ok(CardStyle {
+            color: color.unwrap_or_else(|| String::from(DEFAULT_CARD_COLOR)),
+            template: template.unwrap_or_else(|| String::from("plain")),
+        })
+    }
+
+    async fn set_modlog_target(
+        &self,
+        guild_id: String,
+        target: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, modlog_target)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET modlog_target = EXCLUDED.modlog_target",
+                &[&guild_id, &target],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_modlog_target(&self, guild_id: String) -> Result<Option<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT modlog_target FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.first().and_then(|row| row.get(0)))
+    }
+
+    async fn set_modlog_events(
+        &self,
+        guild_id: String,
+        events: Option<Vec<String>>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, modlog_events)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET modlog_events = EXCLUDED.modlog_events",
+                &[&guild_id, &events],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_modlog_events(&self, guild_id: String) -> Result<Option<Vec<String>>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT modlog_events FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.first().and_then(|row| row.get(0)))
+    }
+
+    async fn set_poll_buttons(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, poll_buttons)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET poll_buttons = EXCLUDED.poll_buttons",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_poll_buttons(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT poll_buttons FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_ephemeral_replies(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, ephemeral_replies)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET ephemeral_replies = EXCLUDED.ephemeral_replies",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_ephemeral_replies(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT ephemeral_replies FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            true
+        })
+    }
+
+    async fn set_removal_export(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, removal_export)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET removal_export = EXCLUDED.removal_export",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_removal_export(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT removal_export FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            true
+        })
+    }
+
+    async fn set_poll_nudge(&self, guild_id: String, mode: String) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, poll_nudge)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET poll_nudge = EXCLUDED.poll_nudge",
+                &[&guild_id, &mode],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_poll_nudge(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT poll_nudge FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            String::from("off")
+        })
+    }
+
+    async fn set_poll_intro(
+        &self,
+        guild_id: String,
+        intro: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, poll_intro)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET poll_intro = EXCLUDED.poll_intro",
+                &[&guild_id, &intro],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn set_poll_title(
+        &self,
+        guild_id: String,
+        title: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, poll_title)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET poll_title = EXCLUDED.poll_title",
+                &[&guild_id, &title],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_poll_texts(&self, guild_id: String) -> Result<(String, String), DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT poll_intro, poll_title FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        let (intro, title): (Option<String>, Option<String>) = if !rows.is_empty() {
+            (rows[0].get(0), rows[0].get(1))
+        } else {
+            (None, None)
+        };
+        Ok((
+            intro.unwrap_or_else(|| String::from("Poll of the day!")),
+            title.unwrap_or_else(|| String::from("{question}")),
+        ))
+    }
+
+    async fn set_paused(
+        &self,
+        guild_id: String,
+        paused: bool,
+        next_post_at: Option<DateTime<Utc>>,
+        next_poll_at: Option<DateTime<Utc>>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, paused)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET paused = EXCLUDED.paused,
+                    next_post_at = CASE WHEN EXCLUDED.paused THEN guild_settings.next_post_at ELSE $3 END,
+                    next_poll_at = CASE WHEN EXCLUDED.paused THEN guild_settings.next_poll_at ELSE $4 END",
+                &[&guild_id, &paused, &next_post_at, &next_poll_at],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_paused(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT paused FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+
+    async fn set_post_schedule(
+        &self,
+        guild_id: String,
+        schedule: Option<String>,
+        next_post_at: Option<DateTime<Utc>>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, post_schedule, next_post_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET post_schedule = EXCLUDED.post_schedule, next_post_at = EXCLUDED.next_post_at",
+                &[&guild_id, &schedule, &next_post_at],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_post_schedule(
+        &self,
+        guild_id: String,
+    ) -> Result<Option<(String, Option<DateTime<Utc>>)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT post_schedule, next_post_at FROM guild_settings
+                WHERE guild_id = $1 AND post_schedule IS NOT NULL",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            Some((rows[0].get(0), rows[0].get(1)))
+        } else {
+            None
+        })
+    }
+
+    async fn get_due_schedules(&self, tick: DateTime<Utc>) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT gs.guild_id, gs.timezone, gs.skip_days,
+                    gs.post_schedule, gs.next_post_at, gs.teaser_minutes, gs.teased_post,
+                    gs.poll_schedule, gs.next_poll_at, gs.poll_source,
+                    c.channel_id, COALESCE(pr.ping_role, '0') AS ping_role, gs.mention_template
+                FROM guild_settings gs
+                LEFT JOIN channels c ON c.guild_id = gs.guild_id
+                LEFT JOIN ping_roles pr ON pr.guild_id = gs.guild_id
+                WHERE NOT gs.paused
+                AND (
+                    (gs.post_schedule IS NOT NULL AND (
+                        gs.next_post_at IS NULL
+                        OR gs.next_post_at <= $1
+                        OR (add_seconds(gs.next_post_at, -60 * gs.teaser_minutes) <= $1
+                            AND (gs.teased_post IS NULL OR gs.teased_post < gs.next_post_at))
+                    ))
+                    OR (gs.poll_schedule IS NOT NULL AND (gs.next_poll_at IS NULL OR gs.next_poll_at <= $1))
+                )",
+                &[&tick],
+            )
+            .await;
+
+        rows
+    }
+
+    async fn set_skip_days(
+        &self,
+        guild_id: String,
+        skip_days: Vec<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, skip_days)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET skip_days = EXCLUDED.skip_days",
+                &[&guild_id, &skip_days],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_skip_days(&self, guild_id: String) -> Result<Vec<Weekday>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT skip_days FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            parse_skip_days(rows[0].get(0))
+        } else {
+            Vec::new()
+        })
+    }
+
+    async fn set_double_days(
+        &self,
+        guild_id: String,
+        double_days: Vec<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, double_days)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET double_days = EXCLUDED.double_days",
+                &[&guild_id, &double_days],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_double_days(&self, guild_id: String) -> Result<Vec<Weekday>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT double_days FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            parse_skip_days(rows[0].get(0))
+        } else {
+            Vec::new()
+        })
+    }
+
+    async fn get_drawn_question(
+        &self,
+        guild_id: String,
+    ) -> Result<Option<(String, Option<String>)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT next_question, next_attribution FROM guild_settings
+                WHERE guild_id = $1 AND next_question IS NOT NULL",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            Some((rows[0].get(0), rows[0].get(1)))
+        } else {
+            None
+        })
+    }
+
+    async fn take_drawn_question(
+        &self,
+        guild_id: String,
+    ) -> Result<Option<(String, Option<String>)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT next_question, next_attribution FROM guild_settings
+                WHERE guild_id = $1 AND next_question IS NOT NULL",
+                &[&guild_id],
+            )
+            .await?;
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let question: String = rows[0].get(0);
+
+        // Only clearing the question that was read, if another post took it first this one gets nothing
+        let taken = client
+            .execute(
+                "UPDATE guild_settings SET next_question = NULL, next_attribution = NULL
+                WHERE guild_id = $1 AND next_question = $2",
+                &[&guild_id, &question],
+            )
+            .await?;
+
+        if taken > 0 {
+            Ok(Some((question, rows[0].get(1))))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn set_poll_schedule(
+        &self,
+        guild_id: String,
+        schedule: Option<String>,
+        poll_source: String,
+        next_poll_at: Option<DateTime<Utc>>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, poll_schedule, poll_source, next_poll_at)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET poll_schedule = EXCLUDED.poll_schedule, poll_source = EXCLUDED.poll_source,
+                    next_poll_at = EXCLUDED.next_poll_at",
+                &[&guild_id, &schedule, &poll_source, &next_poll_at],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_poll_schedule(
+        &self,
+        guild_id: String,
+    ) -> Result<Option<(String, String, Option<DateTime<Utc>>)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT poll_schedule, poll_source, next_poll_at FROM guild_settings
+                WHERE guild_id = $1 AND poll_schedule IS NOT NULL",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            Some((rows[0].get(0), rows[0].get(1), rows[0].get(2)))
+        } else {
+            None
+        })
+    }
+
+    async fn claim_scheduled_poll(
+        &self,
+        guild_id: String,
+        due: Option<DateTime<Utc>>,
+        next: Option<DateTime<Utc>>,
+    ) -> Result<bool, DbError> {
+        let client = self.get().await?;
+        let transaction = client.transaction().await?;
+
+        let claimed = transaction
+            .execute(
+                "UPDATE guild_settings SET next_poll_at = $3
+                WHERE guild_id = $1 AND next_poll_at IS NOT DISTINCT FROM $2",
+                &[&guild_id, &due, &next],
+            )
+            .await?;
+        if claimed > 0 {
+            if let Some(due) = due {
+                queue_outbox_entry(&guild_id, "poll", due, &transaction).await?;
+            }
+        }
+        transaction.commit().await?;
+
+        Ok(claimed > 0)
+    }
+
+    async fn claim_scheduled_post(
+        &self,
+        guild_id: String,
+        due: Option<DateTime<Utc>>,
+        next: Option<DateTime<Utc>>,
+    ) -> Result<bool, DbError> {
+        let client = self.get().await?;
+        let transaction = client.transaction().await?;
+
+        let claimed = transaction
+            .execute(
+                "UPDATE guild_settings SET next_post_at = $3
+                WHERE guild_id = $1 AND next_post_at IS NOT DISTINCT FROM $2",
+                &[&guild_id, &due, &next],
+            )
+            .await?;
+        if claimed > 0 {
+            if let Some(due) = due {
+                queue_outbox_entry(&guild_id, "question", due, &transaction).await?;
+            }
+        }
+        transaction.commit().await?;
+
+        Ok(claimed > 0)
+    }
+
+    async fn get_due_outbox(&self, now: DateTime<Utc>) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT o.outbox_id, o.kind, o.due_at, o.attempts, o.attempted_at,
+                    gs.guild_id, gs.timezone, gs.poll_source,
+                    c.channel_id, COALESCE(pr.ping_role, '0') AS ping_role, gs.mention_template
+                FROM outbox o
+                JOIN guild_settings gs ON gs.guild_id = o.guild_id
+                LEFT JOIN channels c ON c.guild_id = o.guild_id
+                LEFT JOIN ping_roles pr ON pr.guild_id = o.guild_id
+                WHERE o.status = 'pending' AND o.send_after <= $1
+                ORDER BY o.outbox_id",
+                &[&now],
+            )
+            .await;
+
+        rows
+    }
+
+    async fn claim_outbox_entry(
+        &self,
+        outbox_id: i32,
+        attempts: i32,
+        lease_seconds: i32,
+    ) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let claimed = client
+            .execute(
+                "UPDATE outbox SET attempts = attempts + 1, attempted_at = now(),
+                    send_after = add_seconds(now(), $3::integer)
+                WHERE outbox_id = $1 AND attempts = $2 AND status = 'pending'",
+                &[&outbox_id, &attempts, &lease_seconds],
+            )
+            .await?;
+
+        Ok(claimed > 0)
+    }
+
+    async fn retry_outbox_entry(&self, outbox_id: i32, seconds: i32) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE outbox SET send_after = add_seconds(now(), $2::integer) WHERE outbox_id = $1",
+                &[&outbox_id, &seconds],
+            )
+            .await;
+
+        update
+    }
+
+    async fn settle_outbox_entry(
+        &self,
+        outbox_id: i32,
+        status: &str,
+        posted: Option<(String, String)>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let (channel_id, message_id) = posted.unzip();
+        let update = client
+            .execute(
+                "UPDATE outbox SET status = $2, channel_id = $3, message_id = $4, settled_at = now()
+                WHERE outbox_id = $1",
+                &[&outbox_id, &status, &channel_id, &message_id],
+            )
+            .await;
+
+        update
+    }
+
+    async fn find_scheduled_post(
+        &self,
+        guild_id: String,
+        kind: String,
+        since: DateTime<Utc>,
+    ) -> Result<Option<(String, String)>, DbError> {
+        let client = self.get().await?;
+
+        let statement = if kind == "poll" {
+            "SELECT channel_id, message_id FROM poll_posts
+            WHERE guild_id = $1 AND posted_at >= $2 ORDER BY posted_at LIMIT 1"
+        } else {
+            "SELECT channel_id, message_id FROM post_history
+            WHERE guild_id = $1 AND posted_at >= $2 ORDER BY posted_at LIMIT 1"
+        };
+        let rows = client.query(statement, &[&guild_id, &since]).await?;
+
+        Ok(rows.first().map(|row| (row.get(0), row.get(1))))
+    }
+
+    async fn get_outbox(&self, guild_id: String, limit: i64) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT kind, due_at, status, attempts, channel_id, message_id
+                FROM outbox WHERE guild_id = $1
+                ORDER BY outbox_id DESC LIMIT $2",
+                &[&guild_id, &limit],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn set_teaser_minutes(
+        &self,
+        guild_id: String,
+        minutes: Option<i32>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, teaser_minutes)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET teaser_minutes = EXCLUDED.teaser_minutes",
+                &[&guild_id, &minutes],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_teaser_minutes(&self, guild_id: String) -> Result<Option<i32>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT teaser_minutes FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            None
+        })
+    }
+
+    async fn claim_teaser(
+        &self,
+        guild_id: String,
+        next_post: DateTime<Utc>,
+    ) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let claimed = client
+            .execute(
+                "UPDATE guild_settings SET teased_post = $2
+                WHERE guild_id = $1 AND (teased_post IS NULL OR teased_post < $2)",
+                &[&guild_id, &next_post],
+            )
+            .await?;
+
+        Ok(claimed > 0)
+    }
+
+    async fn set_guild_timezone(
+        &self,
+        guild_id: String,
+        timezone: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, timezone)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET timezone = EXCLUDED.timezone",
+                &[&guild_id, &timezone],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_guild_timezone(&self, guild_id: String) -> Result<Tz, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT timezone FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            parse_timezone(rows[0].get(0))
+        } else {
+            Tz::UTC
+        })
+    }
+
+    async fn set_ping_role(&self, guild_id: String, ping_role: String) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO ping_roles (guild_id, ping_role)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET ping_role = EXCLUDED.ping_role",
+                &[&guild_id, &ping_role],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_ping_role(&self, guild_id: String) -> Result<String, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT ping_role FROM ping_roles WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(
+            // Return the ping role as string
+            if !rows.is_empty() {
+                rows[0].get(0)
+            } else {
+                //Return 0 if there's no ping role assigned
+                String::from("0")
+            },
+        )
+    }
+
+    async fn set_mention_template(
+        &self,
+        guild_id: String,
+        template: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, mention_template)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET mention_template = EXCLUDED.mention_template",
+                &[&guild_id, &template],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_mention_template(&self, guild_id: String) -> Result<Option<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT mention_template FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            None
+        })
+    }
+
+    async fn set_everyone_approval(&self, guild_id: String, enabled: bool) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, everyone_approval)
+                VALUES ($1, $2)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET everyone_approval = EXCLUDED.everyone_approval",
+                &[&guild_id, &enabled],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_everyone_approval(&self, guild_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT everyone_approval FROM guild_settings WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            rows[0].get(0)
+        } else {
+            false
+        })
+    }
+    async fn block_user(
+        &self,
+        guild_id: String,
+        user_id: String,
+        blocked_by: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO blocked_users (guild_id, user_id, blocked_by)
+                VALUES ($1, $2, $3)
+                ON CONFLICT DO NOTHING",
+                &[&guild_id, &user_id, &blocked_by],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn unblock_user(&self, guild_id: String, user_id: String) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute(
+                "DELETE FROM blocked_users WHERE guild_id = $1 AND user_id = $2",
+                &[&guild_id, &user_id],
+            )
+            .await;
+
+        delete
+    }
+
+    async fn is_user_blocked(&self, guild_id: String, user_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT 1 FROM blocked_users WHERE guild_id = $1 AND user_id = $2",
+                &[&guild_id, &user_id],
+            )
+            .await?;
+
+        Ok(!rows.is_empty())
+    }
+
+    async fn get_blocked_users(&self, guild_id: String) -> Result<Vec<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT user_id FROM blocked_users WHERE guild_id = $1 ORDER BY blocked_at",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    async fn add_deadline(
+        &self,
+        kind: &str,
+        guild_id: String,
+        channel_id: String,
+        message_id: Option<String>,
+        delay_seconds: i32,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO deadlines (kind, guild_id, channel_id, message_id, due_at)
+                VALUES ($1, $2, $3, $4, add_seconds(now(), $5::integer))",
+                &[&kind, &guild_id, &channel_id, &message_id, &delay_seconds],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn take_message_deadline(&self, kind: &str, message_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let deleted = client
+            .execute(
+                "DELETE FROM deadlines WHERE kind = $1 AND message_id = $2",
+                &[&kind, &message_id],
+            )
+            .await?;
+
+        Ok(deleted > 0)
+    }
+
+    async fn take_due_deadlines(&self) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "DELETE FROM deadlines WHERE due_at <= now() RETURNING kind, guild_id, channel_id, message_id",
+                &[],
+            )
+            .await;
+
+        rows
+    }
+
+    async fn queue_dms(
+        &self,
+        users: &[UserId],
+        content: &str,
+        embed: Option<(&str, &str)>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let user_ids: Vec<String> = users.iter().map(|user| user.to_string()).collect();
+        let (embed_title, embed_description) = embed.unzip();
+        let insert = client
+            .execute(
+                "INSERT INTO dm_queue (user_id, content, embed_title, embed_description)
+                SELECT user_id, $2, $3, $4 FROM unnest($1::varchar[]) AS user_id
+                WHERE user_id NOT IN (SELECT user_id FROM dm_failures WHERE failures >= $5)
+                AND (SELECT count(*) FROM dm_queue) < $6",
+                &[
+                    &user_ids,
+                    &content,
+                    &embed_title,
+                    &embed_description,
+                    &DM_FAILURE_LIMIT,
+                    &DM_QUEUE_LIMIT,
+                ],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn take_due_dms(&self) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "UPDATE dm_queue SET attempts = attempts + 1, send_after = add_seconds(now(), $2::integer)
+                WHERE dm_id IN (
+                    SELECT dm_id FROM dm_queue WHERE send_after <= now() ORDER BY dm_id LIMIT $1
+                )
+                RETURNING dm_id, user_id, content, embed_title, embed_description, attempts",
+                &[&DM_RATE, &DM_LEASE_SECONDS],
+            )
+            .await;
+
+        rows
+    }
+
+    async fn remove_queued_dm(&self, dm_id: i32) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute("DELETE FROM dm_queue WHERE dm_id = $1", &[&dm_id])
+            .await;
+
+        delete
+    }
+
+    async fn retry_queued_dm(&self, dm_id: i32, seconds: i32) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE dm_queue SET send_after = add_seconds(now(), $2::integer) WHERE dm_id = $1",
+                &[&dm_id, &seconds],
+            )
+            .await;
+
+        update
+    }
+
+    async fn clear_dm_failures(&self, user_id: String) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute("DELETE FROM dm_failures WHERE user_id = $1", &[&user_id])
+            .await;
+
+        delete
+    }
+
+    async fn record_dm_failure(&self, user_id: String) -> Result<bool, DbError> {
+        let client = self.get().await?;
+
+        let transaction = client.transaction().await?;
+        let failures: i32 = transaction
+            .query(
+                "INSERT INTO dm_failures (user_id, failures)
+                VALUES ($1, 1)
+                ON CONFLICT (user_id)
+                DO
+                UPDATE SET failures = dm_failures.failures + 1, failed_at = now()
+                RETURNING failures",
+                &[&user_id],
+            )
+            .await?
+            .first()
+            .map(|row| row.get(0))
+            .unwrap_or(0);
+
+        let unsubscribed = failures == DM_FAILURE_LIMIT;
+        if unsubscribed {
+            transaction
+                .execute(
+                    "UPDATE notify_preferences SET mode = 'none' WHERE user_id = $1 AND mode = 'dm'",
+                    &[&user_id],
+                )
+                .await?;
+            transaction
+                .execute("DELETE FROM dm_queue WHERE user_id = $1", &[&user_id])
+                .await?;
+        }
+        transaction.commit().await?;
+
+        Ok(unsubscribed)
+    }
+
+    async fn set_drawn_question(
+        &self,
+        guild_id: String,
+        drawn: Option<(String, Option<String>)>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let (question, attribution) = match drawn {
+            Some((question, attribution)) => (Some(question), attribution),
+            None => (None, None),
+        };
+
+        let upsert = client
+            .execute(
+                "INSERT INTO guild_settings (guild_id, next_question, next_attribution)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id)
+                DO
+                UPDATE SET next_question = EXCLUDED.next_question, next_attribution = EXCLUDED.next_attribution",
+                &[&guild_id, &question, &attribution],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn set_notify_preference(
+        &self,
+        guild_id: String,
+        user_id: String,
+        mode: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        match mode {
+            Some(mode) => {
+                let upsert = client
+                    .execute(
+                        "INSERT INTO notify_preferences (guild_id, user_id, mode)
+                        VALUES ($1, $2, $3)
+                        ON CONFLICT (guild_id, user_id)
+                        DO
+                        UPDATE SET mode = EXCLUDED.mode",
+                        &[&guild_id, &user_id, &mode],
+                    )
+                    .await;
+
+                upsert
+            }
+            None => {
+                let delete = client
+                    .execute(
+                        "DELETE FROM notify_preferences WHERE guild_id = $1 AND user_id = $2",
+                        &[&guild_id, &user_id],
+                    )
+                    .await;
+
+                delete
+            }
+        }
+    }
+
+    async fn get_notify_preference(
+        &self,
+        guild_id: String,
+        user_id: String,
+    ) -> Result<Option<String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT mode FROM notify_preferences WHERE guild_id = $1 AND user_id = $2",
+                &[&guild_id, &user_id],
+            )
+            .await?;
+
+        Ok(rows.first().map(|row| row.get(0)))
+    }
+
+    async fn get_notify_preferences(
+        &self,
+        guild_id: String,
+    ) -> Result<HashMap<String, String>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT user_id, mode FROM notify_preferences WHERE guild_id = $1",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    async fn record_guild_event(&self, guild_id: String, event: &str) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO guild_events (guild_id, event)
+                SELECT $1::varchar, $2::varchar
+                WHERE $2 IS DISTINCT FROM (
+                    SELECT event FROM guild_events WHERE guild_id = $1
+                    ORDER BY happened_at DESC LIMIT 1
+                )",
+                &[&guild_id, &event],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn record_audit_event(
+        &self,
+        guild_id: String,
+        action: &str,
+        detail: &str,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let insert = client
+            .execute(
+                "INSERT INTO audit_log (guild_id, action, detail) VALUES ($1, $2, $3)",
+                &[&guild_id, &action, &detail],
+            )
+            .await;
+
+        insert
+    }
+
+    async fn get_audit_log(&self, guild_id: String, limit: i64) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT action, detail, logged_at FROM audit_log
+                WHERE guild_id = $1
+                ORDER BY logged_at DESC
+                LIMIT $2",
+                &[&guild_id, &limit],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn record_command_use(
+        &self,
+        guild_id: String,
+        command: &str,
+        failed: bool,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO command_stats (guild_id, command, failures)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id, command, day)
+                DO
+                UPDATE SET uses = command_stats.uses + 1, failures = command_stats.failures + EXCLUDED.failures",
+                &[&guild_id, &command, &(failed as i32)],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn get_guild_usage(&self, guild_id: String) -> Result<[i64; 3], DbError> {
+        let client = self.get().await?;
+
+        let today = Utc::now().date_naive();
+        let month_start = today.with_day(1).unwrap_or(today);
+        let import_commands: Vec<String> = IMPORT_COMMANDS.iter().map(|c| c.to_string()).collect();
+        let row = client
+            .query_one(
+                "SELECT
+                    COALESCE(sum(CASE WHEN day = $2 AND command <> $4 THEN uses ELSE 0 END), 0),
+                    COALESCE(sum(CASE WHEN command = $4 THEN uses ELSE 0 END), 0),
+                    COALESCE(sum(CASE WHEN command = ANY($5) THEN uses ELSE 0 END), 0)
+                FROM command_stats
+                WHERE guild_id = $1 AND day >= $3",
+                &[
+                    &guild_id,
+                    &today,
+                    &month_start,
+                    &DM_FANOUT_STAT,
+                    &import_commands,
+                ],
+            )
+            .await?;
+
+        Ok([row.get(0), row.get(1), row.get(2)])
+    }
+
+    async fn get_weekly_growth(&self) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        client
+            .query(
+                "WITH RECURSIVE weeks (week) AS (
+                    SELECT add_seconds(date_trunc('week', now()), -11 * 604800)
+                    UNION ALL
+                    SELECT add_seconds(week, 604800) FROM weeks WHERE week < date_trunc('week', now())
+                )
+                SELECT to_char(w.week, 'YYYY-MM-DD'),
+                    count(*) FILTER (WHERE ge.event = 'join' AND ge.happened_at >= w.week),
+                    count(*) FILTER (WHERE ge.event = 'leave' AND ge.happened_at >= w.week),
+                    count(*) FILTER (WHERE ge.event = 'join') - count(*) FILTER (WHERE ge.event = 'leave')
+                FROM weeks w
+                LEFT JOIN guild_events ge ON ge.happened_at < add_seconds(w.week, 604800)
+                GROUP BY w.week
+                ORDER BY w.week",
+                &[],
+            )
+            .await
+    }
+
+    async fn get_guild_retention(&self) -> Result<(i64, i64), DbError> {
+        let client = self.get().await?;
+
+        let row = client
+            .query_one(
+                "WITH first_joins AS (
+                    SELECT guild_id, min(happened_at) AS joined_at FROM guild_events
+                    WHERE event = 'join'
+                    GROUP BY guild_id
+                    HAVING min(happened_at) < add_seconds(now(), -2592000)
+                )
+                SELECT count(*), count(*) FILTER (WHERE NOT EXISTS (
+                    SELECT 1 FROM guild_events ge
+                    WHERE ge.guild_id = fj.guild_id AND ge.event = 'leave'
+                    AND ge.happened_at < add_seconds(fj.joined_at, 2592000)
+                ))
+                FROM first_joins fj",
+                &[],
+            )
+            .await?;
+
+        Ok((row.get(0), row.get(1)))
+    }
+
+    async fn get_most_active_guilds(&self, limit: i64) -> Result<Vec<(String, i64)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT guild_id, sum(uses) AS total FROM command_stats
+                WHERE day > $2 AND command <> $3
+                GROUP BY guild_id
+                ORDER BY total DESC
+                LIMIT $1",
+                &[
+                    &limit,
+                    &(Utc::now().date_naive() - chrono::Duration::days(30)),
+                    &DM_FANOUT_STAT,
+                ],
+            )
+            .await?;
+
+        Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+    }
+
+    async fn get_top_questions_globally(&self, days: i64, limit: i64) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        client
+            .query(
+                "SELECT question_string, count(*) AS posts, count(DISTINCT guild_id) AS guilds
+                FROM post_history
+                WHERE posted_at > add_seconds(now(), $2::integer)
+                GROUP BY question_string
+                ORDER BY posts DESC, guilds DESC
+                LIMIT $1",
+                &[&limit, &(-(days as i32) * 86400)],
+            )
+            .await
+    }
+
+    async fn get_command_volumes(&self, days: i64) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        client
+            .query(
+                "SELECT command, sum(uses) AS uses, sum(failures) AS failures FROM command_stats
+                WHERE day > $1 AND command <> $2
+                GROUP BY command
+                ORDER BY uses DESC",
+                &[
+                    &(Utc::now().date_naive() - chrono::Duration::days(days)),
+                    &DM_FANOUT_STAT,
+                ],
+            )
+            .await
+    }
+
+    async fn get_stream(&self, guild_id: String, name: String) -> Result<Option<Stream>, DbError> {
+        if name == "daily" {
+            return Ok(Some(self.get_default_stream(guild_id).await?));
+        }
+
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT channel_id, ping_role, mention_template FROM streams WHERE guild_id = $1 AND name = $2",
+                &[&guild_id, &name],
+            )
+            .await?;
+
+        Ok(if !rows.is_empty() {
+            let channel_id: String = rows[0].get(0);
+            Some(Stream {
+                name,
+                channel: channel_id.parse::<u64>().ok().map(ChannelId),
+                ping_role: rows[0].get(1),
+                mention_template: rows[0].get(2),
+            })
+        } else {
+            None
+        })
+    }
+
+    async fn get_list_of_streams(&self, guild_id: String) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT name, channel_id, ping_role, mention_template FROM streams WHERE guild_id = $1 ORDER BY name",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn set_stream_channel(
+        &self,
+        guild_id: String,
+        name: String,
+        channel_id: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO streams (guild_id, name, channel_id)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id, name)
+                DO
+                UPDATE SET channel_id = EXCLUDED.channel_id",
+                &[&guild_id, &name, &channel_id],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn set_stream_ping_role(
+        &self,
+        guild_id: String,
+        name: String,
+        ping_role: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE streams SET ping_role = $3 WHERE guild_id = $1 AND name = $2",
+                &[&guild_id, &name, &ping_role],
+            )
+            .await;
+
+        update
+    }
+
+    async fn set_stream_template(
+        &self,
+        guild_id: String,
+        name: String,
+        template: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let update = client
+            .execute(
+                "UPDATE streams SET mention_template = $3 WHERE guild_id = $1 AND name = $2",
+                &[&guild_id, &name, &template],
+            )
+            .await;
+
+        update
+    }
+
+    async fn delete_stream(&self, guild_id: String, name: String) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute(
+                "DELETE FROM streams WHERE guild_id = $1 AND name = $2",
+                &[&guild_id, &name],
+            )
+            .await;
+
+        delete
+    }
+
+    async fn set_api_token(
+        &self,
+        guild_id: String,
+        scope: String,
+        token_hash: String,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let upsert = client
+            .execute(
+                "INSERT INTO api_tokens (guild_id, scope, token_hash)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (guild_id, scope)
+                DO
+                UPDATE SET token_hash = EXCLUDED.token_hash, created_at = now()",
+                &[&guild_id, &scope, &token_hash],
+            )
+            .await;
+
+        upsert
+    }
+
+    async fn revoke_api_tokens(
+        &self,
+        guild_id: String,
+        scope: Option<String>,
+    ) -> Result<u64, DbError> {
+        let client = self.get().await?;
+
+        let delete = client
+            .execute(
+                "DELETE FROM api_tokens WHERE guild_id = $1 AND ($2::varchar IS NULL OR scope = $2)",
+                &[&guild_id, &scope],
+            )
+            .await;
+
+        delete
+    }
+
+    async fn get_api_tokens(&self, guild_id: String) -> Result<Vec<Row>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT scope, to_char(created_at, 'YYYY-MM-DD') FROM api_tokens WHERE guild_id = $1 ORDER BY scope",
+                &[&guild_id],
+            )
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn get_api_token_guild(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<(String, String)>, DbError> {
+        let client = self.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT guild_id, scope FROM api_tokens WHERE token_hash = $1",
+                &[&token_hash],
+            )
+            .await?;
+
+        if !rows.is_empty() {
+            Ok(Some((rows[0].get(0), rows[0].get(1))))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn get_default_stream(&self, guild_id: String) -> Result<Stream, DbError> {
+        let channel_id = self.get_ping_channel_id(guild_id.clone()).await?;
+
+        Ok(Stream {
+            name: String::from("daily"),
+            channel: parse_channel(&channel_id).map(ChannelId),
+            ping_role: self.get_ping_role(guild_id.clone()).await?,
+            mention_template: self.get_mention_template(guild_id).await?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sqlite_statement_keeps_plain_statements() {
+        let statement = "SELECT question_string FROM questions WHERE in_use ORDER BY question_id";
+        assert_eq!(sqlite_statement(statement), statement);
+    }
+
+    #[test]
+    fn sqlite_statement_numbers_parameters() {
+        assert_eq!(
+            sqlite_statement("UPDATE channels SET channel_id = $1 WHERE guild_id = $12"),
+            "UPDATE channels SET channel_id = ?1 WHERE guild_id = ?12"
+        );
+    }
+
+    #[test]
+    fn sqlite_statement_drops_casts() {
+        assert_eq!(
+            sqlite_statement("SELECT $1::varchar, $2::varchar[], count(*)::bigint FROM polls"),
+            "SELECT ?1, ?2, count(*) FROM polls"
+        );
+    }
+
+    #[test]
+    fn sqlite_statement_rewrites_distinct_comparisons() {
+        assert_eq!(
+            sqlite_statement(
+                "SELECT 1 FROM streams WHERE ping_role IS NOT DISTINCT FROM $1 OR channel_id IS DISTINCT FROM $2"
+            ),
+            "SELECT 1 FROM streams WHERE ping_role IS ?1 OR channel_id IS NOT ?2"
+        );
+    }
+
+    #[test]
+    fn sqlite_statement_reads_any_from_json() {
+        assert_eq!(
+            sqlite_statement("DELETE FROM custom_questions WHERE question_id = ANY($2)"),
+            "DELETE FROM custom_questions WHERE question_id IN (SELECT value FROM json_each(?2))"
+        );
+    }
+
+    #[test]
+    fn sqlite_statement_unnests_arrays_from_json() {
+        assert_eq!(
+            sqlite_statement(
+                "INSERT INTO custom_questions (guild_id, question_string) SELECT $1, question FROM unnest($2::varchar[]) AS question"
+            ),
+            "INSERT INTO custom_questions (guild_id, question_string) SELECT ?1, question FROM (SELECT value AS question FROM json_each(?2))"
+        );
+    }
+
+    #[test]
+    fn sqlite_statement_unnests_and_matches_any_together() {
+        assert_eq!(
+            sqlite_statement(
+                "SELECT 1 FROM questions WHERE ($1::varchar[] IS NULL OR EXISTS (SELECT 1 FROM unnest(tags) AS tag WHERE tag = ANY($1)))"
+            ),
+            "SELECT 1 FROM questions WHERE (?1 IS NULL OR EXISTS (SELECT 1 FROM (SELECT value AS tag FROM json_each(tags)) WHERE tag IN (SELECT value FROM json_each(?1))))"
+        );
+    }
+
+    #[test]
+    fn sqlite_statement_indexes_arrays_from_zero() {
+        assert_eq!(
+            sqlite_statement("SELECT poll_string[1], poll_string[3] FROM polls"),
+            "SELECT json_extract(poll_string, '$[0]'), json_extract(poll_string, '$[2]') FROM polls"
+        );
+    }
+
+    #[test]
+    fn sqlite_statement_rewrites_postgres_only_syntax() {
+        assert_eq!(
+            sqlite_statement("SELECT GREATEST(a, b) FROM t WHERE name ILIKE $1 FOR UPDATE"),
+            "SELECT max(a, b) FROM t WHERE name LIKE ?1"
+        );
+    }
+
+    #[test]
+    fn sqlite_statement_keeps_standard_casts() {
+        assert_eq!(
+            sqlite_statement("SELECT 1 LIMIT 1 OFFSET CAST(floor(random() * $1) AS bigint)"),
+            "SELECT 1 LIMIT 1 OFFSET CAST(floor(random() * ?1) AS bigint)"
+        );
+    }
+
+    async fn memory_pool() -> ClientPool {
+        let pool = ClientPool::sqlite(String::from(":memory:")).expect("Opening SQLite failed");
+        run_migrations(&pool)
+            .await
+            .expect("Migrating SQLite failed");
+        pool
+    }
+
+    #[tokio::test]
+    async fn sqlite_query_errors_are_returned() {
+        // Without migrations none of the tables exist
+        let pool = ClientPool::sqlite(String::from(":memory:")).expect("Opening SQLite failed");
+        assert!(matches!(
+            pool.get_ping_role(String::from("1")).await,
+            Err(DbError::Sqlite(_))
+        ));
+        assert!(pool
+            .get_list_custom_questions(String::from("1"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn sqlite_drafts_belong_to_their_user() {
+        let pool = memory_pool().await;
+        let content = vec![
+            String::from("Cats or dogs?"),
+            String::from("Cats"),
+            String::from("Dogs"),
+        ];
+        let draft_id = pool
+            .add_draft(
+                String::from("1"),
+                String::from("10"),
+                "poll",
+                content.clone(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            pool.get_draft(String::from("1"), String::from("10"), draft_id)
+                .await
+                .unwrap(),
+            Some((String::from("poll"), content))
+        );
+        assert_eq!(
+            pool.get_drafts(String::from("1"), String::from("10"))
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            pool.get_draft(String::from("1"), String::from("11"), draft_id)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            pool.delete_draft(String::from("1"), String::from("11"), draft_id)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            pool.delete_draft(String::from("1"), String::from("10"), draft_id)
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(pool
+            .get_drafts(String::from("1"), String::from("10"))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn sqlite_trivia_round_has_one_winner() {
+        let pool = memory_pool().await;
+        pool.add_trivia_round(
+            String::from("1"),
+            String::from("100"),
+            String::from("Paris"),
+        )
+        .await
+        .unwrap();
+
+        assert!(pool
+            .set_trivia_winner(String::from("100"), Some(String::from("10")), false)
+            .await
+            .unwrap());
+        assert!(!pool
+            .set_trivia_winner(String::from("100"), Some(String::from("11")), false)
+            .await
+            .unwrap());
+        assert_eq!(
+            pool.get_trivia_round(String::from("100")).await.unwrap(),
+            Some((String::from("Paris"), Some(String::from("10"))))
+        );
+        assert!(pool
+            .set_trivia_winner(String::from("100"), None, true)
+            .await
+            .unwrap());
+        assert_eq!(
+            pool.get_trivia_round(String::from("100")).await.unwrap(),
+            Some((String::from("Paris"), None))
+        );
+
+        assert_eq!(
+            pool.add_trivia_points(String::from("1"), String::from("10"), 3)
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            pool.add_trivia_points(String::from("1"), String::from("10"), -1)
+                .await
+                .unwrap(),
+            2
+        );
+        let scores = pool.get_trivia_scores(String::from("1")).await.unwrap();
+        assert_eq!(scores.len(), 1);
+        assert_eq!(scores[0].get::<_, i32>(1), 2);
+    }
+
+    #[tokio::test]
+    async fn sqlite_pool_invite_joins_the_pool_once() {
+        let pool = memory_pool().await;
+        let code = pool.create_pool_invite(String::from("1")).await.unwrap();
+        let pool_id = pool.get_pool_invite(code.clone()).await.unwrap().unwrap();
+
+        pool.join_pool(String::from("2"), pool_id, code.clone())
+            .await
+            .unwrap();
+        assert_eq!(pool.get_pool_invite(code).await.unwrap(), None);
+        assert!(pool.is_in_pool(String::from("2")).await.unwrap());
+        assert_eq!(
+            pool.get_pool_partners(String::from("1")).await.unwrap(),
+            vec![String::from("2")]
+        );
+
+        assert_eq!(pool.leave_pool(String::from("2")).await.unwrap(), 1);
+        assert!(!pool.is_in_pool(String::from("2")).await.unwrap());
+        assert!(pool
+            .get_pool_partners(String::from("1"))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn sqlite_notify_preference_falls_back_to_default() {
+        let pool = memory_pool().await;
+        pool.set_notify_preference(
+            String::from("1"),
+            String::from("10"),
+            Some(String::from("dm")),
+        )
+        .await
+        .unwrap();
+        pool.set_notify_preference(
+            String::from("1"),
+            String::from("10"),
+            Some(String::from("none")),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            pool.get_notify_preference(String::from("1"), String::from("10"))
+                .await
+                .unwrap(),
+            Some(String::from("none"))
+        );
+        assert_eq!(
+            pool.get_notify_preferences(String::from("1"))
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        pool.set_notify_preference(String::from("1"), String::from("10"), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            pool.get_notify_preference(String::from("1"), String::from("10"))
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn sqlite_question_candidates_are_different_and_reach_every_question() {
+        let pool = memory_pool().await;
+        let questions: Vec<String> = (0..30).map(|n| format!("Question {}?", n)).collect();
+        pool.add_default_questions(
+            questions.clone(),
+            String::from("Test"),
+            String::from("Tester"),
+            QuestionSource::default(),
+        )
+        .await
+        .unwrap();
+
+        let mut seen = HashSet::new();
+        for _ in 0..200 {
+            let candidates = pool
+                .get_random_question_candidates(10, None, None)
+                .await
+                .unwrap();
+            let drawn: HashSet<String> = candidates
+                .into_iter()
+                .map(|(question, _, _)| question)
+                .collect();
+            assert_eq!(drawn.len(), 10);
+            seen.extend(drawn);
+        }
+        assert_eq!(seen.len(), questions.len());
+
+        let candidates = pool
+            .get_random_question_candidates(50, None, None)
+            .await
+            .unwrap();
+        assert_eq!(candidates.len(), questions.len());
+    }
+
+    #[tokio::test]
+    async fn sqlite_drawn_question_stops_being_eligible() {
+        let pool = memory_pool().await;
+        let guild = || String::from("1");
+        pool.add_default_questions(
+            vec![String::from("Default?")],
+            String::from("Test"),
+            String::from("Tester"),
+            QuestionSource::default(),
+        )
+        .await
+        .unwrap();
+        pool.add_custom_question(guild(), String::from("Custom?"), None, None, false)
+            .await
+            .unwrap();
+        let eligible = |question: &'static str, tags: Option<Vec<String>>, custom_only: bool| {
+            pool.is_question_eligible(guild(), String::from(question), tags, custom_only)
+        };
+
+        assert!(eligible("Default?", None, false).await.unwrap());
+        assert!(eligible("custom", None, false).await.unwrap());
+        assert!(!eligible("Not saved?", None, false).await.unwrap());
+
+        assert!(!eligible("Default?", None, true).await.unwrap());
+        assert!(eligible("Custom?", None, true).await.unwrap());
+        assert!(
+            !eligible("Default?", Some(vec![String::from("deep")]), false)
+                .await
+                .unwrap()
+        );
+
+        pool.set_family_friendly(guild(), true).await.unwrap();
+        pool.set_default_question_mature(1, true).await.unwrap();
+        assert!(!eligible("Default?", None, false).await.unwrap());
+
+        pool.set_custom_question_in_use(guild(), 1, false)
+            .await
+            .unwrap();
+        assert!(!eligible("Custom?", None, false).await.unwrap());
+    }
+}